@@ -5,14 +5,18 @@ use crate::plugin::PluginContractVersion;
 use crate::runtime::api::ThreadStartParams;
 use crate::runtime::approvals::ServerRequest;
 use crate::runtime::core::Runtime;
-use crate::runtime::errors::RuntimeError;
+use crate::runtime::errors::{RpcError, RuntimeError};
 use crate::runtime::events::Envelope;
 use crate::runtime::rpc_contract::methods as rpc_methods;
+use crate::runtime::state::TurnStatus;
 use serde_json::Value;
 use tokio::sync::{broadcast, mpsc};
 
 use super::WebError;
 
+/// `turn/interrupt` reason sent by `WebAdapter::cancel_turn`.
+const TURN_CANCEL_REASON: &str = "user-cancel";
+
 pub type WebAdapterFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
 #[derive(Debug)]
@@ -40,6 +44,20 @@ pub trait WebPluginAdapter: Send + Sync {
         &'a self,
         turn_params: Value,
     ) -> WebAdapterFuture<'a, Result<Value, WebError>>;
+    /// Interrupt `turn_id` on `thread_id`. `Ok(false)` when the turn had already
+    /// finished and nothing was sent. Defaults to `WebError::Unsupported`, so adapters
+    /// written before cancellation existed keep compiling.
+    fn turn_interrupt<'a>(
+        &'a self,
+        _thread_id: &'a str,
+        _turn_id: &'a str,
+    ) -> WebAdapterFuture<'a, Result<bool, WebError>> {
+        Box::pin(async {
+            Err(WebError::Unsupported(
+                "turn_interrupt is not implemented by this adapter".to_owned(),
+            ))
+        })
+    }
     fn thread_archive<'a>(
         &'a self,
         thread_id: &'a str,
@@ -118,7 +136,32 @@ impl WebPluginAdapter for RuntimeWebAdapter {
             self.runtime
                 .call_raw(rpc_methods::TURN_START, turn_params)
                 .await
-                .map_err(map_rpc_error)
+                .map_err(|err| match err {
+                    RpcError::Draining => WebError::Draining,
+                    other => map_rpc_error(other),
+                })
+        })
+    }
+
+    fn turn_interrupt<'a>(
+        &'a self,
+        thread_id: &'a str,
+        turn_id: &'a str,
+    ) -> WebAdapterFuture<'a, Result<bool, WebError>> {
+        Box::pin(async move {
+            if turn_finished(&self.runtime, thread_id, turn_id) {
+                return Ok(false);
+            }
+            match self
+                .runtime
+                .turn_interrupt_with_reason(thread_id, turn_id, TURN_CANCEL_REASON)
+                .await
+            {
+                Ok(()) => Ok(true),
+                // The turn may finish between the state check and the RPC.
+                Err(_) if turn_finished(&self.runtime, thread_id, turn_id) => Ok(false),
+                Err(err) => Err(map_rpc_error(err)),
+            }
         })
     }
 
@@ -157,6 +200,17 @@ impl WebPluginAdapter for RuntimeWebAdapter {
     }
 }
 
+/// True when the runtime state has seen `turn_id` reach a terminal status.
+/// Unknown turns count as running so the interrupt is still sent.
+fn turn_finished(runtime: &Runtime, thread_id: &str, turn_id: &str) -> bool {
+    runtime
+        .state_snapshot()
+        .threads
+        .get(thread_id)
+        .and_then(|thread| thread.turns.get(turn_id))
+        .is_some_and(|turn| turn.status != TurnStatus::InProgress)
+}
+
 fn map_take_stream_error(err: RuntimeError) -> WebError {
     match err {
         RuntimeError::ServerRequestReceiverTaken => WebError::AlreadyBound,
@@ -8,30 +8,93 @@ use crate::runtime::approvals::ServerRequest;
 use crate::runtime::events::Envelope;
 
 use super::state::{self, WebState};
+use super::tenant_sink::{self, TenantSinkWriter};
 use super::{
-    wire, ApprovalResponsePayload, CloseSessionResponse, CreateSessionRequest,
-    CreateSessionResponse, CreateTurnRequest, CreateTurnResponse, WebAdapterConfig, WebError,
-    WebPluginAdapter,
+    wire, ApprovalResponsePayload, CancelTurnResponse, CloseSessionResponse, CreateSessionRequest,
+    CreateSessionResponse, CreateTurnRequest, CreateTurnResponse, TenantSinkFactory,
+    WebAdapterConfig, WebError, WebPluginAdapter,
 };
 
 // --- routing ---
 
-/// Route one live envelope to the owning session topic.
-/// Allocation: none. Complexity: O(1).
-pub(super) async fn route_session_event(state: &Arc<RwLock<WebState>>, envelope: Envelope) {
+/// Route one live envelope to the owning session topic, then queue it for the owning
+/// tenant's sink writer when a factory is configured. Never waits on a sink.
+/// Allocation: one envelope clone when a tenant sink is active. Complexity: O(1).
+pub(super) async fn route_session_event(
+    state: &Arc<RwLock<WebState>>,
+    tenant_sink_factory: Option<&TenantSinkFactory>,
+    envelope: Envelope,
+) {
     let Some(thread_id) = envelope.thread_id.as_deref() else {
         return;
     };
 
-    let sender = {
+    let (sender, tenant_id) = {
         let guard = state.read().await;
         let Some(session_id) = guard.thread_to_session.get(thread_id) else {
             return;
         };
-        guard.event_topics.get(session_id).cloned()
+        let tenant_id = guard
+            .sessions
+            .get(session_id)
+            .map(|session| session.tenant_id.clone());
+        (guard.event_topics.get(session_id).cloned(), tenant_id)
+    };
+    let (Some(factory), Some(tenant_id)) = (tenant_sink_factory, tenant_id) else {
+        if let Some(sender) = sender {
+            let _ = sender.send(envelope);
+        }
+        return;
     };
     if let Some(sender) = sender {
-        let _ = sender.send(envelope);
+        let _ = sender.send(envelope.clone());
+    }
+    enqueue_tenant_envelope(state, factory, &tenant_id, envelope).await;
+}
+
+/// Queue `envelope` on the writer for `tenant_id`, building the sink through `factory`
+/// on first use. The factory runs under the state write lock so each tenant has one writer.
+async fn enqueue_tenant_envelope(
+    state: &Arc<RwLock<WebState>>,
+    factory: &TenantSinkFactory,
+    tenant_id: &str,
+    envelope: Envelope,
+) {
+    if let Some(writer) = state.read().await.tenant_sinks.get(tenant_id) {
+        writer.enqueue(envelope);
+        return;
+    }
+    let mut guard = state.write().await;
+    guard
+        .tenant_sinks
+        .entry(tenant_id.to_owned())
+        .or_insert_with(|| TenantSinkWriter::spawn(tenant_id, factory(tenant_id)))
+        .enqueue(envelope);
+}
+
+/// Record that the routing task lagged past `skipped` live envelopes and write a
+/// `runtime/live/lagged` gap marker to every tenant sink, so audit logs show the hole.
+/// Allocation: one marker per tenant. Complexity: O(t), t = tenant sink count.
+pub(super) async fn record_live_lag(state: &Arc<RwLock<WebState>>, skipped: u64) {
+    let mut guard = state.write().await;
+    guard.live_events_lagged = guard.live_events_lagged.saturating_add(skipped);
+    tracing::warn!(skipped, "web routing lagged; live envelopes were skipped");
+    for writer in guard.tenant_sinks.values() {
+        writer.enqueue(tenant_sink::lagged_marker(skipped));
+    }
+}
+
+/// Close every tenant sink writer, waiting until queued envelopes are written and flushed.
+pub(super) async fn close_tenant_sinks(state: &Arc<RwLock<WebState>>) {
+    let writers: Vec<TenantSinkWriter> = state
+        .write()
+        .await
+        .tenant_sinks
+        .drain()
+        .map(|(_, writer)| writer)
+        .collect();
+    for writer in writers {
+        writer.close().await;
     }
 }
 
@@ -228,7 +291,7 @@ impl Drop for SessionCloseRollbackGuard {
 pub(super) async fn create_session(
     adapter: &Arc<dyn WebPluginAdapter>,
     state: &Arc<RwLock<WebState>>,
-    config: WebAdapterConfig,
+    config: &WebAdapterConfig,
     tenant_id: &str,
     request: CreateSessionRequest,
 ) -> Result<CreateSessionResponse, WebError> {
@@ -311,6 +374,9 @@ pub(super) async fn close_session(
         Ok(()) => {
             let closed = state::finalize_close_owned_session(state, tenant_id, session_id).await?;
             close_guard.disarm();
+            if let Some(writer) = state::take_idle_tenant_sink(state, tenant_id).await {
+                writer.close().await;
+            }
             Ok(CloseSessionResponse {
                 thread_id: closed.thread_id,
                 archived: true,
@@ -349,6 +415,26 @@ pub(super) async fn create_turn(
     Ok(CreateTurnResponse { turn_id })
 }
 
+pub(super) async fn cancel_turn(
+    adapter: &Arc<dyn WebPluginAdapter>,
+    state: &Arc<RwLock<WebState>>,
+    tenant_id: &str,
+    session_id: &str,
+    turn_id: &str,
+) -> Result<CancelTurnResponse, WebError> {
+    if turn_id.trim().is_empty() {
+        return Err(WebError::InvalidTurnPayload(
+            "turnId must not be empty".to_owned(),
+        ));
+    }
+    let session = state::load_owned_session(state, tenant_id, session_id).await?;
+    let interrupted = adapter.turn_interrupt(&session.thread_id, turn_id).await?;
+    Ok(CancelTurnResponse {
+        turn_id: turn_id.to_owned(),
+        interrupted,
+    })
+}
+
 // --- subscription_service ---
 
 pub(super) async fn subscribe_session_events(
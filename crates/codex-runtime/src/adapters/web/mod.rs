@@ -10,6 +10,7 @@ mod adapter;
 mod handlers;
 mod service;
 mod state;
+mod tenant_sink;
 mod wire;
 
 #[cfg(test)]
@@ -19,8 +20,9 @@ pub use adapter::{RuntimeWebAdapter, WebPluginAdapter, WebRuntimeStreams};
 mod types;
 
 pub use types::{
-    ApprovalResponsePayload, CloseSessionResponse, CreateSessionRequest, CreateSessionResponse,
-    CreateTurnRequest, CreateTurnResponse, WebAdapterConfig, WebError,
+    ApprovalResponsePayload, CancelTurnResponse, CloseSessionResponse, CreateSessionRequest,
+    CreateSessionResponse, CreateTurnRequest, CreateTurnResponse, TenantSinkFactory,
+    WebAdapterConfig, WebError,
 };
 
 #[derive(Clone)]
@@ -67,8 +69,12 @@ impl WebAdapter {
     ) -> Result<Self, WebError> {
         let streams = service::prepare_spawn(&adapter, &config).await?;
         let state = Arc::new(RwLock::new(state::WebState::default()));
-        let handles =
-            service::spawn_routing_tasks(Arc::clone(&adapter), Arc::clone(&state), streams);
+        let handles = service::spawn_routing_tasks(
+            Arc::clone(&adapter),
+            Arc::clone(&state),
+            config.tenant_sink_factory.clone(),
+            streams,
+        );
         let background_tasks = Arc::new(BackgroundTasks::new(handles));
 
         Ok(Self {
@@ -84,7 +90,7 @@ impl WebAdapter {
         tenant_id: &str,
         request: CreateSessionRequest,
     ) -> Result<CreateSessionResponse, WebError> {
-        handlers::create_session(&self.adapter, &self.state, self.config, tenant_id, request).await
+        handlers::create_session(&self.adapter, &self.state, &self.config, tenant_id, request).await
     }
 
     pub async fn create_turn(
@@ -96,6 +102,17 @@ impl WebAdapter {
         handlers::create_turn(&self.adapter, &self.state, tenant_id, session_id, request).await
     }
 
+    /// Interrupt one turn of the session's thread (e.g. a browser "stop" button).
+    /// Succeeds with `interrupted: false` when the turn already finished.
+    pub async fn cancel_turn(
+        &self,
+        tenant_id: &str,
+        session_id: &str,
+        turn_id: &str,
+    ) -> Result<CancelTurnResponse, WebError> {
+        handlers::cancel_turn(&self.adapter, &self.state, tenant_id, session_id, turn_id).await
+    }
+
     pub async fn close_session(
         &self,
         tenant_id: &str,
@@ -138,6 +155,18 @@ impl WebAdapter {
         .await
     }
 
+    /// Stop routing and close every tenant sink: queued envelopes are written and each
+    /// sink is flushed before this returns. Affects every clone of this adapter.
+    pub async fn shutdown(&self) {
+        self.background_tasks.abort_all();
+        handlers::close_tenant_sinks(&self.state).await;
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn debug_live_events_lagged(&self) -> u64 {
+        self.state.read().await.live_events_lagged
+    }
+
     #[cfg(test)]
     pub(crate) async fn debug_server_request_route_miss_counts(&self) -> (u64, u64, u64) {
         let guard = self.state.read().await;
@@ -6,7 +6,9 @@ use crate::plugin::PluginContractVersion;
 use crate::runtime::rpc_contract::methods as events;
 
 use super::state::WebState;
-use super::{handlers, WebAdapterConfig, WebError, WebPluginAdapter, WebRuntimeStreams};
+use super::{
+    handlers, TenantSinkFactory, WebAdapterConfig, WebError, WebPluginAdapter, WebRuntimeStreams,
+};
 
 pub(super) async fn prepare_spawn(
     adapter: &Arc<dyn WebPluginAdapter>,
@@ -14,12 +16,15 @@ pub(super) async fn prepare_spawn(
 ) -> Result<WebRuntimeStreams, WebError> {
     validate_web_adapter_config(config)?;
     ensure_adapter_contract_compatible(adapter.as_ref())?;
-    adapter.take_streams().await
+    tokio::time::timeout(config.bind_timeout, adapter.take_streams())
+        .await
+        .map_err(|_| WebError::BindTimeout(config.bind_timeout))?
 }
 
 pub(super) fn spawn_routing_tasks(
     adapter: Arc<dyn WebPluginAdapter>,
     state: Arc<RwLock<WebState>>,
+    tenant_sink_factory: Option<TenantSinkFactory>,
     streams: WebRuntimeStreams,
 ) -> Vec<tokio::task::AbortHandle> {
     let WebRuntimeStreams {
@@ -35,9 +40,17 @@ pub(super) fn spawn_routing_tasks(
         loop {
             match live_rx.recv().await {
                 Ok(envelope) => {
-                    handle_live_event(&state_for_events, &adapter_for_events, envelope).await;
+                    handle_live_event(
+                        &state_for_events,
+                        &adapter_for_events,
+                        tenant_sink_factory.as_ref(),
+                        envelope,
+                    )
+                    .await;
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    handlers::record_live_lag(&state_for_events, skipped).await;
                 }
-                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
                 Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
             }
         }
@@ -80,6 +93,11 @@ fn validate_web_adapter_config(config: &WebAdapterConfig) -> Result<(), WebError
         "session_approval_channel_capacity",
         config.session_approval_channel_capacity,
     )?;
+    if config.bind_timeout.is_zero() {
+        return Err(WebError::InvalidConfig(
+            "bind_timeout must be > 0".to_owned(),
+        ));
+    }
     Ok(())
 }
 
@@ -95,10 +113,11 @@ fn ensure_positive_capacity(name: &str, value: usize) -> Result<(), WebError> {
 async fn handle_live_event(
     state: &Arc<RwLock<WebState>>,
     adapter: &Arc<dyn WebPluginAdapter>,
+    tenant_sink_factory: Option<&TenantSinkFactory>,
     envelope: crate::runtime::events::Envelope,
 ) {
     let should_prune = envelope.method.as_deref() == Some(events::APPROVAL_ACK);
-    handlers::route_session_event(state, envelope).await;
+    handlers::route_session_event(state, tenant_sink_factory, envelope).await;
     if should_prune {
         handlers::prune_stale_approval_index(state, adapter).await;
     }
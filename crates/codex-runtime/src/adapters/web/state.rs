@@ -3,9 +3,11 @@ use std::sync::Arc;
 
 use crate::runtime::approvals::ServerRequest;
 use crate::runtime::events::Envelope;
+use crate::runtime::IdKind;
 use tokio::sync::{broadcast, RwLock};
 use uuid::Uuid;
 
+use super::tenant_sink::TenantSinkWriter;
 use super::{CreateSessionResponse, WebAdapterConfig, WebError};
 
 const THREAD_INDEX_INCONSISTENT: &str = "thread index points to missing session";
@@ -42,6 +44,11 @@ pub(super) struct WebState {
     pub(super) approval_to_session: HashMap<String, String>,
     pub(super) queued_approvals: HashMap<String, Vec<ServerRequest>>,
     pub(super) server_request_route_miss: ServerRequestRouteMissMetrics,
+    /// Lazily built tenant sink writers, keyed by tenant id; removed with the tenant's
+    /// last session.
+    pub(super) tenant_sinks: HashMap<String, TenantSinkWriter>,
+    /// Live envelopes the routing task skipped because it lagged the runtime broadcast.
+    pub(super) live_events_lagged: u64,
 }
 
 pub(super) async fn assert_thread_access(
@@ -59,7 +66,7 @@ pub(super) async fn assert_thread_access(
 
 pub(super) async fn register_session(
     state: &Arc<RwLock<WebState>>,
-    config: WebAdapterConfig,
+    config: &WebAdapterConfig,
     tenant_id: &str,
     artifact_id: &str,
     thread_id: &str,
@@ -73,7 +80,10 @@ pub(super) async fn register_session(
         });
     }
 
-    let session_id = new_session_id();
+    let session_id = match &config.id_generator {
+        Some(id_generator) => id_generator.next_id(IdKind::WebSession),
+        None => new_session_id(),
+    };
     let session = SessionRecord {
         session_id: session_id.clone(),
         tenant_id: tenant_id.to_owned(),
@@ -162,6 +172,24 @@ pub(super) async fn finalize_close_owned_session(
     Ok(session)
 }
 
+/// Remove the sink writer of `tenant_id` once the tenant has no sessions left.
+/// A later session for the tenant builds a fresh sink through the factory.
+/// Allocation: none. Complexity: O(s), s = session count.
+pub(super) async fn take_idle_tenant_sink(
+    state: &Arc<RwLock<WebState>>,
+    tenant_id: &str,
+) -> Option<TenantSinkWriter> {
+    let mut state = state.write().await;
+    if state
+        .sessions
+        .values()
+        .any(|session| session.tenant_id == tenant_id)
+    {
+        return None;
+    }
+    state.tenant_sinks.remove(tenant_id)
+}
+
 pub(super) async fn rollback_close_owned_session(
     state: &Arc<RwLock<WebState>>,
     tenant_id: &str,
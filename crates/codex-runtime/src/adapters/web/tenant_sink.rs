@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::runtime::events::{Direction, Envelope, MsgKind};
+use crate::runtime::now_millis;
+use crate::runtime::rpc_contract::methods;
+use crate::runtime::sink::EventSink;
+
+/// One tenant's sink behind its own queue and writer task, so a slow sink never stalls
+/// the shared routing task or other tenants. The queue is unbounded: a stalled sink grows
+/// memory instead of losing audit events. Dropping the writer closes the queue; the task
+/// still drains what is queued and flushes the sink before it exits.
+pub(super) struct TenantSinkWriter {
+    tx: mpsc::UnboundedSender<Envelope>,
+    task: JoinHandle<()>,
+}
+
+impl TenantSinkWriter {
+    /// Allocation: one unbounded channel and one task. Complexity: O(1).
+    pub(super) fn spawn(tenant_id: &str, sink: Arc<dyn EventSink>) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let task = tokio::spawn(write_loop(tenant_id.to_owned(), sink, rx));
+        Self { tx, task }
+    }
+
+    /// Queue one envelope without waiting for the sink.
+    /// Allocation: none beyond the queue slot. Complexity: O(1).
+    pub(super) fn enqueue(&self, envelope: Envelope) {
+        let _ = self.tx.send(envelope);
+    }
+
+    /// Close the queue and wait until everything queued is written and the sink flushed.
+    pub(super) async fn close(self) {
+        drop(self.tx);
+        if let Err(err) = self.task.await {
+            tracing::warn!(error = %err, "tenant sink writer join failed");
+        }
+    }
+}
+
+async fn write_loop(
+    tenant_id: String,
+    sink: Arc<dyn EventSink>,
+    mut rx: mpsc::UnboundedReceiver<Envelope>,
+) {
+    while let Some(envelope) = rx.recv().await {
+        if let Err(err) = sink.on_envelope(&envelope).await {
+            tracing::warn!(tenant_id = %tenant_id, error = %err, seq = envelope.seq, "tenant sink write failed");
+        }
+    }
+    if let Err(err) = sink.flush().await {
+        tracing::warn!(tenant_id = %tenant_id, error = %err, "tenant sink flush failed");
+    }
+}
+
+/// Gap marker written to tenant sinks when the routing task lagged past `skipped` live
+/// envelopes (`runtime/live/lagged`, `params = {skipped}`). It carries `seq` 0, which the
+/// runtime never assigns, so it cannot be mistaken for a routed envelope.
+/// Allocation: one JSON payload. Complexity: O(1).
+pub(super) fn lagged_marker(skipped: u64) -> Envelope {
+    Envelope {
+        seq: 0,
+        ts_millis: now_millis(),
+        direction: Direction::Inbound,
+        kind: MsgKind::Notification,
+        rpc_id: None,
+        method: Some(Arc::from(methods::RUNTIME_LIVE_LAGGED)),
+        thread_id: None,
+        turn_id: None,
+        item_id: None,
+        json: Arc::new(json!({
+            "method": methods::RUNTIME_LIVE_LAGGED,
+            "params": { "skipped": skipped },
+        })),
+    }
+}
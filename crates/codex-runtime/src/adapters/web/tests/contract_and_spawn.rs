@@ -27,6 +27,19 @@ async fn spawn_with_adapter_accepts_compatible_minor_contract_version() {
     drop(web);
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn turn_interrupt_defaults_to_unsupported() {
+    let adapter: Arc<dyn WebPluginAdapter> = Arc::new(CompatibleMinorWebAdapter);
+    let err = adapter
+        .turn_interrupt("thr_1", "turn_1")
+        .await
+        .expect_err("adapters without turn_interrupt report unsupported");
+    assert!(
+        matches!(err, WebError::Unsupported(ref message) if message.contains("turn_interrupt")),
+        "unexpected error: {err:?}"
+    );
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn web_adapter_uses_plugin_boundary_without_runtime_dependency() {
     let (_live_tx, live_rx) = broadcast::channel::<Envelope>(8);
@@ -69,6 +82,12 @@ async fn web_adapter_uses_plugin_boundary_without_runtime_dependency() {
         .expect("create turn");
     assert_eq!(turn.turn_id, "turn_fake_web");
 
+    let cancelled = web
+        .cancel_turn("tenant_a", &session.session_id, &turn.turn_id)
+        .await
+        .expect("cancel turn");
+    assert!(cancelled.interrupted);
+
     let closed = web
         .close_session("tenant_a", &session.session_id)
         .await
@@ -81,6 +100,10 @@ async fn web_adapter_uses_plugin_boundary_without_runtime_dependency() {
     assert_eq!(state.start_calls, 1);
     assert_eq!(state.turn_start_calls.len(), 1);
     assert_eq!(state.turn_start_calls[0]["threadId"], "thr_fake_web");
+    assert_eq!(
+        state.interrupt_calls,
+        vec![("thr_fake_web".to_owned(), "turn_fake_web".to_owned())]
+    );
     assert_eq!(state.archive_calls, vec!["thr_fake_web".to_owned()]);
 }
 
@@ -159,6 +182,9 @@ async fn spawn_rejects_zero_capacity_config() {
         WebAdapterConfig {
             session_event_channel_capacity: 0,
             session_approval_channel_capacity: 128,
+            id_generator: None,
+            tenant_sink_factory: None,
+            bind_timeout: Duration::from_secs(5),
         },
     )
     .await
@@ -173,6 +199,9 @@ async fn spawn_rejects_zero_capacity_config() {
         WebAdapterConfig {
             session_event_channel_capacity: 128,
             session_approval_channel_capacity: 0,
+            id_generator: None,
+            tenant_sink_factory: None,
+            bind_timeout: Duration::from_secs(5),
         },
     )
     .await
@@ -185,6 +214,31 @@ async fn spawn_rejects_zero_capacity_config() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn spawn_with_adapter_fails_when_stream_bind_stalls() {
+    let adapter: Arc<dyn WebPluginAdapter> = Arc::new(StalledBindWebAdapter);
+    let config = WebAdapterConfig::default().with_bind_timeout(Duration::from_millis(50));
+    let result = timeout(
+        Duration::from_secs(2),
+        WebAdapter::spawn_with_adapter(adapter, config),
+    )
+    .await
+    .expect("spawn must not hang on a stalled bind");
+    let err = match result {
+        Ok(_) => panic!("stalled bind must fail"),
+        Err(err) => err,
+    };
+    assert_eq!(err, WebError::BindTimeout(Duration::from_millis(50)));
+
+    let adapter: Arc<dyn WebPluginAdapter> = Arc::new(StalledBindWebAdapter);
+    let config = WebAdapterConfig::default().with_bind_timeout(Duration::ZERO);
+    let err = match WebAdapter::spawn_with_adapter(adapter, config).await {
+        Ok(_) => panic!("zero bind timeout must be rejected"),
+        Err(err) => err,
+    };
+    assert!(matches!(err, WebError::InvalidConfig(_)));
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn spawn_rejects_second_adapter_on_same_runtime() {
     let runtime = spawn_mock_runtime().await;
@@ -137,6 +137,7 @@ struct FakeWebAdapterState {
     resume_calls: Vec<(String, ThreadStartParams)>,
     resume_result_thread_id: Option<String>,
     turn_start_calls: Vec<Value>,
+    interrupt_calls: Vec<(String, String)>,
     archive_calls: Vec<String>,
     archive_failures_remaining: usize,
     archive_block_on: Option<Arc<tokio::sync::Notify>>,
@@ -155,6 +156,7 @@ impl Default for FakeWebAdapterState {
             resume_calls: Vec::new(),
             resume_result_thread_id: None,
             turn_start_calls: Vec::new(),
+            interrupt_calls: Vec::new(),
             archive_calls: Vec::new(),
             archive_failures_remaining: 0,
             archive_block_on: None,
@@ -214,6 +216,20 @@ impl WebPluginAdapter for FakeWebAdapter {
         })
     }
 
+    fn turn_interrupt<'a>(
+        &'a self,
+        thread_id: &'a str,
+        turn_id: &'a str,
+    ) -> WebAdapterFuture<'a, Result<bool, WebError>> {
+        Box::pin(async move {
+            let mut state = self.state.lock().expect("fake adapter state lock");
+            state
+                .interrupt_calls
+                .push((thread_id.to_owned(), turn_id.to_owned()));
+            Ok(true)
+        })
+    }
+
     fn thread_archive<'a>(
         &'a self,
         thread_id: &'a str,
@@ -318,6 +334,57 @@ impl WebPluginAdapter for IncompatibleWebAdapter {
     }
 }
 
+/// Adapter whose `take_streams` never resolves, as when another consumer holds the streams.
+#[derive(Clone)]
+struct StalledBindWebAdapter;
+
+impl WebPluginAdapter for StalledBindWebAdapter {
+    fn take_streams<'a>(&'a self) -> WebAdapterFuture<'a, Result<WebRuntimeStreams, WebError>> {
+        Box::pin(std::future::pending())
+    }
+
+    fn thread_start<'a>(
+        &'a self,
+        _params: ThreadStartParams,
+    ) -> WebAdapterFuture<'a, Result<String, WebError>> {
+        Box::pin(async move { panic!("thread_start must not run before bind") })
+    }
+
+    fn thread_resume<'a>(
+        &'a self,
+        _thread_id: &'a str,
+        _params: ThreadStartParams,
+    ) -> WebAdapterFuture<'a, Result<String, WebError>> {
+        Box::pin(async move { panic!("thread_resume must not run before bind") })
+    }
+
+    fn turn_start<'a>(
+        &'a self,
+        _turn_params: Value,
+    ) -> WebAdapterFuture<'a, Result<Value, WebError>> {
+        Box::pin(async move { panic!("turn_start must not run before bind") })
+    }
+
+    fn thread_archive<'a>(
+        &'a self,
+        _thread_id: &'a str,
+    ) -> WebAdapterFuture<'a, Result<(), WebError>> {
+        Box::pin(async move { panic!("thread_archive must not run before bind") })
+    }
+
+    fn respond_approval_ok<'a>(
+        &'a self,
+        _approval_id: &'a str,
+        _result: Value,
+    ) -> WebAdapterFuture<'a, Result<(), WebError>> {
+        Box::pin(async move { panic!("respond_approval_ok must not run before bind") })
+    }
+
+    fn pending_approval_ids(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
 #[derive(Clone)]
 struct CompatibleMinorWebAdapter;
 
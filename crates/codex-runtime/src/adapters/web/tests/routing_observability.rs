@@ -1,4 +1,5 @@
 use super::*;
+use crate::runtime::sink::{EventSink, EventSinkFuture};
 use tokio::time::{sleep, Instant};
 
 async fn spawn_fake_web_adapter_with_request_tx(
@@ -102,6 +103,9 @@ async fn queued_approvals_are_bounded_by_session_config_capacity() {
     let (web, request_tx) = spawn_fake_web_adapter_with_request_tx_and_config(WebAdapterConfig {
         session_event_channel_capacity: 8,
         session_approval_channel_capacity: 1,
+        id_generator: None,
+        tenant_sink_factory: None,
+        bind_timeout: Duration::from_secs(5),
     })
     .await;
     let session = web
@@ -150,3 +154,238 @@ async fn queued_approvals_are_bounded_by_session_config_capacity() {
         "queue should be capped at configured session capacity",
     );
 }
+
+#[derive(Default)]
+struct RecordingSink {
+    seqs: Mutex<Vec<u64>>,
+    methods: Mutex<Vec<String>>,
+    flushes: Mutex<usize>,
+}
+
+impl EventSink for RecordingSink {
+    fn on_envelope<'a>(&'a self, envelope: &'a Envelope) -> EventSinkFuture<'a> {
+        Box::pin(async move {
+            self.seqs.lock().expect("sink lock").push(envelope.seq);
+            self.methods
+                .lock()
+                .expect("sink lock")
+                .push(envelope.method.as_deref().unwrap_or_default().to_owned());
+            Ok(())
+        })
+    }
+
+    fn flush(&self) -> EventSinkFuture<'_> {
+        Box::pin(async move {
+            *self.flushes.lock().expect("sink lock") += 1;
+            Ok(())
+        })
+    }
+}
+
+fn thread_notification(seq: u64, thread_id: &str) -> Envelope {
+    Envelope {
+        seq,
+        ts_millis: 0,
+        direction: Direction::Inbound,
+        kind: MsgKind::Notification,
+        rpc_id: None,
+        method: Some(Arc::<str>::from("item/started")),
+        thread_id: Some(Arc::<str>::from(thread_id)),
+        turn_id: None,
+        item_id: None,
+        json: Arc::new(json!({"method":"item/started","params":{"threadId":thread_id}})),
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn tenant_sink_factory_partitions_routed_events_by_tenant() {
+    let sinks = Arc::new(Mutex::new(Vec::<(String, Arc<RecordingSink>)>::new()));
+    let factory_sinks = Arc::clone(&sinks);
+    let config = WebAdapterConfig::default().with_tenant_sink_factory(Arc::new(move |tenant| {
+        let sink = Arc::new(RecordingSink::default());
+        factory_sinks
+            .lock()
+            .expect("sinks lock")
+            .push((tenant.to_owned(), Arc::clone(&sink)));
+        sink
+    }));
+
+    let (live_tx, live_rx) = broadcast::channel::<Envelope>(8);
+    let (_request_tx, request_rx) = tokio::sync::mpsc::channel::<ServerRequest>(8);
+    let fake_state = Arc::new(Mutex::new(FakeWebAdapterState::default()));
+    let adapter: Arc<dyn WebPluginAdapter> = Arc::new(FakeWebAdapter {
+        state: Arc::clone(&fake_state),
+        streams: Arc::new(Mutex::new(Some(WebRuntimeStreams {
+            request_rx,
+            live_rx,
+        }))),
+    });
+    let web = WebAdapter::spawn_with_adapter(adapter, config)
+        .await
+        .expect("spawn with fake adapter");
+
+    for (tenant, thread_id) in [("tenant_a", "thr_a"), ("tenant_b", "thr_b")] {
+        fake_state.lock().expect("fake state lock").start_thread_id = thread_id.to_owned();
+        web.create_session(
+            tenant,
+            CreateSessionRequest {
+                artifact_id: format!("doc:{tenant}"),
+                model: None,
+                thread_id: None,
+            },
+        )
+        .await
+        .expect("create session");
+    }
+
+    for (seq, thread_id) in [(1, "thr_a"), (2, "thr_b"), (3, "thr_a"), (4, "thr_unknown")] {
+        live_tx
+            .send(thread_notification(seq, thread_id))
+            .expect("send live event");
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    loop {
+        let routed: usize = sinks
+            .lock()
+            .expect("sinks lock")
+            .iter()
+            .map(|(_, sink)| sink.seqs.lock().expect("sink lock").len())
+            .sum();
+        if routed == 3 {
+            break;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "tenant sinks saw {routed} envelopes"
+        );
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    let sinks = sinks.lock().expect("sinks lock");
+    let by_tenant: Vec<(String, Vec<u64>)> = sinks
+        .iter()
+        .map(|(tenant, sink)| (tenant.clone(), sink.seqs.lock().expect("sink lock").clone()))
+        .collect();
+    assert_eq!(
+        by_tenant,
+        vec![
+            ("tenant_a".to_owned(), vec![1, 3]),
+            ("tenant_b".to_owned(), vec![2]),
+        ]
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn tenant_sink_gets_gap_marker_on_lag_and_is_flushed_on_shutdown() {
+    let sink = Arc::new(RecordingSink::default());
+    let factory_sink = Arc::clone(&sink);
+    let config = WebAdapterConfig::default()
+        .with_tenant_sink_factory(Arc::new(move |_| factory_sink.clone()));
+
+    let (live_tx, live_rx) = broadcast::channel::<Envelope>(2);
+    let (_request_tx, request_rx) = tokio::sync::mpsc::channel::<ServerRequest>(8);
+    let fake_state = Arc::new(Mutex::new(FakeWebAdapterState::default()));
+    fake_state.lock().expect("fake state lock").start_thread_id = "thr_a".to_owned();
+    let adapter: Arc<dyn WebPluginAdapter> = Arc::new(FakeWebAdapter {
+        state: Arc::clone(&fake_state),
+        streams: Arc::new(Mutex::new(Some(WebRuntimeStreams {
+            request_rx,
+            live_rx,
+        }))),
+    });
+    let web = WebAdapter::spawn_with_adapter(adapter, config)
+        .await
+        .expect("spawn with fake adapter");
+    web.create_session(
+        "tenant_a",
+        CreateSessionRequest {
+            artifact_id: "doc:tenant_a".to_owned(),
+            model: None,
+            thread_id: None,
+        },
+    )
+    .await
+    .expect("create session");
+
+    live_tx
+        .send(thread_notification(1, "thr_a"))
+        .expect("send live event");
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while sink.seqs.lock().expect("sink lock").is_empty() {
+        assert!(
+            Instant::now() < deadline,
+            "first envelope never reached sink"
+        );
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    // No await between sends: the capacity-2 broadcast overflows before routing runs.
+    for seq in 2..=6 {
+        live_tx
+            .send(thread_notification(seq, "thr_a"))
+            .expect("send live event");
+    }
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while web.debug_live_events_lagged().await == 0 {
+        assert!(Instant::now() < deadline, "routing never reported lag");
+        sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(web.debug_live_events_lagged().await, 3);
+
+    web.shutdown().await;
+    assert_eq!(*sink.flushes.lock().expect("sink lock"), 1);
+    assert_eq!(*sink.seqs.lock().expect("sink lock"), vec![1, 0, 5, 6]);
+    assert_eq!(
+        sink.methods.lock().expect("sink lock")[1],
+        crate::runtime::rpc_contract::methods::RUNTIME_LIVE_LAGGED
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn closing_last_tenant_session_flushes_its_sink() {
+    let sink = Arc::new(RecordingSink::default());
+    let factory_sink = Arc::clone(&sink);
+    let config = WebAdapterConfig::default()
+        .with_tenant_sink_factory(Arc::new(move |_| factory_sink.clone()));
+
+    let (live_tx, live_rx) = broadcast::channel::<Envelope>(8);
+    let (_request_tx, request_rx) = tokio::sync::mpsc::channel::<ServerRequest>(8);
+    let fake_state = Arc::new(Mutex::new(FakeWebAdapterState::default()));
+    fake_state.lock().expect("fake state lock").start_thread_id = "thr_a".to_owned();
+    let adapter: Arc<dyn WebPluginAdapter> = Arc::new(FakeWebAdapter {
+        state: Arc::clone(&fake_state),
+        streams: Arc::new(Mutex::new(Some(WebRuntimeStreams {
+            request_rx,
+            live_rx,
+        }))),
+    });
+    let web = WebAdapter::spawn_with_adapter(adapter, config)
+        .await
+        .expect("spawn with fake adapter");
+    let session = web
+        .create_session(
+            "tenant_a",
+            CreateSessionRequest {
+                artifact_id: "doc:tenant_a".to_owned(),
+                model: None,
+                thread_id: None,
+            },
+        )
+        .await
+        .expect("create session");
+
+    live_tx
+        .send(thread_notification(1, "thr_a"))
+        .expect("send live event");
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while sink.seqs.lock().expect("sink lock").is_empty() {
+        assert!(Instant::now() < deadline, "envelope never reached sink");
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    web.close_session("tenant_a", &session.session_id)
+        .await
+        .expect("close session");
+    assert_eq!(*sink.flushes.lock().expect("sink lock"), 1);
+}
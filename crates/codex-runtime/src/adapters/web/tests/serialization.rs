@@ -76,3 +76,32 @@ fn extract_thread_id_from_server_request_params_supports_common_shapes() {
         Some("thr_nested_params".to_owned())
     );
 }
+
+#[test]
+fn normalize_turn_start_params_names_the_offending_field() {
+    let detail = |task: Value| match wire::normalize_turn_start_params("thr_1", task) {
+        Err(WebError::InvalidTurnPayload(detail)) => detail,
+        other => panic!("expected invalid turn payload, got {other:?}"),
+    };
+
+    assert_eq!(
+        detail(json!("hello")),
+        "task must be a JSON object, got string"
+    );
+    assert_eq!(
+        detail(json!({"input": "hello"})),
+        "`input` must be an array, got string"
+    );
+    assert_eq!(
+        detail(json!({"input": [{"type": "text", "text": "ok"}, {"text": "no type"}]})),
+        "`input[1]` must be an object with a string `type`"
+    );
+    let passthrough = json!({"approvalPolicy": "sometimes", "sandboxPolicy": "readOnly"});
+    let normalized = wire::normalize_turn_start_params("thr_1", passthrough)
+        .expect("policies and a missing input are left to the server");
+    assert_eq!(normalized["approvalPolicy"], "sometimes");
+
+    let normalized =
+        wire::normalize_turn_start_params("thr_1", turn_task("ok")).expect("valid task");
+    assert_eq!(normalized["threadId"], "thr_1");
+}
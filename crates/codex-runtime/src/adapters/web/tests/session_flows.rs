@@ -17,6 +17,35 @@ impl Lcg {
     }
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn sequential_id_generator_makes_session_ids_reproducible() {
+    let runtime = spawn_mock_runtime().await;
+    let config = WebAdapterConfig::default()
+        .with_id_generator(Arc::new(crate::runtime::SequentialIdGenerator::new()));
+    let adapter = WebAdapter::spawn(runtime.clone(), config)
+        .await
+        .expect("adapter spawn");
+
+    let mut session_ids = Vec::new();
+    for artifact_id in ["doc:a", "doc:b"] {
+        let session = adapter
+            .create_session(
+                "tenant_a",
+                CreateSessionRequest {
+                    artifact_id: artifact_id.to_owned(),
+                    model: None,
+                    thread_id: None,
+                },
+            )
+            .await
+            .expect("create session");
+        session_ids.push(session.session_id);
+    }
+    assert_eq!(session_ids, ["id-1", "id-2"]);
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn sessions_turns_and_events_are_isolated() {
     let runtime = spawn_mock_runtime().await;
@@ -403,6 +432,68 @@ async fn close_session_removes_session_indexes() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn cancel_turn_interrupts_running_turns_and_noops_on_finished_ones() {
+    let runtime = spawn_mock_runtime().await;
+    let adapter = WebAdapter::spawn(runtime.clone(), WebAdapterConfig::default())
+        .await
+        .expect("adapter spawn");
+
+    let session = adapter
+        .create_session(
+            "tenant_a",
+            CreateSessionRequest {
+                artifact_id: "doc:cancel".to_owned(),
+                model: None,
+                thread_id: None,
+            },
+        )
+        .await
+        .expect("create session");
+    let turn = adapter
+        .create_turn(
+            "tenant_a",
+            &session.session_id,
+            CreateTurnRequest {
+                task: turn_task("finish"),
+            },
+        )
+        .await
+        .expect("create turn");
+
+    // The mock completes every turn before answering turn/start.
+    let finished = adapter
+        .cancel_turn("tenant_a", &session.session_id, &turn.turn_id)
+        .await
+        .expect("cancel finished turn");
+    assert_eq!(
+        finished,
+        CancelTurnResponse {
+            turn_id: turn.turn_id.clone(),
+            interrupted: false,
+        }
+    );
+
+    let running = adapter
+        .cancel_turn("tenant_a", &session.session_id, "turn_running")
+        .await
+        .expect("cancel running turn");
+    assert!(running.interrupted);
+
+    let err = adapter
+        .cancel_turn("tenant_b", &session.session_id, &turn.turn_id)
+        .await
+        .expect_err("other tenant must not cancel");
+    assert_eq!(err, WebError::Forbidden);
+    let err = adapter
+        .cancel_turn("tenant_a", &session.session_id, " ")
+        .await
+        .expect_err("empty turn id");
+    assert!(matches!(err, WebError::InvalidTurnPayload(_)));
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn close_session_rolls_back_when_archive_fails() {
     let runtime = spawn_mock_runtime().await;
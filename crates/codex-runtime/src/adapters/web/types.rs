@@ -1,7 +1,17 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
+use crate::runtime::sink::EventSink;
+use crate::runtime::IdGenerator;
+
+/// Builds the event sink for one tenant id; called on the tenant's first routed envelope
+/// and again only after its last session closed (which flushes and drops the old sink).
+pub type TenantSinkFactory = Arc<dyn Fn(&str) -> Arc<dyn EventSink> + Send + Sync>;
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateSessionRequest {
@@ -36,6 +46,14 @@ pub struct CreateTurnResponse {
     pub turn_id: String,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTurnResponse {
+    pub turn_id: String,
+    /// False when the turn had already finished; cancelling it again is a no-op.
+    pub interrupted: bool,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ApprovalResponsePayload {
@@ -45,10 +63,16 @@ pub struct ApprovalResponsePayload {
     pub result: Option<Value>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone)]
 pub struct WebAdapterConfig {
     pub session_event_channel_capacity: usize,
     pub session_approval_channel_capacity: usize,
+    /// Session id source; `None` keeps the built-in `sess_<uuid>` ids.
+    pub id_generator: Option<Arc<dyn IdGenerator>>,
+    /// Per-tenant sink source; `None` disables tenant sinks.
+    pub tenant_sink_factory: Option<TenantSinkFactory>,
+    /// Max wait for `WebPluginAdapter::take_streams` during spawn.
+    pub bind_timeout: Duration,
 }
 
 impl Default for WebAdapterConfig {
@@ -56,22 +80,70 @@ impl Default for WebAdapterConfig {
         Self {
             session_event_channel_capacity: 512,
             session_approval_channel_capacity: 128,
+            id_generator: None,
+            tenant_sink_factory: None,
+            bind_timeout: Duration::from_secs(5),
         }
     }
 }
 
+impl WebAdapterConfig {
+    /// Replace the built-in session id source (e.g. `SequentialIdGenerator` in tests).
+    pub fn with_id_generator(mut self, id_generator: Arc<dyn IdGenerator>) -> Self {
+        self.id_generator = Some(id_generator);
+        self
+    }
+
+    /// Also write every envelope routed to a tenant's sessions into that tenant's sink.
+    /// Each tenant sink gets its own queue and writer task, so a slow sink delays only its
+    /// own writes. If routing lags the runtime broadcast, every sink receives a
+    /// `runtime/live/lagged` gap marker. Sinks are flushed when the tenant's last session
+    /// closes and on `WebAdapter::shutdown`.
+    pub fn with_tenant_sink_factory(mut self, factory: TenantSinkFactory) -> Self {
+        self.tenant_sink_factory = Some(factory);
+        self
+    }
+
+    /// Override how long spawn waits for the adapter to hand over runtime streams.
+    pub fn with_bind_timeout(mut self, bind_timeout: Duration) -> Self {
+        self.bind_timeout = bind_timeout;
+        self
+    }
+}
+
+impl std::fmt::Debug for WebAdapterConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebAdapterConfig")
+            .field(
+                "session_event_channel_capacity",
+                &self.session_event_channel_capacity,
+            )
+            .field(
+                "session_approval_channel_capacity",
+                &self.session_approval_channel_capacity,
+            )
+            .field("id_generator", &self.id_generator.is_some())
+            .field("tenant_sink_factory", &self.tenant_sink_factory.is_some())
+            .field("bind_timeout", &self.bind_timeout)
+            .finish()
+    }
+}
+
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
 pub enum WebError {
     #[error("invalid session")]
     InvalidSession,
     #[error("runtime already bound to a web adapter")]
     AlreadyBound,
+    /// `take_streams` did not finish within `WebAdapterConfig::bind_timeout`.
+    #[error("runtime stream bind timed out after {0:?}")]
+    BindTimeout(Duration),
     #[error("invalid approval")]
     InvalidApproval,
     #[error("invalid config: {0}")]
     InvalidConfig(String),
-    #[error("invalid turn payload")]
-    InvalidTurnPayload,
+    #[error("invalid turn payload: {0}")]
+    InvalidTurnPayload(String),
     #[error("invalid approval payload")]
     InvalidApprovalPayload,
     #[error(
@@ -95,6 +167,12 @@ pub enum WebError {
         existing_artifact_id: String,
         requested_artifact_id: String,
     },
+    /// The runtime refused `turn/start` while reconfiguring; retry after the swap.
+    #[error("runtime is draining for reconfigure")]
+    Draining,
+    /// The adapter does not implement the requested operation.
+    #[error("unsupported: {0}")]
+    Unsupported(String),
     #[error("internal error: {0}")]
     Internal(String),
 }
@@ -1,3 +1,4 @@
+use crate::runtime::api::json_type_name;
 use crate::runtime::events::{Direction, Envelope, MsgKind};
 use crate::runtime::id::{extract_thread_id, parse_result_turn_id};
 use serde::Serialize;
@@ -6,12 +7,21 @@ use serde_json::{json, Value};
 use super::{ApprovalResponsePayload, WebError};
 
 /// Validate and normalize incoming turn payload.
-/// Side effects: none. Allocation: None (mutates in place). Complexity: O(1).
+/// Rejections name the offending field so web clients can fix their request. Only the
+/// task shape and `input` items are checked; policies are left for the server to validate.
+/// Side effects: none. Allocation: None on success (mutates in place).
+/// Complexity: O(n), n = input item count.
 pub(super) fn normalize_turn_start_params(
     thread_id: &str,
     mut task: Value,
 ) -> Result<Value, WebError> {
-    let obj = task.as_object_mut().ok_or(WebError::InvalidTurnPayload)?;
+    let task_type = json_type_name(&task);
+    let obj = task.as_object_mut().ok_or_else(|| {
+        invalid_turn_payload(format!("task must be a JSON object, got {task_type}"))
+    })?;
+    if let Some(input) = obj.get("input") {
+        validate_turn_input(input)?;
+    }
 
     if let Some(existing_thread_id) = obj.get("threadId") {
         match existing_thread_id {
@@ -23,6 +33,27 @@ pub(super) fn normalize_turn_start_params(
     Ok(task)
 }
 
+fn invalid_turn_payload(detail: impl Into<String>) -> WebError {
+    WebError::InvalidTurnPayload(detail.into())
+}
+
+fn validate_turn_input(input: &Value) -> Result<(), WebError> {
+    let Value::Array(items) = input else {
+        return Err(invalid_turn_payload(format!(
+            "`input` must be an array, got {}",
+            json_type_name(input)
+        )));
+    };
+    for (index, item) in items.iter().enumerate() {
+        if !item.get("type").is_some_and(Value::is_string) {
+            return Err(invalid_turn_payload(format!(
+                "`input[{index}]` must be an object with a string `type`"
+            )));
+        }
+    }
+    Ok(())
+}
+
 impl ApprovalResponsePayload {
     pub(super) fn into_result_payload(self) -> Result<Value, WebError> {
         if let Some(result) = self.result {
@@ -15,9 +15,10 @@ mod service;
 pub mod methods {
     pub use crate::runtime::rpc_contract::methods::{
         COMMAND_EXEC, COMMAND_EXEC_OUTPUT_DELTA, COMMAND_EXEC_RESIZE, COMMAND_EXEC_TERMINATE,
-        COMMAND_EXEC_WRITE, SKILLS_CHANGED, SKILLS_LIST, THREAD_ARCHIVE, THREAD_FORK, THREAD_LIST,
-        THREAD_LOADED_LIST, THREAD_READ, THREAD_RESUME, THREAD_ROLLBACK, THREAD_START,
-        TURN_CANCELLED, TURN_COMPLETED, TURN_FAILED, TURN_INTERRUPT, TURN_START,
+        COMMAND_EXEC_WRITE, MODEL_LIST, SKILLS_CHANGED, SKILLS_LIST, THREAD_ARCHIVE, THREAD_FORK,
+        THREAD_GET_METADATA, THREAD_LIST, THREAD_LOADED_LIST, THREAD_READ, THREAD_RESUME,
+        THREAD_ROLLBACK, THREAD_SET_METADATA, THREAD_START, THREAD_UNARCHIVE, TURN_CANCELLED,
+        TURN_COMPLETED, TURN_FAILED, TURN_INTERRUPT, TURN_START,
     };
 }
 
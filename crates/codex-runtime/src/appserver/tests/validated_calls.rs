@@ -26,6 +26,7 @@ async fn request_typed_thread_read_returns_started_thread() {
             ThreadReadParams {
                 thread_id: thread_id.clone(),
                 include_turns: Some(false),
+                last_n_turns: None,
             },
         )
         .await
@@ -727,6 +727,7 @@ mod tests {
                         self.state.turn_counter.fetch_add(1, Ordering::SeqCst)
                     ),
                     assistant_text: "ok".to_owned(),
+                    schema_valid: None,
                 })
             })
         }
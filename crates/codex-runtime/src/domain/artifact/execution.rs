@@ -4,9 +4,9 @@ use serde_json::Value;
 use std::cell::Cell;
 
 use super::models::{
-    apply_doc_patch, compute_revision, map_patch_conflict, validate_doc_patch, ArtifactMeta,
-    ArtifactSession, ArtifactStore, ArtifactTaskKind, ArtifactTaskResult, ArtifactTaskSpec,
-    DocPatch, DomainError, SaveMeta, StoreErr,
+    apply_doc_patch, compute_revision, diff_artifact_meta, map_patch_conflict, validate_doc_patch,
+    ArtifactMeta, ArtifactSession, ArtifactStore, ArtifactTaskKind, ArtifactTaskResult,
+    ArtifactTaskSpec, DocPatch, DomainError, MetaChange, SaveMeta, SchemaStrictness, StoreErr,
 };
 use super::ArtifactSessionManager;
 use crate::runtime::api::{ApprovalPolicy, ReasoningEffort, SandboxPreset};
@@ -16,6 +16,7 @@ use crate::runtime::events::Envelope;
 use crate::runtime::rpc_contract::methods as events;
 use crate::runtime::turn_lifecycle::{
     collect_turn_terminal_with_limits, interrupt_turn_best_effort_with_timeout, TurnCollectError,
+    INTERRUPT_REASON_ERROR,
 };
 use crate::runtime::turn_output::{
     parse_thread_id, parse_turn_id, TurnStreamCollector, TurnTerminalEvent,
@@ -29,6 +30,10 @@ const MAX_TURN_EVENT_SCAN: usize = 20_000;
 const TURN_OUTPUT_TIMEOUT: Duration = Duration::from_secs(120);
 const INTERRUPT_RPC_TIMEOUT: Duration = Duration::from_millis(500);
 const TURN_OUTPUT_FIELDS: [&str; 1] = ["output"];
+const DOC_GENERATE_KEYS: [&str; 3] = ["format", "title", "text"];
+const DOC_EDIT_REQUIRED_KEYS: [&str; 3] = ["format", "expectedRevision", "edits"];
+const DOC_EDIT_ALLOWED_KEYS: [&str; 4] = ["format", "expectedRevision", "edits", "notes"];
+const DOC_EDIT_ENTRY_KEYS: [&str; 3] = ["startLine", "endLine", "replacement"];
 
 #[cfg(test)]
 thread_local! {
@@ -45,6 +50,39 @@ struct DocGenerateOutput {
     text: String,
 }
 
+/// DocGenerate spec for `ArtifactSessionManager::generate_and_save`: pins the output
+/// format via the schema and asks for exactly `format`/`title`/`text`; `Strict` so
+/// extra output keys are rejected, matching `additionalProperties: false`.
+/// Allocation: schema Value + goal/constraint Strings. Complexity: O(g), g = goal size.
+pub(super) fn doc_generate_spec(
+    artifact_id: &str,
+    user_goal: &str,
+    format: &str,
+) -> ArtifactTaskSpec {
+    ArtifactTaskSpec {
+        artifact_id: artifact_id.to_owned(),
+        kind: ArtifactTaskKind::DocGenerate,
+        user_goal: user_goal.to_owned(),
+        current_text: None,
+        constraints: vec![format!("Write the whole document as {format}")],
+        examples: Vec::new(),
+        model: None,
+        effort: None,
+        summary: None,
+        output_schema: json!({
+            "type": "object",
+            "required": DOC_GENERATE_KEYS,
+            "additionalProperties": false,
+            "properties": {
+                "format": { "type": "string", "const": format },
+                "title": { "type": "string" },
+                "text": { "type": "string" }
+            }
+        }),
+        schema_strictness: SchemaStrictness::Strict,
+    }
+}
+
 pub(super) async fn run_task(
     manager: &ArtifactSessionManager,
     spec: ArtifactTaskSpec,
@@ -115,16 +153,20 @@ async fn run_doc_generate(
     turn_id: Option<String>,
     turn_output: Value,
 ) -> Result<ArtifactTaskResult, DomainError> {
-    let output_json = extract_output_json(&turn_output, &["format", "title", "text"])?;
-    let output: DocGenerateOutput = serde_json::from_value(output_json)
+    let output_json = extract_output_json(&turn_output, &DOC_GENERATE_KEYS)?;
+    if spec.schema_strictness == SchemaStrictness::Strict {
+        reject_unknown_keys(&output_json, &DOC_GENERATE_KEYS, "docGenerate output")?;
+    }
+    let mut output: DocGenerateOutput = serde_json::from_value(output_json)
         .map_err(|err| DomainError::Parse(format!("docGenerate payload parse failed: {err}")))?;
+    output.text = manager.text_normalization.apply(&output.text);
 
     let new_revision = compute_revision(&output.text);
     let output_title = output.title.clone();
     let output_format = output.format.clone();
     let thread_id_for_meta = session.thread_id.clone();
     let revision_for_meta = new_revision.clone();
-    persist_text_and_update_meta(
+    let meta_changes = persist_text_and_update_meta(
         manager,
         &spec.artifact_id,
         &output.text,
@@ -152,6 +194,7 @@ async fn run_doc_generate(
         format: output.format,
         revision: new_revision,
         text: output.text,
+        meta_changes,
     })
 }
 
@@ -164,7 +207,10 @@ async fn run_doc_edit(
     turn_id: Option<String>,
     turn_output: Value,
 ) -> Result<ArtifactTaskResult, DomainError> {
-    let output_json = extract_output_json(&turn_output, &["format", "expectedRevision", "edits"])?;
+    let output_json = extract_output_json(&turn_output, &DOC_EDIT_REQUIRED_KEYS)?;
+    if spec.schema_strictness == SchemaStrictness::Strict {
+        reject_unknown_doc_edit_keys(&output_json)?;
+    }
     let patch: DocPatch = serde_json::from_value(output_json)
         .map_err(|err| DomainError::Parse(format!("docEdit patch parse failed: {err}")))?;
 
@@ -175,7 +221,7 @@ async fn run_doc_edit(
     let patch_format = patch.format.clone();
     let thread_id_for_meta = session.thread_id.clone();
     let revision_for_meta = new_revision.clone();
-    persist_text_and_update_meta(
+    let meta_changes = persist_text_and_update_meta(
         manager,
         &spec.artifact_id,
         &new_text,
@@ -202,6 +248,7 @@ async fn run_doc_edit(
         revision: new_revision,
         text: new_text,
         notes: patch.notes,
+        meta_changes,
     })
 }
 
@@ -211,7 +258,7 @@ async fn persist_text_and_update_meta(
     new_text: &str,
     save_meta: SaveMeta,
     update_meta: impl FnOnce(&mut ArtifactMeta),
-) -> Result<(), DomainError> {
+) -> Result<Vec<MetaChange>, DomainError> {
     let artifact_id_owned = artifact_id.to_owned();
     let mut meta = manager
         .store_io({
@@ -219,13 +266,16 @@ async fn persist_text_and_update_meta(
             move |store| store.get_meta(&artifact_id)
         })
         .await?;
+    let previous_meta = meta.clone();
     update_meta(&mut meta);
+    let meta_changes = diff_artifact_meta(&previous_meta, &meta);
     let text_to_save = new_text.to_owned();
     manager
         .store_io(move |store| {
             store.save_text_and_meta(&artifact_id_owned, &text_to_save, save_meta, meta)
         })
-        .await
+        .await?;
+    Ok(meta_changes)
 }
 
 // --- from task.rs ---
@@ -269,8 +319,14 @@ pub(crate) async fn run_turn_and_collect_output(
 }
 
 async fn interrupt_turn_best_effort(runtime: &Runtime, thread_id: &str, turn_id: &str) {
-    interrupt_turn_best_effort_with_timeout(runtime, thread_id, turn_id, INTERRUPT_RPC_TIMEOUT)
-        .await;
+    interrupt_turn_best_effort_with_timeout(
+        runtime,
+        thread_id,
+        turn_id,
+        INTERRUPT_REASON_ERROR,
+        INTERRUPT_RPC_TIMEOUT,
+    )
+    .await;
 }
 
 async fn collect_turn_output_from_live(
@@ -662,6 +718,35 @@ fn normalize_output_candidate(candidate: &Value) -> Result<Value, DomainError> {
     }
 }
 
+/// Strict-mode check for a DocEdit patch: top-level keys and each `edits[i]` entry.
+fn reject_unknown_doc_edit_keys(patch: &Value) -> Result<(), DomainError> {
+    reject_unknown_keys(patch, &DOC_EDIT_ALLOWED_KEYS, "docEdit patch")?;
+    if let Some(edits) = patch.get("edits").and_then(Value::as_array) {
+        for (index, edit) in edits.iter().enumerate() {
+            reject_unknown_keys(
+                edit,
+                &DOC_EDIT_ENTRY_KEYS,
+                &format!("docEdit edits[{index}]"),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Reject object keys outside `allowed`. Non-objects are left to the typed parse.
+/// Side effects: none. Allocation: error string only. Complexity: O(k * a).
+fn reject_unknown_keys(value: &Value, allowed: &[&str], context: &str) -> Result<(), DomainError> {
+    let Some(obj) = value.as_object() else {
+        return Ok(());
+    };
+    match obj.keys().find(|key| !allowed.contains(&key.as_str())) {
+        Some(key) => Err(DomainError::Validation(format!(
+            "{context} has unexpected field `{key}` (strict schema)"
+        ))),
+        None => Ok(()),
+    }
+}
+
 fn has_required_keys(value: &Value, required_keys: &[&str]) -> bool {
     let Some(obj) = value.as_object() else {
         return false;
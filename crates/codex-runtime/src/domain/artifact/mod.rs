@@ -16,9 +16,10 @@ pub use adapter::{ArtifactPluginAdapter, RuntimeArtifactAdapter};
 #[cfg(test)]
 pub(crate) use models::DocEdit;
 pub use models::{
-    apply_doc_patch, compute_revision, validate_doc_patch, ArtifactMeta, ArtifactSession,
-    ArtifactStore, ArtifactTaskKind, ArtifactTaskResult, ArtifactTaskSpec, DocPatch, DomainError,
-    FsArtifactStore, PatchConflict, SaveMeta, StoreErr, ValidatedPatch,
+    apply_doc_patch, compute_revision, diff_artifact_meta, validate_doc_patch, ArtifactMeta,
+    ArtifactSession, ArtifactStore, ArtifactTaskKind, ArtifactTaskResult, ArtifactTaskSpec,
+    DocPatch, DomainError, FsArtifactStore, LineEnding, MetaChange, PatchConflict, SaveMeta,
+    SchemaStrictness, StoreErr, TextNormalization, ValidatedPatch,
 };
 
 #[cfg(test)]
@@ -27,14 +28,14 @@ pub(crate) use execution::collect_turn_output_from_live_with_limits;
 pub(crate) use execution::debug_with_forced_turn_start_params_serialization_failure;
 #[cfg(test)]
 pub(crate) use execution::{build_turn_prompt, build_turn_start_params};
-#[cfg(test)]
-pub(crate) use store::artifact_key;
+pub use store::artifact_storage_key;
 
 #[derive(Clone)]
 pub struct ArtifactSessionManager {
     adapter: Arc<dyn ArtifactPluginAdapter>,
     store: Arc<dyn ArtifactStore>,
     contract_mismatch: Option<ContractMismatch>,
+    text_normalization: TextNormalization,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -59,9 +60,17 @@ impl ArtifactSessionManager {
             adapter,
             store,
             contract_mismatch,
+            text_normalization: TextNormalization::default(),
         }
     }
 
+    /// Normalize DocGenerate output before it is saved and its revision computed.
+    /// Default: no normalization (text is saved verbatim).
+    pub fn with_text_normalization(mut self, text_normalization: TextNormalization) -> Self {
+        self.text_normalization = text_normalization;
+        self
+    }
+
     // ArtifactStore implementations may perform synchronous filesystem I/O.
     // spawn_blocking moves that work off the async executor thread so it
     // cannot block other tasks while the store operation runs.
@@ -120,6 +129,42 @@ impl ArtifactSessionManager {
         execution::run_task(self, spec).await
     }
 
+    /// Ask the model to write a `format` document for `user_goal` and persist it as
+    /// `artifact_id`. Shorthand for `run_task` with a DocGenerate spec whose output
+    /// schema pins `format`; build an `ArtifactTaskSpec` directly for anything else.
+    /// Side effects: same as `run_task`. Complexity: same as `run_task`.
+    pub async fn generate_and_save(
+        &self,
+        artifact_id: &str,
+        user_goal: &str,
+        format: &str,
+    ) -> Result<ArtifactTaskResult, DomainError> {
+        self.run_task(execution::doc_generate_spec(artifact_id, user_goal, format))
+            .await
+    }
+
+    /// Dry-run `patch` against the persisted text of `artifact_id` (empty when missing)
+    /// for previews and conflict detection. Errors match a DocEdit task: stale
+    /// `expected_revision` is `DomainError::Conflict`, bad edit ranges are `Validation`.
+    /// Side effects: one store read; no model call, no write.
+    /// Allocation: persisted text + validated edit list. Complexity: O(L + e).
+    pub async fn validate_patch(
+        &self,
+        artifact_id: &str,
+        patch: &DocPatch,
+    ) -> Result<ValidatedPatch, DomainError> {
+        let persisted_text = self
+            .store_io({
+                let artifact_id = artifact_id.to_owned();
+                move |store| match store.load_text(&artifact_id) {
+                    Err(StoreErr::NotFound(_)) => Ok(String::new()),
+                    other => other,
+                }
+            })
+            .await?;
+        validate_doc_patch(&persisted_text, patch).map_err(models::map_patch_conflict)
+    }
+
     fn ensure_contract_compatible(&self) -> Result<(), DomainError> {
         if let Some(mismatch) = self.contract_mismatch {
             return Err(DomainError::IncompatibleContract {
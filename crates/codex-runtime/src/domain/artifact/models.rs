@@ -16,6 +16,40 @@ pub struct ArtifactMeta {
     pub runtime_thread_id: Option<String>,
 }
 
+/// One `ArtifactMeta` field that differs between the pre- and post-save metadata.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetaChange {
+    /// camelCase field name as stored (`title`, `format`, `revision`, `runtimeThreadId`).
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Field-by-field diff of two metas, in declaration order; empty when equal.
+/// Pure. Allocation: one MetaChange per changed field. Complexity: O(1) fields.
+pub fn diff_artifact_meta(old: &ArtifactMeta, new: &ArtifactMeta) -> Vec<MetaChange> {
+    let fields = [
+        ("title", Some(&old.title), Some(&new.title)),
+        ("format", Some(&old.format), Some(&new.format)),
+        ("revision", Some(&old.revision), Some(&new.revision)),
+        (
+            "runtimeThreadId",
+            old.runtime_thread_id.as_ref(),
+            new.runtime_thread_id.as_ref(),
+        ),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| MetaChange {
+            field: field.to_owned(),
+            old: old.cloned(),
+            new: new.cloned(),
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum ArtifactTaskKind {
@@ -24,6 +58,97 @@ pub enum ArtifactTaskKind {
     Passthrough,
 }
 
+/// How DocGenerate/DocEdit treat model output fields outside the task's known shape.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SchemaStrictness {
+    /// Unknown fields are rejected with `DomainError::Validation`.
+    Strict,
+    /// Unknown fields are ignored.
+    #[default]
+    Lenient,
+}
+
+/// Line terminator enforced by `TextNormalization::line_endings`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::Crlf => "\r\n",
+        }
+    }
+}
+
+/// Canonical formatting applied to DocGenerate output before it is saved and hashed,
+/// so whitespace-only differences between generations keep the same revision.
+/// `Default` changes nothing.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TextNormalization {
+    /// Rewrite every line terminator; `None` keeps each one as written.
+    pub line_endings: Option<LineEnding>,
+    /// Strip trailing whitespace from every line.
+    pub trim_trailing_whitespace: bool,
+    /// Append one line terminator to non-empty text that lacks one.
+    pub ensure_final_newline: bool,
+}
+
+impl TextNormalization {
+    /// LF endings, no trailing whitespace, final newline.
+    pub fn canonical() -> Self {
+        Self {
+            line_endings: Some(LineEnding::Lf),
+            trim_trailing_whitespace: true,
+            ensure_final_newline: true,
+        }
+    }
+
+    /// Normalized copy of `text`. The final newline uses `line_endings`, else the
+    /// first terminator found in `text`, else LF.
+    /// Allocation: one String of about `text` size. Complexity: O(n), n = text bytes.
+    pub fn apply(&self, text: &str) -> String {
+        if *self == Self::default() {
+            return text.to_owned();
+        }
+        let mut out = String::with_capacity(text.len() + 2);
+        let mut first_terminator = None;
+        let mut segments = text.split('\n').peekable();
+        while let Some(segment) = segments.next() {
+            let is_last = segments.peek().is_none();
+            let (line, terminator) = match segment.strip_suffix('\r') {
+                Some(line) if !is_last => (line, "\r\n"),
+                _ => (segment, "\n"),
+            };
+            out.push_str(if self.trim_trailing_whitespace {
+                line.trim_end()
+            } else {
+                line
+            });
+            if !is_last {
+                let terminator = self.line_endings.map_or(terminator, LineEnding::as_str);
+                first_terminator.get_or_insert(terminator);
+                out.push_str(terminator);
+            }
+        }
+        if self.ensure_final_newline && !out.is_empty() && !out.ends_with('\n') {
+            let terminator = self
+                .line_endings
+                .map(LineEnding::as_str)
+                .or(first_terminator)
+                .unwrap_or("\n");
+            out.push_str(terminator);
+        }
+        out
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ArtifactTaskSpec {
@@ -37,6 +162,9 @@ pub struct ArtifactTaskSpec {
     pub effort: Option<ReasoningEffort>,
     pub summary: Option<String>,
     pub output_schema: Value,
+    /// Extra-field policy for the parsed output. Passthrough output is never checked.
+    #[serde(default)]
+    pub schema_strictness: SchemaStrictness,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -182,6 +310,9 @@ pub enum ArtifactTaskResult {
         format: String,
         revision: String,
         text: String,
+        /// Metadata fields this save changed (e.g. an unexpected `format` switch).
+        #[serde(default)]
+        meta_changes: Vec<MetaChange>,
     },
     DocEdit {
         artifact_id: String,
@@ -191,6 +322,9 @@ pub enum ArtifactTaskResult {
         revision: String,
         text: String,
         notes: Option<String>,
+        /// Metadata fields this save changed.
+        #[serde(default)]
+        meta_changes: Vec<MetaChange>,
     },
     Passthrough {
         artifact_id: String,
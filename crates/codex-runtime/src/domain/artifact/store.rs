@@ -23,7 +23,7 @@ impl FsArtifactStore {
     }
 
     fn artifact_dir(&self, artifact_id: &str) -> std::path::PathBuf {
-        self.root.join(artifact_key(artifact_id))
+        self.root.join(artifact_storage_key(artifact_id))
     }
 
     fn text_path(&self, artifact_id: &str) -> std::path::PathBuf {
@@ -341,9 +341,13 @@ fn read_checked(path: &Path, artifact_id: &str) -> Result<Vec<u8>, StoreErr> {
     }
 }
 
-/// Stable artifact path key: visible prefix + hash suffix.
+/// Stable per-artifact key, identical to the directory name `FsArtifactStore` uses.
+/// Derivation: every char outside `[A-Za-z0-9_-]` becomes `_` (empty result becomes
+/// `artifact`), then `_` plus the first 12 hex chars of SHA-256(artifact_id) is appended.
+/// Distinct ids that sanitize the same still differ by hash suffix.
+/// Use it to key external locks or caches consistently with the store.
 /// Allocation: one String. Complexity: O(n), n=artifact_id length.
-pub(crate) fn artifact_key(artifact_id: &str) -> String {
+pub fn artifact_storage_key(artifact_id: &str) -> String {
     let mut prefix = String::with_capacity(artifact_id.len());
     for ch in artifact_id.chars() {
         if ch.is_ascii_alphanumeric() || ch == '_' || ch == '-' {
@@ -348,6 +348,7 @@ fn make_task_spec(artifact_id: &str, kind: ArtifactTaskKind, goal: &str) -> Arti
         effort: None,
         summary: None,
         output_schema: json!({"type":"object"}),
+        schema_strictness: SchemaStrictness::Lenient,
     }
 }
 
@@ -35,11 +35,25 @@ async fn run_task_doc_generate_end_to_end() {
             revision,
             text,
             title,
+            meta_changes,
             ..
         } => {
             assert_eq!(title, "Generated Title");
             assert_eq!(text, "# Generated\ncontent\n");
             assert_eq!(revision, compute_revision(&text));
+            let fields: Vec<&str> = meta_changes
+                .iter()
+                .map(|change| change.field.as_str())
+                .collect();
+            assert_eq!(fields, ["title", "revision"]);
+            assert_eq!(
+                meta_changes[0],
+                MetaChange {
+                    field: "title".to_owned(),
+                    old: Some("Seed".to_owned()),
+                    new: Some("Generated Title".to_owned()),
+                }
+            );
         }
         other => panic!("unexpected result: {other:?}"),
     }
@@ -56,6 +70,92 @@ async fn run_task_doc_generate_end_to_end() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn generate_and_save_builds_doc_generate_spec_and_persists() {
+    let temp = TempDir::new("runtime_artifact_generate_and_save");
+    let store = seeded_store(&temp, "doc:quick", "");
+    let state = Arc::new(Mutex::new(FakeArtifactAdapterState {
+        start_thread_id: "thr_quick".to_owned(),
+        turn_output: json!({
+            "format": "markdown",
+            "title": "Quick Title",
+            "text": "# Quick\n"
+        }),
+        ..FakeArtifactAdapterState::default()
+    }));
+    let adapter: Arc<dyn ArtifactPluginAdapter> = Arc::new(FakeArtifactAdapter {
+        state: Arc::clone(&state),
+    });
+    let manager = ArtifactSessionManager::new_with_adapter(adapter, Arc::clone(&store));
+
+    let result = manager
+        .generate_and_save("doc:quick", "WRITE_QUICK_DOC", "markdown")
+        .await
+        .expect("generate and save");
+
+    match result {
+        ArtifactTaskResult::DocGenerate { title, text, .. } => {
+            assert_eq!(title, "Quick Title");
+            assert_eq!(text, "# Quick\n");
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert_eq!(store.load_text("doc:quick").expect("load"), "# Quick\n");
+    assert_eq!(
+        store.get_meta("doc:quick").expect("meta").title,
+        "Quick Title"
+    );
+
+    let state = state.lock().expect("fake adapter state");
+    let (_, prompt, spec) = &state.run_turn_calls[0];
+    assert!(prompt.contains("GOAL:\nWRITE_QUICK_DOC"));
+    assert_eq!(spec.kind, ArtifactTaskKind::DocGenerate);
+    assert_eq!(
+        spec.output_schema["properties"]["format"]["const"],
+        json!("markdown")
+    );
+    assert_eq!(spec.output_schema["additionalProperties"], json!(false));
+    assert_eq!(spec.schema_strictness, SchemaStrictness::Strict);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn text_normalization_applies_before_save_and_revision() {
+    let temp = TempDir::new("runtime_artifact_normalize");
+    let store = seeded_store(&temp, "doc:norm", "");
+    let state = Arc::new(Mutex::new(FakeArtifactAdapterState {
+        start_thread_id: "thr_norm".to_owned(),
+        turn_output: json!({
+            "format": "markdown",
+            "title": "Norm",
+            "text": "# Norm  \r\nbody\t"
+        }),
+        ..FakeArtifactAdapterState::default()
+    }));
+    let adapter: Arc<dyn ArtifactPluginAdapter> = Arc::new(FakeArtifactAdapter {
+        state: Arc::clone(&state),
+    });
+    let manager = ArtifactSessionManager::new_with_adapter(adapter, Arc::clone(&store))
+        .with_text_normalization(TextNormalization::canonical());
+
+    let result = manager
+        .generate_and_save("doc:norm", "WRITE", "markdown")
+        .await
+        .expect("generate and save");
+
+    match result {
+        ArtifactTaskResult::DocGenerate { text, revision, .. } => {
+            assert_eq!(text, "# Norm\nbody\n");
+            assert_eq!(revision, compute_revision("# Norm\nbody\n"));
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert_eq!(store.load_text("doc:norm").expect("load"), "# Norm\nbody\n");
+    assert_eq!(
+        store.get_meta("doc:norm").expect("meta").revision,
+        compute_revision("# Norm\nbody\n")
+    );
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn run_task_uses_artifact_adapter_boundary_without_runtime_dependency() {
     let temp = TempDir::new("runtime_artifact_fake_adapter");
@@ -138,6 +238,67 @@ async fn run_task_doc_edit_end_to_end() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn run_task_doc_edit_strict_schema_accepts_known_fields() {
+    let temp = TempDir::new("runtime_artifact_edit_strict");
+    let (store, runtime, manager) =
+        seeded_manager_with_mock_runtime(&temp, "doc:edit-strict", "a\nb\nc\n").await;
+
+    let mut spec = make_task_spec("doc:edit-strict", ArtifactTaskKind::DocEdit, "EDIT_DOC");
+    spec.schema_strictness = SchemaStrictness::Strict;
+    manager.run_task(spec).await.expect("strict edit run");
+
+    let persisted = store.load_text("doc:edit-strict").expect("load persisted");
+    assert_eq!(persisted, "a\npatched\nc\n");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_task_schema_strictness_controls_extra_output_fields() {
+    let temp = TempDir::new("runtime_artifact_schema_strictness");
+    let store: Arc<dyn ArtifactStore> = Arc::new(FsArtifactStore::new(&temp.root));
+    seed_artifact(store.as_ref(), "doc:strict", "");
+
+    let state = Arc::new(Mutex::new(FakeArtifactAdapterState {
+        start_thread_id: "thr_strict".to_owned(),
+        turn_output: json!({
+            "format": "markdown",
+            "title": "Strict",
+            "text": "# Strict\n",
+            "debug": "leaked"
+        }),
+        ..FakeArtifactAdapterState::default()
+    }));
+    let adapter: Arc<dyn ArtifactPluginAdapter> = Arc::new(FakeArtifactAdapter {
+        state: Arc::clone(&state),
+    });
+    let manager = ArtifactSessionManager::new_with_adapter(adapter, Arc::clone(&store));
+
+    let mut strict = make_task_spec("doc:strict", ArtifactTaskKind::DocGenerate, "GENERATE_DOC");
+    strict.schema_strictness = SchemaStrictness::Strict;
+    let err = manager
+        .run_task(strict)
+        .await
+        .expect_err("strict mode must reject extra fields");
+    match err {
+        DomainError::Validation(message) => assert!(message.contains("`debug`"), "{message}"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+    assert_eq!(store.load_text("doc:strict").expect("load text"), "");
+
+    let lenient = make_task_spec("doc:strict", ArtifactTaskKind::DocGenerate, "GENERATE_DOC");
+    let result = manager
+        .run_task(lenient)
+        .await
+        .expect("lenient mode ignores extra fields");
+    assert!(matches!(result, ArtifactTaskResult::DocGenerate { .. }));
+    assert_eq!(
+        store.load_text("doc:strict").expect("load text"),
+        "# Strict\n"
+    );
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn open_rejects_incompatible_adapter_contract() {
     let temp = TempDir::new("runtime_artifact_contract_mismatch");
@@ -272,6 +433,57 @@ async fn conflict_is_returned_without_auto_retry() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn validate_patch_checks_persisted_text_without_side_effects() {
+    let temp = TempDir::new("runtime_artifact_validate_patch");
+    let store = seeded_store(&temp, "doc:preview", "a\nb\nc\n");
+    let state = Arc::new(Mutex::new(FakeArtifactAdapterState::default()));
+    let adapter: Arc<dyn ArtifactPluginAdapter> = Arc::new(FakeArtifactAdapter {
+        state: Arc::clone(&state),
+    });
+    let manager = ArtifactSessionManager::new_with_adapter(adapter, Arc::clone(&store));
+    let meta_before = store.get_meta("doc:preview").expect("meta before");
+
+    let mut patch = DocPatch {
+        format: "markdown".to_owned(),
+        expected_revision: compute_revision("a\nb\nc\n"),
+        edits: vec![DocEdit {
+            start_line: 2,
+            end_line: 3,
+            replacement: "patched\n".to_owned(),
+        }],
+        notes: None,
+    };
+    let validated = manager
+        .validate_patch("doc:preview", &patch)
+        .await
+        .expect("clean patch");
+    assert_eq!(validated.edits, patch.edits);
+
+    patch.edits[0].end_line = 9;
+    let err = manager
+        .validate_patch("doc:preview", &patch)
+        .await
+        .expect_err("range past the end");
+    assert!(matches!(err, DomainError::Validation(_)), "{err:?}");
+
+    patch.expected_revision = "sha256:deadbeef".to_owned();
+    match manager.validate_patch("doc:preview", &patch).await {
+        Err(DomainError::Conflict { expected, actual }) => {
+            assert_eq!(expected, "sha256:deadbeef");
+            assert_eq!(actual, compute_revision("a\nb\nc\n"));
+        }
+        other => panic!("expected conflict, got {other:?}"),
+    }
+
+    assert_eq!(store.load_text("doc:preview").expect("text"), "a\nb\nc\n");
+    assert_eq!(store.get_meta("doc:preview").expect("meta"), meta_before);
+    let state = state.lock().expect("fake adapter state");
+    assert_eq!(state.start_calls, 0);
+    assert!(state.resume_calls.is_empty());
+    assert!(state.run_turn_calls.is_empty());
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn turn_start_params_use_fixed_safe_policy() {
     let temp = TempDir::new("runtime_artifact_policy");
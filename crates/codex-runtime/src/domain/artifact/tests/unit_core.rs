@@ -13,6 +13,7 @@ fn build_prompt_has_required_blocks() {
         effort: None,
         summary: None,
         output_schema: json!({"type":"object"}),
+        schema_strictness: SchemaStrictness::Lenient,
     };
     let prompt = build_turn_prompt(&spec, "markdown", "sha256:rev", "hello\n");
     assert!(prompt.contains("ROLE:\n"));
@@ -139,11 +140,13 @@ fn detect_invalid_range() {
 
 #[test]
 fn artifact_key_is_stable() {
-    let a = artifact_key("doc:123");
-    let b = artifact_key("doc:123");
-    let c = artifact_key("doc/123");
+    let a = artifact_storage_key("doc:123");
+    let b = artifact_storage_key("doc:123");
+    let c = artifact_storage_key("doc/123");
     assert_eq!(a, b);
     assert_ne!(a, c);
+    assert_eq!(a, "doc_123_7bb0dae9ff0d");
+    assert_eq!(artifact_storage_key(""), "artifact_e3b0c44298fc");
 }
 
 #[test]
@@ -206,7 +209,7 @@ fn fs_store_recovers_orphaned_lock_and_saves() {
     let store = FsArtifactStore::new(&temp.root);
     let artifact_id = "doc:orphaned-lock";
 
-    let artifact_dir = temp.root.join(artifact_key(artifact_id));
+    let artifact_dir = temp.root.join(artifact_storage_key(artifact_id));
     fs::create_dir_all(&artifact_dir).expect("create artifact dir");
     fs::write(artifact_dir.join(".artifact.lock"), "999999:1\n").expect("write orphaned lock");
 
@@ -238,7 +241,7 @@ fn fs_store_does_not_reap_unknown_owner_lock_after_age_threshold() {
     let store = FsArtifactStore::new(&temp.root);
     let artifact_id = "doc:unknown-lock-owner";
 
-    let artifact_dir = temp.root.join(artifact_key(artifact_id));
+    let artifact_dir = temp.root.join(artifact_storage_key(artifact_id));
     fs::create_dir_all(&artifact_dir).expect("create artifact dir");
     fs::write(artifact_dir.join(".artifact.lock"), "999999:1\n").expect("write unknown-owner lock");
 
@@ -267,7 +270,7 @@ fn fs_store_does_not_steal_live_lock_owner() {
     let store = FsArtifactStore::new(&temp.root);
     let artifact_id = "doc:live-lock-owner";
 
-    let artifact_dir = temp.root.join(artifact_key(artifact_id));
+    let artifact_dir = temp.root.join(artifact_storage_key(artifact_id));
     fs::create_dir_all(&artifact_dir).expect("create artifact dir");
     let current_pid = std::process::id();
     fs::write(
@@ -363,7 +366,7 @@ fn fs_store_save_text_and_meta_rolls_back_text_when_meta_write_fails() {
         )
         .expect("seed meta");
 
-    let dir = temp.root.join(artifact_key(artifact_id));
+    let dir = temp.root.join(artifact_storage_key(artifact_id));
     let meta_path = dir.join("meta.json");
     fs::remove_file(&meta_path).expect("remove meta file");
     fs::create_dir(&meta_path).expect("create blocking meta dir");
@@ -401,7 +404,7 @@ async fn open_repairs_meta_revision_mismatch() {
     let store: Arc<dyn ArtifactStore> = Arc::new(FsArtifactStore::new(&temp.root));
 
     let artifact_id = "doc:meta-mismatch";
-    let key = artifact_key(artifact_id);
+    let key = artifact_storage_key(artifact_id);
     let dir = temp.root.join(key);
     fs::create_dir_all(&dir).expect("create dir");
     fs::write(dir.join("text.txt"), "seed\n").expect("write text");
@@ -442,6 +445,7 @@ fn build_turn_start_params_defaults_effort_to_medium() {
         effort: None,
         summary: None,
         output_schema: json!({"type":"object"}),
+        schema_strictness: SchemaStrictness::Lenient,
     };
     let params = build_turn_start_params("thr_1", "prompt", &spec).expect("build turn params");
     assert_eq!(params["effort"], "medium");
@@ -460,6 +464,7 @@ fn build_turn_start_params_surfaces_serialization_failure() {
         effort: None,
         summary: None,
         output_schema: json!({"type":"object"}),
+        schema_strictness: SchemaStrictness::Lenient,
     };
 
     let err = debug_with_forced_turn_start_params_serialization_failure(true, || {
@@ -474,3 +479,31 @@ fn build_turn_start_params_surfaces_serialization_failure() {
         other => panic!("unexpected error: {other:?}"),
     }
 }
+
+#[test]
+fn text_normalization_default_is_verbatim_and_canonical_is_stable() {
+    let raw = "# Title  \r\nbody\t\r\nlast line ";
+    assert_eq!(TextNormalization::default().apply(raw), raw);
+
+    let canonical = TextNormalization::canonical();
+    assert_eq!(canonical.apply(raw), "# Title\nbody\nlast line\n");
+    assert_eq!(
+        compute_revision(&canonical.apply(raw)),
+        compute_revision(&canonical.apply("# Title\nbody \nlast line"))
+    );
+    assert_eq!(canonical.apply(&canonical.apply(raw)), canonical.apply(raw));
+    assert_eq!(canonical.apply(""), "");
+
+    let keep_endings = TextNormalization {
+        trim_trailing_whitespace: true,
+        ensure_final_newline: true,
+        ..TextNormalization::default()
+    };
+    assert_eq!(keep_endings.apply("a \r\nb "), "a\r\nb\r\n");
+
+    let crlf = TextNormalization {
+        line_endings: Some(LineEnding::Crlf),
+        ..TextNormalization::default()
+    };
+    assert_eq!(crlf.apply("a\nb\r\nc "), "a\r\nb\r\nc ");
+}
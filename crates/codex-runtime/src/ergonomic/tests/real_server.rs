@@ -518,6 +518,7 @@ async fn appserver_roundtrip_attempt(cwd: String) -> Result<(), String> {
                 ThreadReadParams {
                     thread_id: thread_id.clone(),
                     include_turns: Some(false),
+                    last_n_turns: None,
                 },
             )
             .await
@@ -626,6 +627,7 @@ async fn appserver_approval_roundtrip_attempt() -> Result<(), String> {
                     ThreadReadParams {
                         thread_id: thread_id.clone(),
                         include_turns: Some(false),
+                        last_n_turns: None,
                     },
                 )
                 .await
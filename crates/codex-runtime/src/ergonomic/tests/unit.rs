@@ -163,7 +163,7 @@ fn to_session_config_projects_profile_without_loss() {
         session.output_schema,
         Some(json!({"type":"object","required":["value"]}))
     );
-    assert_eq!(session.timeout, Duration::from_secs(42));
+    assert_eq!(session.timeout, Some(Duration::from_secs(42)));
     assert_eq!(session.attachments.len(), 1);
 }
 
@@ -213,6 +213,7 @@ fn fold_quick_run_returns_output_when_run_and_shutdown_succeed() {
         thread_id: "thread-1".to_owned(),
         turn_id: "turn-1".to_owned(),
         assistant_text: "ok".to_owned(),
+        schema_valid: None,
     };
     let result = fold_quick_run(Ok(out.clone()), Ok(()));
     assert_eq!(result, Ok(out));
@@ -224,6 +225,7 @@ fn fold_quick_run_returns_shutdown_error_after_successful_run() {
         thread_id: "thread-1".to_owned(),
         turn_id: "turn-1".to_owned(),
         assistant_text: "ok".to_owned(),
+        schema_valid: None,
     };
     let result = fold_quick_run(Ok(out), Err(RuntimeError::Internal("shutdown".to_owned())));
     assert_eq!(
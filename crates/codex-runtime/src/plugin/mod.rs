@@ -1,8 +1,12 @@
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
+use tokio::sync::Notify;
 
 pub type HookFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
@@ -26,7 +30,7 @@ impl PluginContractVersion {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum HookPhase {
     PreRun,
     PostRun,
@@ -51,10 +55,187 @@ pub struct HookContext {
     pub correlation_id: String,
     pub ts_ms: i64,
     pub metadata: Value,
+    /// Final assistant text, set for PostTurn when the turn completed successfully.
+    /// Read-only: the turn is already over, so hooks can only observe it.
+    #[serde(default)]
+    pub assistant_text: Option<String>,
     /// Tool or command name, set for PreToolUse/PostToolUse phases.
     pub tool_name: Option<String>,
     /// Raw tool input params, set for PreToolUse/PostToolUse phases.
     pub tool_input: Option<Value>,
+    /// Not serialized; shell hooks never observe it. Read via [`HookContext::shutdown`].
+    #[serde(skip)]
+    pub(crate) shutdown: ShutdownToken,
+}
+
+impl HookContext {
+    /// Cancelled once the owning runtime stops: explicit shutdown, supervisor
+    /// give-up, or the last handle being dropped.
+    /// Post-phase hooks should check it before expensive work or sink writes.
+    pub fn shutdown(&self) -> &ShutdownToken {
+        &self.shutdown
+    }
+
+    /// Start building a context for one phase, e.g. to unit-test a hook's `call`
+    /// without spawning a `Runtime`.
+    /// Defaults: fresh `correlation_id`, current `ts_ms`, empty metadata object,
+    /// uncancelled shutdown token, every optional field unset.
+    pub fn builder(phase: HookPhase) -> HookContextBuilder {
+        HookContextBuilder {
+            ctx: HookContext {
+                phase,
+                thread_id: None,
+                turn_id: None,
+                cwd: None,
+                model: None,
+                main_status: None,
+                correlation_id: format!("hk-{}", uuid::Uuid::new_v4()),
+                ts_ms: crate::runtime::now_millis(),
+                metadata: Value::Object(Map::new()),
+                assistant_text: None,
+                tool_name: None,
+                tool_input: None,
+                shutdown: ShutdownToken::new(),
+            },
+        }
+    }
+}
+
+/// Builder returned by `HookContext::builder`.
+/// Allocation: one String per setter call. Complexity: O(1) per setter.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct HookContextBuilder {
+    ctx: HookContext,
+}
+
+impl HookContextBuilder {
+    pub fn thread_id(mut self, thread_id: impl Into<String>) -> Self {
+        self.ctx.thread_id = Some(thread_id.into());
+        self
+    }
+
+    pub fn turn_id(mut self, turn_id: impl Into<String>) -> Self {
+        self.ctx.turn_id = Some(turn_id.into());
+        self
+    }
+
+    pub fn cwd(mut self, cwd: impl Into<String>) -> Self {
+        self.ctx.cwd = Some(cwd.into());
+        self
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.ctx.model = Some(model.into());
+        self
+    }
+
+    pub fn main_status(mut self, main_status: impl Into<String>) -> Self {
+        self.ctx.main_status = Some(main_status.into());
+        self
+    }
+
+    pub fn metadata(mut self, metadata: Value) -> Self {
+        self.ctx.metadata = metadata;
+        self
+    }
+
+    pub fn assistant_text(mut self, assistant_text: impl Into<String>) -> Self {
+        self.ctx.assistant_text = Some(assistant_text.into());
+        self
+    }
+
+    pub fn tool_name(mut self, tool_name: impl Into<String>) -> Self {
+        self.ctx.tool_name = Some(tool_name.into());
+        self
+    }
+
+    pub fn tool_input(mut self, tool_input: Value) -> Self {
+        self.ctx.tool_input = Some(tool_input);
+        self
+    }
+
+    /// Override the generated correlation id (useful for deterministic assertions).
+    pub fn correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.ctx.correlation_id = correlation_id.into();
+        self
+    }
+
+    pub fn ts_ms(mut self, ts_ms: i64) -> Self {
+        self.ctx.ts_ms = ts_ms;
+        self
+    }
+
+    /// Share a token so the test can cancel it and observe the hook's reaction.
+    pub fn shutdown(mut self, shutdown: ShutdownToken) -> Self {
+        self.ctx.shutdown = shutdown;
+        self
+    }
+
+    pub fn build(self) -> HookContext {
+        self.ctx
+    }
+}
+
+/// Cooperative shutdown signal shared by one runtime and every hook it invokes.
+/// Cancellation is one-way and never reset.
+/// Allocation: one Arc at construction; clones are refcount bumps. Complexity: O(1).
+#[derive(Clone, Default)]
+pub struct ShutdownToken {
+    inner: Arc<ShutdownTokenInner>,
+}
+
+#[derive(Default)]
+struct ShutdownTokenInner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl ShutdownToken {
+    /// Create an uncancelled token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the token cancelled and wake every `cancelled()` waiter.
+    /// Idempotent. Allocation: none. Complexity: O(w), w = waiter count.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::Release);
+        self.inner.notify.notify_waiters();
+    }
+
+    /// True once `cancel` has been called on this token or any clone.
+    /// Allocation: none. Complexity: O(1).
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Resolve when the token is cancelled; resolves immediately if it already is.
+    /// Intended for `tokio::select!` against long-running hook work.
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+impl std::fmt::Debug for ShutdownToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShutdownToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Tokens compare equal when they share cancellation state.
+impl PartialEq for ShutdownToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -109,9 +290,15 @@ pub enum HookAction {
     Mutate(HookPatch),
     /// Stop execution immediately. No subsequent hooks run. No state is mutated.
     Block(BlockReason),
+    /// PreRun/PreTurn only: answer the prompt with `text` without sending `turn/start`.
+    /// No subsequent pre hooks run; post hooks see main status `"short_circuited"`.
+    /// Ignored in other phases.
+    Respond {
+        text: String,
+    },
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum HookIssueClass {
     Validation,
     Execution,
@@ -119,6 +306,19 @@ pub enum HookIssueClass {
     Internal,
 }
 
+impl HookIssueClass {
+    /// Severity rank: Internal > Execution > Timeout > Validation.
+    /// Independent of declaration order, which `Ord` follows.
+    const fn severity(self) -> u8 {
+        match self {
+            Self::Validation => 0,
+            Self::Timeout => 1,
+            Self::Execution => 2,
+            Self::Internal => 3,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct HookIssue {
     pub hook_name: String,
@@ -127,9 +327,21 @@ pub struct HookIssue {
     pub message: String,
 }
 
+/// Audit record for one approval request answered by `PromptRunParams::auto_approve_all`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoApproval {
+    pub thread_id: String,
+    pub turn_id: String,
+    /// Server request method (command-execution or file-change approval).
+    pub method: String,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct HookReport {
     pub issues: Vec<HookIssue>,
+    /// Approvals the runtime accepted for an `auto_approve_all` run; audit records, not issues.
+    #[serde(default)]
+    pub auto_approvals: Vec<AutoApproval>,
 }
 
 impl HookReport {
@@ -137,9 +349,39 @@ impl HookReport {
         self.issues.push(issue);
     }
 
+    /// True when no hook reported an issue; `auto_approvals` do not count.
     pub fn is_clean(&self) -> bool {
         self.issues.is_empty()
     }
+
+    /// Group issues by hook phase, preserving push order within each phase.
+    /// Allocation: one Vec per distinct phase. Complexity: O(n log p).
+    pub fn by_phase(&self) -> BTreeMap<HookPhase, Vec<&HookIssue>> {
+        let mut grouped = BTreeMap::<HookPhase, Vec<&HookIssue>>::new();
+        for issue in &self.issues {
+            grouped.entry(issue.phase).or_default().push(issue);
+        }
+        grouped
+    }
+
+    /// Count issues per class.
+    /// Allocation: one map node per distinct class. Complexity: O(n log c).
+    pub fn by_class(&self) -> BTreeMap<HookIssueClass, usize> {
+        let mut counts = BTreeMap::<HookIssueClass, usize>::new();
+        for issue in &self.issues {
+            *counts.entry(issue.class).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Highest-severity class present: Internal > Execution > Timeout > Validation.
+    /// Returns `None` for a clean report. Allocation: none. Complexity: O(n).
+    pub fn worst_class(&self) -> Option<HookIssueClass> {
+        self.issues
+            .iter()
+            .map(|issue| issue.class)
+            .max_by_key(|class| class.severity())
+    }
 }
 
 pub trait PreHook: Send + Sync + 'static {
@@ -0,0 +1,75 @@
+use serde_json::json;
+
+use super::*;
+
+struct CwdGuardHook;
+
+impl PreHook for CwdGuardHook {
+    fn name(&self) -> &'static str {
+        "cwd_guard"
+    }
+
+    fn call<'a>(&'a self, ctx: &'a HookContext) -> HookFuture<'a, Result<HookAction, HookIssue>> {
+        Box::pin(async move {
+            if ctx.cwd.as_deref() == Some("/etc") {
+                return Ok(HookAction::Block(BlockReason {
+                    hook_name: self.name().to_owned(),
+                    phase: ctx.phase,
+                    message: "cwd not allowed".to_owned(),
+                }));
+            }
+            Ok(HookAction::Noop)
+        })
+    }
+}
+
+#[test]
+fn hook_context_builder_applies_defaults_and_setters() {
+    let defaults = HookContext::builder(HookPhase::PreTurn).build();
+    assert_eq!(defaults.phase, HookPhase::PreTurn);
+    assert!(defaults.correlation_id.starts_with("hk-"));
+    assert!(defaults.ts_ms > 0);
+    assert_eq!(defaults.metadata, json!({}));
+    assert!(defaults.thread_id.is_none() && defaults.tool_name.is_none());
+    assert!(!defaults.shutdown().is_cancelled());
+    assert_ne!(
+        defaults.correlation_id,
+        HookContext::builder(HookPhase::PreTurn)
+            .build()
+            .correlation_id
+    );
+
+    let ctx = HookContext::builder(HookPhase::PreToolUse)
+        .thread_id("thr_1")
+        .turn_id("turn_1")
+        .cwd("/work")
+        .model("gpt-5")
+        .metadata(json!({"tenant": "acme"}))
+        .tool_name("bash")
+        .tool_input(json!({"command": ["ls"]}))
+        .correlation_id("hk-fixed")
+        .ts_ms(42)
+        .build();
+    assert_eq!(ctx.thread_id.as_deref(), Some("thr_1"));
+    assert_eq!(ctx.turn_id.as_deref(), Some("turn_1"));
+    assert_eq!(ctx.cwd.as_deref(), Some("/work"));
+    assert_eq!(ctx.model.as_deref(), Some("gpt-5"));
+    assert_eq!(ctx.metadata["tenant"], "acme");
+    assert_eq!(ctx.tool_name.as_deref(), Some("bash"));
+    assert_eq!(ctx.correlation_id, "hk-fixed");
+    assert_eq!(ctx.ts_ms, 42);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn hook_context_builder_drives_hook_call_without_runtime() {
+    let hook = CwdGuardHook;
+
+    let allowed = HookContext::builder(HookPhase::PreRun).cwd("/work").build();
+    assert!(matches!(hook.call(&allowed).await, Ok(HookAction::Noop)));
+
+    let blocked = HookContext::builder(HookPhase::PreRun).cwd("/etc").build();
+    match hook.call(&blocked).await {
+        Ok(HookAction::Block(reason)) => assert_eq!(reason.phase, HookPhase::PreRun),
+        other => panic!("expected block, got {other:?}"),
+    }
+}
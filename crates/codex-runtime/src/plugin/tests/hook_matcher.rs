@@ -18,8 +18,10 @@ fn ctx(phase: HookPhase, tool_name: Option<&str>, cwd: Option<&str>) -> HookCont
         correlation_id: "hk-test".to_owned(),
         ts_ms: 0,
         metadata: json!({}),
+        assistant_text: None,
         tool_name: tool_name.map(ToOwned::to_owned),
         tool_input: None,
+        shutdown: Default::default(),
     }
 }
 
@@ -22,3 +22,46 @@ fn hook_patch_default_is_noop() {
     assert!(patch.add_attachments.is_empty());
     assert_eq!(patch.metadata_delta, serde_json::Value::Null);
 }
+
+fn issue(phase: HookPhase, class: HookIssueClass, message: &str) -> HookIssue {
+    HookIssue {
+        hook_name: "h".to_owned(),
+        phase,
+        class,
+        message: message.to_owned(),
+    }
+}
+
+#[test]
+fn hook_report_groups_issues_by_phase_and_class() {
+    let mut report = HookReport::default();
+    assert!(report.by_phase().is_empty());
+    assert!(report.by_class().is_empty());
+    assert_eq!(report.worst_class(), None);
+
+    report.push(issue(HookPhase::PostRun, HookIssueClass::Timeout, "a"));
+    report.push(issue(HookPhase::PreRun, HookIssueClass::Validation, "b"));
+    report.push(issue(HookPhase::PostRun, HookIssueClass::Execution, "c"));
+    report.push(issue(HookPhase::PreRun, HookIssueClass::Validation, "d"));
+
+    let by_phase = report.by_phase();
+    assert_eq!(
+        by_phase.keys().copied().collect::<Vec<_>>(),
+        vec![HookPhase::PreRun, HookPhase::PostRun]
+    );
+    let post_run: Vec<&str> = by_phase[&HookPhase::PostRun]
+        .iter()
+        .map(|issue| issue.message.as_str())
+        .collect();
+    assert_eq!(post_run, vec!["a", "c"]);
+
+    let by_class = report.by_class();
+    assert_eq!(by_class.get(&HookIssueClass::Validation), Some(&2));
+    assert_eq!(by_class.get(&HookIssueClass::Timeout), Some(&1));
+    assert_eq!(by_class.get(&HookIssueClass::Execution), Some(&1));
+    assert_eq!(by_class.get(&HookIssueClass::Internal), None);
+
+    assert_eq!(report.worst_class(), Some(HookIssueClass::Execution));
+    report.push(issue(HookPhase::PreTurn, HookIssueClass::Internal, "e"));
+    assert_eq!(report.worst_class(), Some(HookIssueClass::Internal));
+}
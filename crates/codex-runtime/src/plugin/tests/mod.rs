@@ -2,5 +2,7 @@ use super::*;
 
 // Contract + unit coverage for plugin boundary.
 mod contract_version;
+mod hook_context_builder;
 mod hook_matcher;
 mod hook_report;
+mod shutdown_token;
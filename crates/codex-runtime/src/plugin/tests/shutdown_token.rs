@@ -0,0 +1,44 @@
+use super::*;
+
+#[tokio::test(flavor = "current_thread")]
+async fn shutdown_token_cancel_wakes_waiters_and_is_shared_by_clones() {
+    let token = ShutdownToken::new();
+    let clone = token.clone();
+    assert!(!clone.is_cancelled());
+    assert_eq!(token, clone);
+    assert_ne!(token, ShutdownToken::new());
+
+    let waiter = tokio::spawn(async move { clone.cancelled().await });
+    tokio::task::yield_now().await;
+    token.cancel();
+    tokio::time::timeout(std::time::Duration::from_secs(1), waiter)
+        .await
+        .expect("waiter woke")
+        .expect("waiter join");
+    assert!(token.is_cancelled());
+}
+
+#[test]
+fn hook_context_serialization_omits_shutdown_token() {
+    let ctx = HookContext {
+        phase: HookPhase::PostRun,
+        thread_id: None,
+        turn_id: None,
+        cwd: None,
+        model: None,
+        main_status: None,
+        correlation_id: "hk-shutdown".to_owned(),
+        ts_ms: 0,
+        metadata: Value::Null,
+        assistant_text: None,
+        tool_name: None,
+        tool_input: None,
+        shutdown: ShutdownToken::new(),
+    };
+    ctx.shutdown().cancel();
+
+    let wire = serde_json::to_value(&ctx).expect("serialize context");
+    assert!(wire.get("shutdown").is_none());
+    let decoded: HookContext = serde_json::from_value(wire).expect("deserialize context");
+    assert!(!decoded.shutdown().is_cancelled());
+}
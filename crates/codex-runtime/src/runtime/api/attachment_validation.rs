@@ -1,30 +1,225 @@
+use std::path::Path;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
 use tokio::fs;
+use tokio::io::AsyncReadExt;
+
+use super::{InputItem, PromptAttachment, PromptRunError, PromptRunParams};
 
-use super::{PromptAttachment, PromptRunError};
+/// Longest magic-byte prefix inspected when sniffing image content.
+const SNIFF_PREFIX_LEN: usize = 12;
+
+/// Per-run attachment caps from `RuntimeConfig`; both `None` means unchecked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AttachmentLimits {
+    pub(crate) max_count: Option<usize>,
+    pub(crate) max_bytes: Option<u64>,
+}
+
+/// Local checks every prompt run passes (after PreRun hook patches) before any RPC:
+/// non-empty input, then attachment existence, type, and limits.
+pub(super) async fn validate_prompt(
+    p: &PromptRunParams,
+    limits: &AttachmentLimits,
+) -> Result<(), PromptRunError> {
+    ensure_prompt_not_empty(p)?;
+    validate_prompt_attachments(&p.cwd, &p.attachments, limits).await
+}
+
+/// A blank (empty or whitespace-only) prompt without attachments has nothing to send.
+pub(super) fn ensure_prompt_not_empty(p: &PromptRunParams) -> Result<(), PromptRunError> {
+    if p.prompt.trim().is_empty() && p.attachments.is_empty() {
+        return Err(PromptRunError::EmptyPrompt);
+    }
+    Ok(())
+}
 
 pub(super) async fn validate_prompt_attachments(
     cwd: &str,
     attachments: &[PromptAttachment],
+    limits: &AttachmentLimits,
 ) -> Result<(), PromptRunError> {
+    if let Some(max_count) = limits.max_count {
+        if attachments.len() > max_count {
+            return Err(PromptRunError::AttachmentLimitExceeded {
+                unit: "attachments",
+                limit: max_count as u64,
+                actual: attachments.len() as u64,
+            });
+        }
+    }
     for attachment in attachments {
-        match attachment {
+        validate_prompt_attachment(cwd, attachment).await?;
+    }
+    if let Some(max_bytes) = limits.max_bytes {
+        let total = total_attachment_bytes(cwd, attachments).await;
+        if total > max_bytes {
+            return Err(PromptRunError::AttachmentLimitExceeded {
+                unit: "bytes",
+                limit: max_bytes,
+                actual: total,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Sum local attachment file sizes; image URLs and unreadable paths count as zero.
+/// Allocation: one resolved path per local attachment. Complexity: O(n) metadata calls.
+async fn total_attachment_bytes(cwd: &str, attachments: &[PromptAttachment]) -> u64 {
+    let mut total = 0u64;
+    for attachment in attachments {
+        let path = match attachment {
             PromptAttachment::AtPath { path, .. }
-            | PromptAttachment::LocalImage { path }
-            | PromptAttachment::Skill { path, .. } => {
-                let resolved = super::resolve_attachment_path(cwd, path);
-                if fs::metadata(&resolved).await.is_err() {
-                    return Err(PromptRunError::AttachmentNotFound(
-                        resolved.to_string_lossy().to_string(),
-                    ));
-                }
+            | PromptAttachment::Skill { path, .. }
+            | PromptAttachment::LocalImage { path } => path,
+            PromptAttachment::ImageUrl { .. } => continue,
+        };
+        let resolved = super::resolve_attachment_path(cwd, path);
+        if let Ok(metadata) = fs::metadata(&resolved).await {
+            total = total.saturating_add(metadata.len());
+        }
+    }
+    total
+}
+
+/// Check one attachment: local paths must exist, local images must sniff as a supported image.
+/// Allocation: resolved path + one small read buffer for images. Complexity: O(1) I/O calls.
+pub(super) async fn validate_prompt_attachment(
+    cwd: &str,
+    attachment: &PromptAttachment,
+) -> Result<(), PromptRunError> {
+    match attachment {
+        PromptAttachment::AtPath { path, .. } | PromptAttachment::Skill { path, .. } => {
+            let resolved = super::resolve_attachment_path(cwd, path);
+            if fs::metadata(&resolved).await.is_err() {
+                return Err(PromptRunError::AttachmentNotFound(
+                    resolved.to_string_lossy().to_string(),
+                ));
+            }
+        }
+        PromptAttachment::LocalImage { path } => {
+            sniff_local_image(cwd, path).await?;
+        }
+        PromptAttachment::ImageUrl { .. } => {}
+    }
+    Ok(())
+}
+
+/// Fill `mime_type` on every `LocalImage` input item from its magic bytes.
+/// Items whose file cannot be read or sniffed keep `None`; validation already rejected them.
+pub(super) async fn annotate_local_image_mime_types(cwd: &str, input: &mut [InputItem]) {
+    for item in input {
+        if let InputItem::LocalImage { path, mime_type } = item {
+            if mime_type.is_none() {
+                *mime_type = sniff_local_image(cwd, path)
+                    .await
+                    .ok()
+                    .map(ToOwned::to_owned);
+            }
+        }
+    }
+}
+
+/// Replace every `LocalImage` input item with an `ImageUrl` carrying a base64 `data:` URL.
+/// Each file is read once: the MIME type is sniffed from the same bytes that get encoded.
+/// The byte cap is checked against bytes actually read, and unreadable files fail
+/// instead of counting as zero.
+/// Allocation: one file buffer + one encoded URL per image. Complexity: O(total image bytes).
+pub(super) async fn inline_local_images(
+    cwd: &str,
+    input: &mut [InputItem],
+    limits: &AttachmentLimits,
+) -> Result<(), PromptRunError> {
+    let mut total = 0u64;
+    for item in input {
+        let InputItem::LocalImage { path, mime_type } = item else {
+            continue;
+        };
+        let resolved = super::resolve_attachment_path(cwd, path);
+        let bytes = fs::read(&resolved).await.map_err(|_| {
+            PromptRunError::AttachmentNotFound(resolved.to_string_lossy().to_string())
+        })?;
+        let mime = match mime_type.take() {
+            Some(mime) => mime,
+            None => sniff_image_mime(&bytes)
+                .ok_or_else(|| unsupported_image(&resolved))?
+                .to_owned(),
+        };
+        total = total.saturating_add(bytes.len() as u64);
+        if let Some(max_bytes) = limits.max_bytes {
+            if total > max_bytes {
+                return Err(PromptRunError::AttachmentLimitExceeded {
+                    unit: "bytes",
+                    limit: max_bytes,
+                    actual: total,
+                });
             }
-            PromptAttachment::ImageUrl { .. } => {}
         }
+        *item = InputItem::ImageUrl {
+            url: format!("data:{mime};base64,{}", BASE64.encode(&bytes)),
+        };
     }
     Ok(())
 }
 
-pub(super) async fn hook_attachment_path_exists(cwd: &str, path: &str) -> bool {
+/// Resolve and sniff one local image attachment.
+/// Allocation: resolved path + one read buffer. Complexity: O(1) I/O calls.
+async fn sniff_local_image(cwd: &str, path: &str) -> Result<&'static str, PromptRunError> {
     let resolved = super::resolve_attachment_path(cwd, path);
-    fs::metadata(&resolved).await.is_ok()
+    let not_found = || PromptRunError::AttachmentNotFound(resolved.to_string_lossy().to_string());
+    let mut file = fs::File::open(&resolved).await.map_err(|_| not_found())?;
+    let mut head = Vec::with_capacity(SNIFF_PREFIX_LEN);
+    (&mut file)
+        .take(SNIFF_PREFIX_LEN as u64)
+        .read_to_end(&mut head)
+        .await
+        .map_err(|_| not_found())?;
+
+    sniff_image_mime(&head).ok_or_else(|| unsupported_image(&resolved))
+}
+
+fn unsupported_image(resolved: &Path) -> PromptRunError {
+    PromptRunError::UnsupportedAttachmentType {
+        path: resolved.to_string_lossy().to_string(),
+        detected: mime_from_extension(resolved)
+            .unwrap_or("unknown")
+            .to_owned(),
+    }
+}
+
+/// Detect a supported image MIME type from leading magic bytes.
+/// Pure function. Allocation: none. Complexity: O(1).
+pub(super) fn sniff_image_mime(head: &[u8]) -> Option<&'static str> {
+    if head.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if head.starts_with(b"\xff\xd8\xff") {
+        Some("image/jpeg")
+    } else if head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if head.len() >= 12 && head.starts_with(b"RIFF") && &head[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// Best-effort MIME guess from the file extension, used only to explain rejections.
+/// Pure function. Allocation: none. Complexity: O(extension length).
+fn mime_from_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        _ => return None,
+    })
 }
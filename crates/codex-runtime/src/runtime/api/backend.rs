@@ -0,0 +1,164 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use crate::runtime::core::Runtime;
+use crate::runtime::errors::{RpcError, RuntimeError};
+use crate::runtime::events::Envelope;
+use crate::runtime::turn_lifecycle::{collect_turn_terminal_with_limits, TurnCollectError};
+use crate::runtime::turn_output::TurnStreamCollector;
+
+use super::attachment_validation::ensure_prompt_not_empty;
+use super::output_schema::assistant_text_matches_schema;
+use super::prompt_run::ReasoningBudget;
+use super::turn_error::{extract_turn_error_signal, PromptTurnErrorSignal};
+use super::wire::{thread_start_params_from_prompt, turn_start_params_from_prompt};
+use super::{
+    AttachmentPlaceholderStrategy, PromptRunError, PromptRunParams, PromptRunResult, ThreadHandle,
+    ThreadId, ThreadStartParams, TurnDiagnostics, TurnId, TurnStartParams,
+};
+
+pub type PromptBackendFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Minimal surface prompt orchestration needs: open a thread, start a turn, and watch
+/// the live stream. `Runtime` implements it over stdio; tests can implement it over a
+/// broadcast channel without spawning a process.
+pub trait PromptBackend: Send + Sync {
+    fn thread_start<'a>(
+        &'a self,
+        params: ThreadStartParams,
+    ) -> PromptBackendFuture<'a, Result<ThreadId, RpcError>>;
+    fn turn_start<'a>(
+        &'a self,
+        thread_id: &'a str,
+        params: TurnStartParams,
+    ) -> PromptBackendFuture<'a, Result<TurnId, RpcError>>;
+    /// Live envelope stream; subscribed before `turn_start` so early events are kept.
+    fn subscribe(&self) -> broadcast::Receiver<Envelope>;
+
+    /// Turn wait when `PromptRunParams::timeout` is unset.
+    fn default_prompt_timeout(&self) -> Duration {
+        Duration::from_secs(120)
+    }
+}
+
+impl PromptBackend for Runtime {
+    fn thread_start<'a>(
+        &'a self,
+        params: ThreadStartParams,
+    ) -> PromptBackendFuture<'a, Result<ThreadId, RpcError>> {
+        Box::pin(async move { Ok(Runtime::thread_start(self, params).await?.thread_id) })
+    }
+
+    fn turn_start<'a>(
+        &'a self,
+        thread_id: &'a str,
+        params: TurnStartParams,
+    ) -> PromptBackendFuture<'a, Result<TurnId, RpcError>> {
+        Box::pin(async move {
+            let thread = ThreadHandle {
+                thread_id: thread_id.to_owned(),
+                runtime: self.clone(),
+            };
+            Ok(thread.turn_start(params).await?.turn_id)
+        })
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<Envelope> {
+        self.subscribe_live()
+    }
+
+    fn default_prompt_timeout(&self) -> Duration {
+        self.resolve_prompt_timeout(None)
+    }
+}
+
+/// Run one prompt against any `PromptBackend`: new thread, one turn, assistant text.
+/// Same empty-prompt check and terminal mapping as `Runtime::run_prompt` (failed/interrupted
+/// turns, empty text, `fail_on_command_error`, output schema check), without hooks,
+/// attachment checks, steering, effort retry, or lag recovery; a lagged stream keeps waiting.
+/// `max_reasoning_tokens` is enforced the same way, but on timeout or an exceeded budget the
+/// turn is left running: the trait has no interrupt.
+/// Allocation: wire params + collected assistant text. Complexity: O(e), e = envelopes.
+pub async fn run_prompt_with_backend<B: PromptBackend + ?Sized>(
+    backend: &B,
+    p: PromptRunParams,
+) -> Result<PromptRunResult, PromptRunError> {
+    ensure_prompt_not_empty(&p)?;
+    let thread_id = backend
+        .thread_start(thread_start_params_from_prompt(&p))
+        .await?;
+    let mut live_rx = backend.subscribe();
+    let turn_id = backend
+        .turn_start(
+            &thread_id,
+            turn_start_params_from_prompt(&p, &AttachmentPlaceholderStrategy::default()),
+        )
+        .await?;
+
+    let timeout_duration = p
+        .timeout
+        .unwrap_or_else(|| backend.default_prompt_timeout());
+    let mut stream = TurnStreamCollector::new(&thread_id, &turn_id)
+        .with_item_separator(&p.assistant_item_separator);
+    let mut last_turn_error: Option<PromptTurnErrorSignal> = None;
+    let mut diagnostics = TurnDiagnostics::default();
+    let mut reasoning_budget = p.max_reasoning_tokens.map(ReasoningBudget::new);
+    let collected = collect_turn_terminal_with_limits(
+        &mut live_rx,
+        &mut stream,
+        usize::MAX,
+        timeout_duration,
+        |envelope| {
+            diagnostics.observe(envelope);
+            if let Some(err) = extract_turn_error_signal(envelope) {
+                last_turn_error = Some(err);
+            }
+            match reasoning_budget.as_mut() {
+                Some(budget) => budget.observe(envelope),
+                None => Ok(()),
+            }
+        },
+        |_| async { Ok(None) },
+    )
+    .await;
+    let terminal = match collected {
+        Ok((terminal, _)) => terminal,
+        Err(TurnCollectError::Timeout) => return Err(PromptRunError::Timeout(timeout_duration)),
+        Err(TurnCollectError::StreamClosed) => {
+            return Err(PromptRunError::Runtime(RuntimeError::Internal(
+                "live stream closed".to_owned(),
+            )))
+        }
+        Err(TurnCollectError::EventBudgetExceeded) => {
+            return Err(PromptRunError::Runtime(RuntimeError::Internal(
+                "turn event budget exhausted while collecting assistant output".to_owned(),
+            )))
+        }
+        Err(TurnCollectError::TargetEnvelope(err) | TurnCollectError::LagProbe(err)) => {
+            return Err(err)
+        }
+    };
+    if let Some(budget) = reasoning_budget.as_mut() {
+        budget
+            .observe_trailing_usage(&mut live_rx, &thread_id, &turn_id)
+            .await?;
+    }
+
+    let assistant_text = Runtime::resolve_prompt_turn_assistant_text(
+        terminal,
+        stream.into_assistant_text(),
+        None,
+        last_turn_error,
+        &diagnostics,
+        p.fail_on_command_error,
+    )?;
+    Ok(PromptRunResult {
+        thread_id,
+        turn_id,
+        schema_valid: assistant_text_matches_schema(p.output_schema.as_ref(), &assistant_text),
+        assistant_text,
+    })
+}
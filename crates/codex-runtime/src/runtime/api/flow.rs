@@ -1,13 +1,14 @@
 use crate::plugin::{
     HookAction, HookAttachment, HookContext, HookIssue, HookIssueClass, HookPatch, HookPhase,
-    HookReport,
+    HookReport, ShutdownToken,
 };
 use serde_json::{Map, Value};
 
 use crate::runtime::hooks::PreHookDecision;
 
 use super::{
-    PromptAttachment, PromptRunParams, ThreadItemPayloadView, ThreadStartParams, ThreadTurnView,
+    PromptAttachment, PromptRunError, PromptRunParams, ThreadItemPayloadView, ThreadStartParams,
+    ThreadTurnView,
 };
 
 #[derive(Clone, Debug)]
@@ -15,6 +16,8 @@ pub(crate) struct HookExecutionState {
     pub(super) correlation_id: String,
     pub(super) report: HookReport,
     pub(super) metadata: Value,
+    /// Text from a PreRun/PreTurn `HookAction::Respond`; the run skips `turn/start`.
+    pub(super) short_circuit: Option<String>,
 }
 
 impl HookExecutionState {
@@ -23,8 +26,30 @@ impl HookExecutionState {
             correlation_id,
             report: HookReport::default(),
             metadata: Value::Object(Map::new()),
+            short_circuit: None,
         }
     }
+
+    /// Post-hook main status: `"short_circuited"` once a Respond hook answered the run,
+    /// else `"ok"` / `"error"` from `result`.
+    pub(super) fn main_status<T, E>(&self, result: &Result<T, E>) -> &'static str {
+        if self.short_circuit.is_some() {
+            SHORT_CIRCUITED_STATUS
+        } else {
+            result_status(result)
+        }
+    }
+
+    /// Start hook state from caller-supplied metadata.
+    /// Non-object metadata is ignored and replaced by an empty object.
+    /// Allocation: one metadata clone. Complexity: O(m), m = metadata size.
+    pub(super) fn seeded(correlation_id: String, metadata: &Value) -> Self {
+        let mut state = Self::new(correlation_id);
+        if metadata.is_object() {
+            state.metadata = metadata.clone();
+        }
+        state
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -33,6 +58,7 @@ pub(super) struct PromptMutationState {
     pub(super) model: Option<String>,
     pub(super) attachments: Vec<PromptAttachment>,
     pub(super) metadata: Value,
+    pub(super) response: Option<String>,
 }
 
 impl PromptMutationState {
@@ -42,6 +68,7 @@ impl PromptMutationState {
             model: p.model.clone(),
             attachments: p.attachments.clone(),
             metadata,
+            response: None,
         }
     }
 }
@@ -69,12 +96,14 @@ pub(super) struct HookContextInput<'a> {
     pub(super) thread_id: Option<&'a str>,
     pub(super) turn_id: Option<&'a str>,
     pub(super) main_status: Option<&'a str>,
+    pub(super) assistant_text: Option<&'a str>,
 }
 
 pub(super) fn build_hook_context(
     correlation_id: &str,
     metadata: &Value,
     input: HookContextInput<'_>,
+    shutdown: ShutdownToken,
 ) -> HookContext {
     HookContext {
         phase: input.phase,
@@ -86,8 +115,10 @@ pub(super) fn build_hook_context(
         correlation_id: correlation_id.to_owned(),
         ts_ms: super::super::now_millis(),
         metadata: metadata.clone(),
+        assistant_text: input.assistant_text.map(ToOwned::to_owned),
         tool_name: None,
         tool_input: None,
+        shutdown,
     }
 }
 
@@ -171,23 +202,22 @@ async fn apply_prompt_patch(
     }
     for attachment in patch.add_attachments {
         let prompt_attachment = hook_attachment_to_prompt_attachment(attachment);
-        let valid = match &prompt_attachment {
-            PromptAttachment::AtPath { path, .. }
-            | PromptAttachment::LocalImage { path }
-            | PromptAttachment::Skill { path, .. } => {
-                super::attachment_validation::hook_attachment_path_exists(cwd, path).await
-            }
-            PromptAttachment::ImageUrl { .. } => true,
-        };
-        if valid {
-            state.attachments.push(prompt_attachment);
-        } else {
-            push_validation_issue(
+        match super::attachment_validation::validate_prompt_attachment(cwd, &prompt_attachment)
+            .await
+        {
+            Ok(()) => state.attachments.push(prompt_attachment),
+            Err(PromptRunError::UnsupportedAttachmentType { .. }) => push_validation_issue(
+                report,
+                hook_name,
+                phase,
+                "hook attachment is not a supported image; mutation ignored",
+            ),
+            Err(_) => push_validation_issue(
                 report,
                 hook_name,
                 phase,
                 "hook attachment path not found; mutation ignored",
-            );
+            ),
         }
     }
     merge_metadata_delta(
@@ -244,6 +274,9 @@ pub(super) async fn apply_pre_hook_actions_to_prompt(
     for decision in decisions {
         match decision.action {
             HookAction::Noop | HookAction::Block(_) => {}
+            HookAction::Respond { text } => {
+                state.response.get_or_insert(text);
+            }
             HookAction::Mutate(patch) => {
                 apply_prompt_patch(
                     state,
@@ -267,7 +300,7 @@ pub(super) fn apply_pre_hook_actions_to_session(
 ) {
     for decision in decisions {
         match decision.action {
-            HookAction::Noop | HookAction::Block(_) => {}
+            HookAction::Noop | HookAction::Block(_) | HookAction::Respond { .. } => {}
             HookAction::Mutate(patch) => {
                 apply_session_patch(state, decision.hook_name.as_str(), phase, patch, report)
             }
@@ -275,6 +308,9 @@ pub(super) fn apply_pre_hook_actions_to_session(
     }
 }
 
+/// Post-hook main status for a run answered by `HookAction::Respond`.
+pub(super) const SHORT_CIRCUITED_STATUS: &str = "short_circuited";
+
 pub(super) fn result_status<T, E>(result: &Result<T, E>) -> &'static str {
     if result.is_ok() {
         "ok"
@@ -283,7 +319,10 @@ pub(super) fn result_status<T, E>(result: &Result<T, E>) -> &'static str {
     }
 }
 
-pub(super) fn extract_assistant_text_from_turn(turn: &ThreadTurnView) -> Option<String> {
+pub(super) fn extract_assistant_text_from_turn(
+    turn: &ThreadTurnView,
+    item_separator: &str,
+) -> Option<String> {
     let mut parts = Vec::<String>::new();
     for item in &turn.items {
         if let ThreadItemPayloadView::AgentMessage(data) = &item.payload {
@@ -295,6 +334,6 @@ pub(super) fn extract_assistant_text_from_turn(turn: &ThreadTurnView) -> Option<
     if parts.is_empty() {
         None
     } else {
-        Some(parts.join("\n"))
+        Some(parts.join(item_separator))
     }
 }
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+use crate::runtime::core::Runtime;
+use crate::runtime::events::Envelope;
+use crate::runtime::rpc_contract::methods;
+
+use super::types::item_text_or_summary;
+use super::{
+    PromptRunError, PromptRunParams, PromptRunResult, ThreadCommandExecutionItemView,
+    ThreadItemPayloadView, ThreadItemType, ThreadItemView, ThreadReadParams, TurnUsage,
+};
+
+/// Everything one `run_prompt_full` call observed for its turn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FullRunResult {
+    /// Same result `run_prompt` returns (ids, assistant text, schema check).
+    pub run: PromptRunResult,
+    /// Text of completed `reasoning` items (summary, else content), newline-joined.
+    pub reasoning_text: String,
+    /// Completed `commandExecution` items in completion order.
+    pub commands: Vec<ThreadCommandExecutionItemView>,
+    /// Completed `fileChange` items in completion order.
+    pub file_changes: Vec<ThreadItemView>,
+    /// Last `thread/tokenUsage/updated` payload for the turn; `None` when not reported.
+    pub usage: Option<Value>,
+    /// `usage` parsed into token counts, tagged with the requested model; feed it to
+    /// `TurnUsage::estimated_cost`.
+    pub turn_usage: Option<TurnUsage>,
+}
+
+impl Runtime {
+    /// Run one prompt like `run_prompt` and also collect reasoning, commands, file changes,
+    /// and token usage for the turn from the same live stream.
+    /// When the collector lags, items come from a `thread/read` snapshot instead (usage
+    /// is then whatever arrived before the lag). Meant for analysis and debugging tools;
+    /// `run_prompt` stays the minimal-allocation path.
+    /// Side effects: same as `run_prompt`, plus one live subscription and at most one
+    /// thread/read RPC. Allocation: one parsed item per `item/completed` and one usage
+    /// value per `(thread, turn)`; other envelopes are dropped on receipt.
+    /// Complexity: O(e), e = envelopes received during the run.
+    pub async fn run_prompt_full(
+        &self,
+        p: PromptRunParams,
+    ) -> Result<FullRunResult, PromptRunError> {
+        let model = p.model.clone();
+        let mut live_rx = self.subscribe_live();
+        let mut collected = TurnCollections::default();
+        let mut lagged = false;
+        let mut live_closed = false;
+
+        let run = self.run_prompt(p);
+        tokio::pin!(run);
+        let run = loop {
+            tokio::select! {
+                result = &mut run => break result?,
+                received = live_rx.recv(), if !live_closed => match received {
+                    Ok(envelope) => collected.observe(&envelope),
+                    Err(RecvError::Lagged(_)) => lagged = true,
+                    Err(RecvError::Closed) => live_closed = true,
+                },
+            }
+        };
+        loop {
+            match live_rx.try_recv() {
+                Ok(envelope) => collected.observe(&envelope),
+                Err(TryRecvError::Lagged(_)) => lagged = true,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+
+        let TurnCollection { mut items, usage } = collected.take(&run.thread_id, &run.turn_id);
+        if lagged {
+            if let Some(snapshot_items) = self.read_turn_items(&run).await {
+                items = snapshot_items;
+            }
+        }
+
+        Ok(full_run_result(run, items, usage, model.as_deref()))
+    }
+
+    /// Items of the run's turn from `thread/read`; `None` when the read fails or the
+    /// turn is missing, so live-collected items are kept.
+    async fn read_turn_items(&self, run: &PromptRunResult) -> Option<Vec<ThreadItemView>> {
+        let response = self
+            .thread_read(ThreadReadParams {
+                thread_id: run.thread_id.clone(),
+                include_turns: Some(true),
+                last_n_turns: None,
+            })
+            .await
+            .ok()?;
+        response
+            .thread
+            .turns
+            .into_iter()
+            .find(|turn| turn.id == run.turn_id)
+            .map(|turn| turn.items)
+    }
+}
+
+/// Completed items and latest usage of one turn.
+#[derive(Default)]
+struct TurnCollection {
+    items: Vec<ThreadItemView>,
+    usage: Option<Value>,
+}
+
+/// Per-`(thread, turn)` collections built while the run's ids are still unknown
+/// (`run_prompt` only reports them at the end).
+#[derive(Default)]
+struct TurnCollections {
+    turns: HashMap<(Arc<str>, Arc<str>), TurnCollection>,
+}
+
+impl TurnCollections {
+    /// Keep `item/completed` and token-usage envelopes that carry thread and turn ids;
+    /// drop everything else. Allocation: one parsed item or usage clone when kept.
+    fn observe(&mut self, envelope: &Envelope) {
+        let (Some(thread_id), Some(turn_id)) = (&envelope.thread_id, &envelope.turn_id) else {
+            return;
+        };
+        match envelope.method.as_deref() {
+            Some(methods::ITEM_COMPLETED) => {
+                if let Some(item) = completed_item_view(envelope) {
+                    self.turn(thread_id, turn_id).items.push(item);
+                }
+            }
+            Some(methods::THREAD_TOKEN_USAGE_UPDATED) => {
+                let usage = envelope
+                    .json
+                    .get("params")
+                    .and_then(|params| params.get("tokenUsage"));
+                if let Some(usage) = usage {
+                    self.turn(thread_id, turn_id).usage = Some(usage.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn turn(&mut self, thread_id: &Arc<str>, turn_id: &Arc<str>) -> &mut TurnCollection {
+        self.turns
+            .entry((Arc::clone(thread_id), Arc::clone(turn_id)))
+            .or_default()
+    }
+
+    fn take(&mut self, thread_id: &str, turn_id: &str) -> TurnCollection {
+        self.turns
+            .remove(&(Arc::from(thread_id), Arc::from(turn_id)))
+            .unwrap_or_default()
+    }
+}
+
+/// Typed view of an `item/completed` payload; the envelope's item id fills a missing `id`.
+fn completed_item_view(envelope: &Envelope) -> Option<ThreadItemView> {
+    let mut item = envelope.json.get("params")?.get("item")?.clone();
+    let fields = item.as_object_mut()?;
+    if !fields.contains_key("id") {
+        let item_id = envelope.item_id.as_deref()?;
+        fields.insert("id".to_owned(), Value::String(item_id.to_owned()));
+    }
+    serde_json::from_value(item).ok()
+}
+
+fn full_run_result(
+    run: PromptRunResult,
+    items: Vec<ThreadItemView>,
+    usage: Option<Value>,
+    model: Option<&str>,
+) -> FullRunResult {
+    let mut reasoning = Vec::<String>::new();
+    let mut commands = Vec::new();
+    let mut file_changes = Vec::new();
+    for item in items {
+        match (&item.item_type, item.payload) {
+            (_, ThreadItemPayloadView::CommandExecution(command)) => commands.push(command),
+            (ThreadItemType::FileChange, payload) => file_changes.push(ThreadItemView {
+                id: item.id,
+                item_type: item.item_type,
+                payload,
+            }),
+            (ThreadItemType::Reasoning, payload) => {
+                let text = item_text_or_summary(&payload);
+                if !text.is_empty() {
+                    reasoning.push(text);
+                }
+            }
+            _ => {}
+        }
+    }
+    FullRunResult {
+        run,
+        reasoning_text: reasoning.join("\n"),
+        commands,
+        file_changes,
+        turn_usage: usage
+            .as_ref()
+            .and_then(|usage| TurnUsage::from_token_usage(usage, model)),
+        usage,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::{json, Value};
+
+    use super::TurnCollections;
+    use crate::runtime::events::{Direction, Envelope, MsgKind};
+
+    fn notification(method: &str, thread_id: &str, turn_id: &str, params: Value) -> Envelope {
+        Envelope {
+            seq: 1,
+            ts_millis: 0,
+            direction: Direction::Inbound,
+            kind: MsgKind::Notification,
+            rpc_id: None,
+            method: Some(Arc::from(method)),
+            thread_id: Some(Arc::from(thread_id)),
+            turn_id: Some(Arc::from(turn_id)),
+            item_id: params.get("itemId").and_then(Value::as_str).map(Arc::from),
+            json: Arc::new(json!({"method": method, "params": params})),
+        }
+    }
+
+    #[test]
+    fn collections_keep_only_items_and_usage_per_turn() {
+        let mut collected = TurnCollections::default();
+        let item = |id: &str| json!({"itemId": id, "item": {"type": "reasoning", "summary": [id], "content": []}});
+        for envelope in [
+            notification("item/completed", "thr", "turn", item("mine")),
+            notification("item/completed", "thr", "other", item("theirs")),
+            notification(
+                "item/agentMessage/delta",
+                "thr",
+                "turn",
+                json!({"delta": "x"}),
+            ),
+            notification(
+                "thread/tokenUsage/updated",
+                "thr",
+                "turn",
+                json!({"tokenUsage": {"total": {"inputTokens": 1}}}),
+            ),
+        ] {
+            collected.observe(&envelope);
+        }
+
+        let mine = collected.take("thr", "turn");
+        assert_eq!(mine.items.len(), 1);
+        assert_eq!(mine.items[0].id, "mine");
+        assert_eq!(mine.usage, Some(json!({"total": {"inputTokens": 1}})));
+        assert!(collected.take("thr", "turn").items.is_empty());
+        assert_eq!(collected.take("thr", "other").items.len(), 1);
+    }
+}
@@ -4,23 +4,32 @@ use crate::runtime::rpc_contract::methods;
 use crate::runtime::turn_output::parse_thread_id;
 
 mod attachment_validation;
+mod backend;
 mod command_exec_api;
 mod flow;
+mod full_run;
+mod model_check;
 mod models;
+mod output_schema;
+mod prompt_batch;
 mod prompt_run;
 mod thread_api;
+mod thread_pool;
 pub(crate) mod tool_use_hooks;
 mod turn_error;
+mod usage;
 mod wire;
 
 use std::path::PathBuf;
 
+pub(crate) use attachment_validation::AttachmentLimits;
 #[cfg(test)]
-use attachment_validation::validate_prompt_attachments;
+use attachment_validation::{validate_prompt, validate_prompt_attachments};
+pub(crate) use output_schema::json_type_name;
 #[cfg(test)]
 use wire::build_prompt_inputs;
 #[cfg(test)]
-use wire::{input_item_to_wire, turn_start_params_to_wire};
+use wire::{input_item_to_wire, turn_start_params_to_wire, validate_turn_extra_params};
 use wire::{thread_start_params_to_wire, validate_thread_start_security};
 
 fn resolve_attachment_path(cwd: &str, path: &str) -> PathBuf {
@@ -34,28 +43,36 @@ fn resolve_attachment_path(cwd: &str, path: &str) -> PathBuf {
 
 mod types;
 
+pub use backend::{run_prompt_with_backend, PromptBackend, PromptBackendFuture};
+pub use full_run::FullRunResult;
+pub use model_check::ModelStatus;
 pub use models::{
     PromptRunError, PromptRunParams, PromptRunResult, PromptRunStream, PromptRunStreamEvent,
-    PromptTurnFailure, PromptTurnTerminalState,
+    PromptTurnFailure, PromptTurnTerminalState, SchemaViolationObserver, TurnDiagnostics,
 };
+pub use thread_pool::ThreadPool;
 pub(crate) use types::{
     sandbox_policy_to_wire_value, summarize_sandbox_policy, summarize_sandbox_policy_wire_value,
 };
 pub use types::{
-    ApprovalPolicy, ByteRange, CommandExecOutputDeltaNotification, CommandExecOutputStream,
-    CommandExecParams, CommandExecResizeParams, CommandExecResizeResponse, CommandExecResponse,
-    CommandExecTerminalSize, CommandExecTerminateParams, CommandExecTerminateResponse,
-    CommandExecWriteParams, CommandExecWriteResponse, ExternalNetworkAccess, InputItem,
-    Personality, PromptAttachment, ReasoningEffort, SandboxPolicy, SandboxPreset, ServiceTier,
-    SkillDependencies, SkillErrorInfo, SkillInterface, SkillMetadata, SkillScope,
-    SkillToolDependency, SkillsListEntry, SkillsListExtraRootsForCwd, SkillsListParams,
-    SkillsListResponse, TextElement, ThreadAgentMessageItemView, ThreadCommandExecutionItemView,
-    ThreadHandle, ThreadId, ThreadItemPayloadView, ThreadItemType, ThreadItemView,
-    ThreadListParams, ThreadListResponse, ThreadListSortKey, ThreadLoadedListParams,
-    ThreadLoadedListResponse, ThreadReadParams, ThreadReadResponse, ThreadRollbackParams,
-    ThreadRollbackResponse, ThreadStartParams, ThreadTurnErrorView, ThreadTurnStatus,
-    ThreadTurnView, ThreadView, TurnHandle, TurnId, TurnStartParams, DEFAULT_REASONING_EFFORT,
+    ApprovalPolicy, AttachmentPlaceholderStrategy, ByteRange, CommandExecOutputDeltaNotification,
+    CommandExecOutputStream, CommandExecParams, CommandExecResizeParams, CommandExecResizeResponse,
+    CommandExecResponse, CommandExecTerminalSize, CommandExecTerminateParams,
+    CommandExecTerminateResponse, CommandExecWriteParams, CommandExecWriteResponse,
+    ExternalNetworkAccess, InputItem, Personality, PromptAttachment, ReasoningEffort,
+    SandboxPolicy, SandboxPreset, ServiceTier, SkillDependencies, SkillErrorInfo, SkillInterface,
+    SkillMetadata, SkillScope, SkillToolDependency, SkillsListEntry, SkillsListExtraRootsForCwd,
+    SkillsListParams, SkillsListResponse, SteerOutcome, TextElement, ThreadAgentMessageItemView,
+    ThreadCommandExecutionItemView, ThreadExport, ThreadExportItem, ThreadExportTurn,
+    ThreadGitInfo, ThreadHandle, ThreadId, ThreadImageViewItemView, ThreadItemPayloadView,
+    ThreadItemType, ThreadItemView, ThreadListParams, ThreadListResponse, ThreadListSortKey,
+    ThreadLoadedListParams, ThreadLoadedListResponse, ThreadMcpToolCallItemView, ThreadReadParams,
+    ThreadReadResponse, ThreadReviewModeItemView, ThreadRollbackParams, ThreadRollbackResponse,
+    ThreadStartParams, ThreadTurnErrorView, ThreadTurnStatus, ThreadTurnView, ThreadView,
+    ThreadWebSearchItemView, TurnGuard, TurnHandle, TurnId, TurnStartParams,
+    DEFAULT_REASONING_EFFORT,
 };
+pub use usage::{ModelPricing, PricingTable, TurnUsage};
 
 impl Runtime {
     pub(crate) async fn thread_start_raw(
@@ -63,7 +80,7 @@ impl Runtime {
         mut p: ThreadStartParams,
     ) -> Result<ThreadHandle, RpcError> {
         p = escalate_approval_if_tool_hooks(self, p);
-        validate_thread_start_security(&p)?;
+        validate_thread_start_security(&p, self.allow_unrestricted_sandbox())?;
         let response = self
             .call_validated(methods::THREAD_START, thread_start_params_to_wire(&p))
             .await?;
@@ -0,0 +1,97 @@
+use serde_json::{Map, Value};
+
+use crate::runtime::core::Runtime;
+use crate::runtime::errors::RpcError;
+use crate::runtime::rpc_contract::methods;
+
+use super::ThreadStartParams;
+
+/// Upper bound on `model/list` pages followed by one `check_model` call.
+const MAX_MODEL_LIST_PAGES: usize = 32;
+
+/// Result of `Runtime::check_model`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModelStatus {
+    /// The server lists the model, or accepted it for a probe thread.
+    Available,
+    /// The server does not list the model, or rejected the probe thread.
+    Unavailable { reason: String },
+}
+
+impl ModelStatus {
+    pub fn is_available(&self) -> bool {
+        matches!(self, Self::Available)
+    }
+}
+
+impl Runtime {
+    /// Check whether `model` is usable before starting a real turn.
+    ///
+    /// Pages through `model/list` and reports `Available` when an entry's `id` or
+    /// `model` matches. When the server answers MethodNotFound, falls back to starting
+    /// an ephemeral probe thread with `model` (no hooks) and archiving it best-effort:
+    /// a server error on `thread/start` becomes `Unavailable` with the error as reason.
+    /// Servers that only validate the model on `turn/start` pass the fallback probe.
+    /// Transport, timeout, and overload errors are returned as `Err`.
+    /// Side effects: model/list RPCs, or thread/start + thread/archive on fallback.
+    /// Allocation: one params object per page. Complexity: O(n), n = listed models.
+    pub async fn check_model(&self, model: &str) -> Result<ModelStatus, RpcError> {
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_MODEL_LIST_PAGES {
+            let mut params = Map::<String, Value>::new();
+            if let Some(cursor) = cursor.take() {
+                params.insert("cursor".to_owned(), Value::String(cursor));
+            }
+            let page = match self
+                .call_validated(methods::MODEL_LIST, Value::Object(params))
+                .await
+            {
+                Ok(page) => page,
+                Err(RpcError::MethodNotFound(_)) => return self.probe_model_thread(model).await,
+                Err(err) => return Err(err),
+            };
+            if model_list_page_contains(&page, model) {
+                return Ok(ModelStatus::Available);
+            }
+            match page.get("nextCursor").and_then(Value::as_str) {
+                Some(next) if !next.is_empty() => cursor = Some(next.to_owned()),
+                _ => break,
+            }
+        }
+        Ok(ModelStatus::Unavailable {
+            reason: format!("model '{model}' is not listed by {}", methods::MODEL_LIST),
+        })
+    }
+
+    async fn probe_model_thread(&self, model: &str) -> Result<ModelStatus, RpcError> {
+        let probe = ThreadStartParams {
+            model: Some(model.to_owned()),
+            ephemeral: Some(true),
+            ..ThreadStartParams::default()
+        };
+        match self.thread_start_raw(probe).await {
+            Ok(thread) => {
+                let _ = self.thread_archive(&thread.thread_id).await;
+                Ok(ModelStatus::Available)
+            }
+            Err(err @ (RpcError::ServerError(_) | RpcError::InvalidRequest(_))) => {
+                Ok(ModelStatus::Unavailable {
+                    reason: err.to_string(),
+                })
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn model_list_page_contains(page: &Value, model: &str) -> bool {
+    page.get("data")
+        .and_then(Value::as_array)
+        .is_some_and(|entries| {
+            entries.iter().any(|entry| {
+                ["id", "model"]
+                    .iter()
+                    .any(|key| entry.get(*key).and_then(Value::as_str) == Some(model))
+            })
+        })
+}
@@ -1,6 +1,7 @@
+use std::sync::Arc;
 use std::time::Duration;
 
-use serde_json::Value;
+use serde_json::{Map, Value};
 use thiserror::Error;
 use tokio::sync::broadcast::Receiver as BroadcastReceiver;
 use tokio::time::Instant;
@@ -14,18 +15,27 @@ use crate::runtime::events::{
 };
 use crate::runtime::hooks::RuntimeHookConfig;
 use crate::runtime::turn_lifecycle::LaggedTurnTerminal;
-use crate::runtime::turn_output::TurnStreamCollector;
+use crate::runtime::turn_limiter::TurnSlot;
+use crate::runtime::turn_output::{TurnStreamCollector, DEFAULT_ASSISTANT_ITEM_SEPARATOR};
 
 use super::{
-    flow::HookExecutionState, turn_error::PromptTurnErrorSignal, ApprovalPolicy, PromptAttachment,
-    ReasoningEffort, SandboxPolicy, ThreadId, TurnId, DEFAULT_REASONING_EFFORT,
+    flow::HookExecutionState, prompt_run::ReasoningBudget, turn_error::PromptTurnErrorSignal,
+    ApprovalPolicy, PromptAttachment, ReasoningEffort, SandboxPolicy, ThreadId, TurnId,
+    DEFAULT_REASONING_EFFORT,
 };
 
+/// Per-run prompt configuration. To reuse one configuration for many prompts, build a
+/// template once and clone it per run:
+/// `let base = PromptRunParams::new(cwd, "").with_model("gpt-5");`
+/// then `runtime.run_prompt(base.clone().with_prompt("question 1"))`.
+/// `clone` copies only owned strings and JSON; it performs no I/O.
 #[derive(Clone, Debug, PartialEq)]
 pub struct PromptRunParams {
     pub cwd: String,
     pub prompt: String,
     pub model: Option<String>,
+    /// Reasoning effort sent on `turn/start`. `new` seeds `DEFAULT_REASONING_EFFORT`;
+    /// `None` omits the wire field entirely (see `no_effort`).
     pub effort: Option<ReasoningEffort>,
     pub approval_policy: ApprovalPolicy,
     pub sandbox_policy: SandboxPolicy,
@@ -33,8 +43,50 @@ pub struct PromptRunParams {
     /// Default stays false to preserve safe-by-default posture.
     pub privileged_escalation_approved: bool,
     pub attachments: Vec<PromptAttachment>,
-    pub timeout: Duration,
+    /// Turn timeout. `None` (the `new` default) uses `RuntimeConfig::default_prompt_timeout`.
+    pub timeout: Option<Duration>,
     pub output_schema: Option<Value>,
+    /// Retry `turn/start` once without `effort` when the server rejects the field.
+    /// Default stays false so unsupported-effort errors surface unchanged.
+    pub drop_effort_on_reject: bool,
+    /// Caller context (tenant id, feature name, ...) seeded into hook metadata.
+    /// Hooks see it from the first phase; non-object values are ignored there.
+    /// Non-null metadata is also recorded once the turn starts as a synthetic
+    /// `runtime/run/metadata` envelope on the sink and live channels.
+    pub metadata: Value,
+    /// Anchor this run as a steer of the given in-flight turn instead of a plain `turn/start`.
+    /// Only `prompt` + attachments are sent; turn overrides (model, effort, sandbox) are skipped.
+    /// A stale anchor surfaces as `PromptRunError::StaleSteerTurn`.
+    pub steer_from: Option<TurnId>,
+    /// Raw fields merged into the `turn/start` wire object (escape hatch for new server features).
+    /// Typed fields win on conflict; `threadId`/`input` are rejected. Ignored for steer runs.
+    pub extra_turn_params: Map<String, Value>,
+    /// Also send `model` in `thread/resume` overrides when continuing an existing thread.
+    /// Default false: resume omits `model` (some servers reject changing an existing
+    /// thread's model); `turn/start` still carries `model` as a per-turn override.
+    pub resend_model_on_resume: bool,
+    /// Send `LocalImage` attachments as base64 `data:` URLs instead of client-side paths,
+    /// for servers that cannot see the local filesystem. Images only: `@path` mentions
+    /// and skills are still sent as paths.
+    pub inline_local_images: bool,
+    /// Inserted between distinct assistant message items in `assistant_text`;
+    /// deltas of one item are never separated. Defaults to a newline.
+    pub assistant_item_separator: String,
+    /// Fail a completed turn with `PromptRunError::CommandFailed` when any
+    /// `commandExecution` item finished with status `failed` or a non-zero exit code.
+    pub fail_on_command_error: bool,
+    /// Accept every command/file-change approval request of this run's turn on the client
+    /// side, keyed on `(thread_id, turn_id)` once `turn/start` returns; other turns of the
+    /// thread still queue. Unlike `ApprovalPolicy::Never` the server still asks; each
+    /// auto-approval is counted in `RuntimeMetricsSnapshot::approvals_auto_approved` and
+    /// recorded in the run's `HookReport::auto_approvals`.
+    pub auto_approve_all: bool,
+    /// Cap on the turn's reasoning tokens (per-step `last.reasoningOutputTokens` summed, or
+    /// the latest `total` when no `last` is sent). Crossing it mid-stream interrupts the turn;
+    /// usage trailing `turn/completed` is checked within a short grace window (best effort
+    /// beyond it). Either way the run fails with `PromptRunError::ReasoningBudgetExceeded`,
+    /// from `run_prompt*` and from `PromptRunStream::recv`/`finish` alike. Never cached.
+    pub max_reasoning_tokens: Option<u64>,
 }
 
 impl PromptRunParams {
@@ -50,11 +102,28 @@ impl PromptRunParams {
             sandbox_policy: SandboxPolicy::Preset(super::SandboxPreset::ReadOnly),
             privileged_escalation_approved: false,
             attachments: Vec::new(),
-            timeout: Duration::from_secs(120),
+            timeout: None,
             output_schema: None,
+            drop_effort_on_reject: false,
+            metadata: Value::Null,
+            steer_from: None,
+            extra_turn_params: Map::new(),
+            resend_model_on_resume: false,
+            inline_local_images: false,
+            assistant_item_separator: DEFAULT_ASSISTANT_ITEM_SEPARATOR.to_owned(),
+            fail_on_command_error: false,
+            auto_approve_all: false,
+            max_reasoning_tokens: None,
         }
     }
 
+    /// Replace the prompt text, e.g. on a clone of a template (see type docs).
+    /// Allocation: one String. Complexity: O(prompt length).
+    pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
     /// Set explicit model override.
     /// Allocation: one String. Complexity: O(model length).
     pub fn with_model(mut self, model: impl Into<String>) -> Self {
@@ -69,6 +138,13 @@ impl PromptRunParams {
         self
     }
 
+    /// Omit `effort` from `turn/start` so the server applies its own default.
+    /// Allocation: none. Complexity: O(1).
+    pub fn no_effort(mut self) -> Self {
+        self.effort = None;
+        self
+    }
+
     /// Set approval policy override.
     /// Allocation: none. Complexity: O(1).
     pub fn with_approval_policy(mut self, approval_policy: ApprovalPolicy) -> Self {
@@ -90,10 +166,80 @@ impl PromptRunParams {
         self
     }
 
-    /// Set prompt timeout.
+    /// Set prompt timeout, overriding `RuntimeConfig::default_prompt_timeout`.
     /// Allocation: none. Complexity: O(1).
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = timeout;
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retry once without `effort` when `turn/start` rejects the reasoning effort field.
+    /// Use for models that do not support reasoning effort at all.
+    pub fn allow_effort_drop_on_reject(mut self) -> Self {
+        self.drop_effort_on_reject = true;
+        self
+    }
+
+    /// Send `model` on the `thread/resume` path too (see `resend_model_on_resume`).
+    /// Allocation: none. Complexity: O(1).
+    pub fn resend_model_on_resume(mut self) -> Self {
+        self.resend_model_on_resume = true;
+        self
+    }
+
+    /// Inline `LocalImage` attachments into the request (see `inline_local_images`).
+    /// Allocation: none. Complexity: O(1).
+    pub fn inline_local_images(mut self, inline: bool) -> Self {
+        self.inline_local_images = inline;
+        self
+    }
+
+    /// Join distinct assistant message items with `separator` (see `assistant_item_separator`).
+    /// Allocation: one String. Complexity: O(separator length).
+    pub fn with_assistant_item_separator(mut self, separator: impl Into<String>) -> Self {
+        self.assistant_item_separator = separator.into();
+        self
+    }
+
+    /// Treat failed commands in a completed turn as a run failure (see `fail_on_command_error`).
+    /// Allocation: none. Complexity: O(1).
+    pub fn fail_on_command_error(mut self, enabled: bool) -> Self {
+        self.fail_on_command_error = enabled;
+        self
+    }
+
+    /// Auto-approve this run's approval requests (see `auto_approve_all`).
+    /// Allocation: none. Complexity: O(1).
+    pub fn auto_approve_all(mut self, enabled: bool) -> Self {
+        self.auto_approve_all = enabled;
+        self
+    }
+
+    /// Fail the run once reasoning usage exceeds `limit` tokens (see `max_reasoning_tokens`).
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_max_reasoning_tokens(mut self, limit: u64) -> Self {
+        self.max_reasoning_tokens = Some(limit);
+        self
+    }
+
+    /// Attach caller metadata that seeds hook context and is recorded for this run's turn.
+    /// Allocation: none (moves Value). Complexity: O(1).
+    pub fn with_metadata(mut self, metadata: Value) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Route this run through `turn_steer_outcome` anchored to `expected_turn_id`.
+    /// Allocation: one String. Complexity: O(id length).
+    pub fn with_steer_from(mut self, expected_turn_id: impl Into<TurnId>) -> Self {
+        self.steer_from = Some(expected_turn_id.into());
+        self
+    }
+
+    /// Add one raw `turn/start` field; typed fields still win on conflict.
+    /// Allocation: one String key. Complexity: O(log n), n = extra field count.
+    pub fn with_extra_turn_param(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.extra_turn_params.insert(key.into(), value);
         self
     }
 
@@ -159,6 +305,10 @@ pub struct PromptRunResult {
     pub thread_id: ThreadId,
     pub turn_id: TurnId,
     pub assistant_text: String,
+    /// Whether `assistant_text` parsed as JSON conforms to the requested output schema.
+    /// `None` when the run did not set `output_schema` or the schema uses keywords the
+    /// built-in validator does not enforce (`$ref`, `minimum`, `pattern`, ...).
+    pub schema_valid: Option<bool>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -173,6 +323,8 @@ pub enum PromptRunStreamEvent {
 pub(crate) struct PromptRunStreamState {
     pub(crate) last_turn_error: Option<PromptTurnErrorSignal>,
     pub(crate) lagged_terminal: Option<LaggedTurnTerminal>,
+    pub(crate) diagnostics: TurnDiagnostics,
+    pub(crate) fail_on_command_error: bool,
     pub(crate) final_result: Option<Result<PromptRunResult, PromptRunError>>,
 }
 
@@ -181,9 +333,15 @@ pub(crate) struct PromptStreamCleanupState {
     pub(crate) run_model: Option<String>,
     pub(crate) scoped_hooks: Option<RuntimeHookConfig>,
     pub(crate) hook_state: Option<HookExecutionState>,
+    /// The run registered its turn for `auto_approve_all`; taken on cleanup.
+    pub(crate) auto_approve: bool,
     pub(crate) cleaned_up: bool,
 }
 
+/// Callback receiving the first definite schema violation (`path: message`) seen while
+/// a structured-output run is still streaming.
+pub type SchemaViolationObserver = Arc<dyn Fn(&str) + Send + Sync>;
+
 pub struct PromptRunStream {
     pub(crate) runtime: Runtime,
     pub(crate) thread_id: ThreadId,
@@ -193,7 +351,15 @@ pub struct PromptRunStream {
     pub(crate) state: PromptRunStreamState,
     pub(crate) deadline: Instant,
     pub(crate) timeout: Duration,
+    pub(crate) output_schema: Option<Value>,
+    pub(crate) schema_violation_observer: Option<SchemaViolationObserver>,
+    /// Set once the schema violation observer has fired.
+    pub(crate) schema_violation_reported: bool,
     pub(crate) cleanup: PromptStreamCleanupState,
+    /// `max_concurrent_turns` slot; released when the terminal result is recorded.
+    pub(crate) turn_slot: Option<TurnSlot>,
+    /// `PromptRunParams::max_reasoning_tokens`, checked on every target envelope.
+    pub(crate) reasoning_budget: Option<ReasoningBudget>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -234,8 +400,93 @@ impl std::fmt::Display for PromptTurnFailure {
     }
 }
 
+/// What the run loop observed for one turn; attached to `EmptyAssistantText`
+/// so "no text" can be told apart from "no items at all".
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TurnDiagnostics {
+    /// `item/started` notifications seen for the turn.
+    pub items_seen: usize,
+    /// Distinct notification methods seen for the turn, in first-seen order.
+    pub methods_seen: Vec<String>,
+    /// Streaming delta notifications (agent message, command output, ...) seen for the turn.
+    pub deltas_seen: usize,
+    /// The terminal state came from a `thread/read` snapshot after the live stream lagged.
+    pub recovered_from_lag: bool,
+    /// `(command, exit code)` of the first `commandExecution` item that completed with
+    /// status `failed` or a non-zero exit code.
+    pub failed_command: Option<(String, Option<i32>)>,
+}
+
+impl TurnDiagnostics {
+    /// Record one target-turn envelope.
+    /// Allocation: one String per newly seen method. Complexity: O(m), m = distinct methods.
+    pub(crate) fn observe(&mut self, envelope: &Envelope) {
+        let Some(method) = envelope.method.as_deref() else {
+            return;
+        };
+        if method == crate::runtime::rpc_contract::methods::ITEM_STARTED {
+            self.items_seen += 1;
+        }
+        if method.ends_with("delta") || method.ends_with("Delta") {
+            self.deltas_seen += 1;
+        }
+        if method == crate::runtime::rpc_contract::methods::ITEM_COMPLETED
+            && self.failed_command.is_none()
+        {
+            self.failed_command = envelope
+                .json
+                .get("params")
+                .and_then(|params| params.get("item"))
+                .and_then(failed_command_from_item);
+        }
+        if !self.methods_seen.iter().any(|seen| seen == method) {
+            self.methods_seen.push(method.to_owned());
+        }
+    }
+}
+
+/// `(command, exit code)` when a completed `commandExecution` item failed.
+/// Pure function. Allocation: one String on failure. Complexity: O(1).
+fn failed_command_from_item(item: &Value) -> Option<(String, Option<i32>)> {
+    let item_type = item.get("type").and_then(Value::as_str)?;
+    if item_type != "commandExecution" && item_type != "command_execution" {
+        return None;
+    }
+    let exit_code = item
+        .get("exitCode")
+        .and_then(Value::as_i64)
+        .and_then(|code| i32::try_from(code).ok());
+    let status_failed = item.get("status").and_then(Value::as_str) == Some("failed");
+    if !status_failed && exit_code.unwrap_or(0) == 0 {
+        return None;
+    }
+    let command = item
+        .get("command")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_owned();
+    Some((command, exit_code))
+}
+
+impl std::fmt::Display for TurnDiagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "items={} deltas={} methods=[{}]",
+            self.items_seen,
+            self.deltas_seen,
+            self.methods_seen.join(", ")
+        )?;
+        if self.recovered_from_lag {
+            f.write_str(" recovered_from_lag")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
 pub enum PromptRunError {
+    /// Includes `RpcError::Draining` when the turn could not start during a reconfigure.
     #[error("rpc error: {0}")]
     Rpc(#[from] RpcError),
     #[error("runtime error: {0}")]
@@ -248,12 +499,45 @@ pub enum PromptRunError {
     TurnInterrupted,
     #[error("turn timed out after {0:?}")]
     Timeout(Duration),
+    /// The caller's cancel `ShutdownToken` fired before the run finished.
+    #[error("prompt run cancelled")]
+    Cancelled,
     #[error("turn completed without assistant text: {0}")]
     TurnCompletedWithoutAssistantText(PromptTurnFailure),
-    #[error("assistant text is empty")]
-    EmptyAssistantText,
+    /// `fail_on_command_error` is set and a command in the completed turn failed.
+    #[error("command failed: {command} (exit code {exit_code:?})")]
+    CommandFailed {
+        command: String,
+        exit_code: Option<i32>,
+    },
+    /// The turn completed with no assistant text and no error signal.
+    #[error("assistant text is empty: {0}")]
+    EmptyAssistantText(TurnDiagnostics),
+    /// Reported reasoning tokens exceeded `PromptRunParams::max_reasoning_tokens`.
+    #[error("reasoning budget exceeded: used {used} > max {limit}")]
+    ReasoningBudgetExceeded { limit: u64, used: u64 },
+    /// The prompt is blank and there are no attachments; nothing was sent.
+    #[error("prompt is empty and has no attachments")]
+    EmptyPrompt,
     #[error("attachment not found: {0}")]
     AttachmentNotFound(String),
+    /// A `LocalImage` attachment whose magic bytes are not a supported image format.
+    #[error("attachment is not a supported image: {path} (detected {detected})")]
+    UnsupportedAttachmentType { path: String, detected: String },
+    /// Attachments exceed `RuntimeConfig::max_attachments` or `max_attachment_bytes`.
+    #[error("attachment limit exceeded: {actual} {unit} > max {limit}")]
+    AttachmentLimitExceeded {
+        /// `"attachments"` or `"bytes"`.
+        unit: &'static str,
+        limit: u64,
+        actual: u64,
+    },
+    /// The server rejected a steer because `expected_turn_id` is no longer the active turn.
+    #[error("steer rejected: expected turn {expected_turn_id} is stale: {message}")]
+    StaleSteerTurn {
+        expected_turn_id: TurnId,
+        message: String,
+    },
     /// A pre-hook explicitly blocked execution before any RPC was sent.
     #[error("blocked by hook '{hook_name}' at {phase:?}: {message}")]
     BlockedByHook {
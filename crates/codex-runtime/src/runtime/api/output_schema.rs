@@ -0,0 +1,767 @@
+//! Minimal JSON Schema checks for final assistant output.
+//! Covers the structural keywords used by turn `outputSchema` payloads:
+//! `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`,
+//! `items`, `anyOf`, `oneOf`, and `allOf`, plus annotation keywords. Schemas using any
+//! other keyword (`$ref`, `minimum`, `pattern`, ...) cannot be judged, so
+//! `assistant_text_matches_schema` returns `None` for them; the violation checks ignore
+//! such keywords and only report what the covered ones definitely reject.
+//! `partial_schema_violations` applies the same checks to a streamed JSON prefix.
+
+use serde_json::Value;
+
+/// Keywords `check_node` enforces.
+const ENFORCED_KEYWORDS: &[&str] = &[
+    "type",
+    "enum",
+    "const",
+    "properties",
+    "required",
+    "additionalProperties",
+    "items",
+    "anyOf",
+    "oneOf",
+    "allOf",
+];
+
+/// Keywords that never constrain an instance.
+const ANNOTATION_KEYWORDS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+];
+
+/// Check final assistant text against an optional output schema.
+/// Returns `None` when no schema was requested or the schema uses a keyword this
+/// validator does not enforce (see `schema_is_supported`); never a guessed `Some(true)`.
+/// Allocation: one parsed JSON value. Complexity: O(n), n = text length + schema size.
+pub(crate) fn assistant_text_matches_schema(
+    schema: Option<&Value>,
+    assistant_text: &str,
+) -> Option<bool> {
+    let schema = schema.filter(|schema| schema_is_supported(schema))?;
+    let matches = match serde_json::from_str::<Value>(assistant_text.trim()) {
+        Ok(instance) => schema_violations(schema, &instance).is_empty(),
+        Err(_) => false,
+    };
+    Some(matches)
+}
+
+/// True when every keyword in `schema` and its subschemas is enforced or an annotation.
+/// Pure function. Allocation: none. Complexity: O(schema size).
+pub(crate) fn schema_is_supported(schema: &Value) -> bool {
+    let schema = match schema {
+        Value::Bool(_) => return true,
+        Value::Object(schema) => schema,
+        _ => return false,
+    };
+    schema
+        .iter()
+        .all(|(keyword, value)| match keyword.as_str() {
+            "properties" => value
+                .as_object()
+                .is_some_and(|props| props.values().all(schema_is_supported)),
+            "additionalProperties" | "items" => schema_is_supported(value),
+            "anyOf" | "oneOf" | "allOf" => value
+                .as_array()
+                .is_some_and(|branches| branches.iter().all(schema_is_supported)),
+            keyword => {
+                ENFORCED_KEYWORDS.contains(&keyword) || ANNOTATION_KEYWORDS.contains(&keyword)
+            }
+        })
+}
+
+/// Collect schema violations as `path: message` strings.
+/// Pure function; empty result means the instance conforms.
+/// Allocation: one String per violation. Complexity: O(schema size * instance size).
+pub(crate) fn schema_violations(schema: &Value, instance: &Value) -> Vec<String> {
+    let mut violations = Vec::new();
+    check_node(schema, instance, "$", &mut violations);
+    violations
+}
+
+/// Collect definite schema violations for a possibly incomplete JSON prefix.
+/// Truncated input is never a violation: in-progress scalars are deferred, and open
+/// containers skip `required`, `enum`, and `const` because later deltas can still satisfy them.
+/// Text that can no longer become JSON is reported as `$: output is not valid JSON`.
+/// Allocation: one repaired copy of the buffer. Complexity: O(n + schema size * instance size).
+pub(crate) fn partial_schema_violations(schema: &Value, buffer: &str) -> Vec<String> {
+    let trimmed = buffer.trim();
+    if trimmed.is_empty() {
+        return Vec::new();
+    }
+    if let Ok(instance) = serde_json::from_str::<Value>(trimmed) {
+        // A complete top-level number can still grow (`1` -> `1.5`), so defer it.
+        if instance.is_number() {
+            return Vec::new();
+        }
+        return schema_violations(schema, &instance);
+    }
+
+    let prefix = match scan_json_prefix(trimmed) {
+        Ok(prefix) => prefix,
+        Err(()) => return vec!["$: output is not valid JSON".to_owned()],
+    };
+    let Some(safe) = prefix else {
+        return Vec::new();
+    };
+    let mut repaired = String::with_capacity(safe.len + safe.closers.len());
+    repaired.push_str(&trimmed[..safe.len]);
+    repaired.push_str(&safe.closers);
+    let Ok(instance) = serde_json::from_str::<Value>(&repaired) else {
+        return Vec::new();
+    };
+
+    let mut violations = Vec::new();
+    check_open_node(schema, &instance, "$", &safe.open_path, &mut violations);
+    violations
+}
+
+fn check_node(schema: &Value, instance: &Value, path: &str, out: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            out.push(format!("{path}: schema rejects all values"));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        if !type_matches(expected, instance) {
+            out.push(format!(
+                "{path}: expected type {expected}, got {}",
+                json_type_name(instance)
+            ));
+            return;
+        }
+    }
+    if let Some(Value::Array(choices)) = schema.get("enum") {
+        if !choices.iter().any(|choice| json_equal(choice, instance)) {
+            out.push(format!("{path}: value is not one of the enum choices"));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if !json_equal(expected, instance) {
+            out.push(format!("{path}: value does not equal const"));
+        }
+    }
+
+    if let Value::Object(fields) = instance {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !fields.contains_key(key) {
+                    out.push(format!("{path}: missing required property '{key}'"));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, value) in fields {
+            let child_path = format!("{path}.{key}");
+            match properties.and_then(|props| props.get(key)) {
+                Some(child) => check_node(child, value, &child_path, out),
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        out.push(format!("{path}: unexpected property '{key}'"));
+                    }
+                    Some(extra @ Value::Object(_)) => check_node(extra, value, &child_path, out),
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    if let (Value::Array(elements), Some(items)) = (instance, schema.get("items")) {
+        for (index, element) in elements.iter().enumerate() {
+            check_node(items, element, &format!("{path}[{index}]"), out);
+        }
+    }
+
+    if let Some(Value::Array(all)) = schema.get("allOf") {
+        for branch in all {
+            check_node(branch, instance, path, out);
+        }
+    }
+    if let Some(Value::Array(any)) = schema.get("anyOf") {
+        if !any
+            .iter()
+            .any(|branch| schema_violations(branch, instance).is_empty())
+        {
+            out.push(format!("{path}: value matches no anyOf branch"));
+        }
+    }
+    if let Some(Value::Array(one)) = schema.get("oneOf") {
+        let matched = one
+            .iter()
+            .filter(|branch| schema_violations(branch, instance).is_empty())
+            .count();
+        if matched != 1 {
+            out.push(format!(
+                "{path}: value matches {matched} oneOf branches, expected exactly 1"
+            ));
+        }
+    }
+}
+
+/// Check a container that later deltas may still extend.
+/// `open_path` leads from this node to its one still-open descendant, if any.
+fn check_open_node(
+    schema: &Value,
+    instance: &Value,
+    path: &str,
+    open_path: &[OpenSegment],
+    out: &mut Vec<String>,
+) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            out.push(format!("{path}: schema rejects all values"));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        if !type_matches(expected, instance) {
+            out.push(format!(
+                "{path}: expected type {expected}, got {}",
+                json_type_name(instance)
+            ));
+            return;
+        }
+    }
+
+    let (open_child, rest) = match open_path.split_first() {
+        Some((segment, rest)) => (Some(segment), rest),
+        None => (None, open_path),
+    };
+    let check_child = |child_schema: &Value,
+                       value: &Value,
+                       child_path: &str,
+                       is_open: bool,
+                       out: &mut Vec<String>| {
+        if is_open {
+            check_open_node(child_schema, value, child_path, rest, out);
+        } else {
+            check_node(child_schema, value, child_path, out);
+        }
+    };
+
+    if let Value::Object(fields) = instance {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (key, value) in fields {
+            let child_path = format!("{path}.{key}");
+            let is_open = matches!(open_child, Some(OpenSegment::Key(open)) if open == key);
+            match properties.and_then(|props| props.get(key)) {
+                Some(child) => check_child(child, value, &child_path, is_open, out),
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        out.push(format!("{path}: unexpected property '{key}'"));
+                    }
+                    Some(extra @ Value::Object(_)) => {
+                        check_child(extra, value, &child_path, is_open, out)
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    if let (Value::Array(elements), Some(items)) = (instance, schema.get("items")) {
+        for (index, element) in elements.iter().enumerate() {
+            let is_open = matches!(open_child, Some(OpenSegment::Index(open)) if *open == index);
+            check_child(items, element, &format!("{path}[{index}]"), is_open, out);
+        }
+    }
+
+    if let Some(Value::Array(all)) = schema.get("allOf") {
+        for branch in all {
+            check_open_node(branch, instance, path, open_path, out);
+        }
+    }
+    // An open value can still satisfy any branch it does not definitely violate.
+    for (keyword, label) in [("anyOf", "anyOf"), ("oneOf", "oneOf")] {
+        if let Some(Value::Array(branches)) = schema.get(keyword) {
+            let all_violated = branches.iter().all(|branch| {
+                let mut branch_out = Vec::new();
+                check_open_node(branch, instance, path, open_path, &mut branch_out);
+                !branch_out.is_empty()
+            });
+            if all_violated {
+                out.push(format!("{path}: value matches no {label} branch"));
+            }
+        }
+    }
+}
+
+/// Child of an open container that is itself still open.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum OpenSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Last point in a JSON prefix where every started value is either complete or an
+/// open container; appending `closers` there yields a valid document.
+struct SafePrefix {
+    len: usize,
+    closers: String,
+    open_path: Vec<OpenSegment>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ScanExpect {
+    Value,
+    ValueOrClose,
+    Key,
+    KeyOrClose,
+    Colon,
+    CommaOrClose,
+}
+
+struct ScanFrame {
+    is_object: bool,
+    /// Segment of the most recently started child.
+    child: Option<OpenSegment>,
+    len: usize,
+}
+
+enum ScanToken {
+    None,
+    Str { is_key: bool, escaped: bool },
+    Number { start: usize },
+    Literal { start: usize },
+}
+
+impl SafePrefix {
+    fn capture(stack: &[ScanFrame], len: usize) -> Self {
+        let closers = stack
+            .iter()
+            .rev()
+            .map(|frame| if frame.is_object { '}' } else { ']' })
+            .collect();
+        // Every frame is open; each one except the innermost leads to the next.
+        let open_path = stack[..stack.len().saturating_sub(1)]
+            .iter()
+            .filter_map(|frame| frame.child.clone())
+            .collect();
+        Self {
+            len,
+            closers,
+            open_path,
+        }
+    }
+}
+
+/// Walk a JSON prefix and remember its last safe truncation point.
+/// `Ok(None)` means no container has opened yet; `Err` means the text can no longer be JSON.
+/// Allocation: one String per object key. Complexity: O(n * depth).
+fn scan_json_prefix(text: &str) -> Result<Option<SafePrefix>, ()> {
+    let mut stack: Vec<ScanFrame> = Vec::new();
+    let mut expect = ScanExpect::Value;
+    let mut token = ScanToken::None;
+    let mut key_start = 0;
+    let mut root_done = false;
+    let mut safe = None;
+
+    for (index, ch) in text.char_indices() {
+        // Finish in-progress scalars first; a delimiter ends numbers and literals.
+        match token {
+            ScanToken::Str { is_key, escaped } => {
+                if escaped {
+                    token = ScanToken::Str {
+                        is_key,
+                        escaped: false,
+                    };
+                } else if ch == '\\' {
+                    token = ScanToken::Str {
+                        is_key,
+                        escaped: true,
+                    };
+                } else if ch == '"' {
+                    token = ScanToken::None;
+                    if is_key {
+                        let key = serde_json::from_str::<String>(&text[key_start..=index])
+                            .map_err(|_| ())?;
+                        if let Some(frame) = stack.last_mut() {
+                            frame.child = Some(OpenSegment::Key(key));
+                        }
+                        expect = ScanExpect::Colon;
+                    } else {
+                        close_scan_value(&stack, &mut expect, &mut root_done);
+                        if !stack.is_empty() {
+                            safe = Some(SafePrefix::capture(&stack, index + 1));
+                        }
+                    }
+                }
+                continue;
+            }
+            ScanToken::Number { start } => {
+                if ch.is_ascii_digit() || matches!(ch, '-' | '+' | '.' | 'e' | 'E') {
+                    continue;
+                }
+                if !serde_json::from_str::<Value>(&text[start..index]).is_ok_and(|v| v.is_number())
+                {
+                    return Err(());
+                }
+                token = ScanToken::None;
+                close_scan_value(&stack, &mut expect, &mut root_done);
+                if !stack.is_empty() {
+                    safe = Some(SafePrefix::capture(&stack, index));
+                }
+            }
+            ScanToken::Literal { start } => {
+                let word = &text[start..index];
+                if ch.is_ascii_alphabetic() {
+                    let next = &text[start..index + ch.len_utf8()];
+                    if !["true", "false", "null"]
+                        .iter()
+                        .any(|w| w.starts_with(next))
+                    {
+                        return Err(());
+                    }
+                    continue;
+                }
+                if !matches!(word, "true" | "false" | "null") {
+                    return Err(());
+                }
+                token = ScanToken::None;
+                close_scan_value(&stack, &mut expect, &mut root_done);
+                if !stack.is_empty() {
+                    safe = Some(SafePrefix::capture(&stack, index));
+                }
+            }
+            ScanToken::None => {}
+        }
+
+        if ch.is_whitespace() {
+            continue;
+        }
+        if root_done {
+            return Err(());
+        }
+        match (expect, ch) {
+            (ScanExpect::Value | ScanExpect::ValueOrClose, _) if starts_value(ch) => {
+                if let Some(frame) = stack.last_mut().filter(|frame| !frame.is_object) {
+                    frame.child = Some(OpenSegment::Index(frame.len));
+                    frame.len += 1;
+                }
+                match ch {
+                    '{' | '[' => {
+                        stack.push(ScanFrame {
+                            is_object: ch == '{',
+                            child: None,
+                            len: 0,
+                        });
+                        expect = if ch == '{' {
+                            ScanExpect::KeyOrClose
+                        } else {
+                            ScanExpect::ValueOrClose
+                        };
+                        safe = Some(SafePrefix::capture(&stack, index + 1));
+                    }
+                    '"' => {
+                        token = ScanToken::Str {
+                            is_key: false,
+                            escaped: false,
+                        }
+                    }
+                    't' | 'f' | 'n' => token = ScanToken::Literal { start: index },
+                    _ => token = ScanToken::Number { start: index },
+                }
+            }
+            (ScanExpect::Key | ScanExpect::KeyOrClose, '"') => {
+                key_start = index;
+                token = ScanToken::Str {
+                    is_key: true,
+                    escaped: false,
+                };
+            }
+            (ScanExpect::Colon, ':') => expect = ScanExpect::Value,
+            (ScanExpect::CommaOrClose, ',') => {
+                expect = match stack.last() {
+                    Some(frame) if frame.is_object => ScanExpect::Key,
+                    _ => ScanExpect::Value,
+                };
+            }
+            (ScanExpect::KeyOrClose | ScanExpect::CommaOrClose, '}')
+            | (ScanExpect::ValueOrClose | ScanExpect::CommaOrClose, ']') => {
+                if stack.last().map(|frame| frame.is_object) != Some(ch == '}') {
+                    return Err(());
+                }
+                stack.pop();
+                close_scan_value(&stack, &mut expect, &mut root_done);
+                if !stack.is_empty() {
+                    safe = Some(SafePrefix::capture(&stack, index + 1));
+                }
+            }
+            _ => return Err(()),
+        }
+    }
+
+    Ok(safe)
+}
+
+fn starts_value(ch: char) -> bool {
+    matches!(ch, '{' | '[' | '"' | '-' | '0'..='9' | 't' | 'f' | 'n')
+}
+
+fn close_scan_value(stack: &[ScanFrame], expect: &mut ScanExpect, root_done: &mut bool) {
+    if stack.is_empty() {
+        *root_done = true;
+    } else {
+        *expect = ScanExpect::CommaOrClose;
+    }
+}
+
+fn type_matches(expected: &Value, instance: &Value) -> bool {
+    match expected {
+        Value::String(name) => type_name_matches(name, instance),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| type_name_matches(name, instance)),
+        _ => true,
+    }
+}
+
+fn type_name_matches(name: &str, instance: &Value) -> bool {
+    match name {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "string" => instance.is_string(),
+        "number" => instance.is_number(),
+        "integer" => {
+            instance.is_i64()
+                || instance.is_u64()
+                || instance.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+/// JSON Schema equality: numbers compare by value (`1 == 1.0`), containers recursively.
+fn json_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => match (a.as_i64(), b.as_i64()) {
+            (Some(a), Some(b)) => a == b,
+            _ => match (a.as_u64(), b.as_u64()) {
+                (Some(a), Some(b)) => a == b,
+                _ => a.as_f64() == b.as_f64(),
+            },
+        },
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_equal(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, value)| b.get(key).is_some_and(|other| json_equal(value, other)))
+        }
+        _ => left == right,
+    }
+}
+
+/// JSON Schema type name of `instance` (`"number"` for every number).
+pub(crate) fn json_type_name(instance: &Value) -> &'static str {
+    match instance {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{assistant_text_matches_schema, partial_schema_violations, schema_violations};
+
+    #[test]
+    fn no_schema_yields_none() {
+        assert_eq!(assistant_text_matches_schema(None, "anything"), None);
+    }
+
+    #[test]
+    fn non_json_text_does_not_match_schema() {
+        let schema = json!({"type": "object"});
+        assert_eq!(
+            assistant_text_matches_schema(Some(&schema), "plain prose"),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn object_schema_checks_required_types_and_extra_properties() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "tags": {"type": "array", "items": {"type": "string"}}
+            },
+            "required": ["name"],
+            "additionalProperties": false
+        });
+        assert_eq!(
+            assistant_text_matches_schema(Some(&schema), r#"{"name":"a","tags":["x"]}"#),
+            Some(true)
+        );
+
+        let violations = schema_violations(&schema, &json!({"tags": ["x", 1], "extra": true}));
+        assert_eq!(
+            violations,
+            vec![
+                "$: missing required property 'name'".to_owned(),
+                "$: unexpected property 'extra'".to_owned(),
+                "$.tags[1]: expected type \"string\", got number".to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn combinators_and_enum_are_enforced() {
+        let schema = json!({
+            "anyOf": [{"type": "integer"}, {"enum": ["auto"]}]
+        });
+        assert!(schema_violations(&schema, &json!(3)).is_empty());
+        assert!(schema_violations(&schema, &json!("auto")).is_empty());
+        assert_eq!(schema_violations(&schema, &json!("manual")).len(), 1);
+
+        let schema = json!({"oneOf": [{"type": "number"}, {"type": "integer"}]});
+        assert_eq!(schema_violations(&schema, &json!(1)).len(), 1);
+        assert!(schema_violations(&schema, &json!(1.5)).is_empty());
+    }
+
+    #[test]
+    fn integer_type_and_numeric_enums_accept_integral_floats() {
+        assert!(schema_violations(&json!({"type": "integer"}), &json!(1.0)).is_empty());
+        assert_eq!(
+            schema_violations(&json!({"type": "integer"}), &json!(1.5)).len(),
+            1
+        );
+        let schema = json!({"enum": [1, [2, {"n": 3}]]});
+        assert!(schema_violations(&schema, &json!(1.0)).is_empty());
+        assert!(schema_violations(&schema, &json!([2.0, {"n": 3.0}])).is_empty());
+        assert!(schema_violations(&json!({"const": 4}), &json!(4.0)).is_empty());
+    }
+
+    #[test]
+    fn unsupported_keywords_make_the_schema_check_inconclusive() {
+        for schema in [
+            json!({"$ref": "#/$defs/item", "$defs": {"item": {"type": "string"}}}),
+            json!({"type": "integer", "minimum": 10}),
+            json!({"type": "object", "properties": {"id": {"type": "string", "pattern": "^a"}}}),
+            json!({"type": "array", "items": {"oneOf": [{"maxLength": 2}]}}),
+        ] {
+            assert_eq!(
+                assistant_text_matches_schema(Some(&schema), "5"),
+                None,
+                "{schema}"
+            );
+        }
+        let annotated = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Count",
+            "description": "a count",
+            "type": "integer"
+        });
+        assert_eq!(
+            assistant_text_matches_schema(Some(&annotated), "5"),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn partial_prefixes_of_valid_output_report_nothing() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "enum": ["alpha"]},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "nested": {"type": "object", "required": ["ok"]}
+            },
+            "required": ["name", "tags"],
+            "additionalProperties": false
+        });
+        let full = r#"{"name": "alpha", "tags": ["x", "y\"z"], "nested": {"ok": true}}"#;
+        for end in 0..=full.len() {
+            assert_eq!(
+                partial_schema_violations(&schema, &full[..end]),
+                Vec::<String>::new(),
+                "prefix {:?}",
+                &full[..end]
+            );
+        }
+    }
+
+    #[test]
+    fn partial_output_flags_definite_violations_early() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "count": {"type": "integer"},
+                "items": {"type": "array", "items": {"type": "object", "properties": {"id": {"type": "string"}}}}
+            },
+            "additionalProperties": false
+        });
+        assert_eq!(
+            partial_schema_violations(&schema, r#"{"count": "three", "#),
+            vec!["$.count: expected type \"integer\", got string".to_owned()]
+        );
+        assert_eq!(
+            partial_schema_violations(&schema, r#"{"extra": 1, "count""#),
+            vec!["$: unexpected property 'extra'".to_owned()]
+        );
+        assert_eq!(
+            partial_schema_violations(&schema, r#"{"items": [{"id": 7}, {"id": "#),
+            vec!["$.items[0].id: expected type \"string\", got number".to_owned()]
+        );
+        assert_eq!(
+            partial_schema_violations(&schema, "["),
+            vec!["$: expected type \"object\", got array".to_owned()]
+        );
+        assert_eq!(
+            partial_schema_violations(&schema, "Sure, here is the JSON"),
+            vec!["$: output is not valid JSON".to_owned()]
+        );
+        assert_eq!(
+            partial_schema_violations(&schema, r#"{"count": 1} trailing"#),
+            vec!["$: output is not valid JSON".to_owned()]
+        );
+    }
+
+    #[test]
+    fn partial_output_defers_open_values_and_combinators() {
+        let schema = json!({
+            "type": "object",
+            "required": ["id"],
+            "anyOf": [
+                {"properties": {"kind": {"const": "a"}}},
+                {"properties": {"kind": {"type": "integer"}}}
+            ]
+        });
+        assert!(partial_schema_violations(&schema, r#"{"kind": 12"#).is_empty());
+        assert!(partial_schema_violations(&schema, r#"{"kind": "a"#).is_empty());
+        assert_eq!(
+            partial_schema_violations(&schema, r#"{"kind": "b","#),
+            vec!["$: value matches no anyOf branch".to_owned()]
+        );
+        assert_eq!(
+            partial_schema_violations(&json!({"type": "integer"}), "12"),
+            Vec::<String>::new()
+        );
+    }
+}
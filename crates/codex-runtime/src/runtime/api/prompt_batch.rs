@@ -0,0 +1,81 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::plugin::ShutdownToken;
+use crate::runtime::core::Runtime;
+use crate::runtime::errors::RuntimeError;
+
+use super::{PromptRunError, PromptRunParams, PromptRunResult};
+
+impl Runtime {
+    /// Run many prompts concurrently, each through `run_prompt`, under one group kill switch.
+    /// Results keep input order. Cancelling `cancel` drops every unfinished run, which
+    /// interrupts its turn (best effort), and resolves it to `PromptRunError::Cancelled`;
+    /// finished items keep their results.
+    /// Side effects: per item, the RPCs and hooks of `run_prompt`.
+    /// Allocation: one task per item + result Vec. Complexity: O(n), n = prompt count.
+    pub async fn run_prompt_batch(
+        &self,
+        prompts: Vec<PromptRunParams>,
+        max_concurrency: usize,
+        cancel: &ShutdownToken,
+    ) -> Vec<Result<PromptRunResult, PromptRunError>> {
+        let permits = Arc::new(Semaphore::new(max_concurrency.max(1)));
+        let mut results: Vec<Option<Result<PromptRunResult, PromptRunError>>> =
+            (0..prompts.len()).map(|_| None).collect();
+        let mut tasks = JoinSet::new();
+
+        for (index, p) in prompts.into_iter().enumerate() {
+            let runtime = self.clone();
+            let permits = Arc::clone(&permits);
+            let cancel = cancel.clone();
+            tasks.spawn(async move {
+                let run = async move {
+                    let _permit = permits
+                        .acquire_owned()
+                        .await
+                        .map_err(|_| PromptRunError::Cancelled)?;
+                    runtime.run_prompt(p).await
+                };
+                (
+                    index,
+                    until_cancelled(&cancel, run)
+                        .await
+                        .and_then(|result| result),
+                )
+            });
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok((index, result)) = joined {
+                results[index] = Some(result);
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    Err(PromptRunError::Runtime(RuntimeError::Internal(
+                        "prompt batch item task failed".to_owned(),
+                    )))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Race `future` against `cancel`; the future is dropped when cancellation wins.
+async fn until_cancelled<F: Future>(
+    cancel: &ShutdownToken,
+    future: F,
+) -> Result<F::Output, PromptRunError> {
+    tokio::select! {
+        biased;
+        _ = cancel.cancelled() => Err(PromptRunError::Cancelled),
+        output = future => Ok(output),
+    }
+}
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use crate::plugin::{BlockReason, HookPhase};
+use serde_json::Value;
 use tokio::sync::broadcast::error::RecvError;
 use tokio::time::{timeout, Instant};
 
@@ -12,19 +13,24 @@ use crate::runtime::events::{
     extract_turn_failed, extract_turn_interrupted, Envelope,
 };
 use crate::runtime::hooks::{PreHookDecision, RuntimeHookConfig};
+use crate::runtime::prompt_cache::PromptCacheKey;
 use crate::runtime::rpc_contract::{methods, RpcValidationMode};
 use crate::runtime::turn_lifecycle::{
     collect_turn_terminal_with_limits, interrupt_turn_best_effort_detached,
     interrupt_turn_best_effort_with_timeout, LaggedTurnTerminal, TurnCollectError,
+    INTERRUPT_REASON_CANCELLED, INTERRUPT_REASON_ERROR, INTERRUPT_REASON_TIMEOUT,
 };
 use crate::runtime::turn_output::{TurnStreamCollector, TurnTerminalEvent};
 
-use super::attachment_validation::validate_prompt_attachments;
+use super::attachment_validation::{
+    annotate_local_image_mime_types, inline_local_images, validate_prompt,
+};
 use super::flow::{
     apply_pre_hook_actions_to_prompt, build_hook_context, extract_assistant_text_from_turn,
-    result_status, HookContextInput, HookExecutionState, PromptMutationState,
+    HookContextInput, HookExecutionState, PromptMutationState, SHORT_CIRCUITED_STATUS,
 };
 use super::models::{PromptRunStreamState, PromptStreamCleanupState};
+use super::output_schema::{assistant_text_matches_schema, partial_schema_violations};
 use super::turn_error::{extract_turn_error_signal, PromptTurnErrorSignal};
 use super::wire::{
     deserialize_result, serialize_params, thread_start_params_from_prompt,
@@ -32,7 +38,7 @@ use super::wire::{
 };
 use super::*;
 
-const INTERRUPT_RPC_TIMEOUT: Duration = Duration::from_millis(500);
+pub(super) const INTERRUPT_RPC_TIMEOUT: Duration = Duration::from_millis(500);
 
 #[derive(Clone, Copy)]
 enum PromptRunTarget<'a> {
@@ -62,7 +68,9 @@ impl Runtime {
     }
 
     /// Run one prompt end-to-end and return the final assistant text.
-    /// Side effects: sends thread/turn RPC calls and consumes live event stream.
+    /// Side effects: sends thread/turn RPC calls and consumes live event stream; with a
+    /// configured `PromptCache`, eligible read-only runs are served from/stored into it.
+    /// Dropping the future after `turn/start` interrupts the turn (best effort).
     /// Allocation: O(n), n = prompt length + attachment count + streamed text.
     pub async fn run_prompt(&self, p: PromptRunParams) -> Result<PromptRunResult, PromptRunError> {
         self.run_prompt_with_hooks(p, None).await
@@ -89,6 +97,42 @@ impl Runtime {
             .await
     }
 
+    /// Fork `source_thread_id` and run one prompt on the fork.
+    /// Thin wrapper over `run_prompt_on_fork` for callers that only need the fork's id;
+    /// use that method to keep the fork's `ThreadHandle`.
+    pub async fn thread_fork_and_prompt(
+        &self,
+        source_thread_id: &str,
+        p: PromptRunParams,
+    ) -> Result<(ThreadId, PromptRunResult), PromptRunError> {
+        let (result, fork) = self.run_prompt_on_fork(source_thread_id, p).await?;
+        Ok((fork.thread_id, result))
+    }
+
+    /// Run one prompt on a fresh fork of `source_thread_id` to keep the source thread
+    /// unaffected. Returns the result and the fork handle; keep the fork or archive it.
+    /// The fork is already loaded, so no thread/resume is sent. When the run fails the
+    /// fork is archived best-effort before the error is returned.
+    /// Side effects: sends thread/fork + turn/start RPC calls (thread/archive on failure).
+    /// Allocation: O(n), n = prompt length + attachment count + streamed text.
+    pub async fn run_prompt_on_fork(
+        &self,
+        source_thread_id: &str,
+        p: PromptRunParams,
+    ) -> Result<(PromptRunResult, ThreadHandle), PromptRunError> {
+        let fork = self.thread_fork(source_thread_id).await?;
+        match self
+            .run_prompt_on_loaded_thread_with_hooks(&fork.thread_id, p, None)
+            .await
+        {
+            Ok(result) => Ok((result, fork)),
+            Err(err) => {
+                let _ = self.thread_archive(&fork.thread_id).await;
+                Err(err)
+            }
+        }
+    }
+
     pub(crate) async fn run_prompt_in_thread_with_hooks(
         &self,
         thread_id: &str,
@@ -115,10 +159,9 @@ impl Runtime {
         p: PromptRunParams,
         scoped_hooks: Option<&RuntimeHookConfig>,
     ) -> Result<PromptRunStream, PromptRunError> {
-        validate_prompt_attachments(&p.cwd, &p.attachments).await?;
-        let effort = p.effort.unwrap_or(DEFAULT_REASONING_EFFORT);
+        validate_prompt(&p, self.attachment_limits()).await?;
         let thread = self.loaded_thread_handle(thread_id);
-        self.run_prompt_on_thread_stream(thread, p, effort, scoped_hooks)
+        self.run_prompt_on_thread_stream(thread, p, scoped_hooks)
             .await
     }
 
@@ -144,7 +187,7 @@ impl Runtime {
     ) -> Result<PromptRunResult, PromptRunError> {
         if !self.hooks_enabled_with(scoped_hooks) {
             return self
-                .run_prompt_target_entry_dispatch(target, p, None, scoped_hooks)
+                .run_prompt_target_entry_cached(target, p, None, scoped_hooks)
                 .await;
         }
 
@@ -152,9 +195,18 @@ impl Runtime {
         let (p, mut hook_state, run_cwd, run_model) = self
             .prepare_prompt_pre_run_hooks(p, fallback_thread_id, scoped_hooks)
             .await?;
-        let result = self
-            .run_prompt_target_entry_dispatch(target, p, Some(&mut hook_state), scoped_hooks)
-            .await;
+        let result = match hook_state.short_circuit.clone() {
+            Some(text) => Ok(short_circuit_result(
+                fallback_thread_id,
+                &hook_state.correlation_id,
+                text,
+                p.output_schema.as_ref(),
+            )),
+            None => {
+                self.run_prompt_target_entry_cached(target, p, Some(&mut hook_state), scoped_hooks)
+                    .await
+            }
+        };
         self.finalize_prompt_run_hooks(
             &mut hook_state,
             run_cwd.as_str(),
@@ -164,10 +216,41 @@ impl Runtime {
             scoped_hooks,
         )
         .await;
-        self.publish_hook_report(hook_state.report);
+        self.publish_hook_report(&hook_state.correlation_id, hook_state.report);
         result
     }
 
+    /// Serve fresh-thread runs from the configured `PromptCache` (see `PromptCacheKey`),
+    /// keyed on the params after PreRun patches; successful misses are stored.
+    /// Hook short-circuits never reach this, so they are never cached.
+    async fn run_prompt_target_entry_cached(
+        &self,
+        target: PromptRunTarget<'_>,
+        p: PromptRunParams,
+        hook_state: Option<&mut HookExecutionState>,
+        scoped_hooks: Option<&RuntimeHookConfig>,
+    ) -> Result<PromptRunResult, PromptRunError> {
+        let cached = match target {
+            PromptRunTarget::OpenOrResume(None) => self
+                .prompt_cache()
+                .cloned()
+                .and_then(|cache| PromptCacheKey::for_params(&p).map(|key| (cache, key))),
+            _ => None,
+        };
+        if let Some((cache, key)) = &cached {
+            if let Some(hit) = cache.get(key) {
+                return Ok(hit);
+            }
+        }
+        let result = self
+            .run_prompt_target_entry_dispatch(target, p, hook_state, scoped_hooks)
+            .await?;
+        if let Some((cache, key)) = cached {
+            cache.put(key, &result);
+        }
+        Ok(result)
+    }
+
     async fn run_prompt_target_entry_dispatch(
         &self,
         target: PromptRunTarget<'_>,
@@ -191,6 +274,7 @@ impl Runtime {
         &self,
         thread_id: Option<&str>,
         p: &PromptRunParams,
+        hook_state: Option<&mut HookExecutionState>,
         scoped_hooks: Option<&RuntimeHookConfig>,
     ) -> Result<ThreadHandle, RpcError> {
         let mut start = thread_start_params_from_prompt(p);
@@ -200,11 +284,54 @@ impl Runtime {
             start.approval_policy = Some(ApprovalPolicy::Untrusted);
         }
         match thread_id {
-            Some(existing_thread_id) => self.thread_resume_raw(existing_thread_id, start).await,
-            None => self.thread_start_raw(start).await,
+            Some(existing_thread_id) => {
+                if !p.resend_model_on_resume {
+                    start.model = None;
+                }
+                self.thread_resume_raw(existing_thread_id, start).await
+            }
+            None => match hook_state {
+                Some(run_hook_state) if self.session_hooks_on_implicit_start_with(scoped_hooks) => {
+                    self.implicit_thread_start_with_session_hooks(
+                        start,
+                        run_hook_state,
+                        scoped_hooks,
+                    )
+                    .await
+                }
+                _ => self.thread_start_raw(start).await,
+            },
         }
     }
 
+    /// PreSessionStart → thread/start → PostSessionStart for the thread a prompt run
+    /// creates. Session hook issues land in the enclosing run's report.
+    async fn implicit_thread_start_with_session_hooks(
+        &self,
+        start: ThreadStartParams,
+        run_hook_state: &mut HookExecutionState,
+        scoped_hooks: Option<&RuntimeHookConfig>,
+    ) -> Result<ThreadHandle, RpcError> {
+        let (start, mut session_hook_state, start_cwd, start_model) = self
+            .prepare_session_start_hooks(start, None, scoped_hooks)
+            .await?;
+        let result = self.thread_start_raw(start).await;
+        self.finalize_session_start_hooks(
+            &mut session_hook_state,
+            start_cwd.as_deref(),
+            start_model.as_deref(),
+            None,
+            &result,
+            scoped_hooks,
+        )
+        .await;
+        run_hook_state
+            .report
+            .issues
+            .extend(session_hook_state.report.issues);
+        result
+    }
+
     async fn run_prompt_entry(
         &self,
         thread_id: Option<&str>,
@@ -212,10 +339,12 @@ impl Runtime {
         hook_state: Option<&mut HookExecutionState>,
         scoped_hooks: Option<&RuntimeHookConfig>,
     ) -> Result<PromptRunResult, PromptRunError> {
-        validate_prompt_attachments(&p.cwd, &p.attachments).await?;
-        let effort = p.effort.unwrap_or(DEFAULT_REASONING_EFFORT);
-        let thread = self.open_prompt_thread(thread_id, &p, scoped_hooks).await?;
-        self.run_prompt_on_thread(thread, p, effort, hook_state, scoped_hooks)
+        let mut hook_state = hook_state;
+        validate_prompt(&p, self.attachment_limits()).await?;
+        let thread = self
+            .open_prompt_thread(thread_id, &p, hook_state.as_deref_mut(), scoped_hooks)
+            .await?;
+        self.run_prompt_on_thread(thread, p, hook_state, scoped_hooks)
             .await
     }
 
@@ -226,10 +355,9 @@ impl Runtime {
         hook_state: Option<&mut HookExecutionState>,
         scoped_hooks: Option<&RuntimeHookConfig>,
     ) -> Result<PromptRunResult, PromptRunError> {
-        validate_prompt_attachments(&p.cwd, &p.attachments).await?;
-        let effort = p.effort.unwrap_or(DEFAULT_REASONING_EFFORT);
+        validate_prompt(&p, self.attachment_limits()).await?;
         let thread = self.loaded_thread_handle(thread_id);
-        self.run_prompt_on_thread(thread, p, effort, hook_state, scoped_hooks)
+        self.run_prompt_on_thread(thread, p, hook_state, scoped_hooks)
             .await
     }
 
@@ -239,7 +367,8 @@ impl Runtime {
         thread_id: Option<&str>,
         scoped_hooks: Option<&RuntimeHookConfig>,
     ) -> Result<(PromptRunParams, HookExecutionState, String, Option<String>), PromptRunError> {
-        let mut hook_state = HookExecutionState::new(self.next_hook_correlation_id());
+        let mut hook_state =
+            HookExecutionState::seeded(self.next_hook_correlation_id(), &p.metadata);
         let mut prompt_state = PromptMutationState::from_params(&p, hook_state.metadata.clone());
         let decisions = self
             .execute_pre_hook_phase(
@@ -262,6 +391,7 @@ impl Runtime {
         )
         .await;
         hook_state.metadata = prompt_state.metadata.clone();
+        hook_state.short_circuit = prompt_state.response;
         p.prompt = prompt_state.prompt;
         p.model = prompt_state.model;
         p.attachments = prompt_state.attachments;
@@ -292,7 +422,8 @@ impl Runtime {
                 model: run_model,
                 thread_id: post_thread_id,
                 turn_id: None,
-                main_status: Some(result_status(result)),
+                main_status: Some(hook_state.main_status(result)),
+                assistant_text: None,
             },
             scoped_hooks,
         )
@@ -332,6 +463,7 @@ impl Runtime {
         )
         .await;
         state.metadata = prompt_state.metadata;
+        state.short_circuit = prompt_state.response;
         p.prompt = prompt_state.prompt;
         p.model = prompt_state.model;
         p.attachments = prompt_state.attachments;
@@ -342,7 +474,6 @@ impl Runtime {
         &self,
         thread: ThreadHandle,
         p: PromptRunParams,
-        effort: ReasoningEffort,
         hook_state: Option<&mut HookExecutionState>,
         scoped_hooks: Option<&RuntimeHookConfig>,
     ) -> Result<PromptRunResult, PromptRunError> {
@@ -357,27 +488,67 @@ impl Runtime {
             .await?;
 
         self.register_thread_scoped_pre_tool_use_hooks(&thread.thread_id, scoped_hooks);
+        // Subscribe before turn/start is sent: servers may emit turn/started and deltas
+        // ahead of the RPC result, and a later subscription would miss them.
         let live_rx = self.subscribe_live();
+        let short_circuit = hook_state
+            .as_deref()
+            .and_then(|state| short_circuit_result_for(state, &thread.thread_id, &p));
         let mut post_turn_id: Option<String> = None;
-        let run_result = match thread
-            .turn_start(turn_start_params_from_prompt(&p, effort))
-            .await
-            .map_err(PromptRunError::Rpc)
-        {
-            Ok(turn) => {
-                post_turn_id = Some(turn.turn_id.clone());
-                self.collect_prompt_turn_assistant_text(live_rx, &thread, &turn.turn_id, p.timeout)
-                    .await
-                    .map(|assistant_text| PromptRunResult {
-                        thread_id: thread.thread_id.clone(),
-                        turn_id: turn.turn_id,
-                        assistant_text,
-                    })
+        let run_result = match short_circuit {
+            Some(result) => {
+                post_turn_id = Some(result.turn_id.clone());
+                Ok(result)
+            }
+            None => {
+                let _turn_slot = self.acquire_turn_slot().await;
+                match self.start_prompt_turn(&thread, &p).await {
+                    Ok(turn) => {
+                        post_turn_id = Some(turn.turn_id.clone());
+                        self.publish_run_metadata(&thread.thread_id, &turn.turn_id, &p.metadata);
+                        if p.auto_approve_all {
+                            self.register_turn_auto_approve(&thread.thread_id, &turn.turn_id)
+                                .await;
+                        }
+                        // Dropping the run future mid-turn interrupts the turn.
+                        let interrupt_on_drop = TurnGuard {
+                            turn: turn.clone(),
+                            runtime: self.clone(),
+                            armed: true,
+                        };
+                        let collected = self
+                            .collect_prompt_turn_assistant_text(
+                                live_rx,
+                                &thread,
+                                &turn.turn_id,
+                                self.resolve_prompt_timeout(p.timeout),
+                                &p,
+                            )
+                            .await;
+                        let _ = interrupt_on_drop.disarm();
+                        collected.map(|assistant_text| PromptRunResult {
+                            thread_id: thread.thread_id.clone(),
+                            turn_id: turn.turn_id,
+                            schema_valid: assistant_text_matches_schema(
+                                p.output_schema.as_ref(),
+                                &assistant_text,
+                            ),
+                            assistant_text,
+                        })
+                    }
+                    Err(err) => Err(err),
+                }
             }
-            Err(err) => Err(err),
         };
 
+        let auto_approvals = match post_turn_id.as_deref() {
+            Some(turn_id) if p.auto_approve_all => {
+                self.take_turn_auto_approvals(&thread.thread_id, turn_id)
+            }
+            _ => Vec::new(),
+        };
         if let Some(state) = hook_state {
+            state.report.auto_approvals.extend(auto_approvals);
             self.execute_post_hook_phase(
                 state,
                 HookContextInput {
@@ -386,11 +557,17 @@ impl Runtime {
                     model: p.model.as_deref(),
                     thread_id: Some(thread.thread_id.as_str()),
                     turn_id: post_turn_id.as_deref(),
-                    main_status: Some(result_status(&run_result)),
+                    main_status: Some(state.main_status(&run_result)),
+                    assistant_text: run_result
+                        .as_ref()
+                        .ok()
+                        .map(|result| result.assistant_text.as_str()),
                 },
                 scoped_hooks,
             )
             .await;
+        } else {
+            self.publish_auto_approvals(auto_approvals);
         }
 
         self.clear_thread_scoped_pre_tool_use_hooks(&thread.thread_id);
@@ -401,11 +578,13 @@ impl Runtime {
         &self,
         thread: ThreadHandle,
         p: PromptRunParams,
-        effort: ReasoningEffort,
         scoped_hooks: Option<&RuntimeHookConfig>,
     ) -> Result<PromptRunStream, PromptRunError> {
         let mut hook_state = if self.hooks_enabled_with(scoped_hooks) {
-            Some(HookExecutionState::new(self.next_hook_correlation_id()))
+            Some(HookExecutionState::seeded(
+                self.next_hook_correlation_id(),
+                &p.metadata,
+            ))
         } else {
             None
         };
@@ -419,61 +598,142 @@ impl Runtime {
             .await?;
 
         self.register_thread_scoped_pre_tool_use_hooks(&thread.thread_id, scoped_hooks);
+        // Same ordering as `run_prompt_on_thread`: subscribe before turn/start is sent.
         let live_rx = self.subscribe_live();
-        let timeout_duration = p.timeout;
+        let timeout_duration = self.resolve_prompt_timeout(p.timeout);
+        let output_schema = p.output_schema.clone();
         let run_cwd = p.cwd.clone();
         let run_model = p.model.clone();
-
-        let turn = match thread
-            .turn_start(turn_start_params_from_prompt(&p, effort))
-            .await
-            .map_err(PromptRunError::Rpc)
-        {
-            Ok(turn) => turn,
-            Err(err) => {
-                if let Some(state) = hook_state.as_mut() {
-                    self.execute_post_hook_phase(
-                        state,
-                        HookContextInput {
-                            phase: HookPhase::PostTurn,
-                            cwd: Some(run_cwd.as_str()),
-                            model: run_model.as_deref(),
-                            thread_id: Some(thread.thread_id.as_str()),
-                            turn_id: None,
-                            main_status: Some("error"),
-                        },
-                        scoped_hooks,
-                    )
-                    .await;
-                    self.publish_hook_report(state.report.clone());
+        let short_circuit = hook_state
+            .as_ref()
+            .and_then(|state| short_circuit_result_for(state, &thread.thread_id, &p));
+
+        let mut turn_slot = None;
+        let turn_id = match short_circuit.as_ref() {
+            Some(result) => result.turn_id.clone(),
+            None => {
+                turn_slot = Some(self.acquire_turn_slot().await);
+                match self.start_prompt_turn(&thread, &p).await {
+                    Ok(turn) => turn.turn_id,
+                    Err(err) => {
+                        if let Some(state) = hook_state.as_mut() {
+                            self.execute_post_hook_phase(
+                                state,
+                                HookContextInput {
+                                    phase: HookPhase::PostTurn,
+                                    cwd: Some(run_cwd.as_str()),
+                                    model: run_model.as_deref(),
+                                    thread_id: Some(thread.thread_id.as_str()),
+                                    turn_id: None,
+                                    main_status: Some("error"),
+                                    assistant_text: None,
+                                },
+                                scoped_hooks,
+                            )
+                            .await;
+                            self.publish_hook_report(&state.correlation_id, state.report.clone());
+                        }
+                        self.clear_thread_scoped_pre_tool_use_hooks(&thread.thread_id);
+                        return Err(err);
+                    }
                 }
-                self.clear_thread_scoped_pre_tool_use_hooks(&thread.thread_id);
-                return Err(err);
             }
         };
+        if short_circuit.is_none() {
+            self.publish_run_metadata(&thread.thread_id, &turn_id, &p.metadata);
+        }
+        let auto_approve = p.auto_approve_all && short_circuit.is_none();
+        if auto_approve {
+            self.register_turn_auto_approve(&thread.thread_id, &turn_id)
+                .await;
+        }
         let cleanup = PromptStreamCleanupState {
             run_cwd,
             run_model,
             scoped_hooks: scoped_hooks.cloned(),
             hook_state,
+            auto_approve,
             cleaned_up: false,
         };
 
-        Ok(PromptRunStream {
+        let mut stream = PromptRunStream {
             runtime: self.clone(),
             thread_id: thread.thread_id.clone(),
-            turn_id: turn.turn_id.clone(),
+            turn_id: turn_id.clone(),
             live_rx,
-            stream: TurnStreamCollector::new(&thread.thread_id, &turn.turn_id),
+            stream: TurnStreamCollector::new(&thread.thread_id, &turn_id)
+                .with_item_separator(p.assistant_item_separator.clone()),
             state: PromptRunStreamState {
                 last_turn_error: None,
                 lagged_terminal: None,
+                diagnostics: TurnDiagnostics::default(),
+                fail_on_command_error: p.fail_on_command_error,
                 final_result: None,
             },
             deadline: Instant::now() + timeout_duration,
             timeout: timeout_duration,
+            output_schema,
+            schema_violation_observer: None,
+            schema_violation_reported: false,
             cleanup,
-        })
+            turn_slot,
+            reasoning_budget: p.max_reasoning_tokens.map(ReasoningBudget::new),
+        };
+        if let Some(result) = short_circuit {
+            stream.complete_short_circuit(result).await;
+        }
+        Ok(stream)
+    }
+
+    /// Send `turn/start` for one prompt run.
+    /// When `steer_from` is set, the turn is anchored through `turn_steer_outcome` instead.
+    /// When `drop_effort_on_reject` is set, an effort rejection triggers one retry without `effort`.
+    async fn start_prompt_turn(
+        &self,
+        thread: &ThreadHandle,
+        p: &PromptRunParams,
+    ) -> Result<TurnHandle, PromptRunError> {
+        let mut params = turn_start_params_from_prompt(p, self.attachment_placeholder_strategy());
+        if p.inline_local_images {
+            inline_local_images(&p.cwd, &mut params.input, self.attachment_limits()).await?;
+        }
+        annotate_local_image_mime_types(&p.cwd, &mut params.input).await;
+        if let Some(expected_turn_id) = p.steer_from.as_deref() {
+            return match thread
+                .turn_steer_outcome(expected_turn_id, params.input)
+                .await
+            {
+                Ok(outcome) => Ok(TurnHandle {
+                    turn_id: outcome.turn_id,
+                    thread_id: thread.thread_id.clone(),
+                }),
+                Err(err) => match stale_steer_rejection_message(&err) {
+                    Some(message) => Err(PromptRunError::StaleSteerTurn {
+                        expected_turn_id: expected_turn_id.to_owned(),
+                        message: message.to_owned(),
+                    }),
+                    None => Err(err.into()),
+                },
+            };
+        }
+        if !p.drop_effort_on_reject || params.effort.is_none() {
+            return Ok(thread.turn_start(params).await?);
+        }
+
+        let retry = TurnStartParams {
+            effort: None,
+            ..params.clone()
+        };
+        match thread.turn_start(params).await {
+            Err(err) if is_effort_rejection(&err) => {
+                tracing::debug!(
+                    thread_id = %thread.thread_id,
+                    "turn/start rejected effort; retrying without effort"
+                );
+                Ok(thread.turn_start(retry).await?)
+            }
+            other => Ok(other?),
+        }
     }
 
     async fn collect_prompt_turn_assistant_text(
@@ -482,23 +742,38 @@ impl Runtime {
         thread: &ThreadHandle,
         turn_id: &str,
         timeout_duration: Duration,
+        p: &PromptRunParams,
     ) -> Result<String, PromptRunError> {
-        let mut stream = TurnStreamCollector::new(&thread.thread_id, turn_id);
+        let item_separator = p.assistant_item_separator.as_str();
+        let mut stream = TurnStreamCollector::new(&thread.thread_id, turn_id)
+            .with_item_separator(item_separator);
         let mut last_turn_error: Option<PromptTurnErrorSignal> = None;
+        let mut diagnostics = TurnDiagnostics::default();
+        let mut reasoning_budget = p.max_reasoning_tokens.map(ReasoningBudget::new);
         let collected = collect_turn_terminal_with_limits(
             &mut live_rx,
             &mut stream,
             usize::MAX,
             timeout_duration,
             |envelope| {
+                diagnostics.observe(envelope);
                 if let Some(err) = extract_turn_error_signal(envelope) {
                     last_turn_error = Some(err);
                 }
-                Ok::<(), RpcError>(())
+                match reasoning_budget.as_mut() {
+                    Some(budget) => budget.observe(envelope),
+                    None => Ok(()),
+                }
             },
             |lag_probe_budget| async move {
-                self.read_turn_terminal_after_lag(&thread.thread_id, turn_id, lag_probe_budget)
-                    .await
+                self.read_turn_terminal_after_lag(
+                    &thread.thread_id,
+                    turn_id,
+                    lag_probe_budget,
+                    item_separator,
+                )
+                .await
+                .map_err(PromptRunError::Rpc)
             },
         )
         .await;
@@ -510,6 +785,7 @@ impl Runtime {
                     thread.runtime().clone(),
                     thread.thread_id.clone(),
                     turn_id.to_owned(),
+                    INTERRUPT_REASON_TIMEOUT,
                     INTERRUPT_RPC_TIMEOUT,
                 );
                 return Err(PromptRunError::Timeout(timeout_duration));
@@ -525,38 +801,73 @@ impl Runtime {
                     "turn event budget exhausted while collecting assistant output".to_owned(),
                 )));
             }
-            Err(TurnCollectError::TargetEnvelope(err)) => return Err(PromptRunError::Rpc(err)),
-            Err(TurnCollectError::LagProbe(RpcError::Timeout)) => {
+            Err(TurnCollectError::TargetEnvelope(err)) => {
+                interrupt_turn_best_effort_detached(
+                    thread.runtime().clone(),
+                    thread.thread_id.clone(),
+                    turn_id.to_owned(),
+                    INTERRUPT_REASON_ERROR,
+                    INTERRUPT_RPC_TIMEOUT,
+                );
+                return Err(err);
+            }
+            Err(TurnCollectError::LagProbe(PromptRunError::Rpc(RpcError::Timeout))) => {
                 interrupt_turn_best_effort_detached(
                     thread.runtime().clone(),
                     thread.thread_id.clone(),
                     turn_id.to_owned(),
+                    INTERRUPT_REASON_TIMEOUT,
                     INTERRUPT_RPC_TIMEOUT,
                 );
                 return Err(PromptRunError::Timeout(timeout_duration));
             }
-            Err(TurnCollectError::LagProbe(err)) => return Err(PromptRunError::Rpc(err)),
+            Err(TurnCollectError::LagProbe(err)) => return Err(err),
         };
 
+        if let Some(budget) = reasoning_budget.as_mut() {
+            budget
+                .observe_trailing_usage(&mut live_rx, &thread.thread_id, turn_id)
+                .await?;
+        }
+
+        diagnostics.recovered_from_lag = lagged_terminal.is_some();
         Self::resolve_prompt_turn_assistant_text(
             terminal,
             stream.into_assistant_text(),
             lagged_terminal.as_ref(),
             last_turn_error,
+            &diagnostics,
+            p.fail_on_command_error,
         )
     }
 
-    fn resolve_prompt_turn_assistant_text(
+    pub(super) fn resolve_prompt_turn_assistant_text(
         terminal: TurnTerminalEvent,
         collected_assistant_text: String,
         lagged_terminal: Option<&LaggedTurnTerminal>,
         last_turn_error: Option<PromptTurnErrorSignal>,
+        diagnostics: &TurnDiagnostics,
+        fail_on_command_error: bool,
     ) -> Result<String, PromptRunError> {
         match terminal {
+            TurnTerminalEvent::Completed if fail_on_command_error => {
+                match completed_turn_failed_command(diagnostics, lagged_terminal) {
+                    Some((command, exit_code)) => {
+                        Err(PromptRunError::CommandFailed { command, exit_code })
+                    }
+                    None => Self::finalize_prompt_turn_assistant_text(
+                        collected_assistant_text,
+                        lagged_completed_text(lagged_terminal),
+                        last_turn_error,
+                        diagnostics,
+                    ),
+                }
+            }
             TurnTerminalEvent::Completed => Self::finalize_prompt_turn_assistant_text(
                 collected_assistant_text,
                 lagged_completed_text(lagged_terminal),
                 last_turn_error,
+                diagnostics,
             ),
             TurnTerminalEvent::Failed => prompt_turn_failed_error(last_turn_error, lagged_terminal),
             TurnTerminalEvent::Interrupted | TurnTerminalEvent::Cancelled => {
@@ -569,6 +880,7 @@ impl Runtime {
         collected_assistant_text: String,
         lagged_completed_text: Option<String>,
         last_turn_error: Option<PromptTurnErrorSignal>,
+        diagnostics: &TurnDiagnostics,
     ) -> Result<String, PromptRunError> {
         let assistant_text = if let Some(snapshot_text) = lagged_completed_text {
             if snapshot_text.trim().is_empty() {
@@ -586,7 +898,7 @@ impl Runtime {
                     err.into_failure(PromptTurnTerminalState::CompletedWithoutAssistantText),
                 ))
             } else {
-                Err(PromptRunError::EmptyAssistantText)
+                Err(PromptRunError::EmptyAssistantText(diagnostics.clone()))
             }
         } else {
             Ok(assistant_text)
@@ -598,12 +910,14 @@ impl Runtime {
         thread_id: &str,
         turn_id: &str,
         timeout_duration: Duration,
+        item_separator: &str,
     ) -> Result<Option<LaggedTurnTerminal>, RpcError> {
         let params = serialize_params(
             methods::THREAD_READ,
             &ThreadReadParams {
                 thread_id: thread_id.to_owned(),
                 include_turns: Some(true),
+                last_n_turns: None,
             },
         )?;
         let response = self
@@ -620,7 +934,7 @@ impl Runtime {
             return Ok(None);
         };
 
-        Ok(lagged_terminal_from_turn(turn))
+        Ok(lagged_terminal_from_turn(turn, item_separator))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -644,7 +958,9 @@ impl Runtime {
                 thread_id,
                 turn_id,
                 main_status: None,
+                assistant_text: None,
             },
+            self.hook_shutdown_token(),
         );
         self.run_pre_hooks_with(&ctx, &mut hook_state.report, scoped_hooks)
             .await
@@ -660,6 +976,7 @@ impl Runtime {
             hook_state.correlation_id.as_str(),
             &hook_state.metadata,
             input,
+            self.hook_shutdown_token(),
         );
         self.run_post_hooks_with(&ctx, &mut hook_state.report, scoped_hooks)
             .await;
@@ -677,6 +994,15 @@ impl PromptRunStream {
         self.turn_id.as_str()
     }
 
+    /// Check streamed assistant text against `output_schema` after every message delta and
+    /// call `observer` once with the first definite violation. Incomplete JSON is not a
+    /// violation. Dropping the stream afterwards interrupts the turn, saving tokens.
+    /// No-op when the run has no `output_schema`.
+    pub fn with_schema_violation_observer(mut self, observer: SchemaViolationObserver) -> Self {
+        self.schema_violation_observer = Some(observer);
+        self
+    }
+
     /// Receive the next typed event for the target turn.
     pub async fn recv(&mut self) -> Result<Option<PromptRunStreamEvent>, PromptRunError> {
         if self.state.final_result.is_some() {
@@ -704,11 +1030,13 @@ impl PromptRunStream {
                             &self.thread_id,
                             &self.turn_id,
                             lag_probe_budget,
+                            self.stream.item_separator(),
                         )
                         .await
                     {
                         Ok(Some(snapshot)) => {
                             self.state.lagged_terminal = Some(snapshot.clone());
+                            self.state.diagnostics.recovered_from_lag = true;
                             let observation =
                                 observe_lagged_terminal(&self.thread_id, &self.turn_id, &snapshot);
                             return Ok(self.apply_observation(observation).await);
@@ -733,8 +1061,33 @@ impl PromptRunStream {
                 continue;
             }
 
+            self.state.diagnostics.observe(&envelope);
+            if let Some(Err(err)) = self
+                .reasoning_budget
+                .as_mut()
+                .map(|budget| budget.observe(&envelope))
+            {
+                self.interrupt_best_effort(INTERRUPT_REASON_ERROR);
+                return Err(self.fail(err).await);
+            }
             let terminal = self.stream.push_envelope(&envelope);
+            if terminal.is_some() {
+                if let Some(budget) = self.reasoning_budget.as_mut() {
+                    if let Err(err) = budget
+                        .observe_trailing_usage(&mut self.live_rx, &self.thread_id, &self.turn_id)
+                        .await
+                    {
+                        return Err(self.fail(err).await);
+                    }
+                }
+            }
             let observation = observe_target_envelope(&envelope, terminal);
+            if matches!(
+                observation.event,
+                Some(PromptRunStreamEvent::AgentMessageDelta(_))
+            ) {
+                self.check_partial_schema();
+            }
             let next = self.apply_observation(observation).await;
             if next.is_some() {
                 return Ok(next);
@@ -761,18 +1114,55 @@ impl PromptRunStream {
         }
     }
 
+    /// Re-validate the accumulated assistant text; fires the observer at most once.
+    /// Complexity: O(n) per delta, n = accumulated text length.
+    fn check_partial_schema(&mut self) {
+        if self.schema_violation_reported {
+            return;
+        }
+        let (Some(schema), Some(observer)) = (
+            self.output_schema.as_ref(),
+            self.schema_violation_observer.as_ref(),
+        ) else {
+            return;
+        };
+        let violations = partial_schema_violations(schema, self.stream.assistant_text());
+        if let Some(first) = violations.first() {
+            self.schema_violation_reported = true;
+            observer(first);
+        }
+    }
+
     async fn complete(&mut self, result: Result<PromptRunResult, PromptRunError>) {
-        self.cleanup(stream_result_status(&result)).await;
+        if let Some(mut plan) = self.take_cleanup_plan(stream_result_status(&result), false) {
+            plan.assistant_text = result
+                .as_ref()
+                .ok()
+                .map(|result| result.assistant_text.clone());
+            run_cleanup_plan(&self.runtime, plan).await;
+        }
+        self.turn_slot = None;
         self.state.final_result = Some(result);
     }
 
+    /// Finish a stream whose PreTurn hook answered with `HookAction::Respond`;
+    /// `recv` then yields no events and `finish` returns `result`.
+    async fn complete_short_circuit(&mut self, result: PromptRunResult) {
+        if let Some(mut plan) = self.take_cleanup_plan(SHORT_CIRCUITED_STATUS, false) {
+            plan.assistant_text = Some(result.assistant_text.clone());
+            run_cleanup_plan(&self.runtime, plan).await;
+        }
+        self.state.final_result = Some(Ok(result));
+    }
+
     async fn timeout_with_interrupt(&mut self) -> PromptRunError {
-        self.interrupt_best_effort();
+        self.interrupt_best_effort(INTERRUPT_REASON_TIMEOUT);
         self.fail(PromptRunError::Timeout(self.timeout)).await
     }
 
     async fn fail(&mut self, err: PromptRunError) -> PromptRunError {
         self.cleanup("error").await;
+        self.turn_slot = None;
         self.state.final_result = Some(Err(err.clone()));
         err
     }
@@ -792,6 +1182,8 @@ impl PromptRunStream {
         let runtime = self.runtime.clone();
         let fallback_runtime = runtime.clone();
         let thread_id = plan.thread_id.clone();
+        let turn_id = plan.turn_id.clone();
+        let auto_approve = plan.auto_approve;
         spawn_detached_task(
             async move {
                 run_cleanup_plan(&runtime, plan).await;
@@ -800,15 +1192,21 @@ impl PromptRunStream {
             move || {
                 fallback_runtime.record_detached_task_init_failed();
                 fallback_runtime.clear_thread_scoped_pre_tool_use_hooks(&thread_id);
+                if auto_approve {
+                    let auto_approvals =
+                        fallback_runtime.take_turn_auto_approvals(&thread_id, &turn_id);
+                    fallback_runtime.publish_auto_approvals(auto_approvals);
+                }
             },
         );
     }
 
-    fn interrupt_best_effort(&self) {
+    fn interrupt_best_effort(&self, reason: &'static str) {
         interrupt_turn_best_effort_detached(
             self.runtime.clone(),
             self.thread_id.clone(),
             self.turn_id.clone(),
+            reason,
             INTERRUPT_RPC_TIMEOUT,
         );
     }
@@ -834,6 +1232,7 @@ impl PromptRunStream {
             &mut self.state,
             self.thread_id.as_str(),
             self.turn_id.as_str(),
+            self.output_schema.as_ref(),
             self.stream.clone().into_assistant_text(),
             observation,
         );
@@ -844,9 +1243,170 @@ impl PromptRunStream {
     }
 }
 
+/// Result for a run answered by a `HookAction::Respond` without `turn/start`.
+/// No turn exists, so the turn id (and the thread id when no thread was opened yet)
+/// is `short_circuit_<hook correlation id>`.
+fn short_circuit_result(
+    thread_id: Option<&str>,
+    correlation_id: &str,
+    assistant_text: String,
+    output_schema: Option<&Value>,
+) -> PromptRunResult {
+    let synthetic_id = format!("short_circuit_{correlation_id}");
+    PromptRunResult {
+        thread_id: thread_id.map_or_else(|| synthetic_id.clone(), str::to_owned),
+        turn_id: synthetic_id,
+        schema_valid: assistant_text_matches_schema(output_schema, &assistant_text),
+        assistant_text,
+    }
+}
+
+fn short_circuit_result_for(
+    state: &HookExecutionState,
+    thread_id: &str,
+    p: &PromptRunParams,
+) -> Option<PromptRunResult> {
+    let text = state.short_circuit.clone()?;
+    Some(short_circuit_result(
+        Some(thread_id),
+        &state.correlation_id,
+        text,
+        p.output_schema.as_ref(),
+    ))
+}
+
+/// True when one `turn/start` error rejects the reasoning effort field.
+/// Pure classifier over error code + message. Allocation: one lowercase String.
+fn is_effort_rejection(err: &RpcError) -> bool {
+    let message = match err {
+        RpcError::InvalidRequest(message) => message.as_str(),
+        RpcError::ServerError(object) if object.code == -32602 => object.message.as_str(),
+        _ => return false,
+    };
+    message.to_ascii_lowercase().contains("effort")
+}
+
+/// Lowercase fragments of the known server messages rejecting a steer whose
+/// `expectedTurnId` no longer names the active turn.
+const STALE_STEER_MESSAGE_MARKERS: [&str; 3] = ["expectedturnid", "expected turn", "stale turn"];
+
+/// Server rejections of a steer whose `expectedTurnId` no longer matches the active turn.
+/// The app-server reports this as a plain request error with no dedicated code or `data`
+/// field, so this is the one place that recognizes it by message; add new server wordings
+/// to `STALE_STEER_MESSAGE_MARKERS` with a test. Pure function. Complexity: O(n), n = message length.
+fn stale_steer_rejection_message(err: &RpcError) -> Option<&str> {
+    let message = match err {
+        RpcError::InvalidRequest(message) => message.as_str(),
+        RpcError::ServerError(object) => object.message.as_str(),
+        _ => return None,
+    };
+    let lowered = message.to_ascii_lowercase();
+    STALE_STEER_MESSAGE_MARKERS
+        .iter()
+        .any(|marker| lowered.contains(marker))
+        .then_some(message)
+}
+
+/// First failed command seen live, else the one recorded in a lag-recovery snapshot.
+fn completed_turn_failed_command(
+    diagnostics: &TurnDiagnostics,
+    lagged_terminal: Option<&LaggedTurnTerminal>,
+) -> Option<(String, Option<i32>)> {
+    diagnostics
+        .failed_command
+        .clone()
+        .or_else(|| match lagged_terminal {
+            Some(LaggedTurnTerminal::Completed { failed_command, .. }) => failed_command.clone(),
+            _ => None,
+        })
+}
+
+/// How long a budgeted run waits after the terminal event for a trailing usage update
+/// when none was seen during the turn.
+const REASONING_USAGE_GRACE: Duration = Duration::from_millis(250);
+
+/// Reasoning-token spend of one turn against `PromptRunParams::max_reasoning_tokens`.
+/// Per-step `last` breakdowns are summed; updates carrying only `total` are taken as the
+/// turn's running total.
+pub(crate) struct ReasoningBudget {
+    limit: u64,
+    used: u64,
+    saw_usage: bool,
+}
+
+impl ReasoningBudget {
+    pub(crate) fn new(limit: u64) -> Self {
+        Self {
+            limit,
+            used: 0,
+            saw_usage: false,
+        }
+    }
+
+    /// Account one target-turn envelope; `ReasoningBudgetExceeded` once spend passes the limit.
+    /// Allocation: none. Complexity: O(1).
+    pub(crate) fn observe(&mut self, envelope: &Envelope) -> Result<(), PromptRunError> {
+        if envelope.method.as_deref() != Some(methods::THREAD_TOKEN_USAGE_UPDATED) {
+            return Ok(());
+        }
+        let Some(usage) = envelope
+            .json
+            .get("params")
+            .and_then(|params| params.get("tokenUsage"))
+        else {
+            return Ok(());
+        };
+        let reasoning = |key: &str| usage.get(key)?.get("reasoningOutputTokens")?.as_u64();
+        if let Some(step) = reasoning("last") {
+            self.used = self.used.saturating_add(step);
+        } else if let Some(total) = reasoning("total") {
+            self.used = self.used.max(total);
+        }
+        self.saw_usage = true;
+        if self.used > self.limit {
+            return Err(PromptRunError::ReasoningBudgetExceeded {
+                limit: self.limit,
+                used: self.used,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check usage that trails the terminal event: everything already buffered, and when
+    /// no usage was seen during the turn, the first update within `REASONING_USAGE_GRACE`.
+    /// Updates arriving later are not enforced.
+    pub(crate) async fn observe_trailing_usage(
+        &mut self,
+        live_rx: &mut tokio::sync::broadcast::Receiver<Envelope>,
+        thread_id: &str,
+        turn_id: &str,
+    ) -> Result<(), PromptRunError> {
+        let grace_deadline = Instant::now() + REASONING_USAGE_GRACE;
+        loop {
+            let envelope = if self.saw_usage {
+                match live_rx.try_recv() {
+                    Ok(envelope) => envelope,
+                    Err(_) => return Ok(()),
+                }
+            } else {
+                match tokio::time::timeout_at(grace_deadline, live_rx.recv()).await {
+                    Ok(Ok(envelope)) => envelope,
+                    Ok(Err(RecvError::Lagged(_))) => continue,
+                    Ok(Err(RecvError::Closed)) | Err(_) => return Ok(()),
+                }
+            };
+            if envelope.thread_id.as_deref() == Some(thread_id)
+                && envelope.turn_id.as_deref() == Some(turn_id)
+            {
+                self.observe(&envelope)?;
+            }
+        }
+    }
+}
+
 fn lagged_completed_text(lagged_terminal: Option<&LaggedTurnTerminal>) -> Option<String> {
     match lagged_terminal {
-        Some(LaggedTurnTerminal::Completed { assistant_text }) => assistant_text.clone(),
+        Some(LaggedTurnTerminal::Completed { assistant_text, .. }) => assistant_text.clone(),
         _ => None,
     }
 }
@@ -898,7 +1458,7 @@ fn lagged_terminal_to_stream_event(
     terminal: &LaggedTurnTerminal,
 ) -> Option<PromptRunStreamEvent> {
     match terminal {
-        LaggedTurnTerminal::Completed { assistant_text } => {
+        LaggedTurnTerminal::Completed { assistant_text, .. } => {
             Some(PromptRunStreamEvent::TurnCompleted(
                 crate::runtime::events::TurnCompletedNotification {
                     thread_id: thread_id.to_owned(),
@@ -925,6 +1485,7 @@ fn lagged_terminal_to_stream_event(
             crate::runtime::events::TurnInterruptedNotification {
                 thread_id: thread_id.to_owned(),
                 turn_id: turn_id.to_owned(),
+                reason: None,
             },
         )),
     }
@@ -953,10 +1514,21 @@ fn observe_lagged_terminal(
     }
 }
 
-fn lagged_terminal_from_turn(turn: &ThreadTurnView) -> Option<LaggedTurnTerminal> {
+fn lagged_terminal_from_turn(
+    turn: &ThreadTurnView,
+    item_separator: &str,
+) -> Option<LaggedTurnTerminal> {
     match turn.status {
         ThreadTurnStatus::Completed => Some(LaggedTurnTerminal::Completed {
-            assistant_text: extract_assistant_text_from_turn(turn),
+            assistant_text: extract_assistant_text_from_turn(turn, item_separator),
+            failed_command: turn.items.iter().find_map(|item| match &item.payload {
+                ThreadItemPayloadView::CommandExecution(data)
+                    if data.status == "failed" || data.exit_code.is_some_and(|code| code != 0) =>
+                {
+                    Some((data.command.clone(), data.exit_code))
+                }
+                _ => None,
+            }),
         }),
         ThreadTurnStatus::Failed => Some(LaggedTurnTerminal::Failed {
             message: turn.error.as_ref().map(|error| error.message.clone()),
@@ -984,6 +1556,9 @@ struct PromptStreamCleanupPlan {
     hook_state: Option<HookExecutionState>,
     main_status: &'static str,
     send_interrupt: bool,
+    auto_approve: bool,
+    /// Final text of a successfully completed turn, surfaced to PostTurn hooks.
+    assistant_text: Option<String>,
 }
 
 struct PromptStreamObservation {
@@ -1001,6 +1576,7 @@ fn reduce_prompt_stream_observation(
     state: &mut PromptRunStreamState,
     thread_id: &str,
     turn_id: &str,
+    output_schema: Option<&Value>,
     collected_assistant_text: String,
     observation: PromptStreamObservation,
 ) -> PromptStreamTransition {
@@ -1012,9 +1588,9 @@ fn reduce_prompt_stream_observation(
         build_prompt_run_result(
             thread_id,
             turn_id,
+            output_schema,
             collected_assistant_text,
-            state.lagged_terminal.as_ref(),
-            state.last_turn_error.clone(),
+            state,
             terminal,
         )
     });
@@ -1028,20 +1604,23 @@ fn reduce_prompt_stream_observation(
 fn build_prompt_run_result(
     thread_id: &str,
     turn_id: &str,
+    output_schema: Option<&Value>,
     collected_assistant_text: String,
-    lagged_terminal: Option<&LaggedTurnTerminal>,
-    last_turn_error: Option<PromptTurnErrorSignal>,
+    state: &PromptRunStreamState,
     terminal: TurnTerminalEvent,
 ) -> Result<PromptRunResult, PromptRunError> {
     Runtime::resolve_prompt_turn_assistant_text(
         terminal,
         collected_assistant_text,
-        lagged_terminal,
-        last_turn_error,
+        state.lagged_terminal.as_ref(),
+        state.last_turn_error.clone(),
+        &state.diagnostics,
+        state.fail_on_command_error,
     )
     .map(|assistant_text| PromptRunResult {
         thread_id: thread_id.to_owned(),
         turn_id: turn_id.to_owned(),
+        schema_valid: assistant_text_matches_schema(output_schema, &assistant_text),
         assistant_text,
     })
 }
@@ -1068,12 +1647,20 @@ impl PromptStreamCleanupState {
             hook_state: self.hook_state.take(),
             main_status,
             send_interrupt,
+            auto_approve: self.auto_approve,
+            assistant_text: None,
         })
     }
 }
 
 async fn run_cleanup_plan(runtime: &Runtime, mut plan: PromptStreamCleanupPlan) {
+    let auto_approvals = if plan.auto_approve {
+        runtime.take_turn_auto_approvals(&plan.thread_id, &plan.turn_id)
+    } else {
+        Vec::new()
+    };
     if let Some(state) = plan.hook_state.as_mut() {
+        state.report.auto_approvals.extend(auto_approvals);
         runtime
             .execute_post_hook_phase(
                 state,
@@ -1084,11 +1671,14 @@ async fn run_cleanup_plan(runtime: &Runtime, mut plan: PromptStreamCleanupPlan)
                     thread_id: Some(plan.thread_id.as_str()),
                     turn_id: Some(plan.turn_id.as_str()),
                     main_status: Some(plan.main_status),
+                    assistant_text: plan.assistant_text.as_deref(),
                 },
                 plan.scoped_hooks.as_ref(),
             )
             .await;
-        runtime.publish_hook_report(state.report.clone());
+        runtime.publish_hook_report(&state.correlation_id, state.report.clone());
+    } else {
+        runtime.publish_auto_approvals(auto_approvals);
     }
 
     if plan.send_interrupt {
@@ -1096,6 +1686,7 @@ async fn run_cleanup_plan(runtime: &Runtime, mut plan: PromptStreamCleanupPlan)
             runtime,
             plan.thread_id.as_str(),
             plan.turn_id.as_str(),
+            INTERRUPT_REASON_CANCELLED,
             INTERRUPT_RPC_TIMEOUT,
         )
         .await;
@@ -1103,3 +1694,47 @@ async fn run_cleanup_plan(runtime: &Runtime, mut plan: PromptStreamCleanupPlan)
 
     runtime.clear_thread_scoped_pre_tool_use_hooks(&plan.thread_id);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::stale_steer_rejection_message;
+    use crate::runtime::errors::{RpcError, RpcErrorObject};
+
+    fn invalid_request(message: &str) -> RpcError {
+        RpcError::InvalidRequest(message.to_owned())
+    }
+
+    #[test]
+    fn stale_steer_matches_expected_turn_id_mismatch() {
+        let err = invalid_request("expectedTurnId turn_1 does not match active turn turn_2");
+        assert_eq!(
+            stale_steer_rejection_message(&err),
+            Some("expectedTurnId turn_1 does not match active turn turn_2")
+        );
+    }
+
+    #[test]
+    fn stale_steer_matches_expected_turn_wording() {
+        let err = RpcError::ServerError(RpcErrorObject {
+            code: -32000,
+            message: "Expected turn turn_1 is not the active turn".to_owned(),
+            data: None,
+        });
+        assert!(stale_steer_rejection_message(&err).is_some());
+    }
+
+    #[test]
+    fn stale_steer_matches_stale_turn_wording() {
+        let err = invalid_request("cannot steer a stale turn");
+        assert!(stale_steer_rejection_message(&err).is_some());
+    }
+
+    #[test]
+    fn stale_steer_ignores_unrelated_rejections() {
+        assert_eq!(
+            stale_steer_rejection_message(&invalid_request("stale cache entry for model list")),
+            None
+        );
+        assert_eq!(stale_steer_rejection_message(&RpcError::Timeout), None);
+    }
+}
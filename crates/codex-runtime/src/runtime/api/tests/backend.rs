@@ -0,0 +1,171 @@
+use std::sync::{Arc, Mutex};
+
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+
+use crate::runtime::errors::RpcError;
+use crate::runtime::events::{Direction, Envelope, MsgKind};
+
+use super::super::*;
+
+/// Backend that answers with fixed ids and replays `script` on `turn/start`.
+struct ScriptedBackend {
+    live_tx: broadcast::Sender<Envelope>,
+    script: Vec<(&'static str, Value)>,
+    turn_inputs: Mutex<Vec<Vec<InputItem>>>,
+}
+
+impl ScriptedBackend {
+    fn new(script: Vec<(&'static str, Value)>) -> Self {
+        let (live_tx, _) = broadcast::channel(64);
+        Self {
+            live_tx,
+            script,
+            turn_inputs: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl PromptBackend for ScriptedBackend {
+    fn thread_start<'a>(
+        &'a self,
+        _params: ThreadStartParams,
+    ) -> PromptBackendFuture<'a, Result<ThreadId, RpcError>> {
+        Box::pin(async { Ok("thr_fake".to_owned()) })
+    }
+
+    fn turn_start<'a>(
+        &'a self,
+        thread_id: &'a str,
+        params: TurnStartParams,
+    ) -> PromptBackendFuture<'a, Result<TurnId, RpcError>> {
+        Box::pin(async move {
+            self.turn_inputs
+                .lock()
+                .expect("turn inputs lock")
+                .push(params.input);
+            for (method, params) in &self.script {
+                let _ = self
+                    .live_tx
+                    .send(turn_envelope(method, thread_id, params.clone()));
+            }
+            Ok("turn_fake".to_owned())
+        })
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<Envelope> {
+        self.live_tx.subscribe()
+    }
+}
+
+fn turn_envelope(method: &str, thread_id: &str, params: Value) -> Envelope {
+    Envelope {
+        seq: 1,
+        ts_millis: 0,
+        direction: Direction::Inbound,
+        kind: MsgKind::Notification,
+        rpc_id: None,
+        method: Some(Arc::from(method)),
+        thread_id: Some(Arc::from(thread_id)),
+        turn_id: Some(Arc::from("turn_fake")),
+        item_id: Some(Arc::from("it_1")),
+        json: Arc::new(json!({"method": method, "params": params})),
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_with_backend_collects_text_without_a_process() {
+    let backend = ScriptedBackend::new(vec![
+        ("item/started", json!({"itemType":"agentMessage"})),
+        ("item/agentMessage/delta", json!({"delta":"hello "})),
+        ("item/agentMessage/delta", json!({"delta":"backend"})),
+        ("turn/completed", json!({})),
+    ]);
+
+    let result = run_prompt_with_backend(&backend, PromptRunParams::new("/tmp", "say hi"))
+        .await
+        .expect("run prompt");
+
+    assert_eq!(result.thread_id, "thr_fake");
+    assert_eq!(result.turn_id, "turn_fake");
+    assert_eq!(result.assistant_text, "hello backend");
+    assert_eq!(result.schema_valid, None);
+    let turn_inputs = backend.turn_inputs.lock().expect("turn inputs lock");
+    assert_eq!(
+        turn_inputs.as_slice(),
+        &[vec![InputItem::Text {
+            text: "say hi".to_owned()
+        }]]
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_with_backend_maps_failed_and_silent_turns() {
+    let failed = ScriptedBackend::new(vec![(
+        "turn/failed",
+        json!({"error":{"message":"model overloaded"}}),
+    )]);
+    let err = run_prompt_with_backend(&failed, PromptRunParams::new("/tmp", "x"))
+        .await
+        .expect_err("failed turn");
+    match err {
+        PromptRunError::TurnFailedWithContext(failure) => {
+            assert_eq!(failure.message, "model overloaded");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let empty = ScriptedBackend::new(vec![]);
+    let err = run_prompt_with_backend(&empty, PromptRunParams::new("/tmp", ""))
+        .await
+        .expect_err("empty prompt");
+    assert!(matches!(err, PromptRunError::EmptyPrompt), "{err:?}");
+    assert!(empty
+        .turn_inputs
+        .lock()
+        .expect("turn inputs lock")
+        .is_empty());
+
+    let silent = ScriptedBackend::new(vec![]);
+    let err = run_prompt_with_backend(
+        &silent,
+        PromptRunParams::new("/tmp", "x").with_timeout(std::time::Duration::from_millis(20)),
+    )
+    .await
+    .expect_err("timeout");
+    assert!(matches!(err, PromptRunError::Timeout(_)), "{err:?}");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_with_backend_enforces_max_reasoning_tokens() {
+    let backend = ScriptedBackend::new(vec![
+        (
+            "thread/tokenUsage/updated",
+            json!({"tokenUsage":{"last":{"reasoningOutputTokens":700}}}),
+        ),
+        ("item/agentMessage/delta", json!({"delta":"ok"})),
+        ("turn/completed", json!({})),
+    ]);
+
+    let err = run_prompt_with_backend(
+        &backend,
+        PromptRunParams::new("/tmp", "x").with_max_reasoning_tokens(500),
+    )
+    .await
+    .expect_err("usage over the cap must fail the run");
+    assert_eq!(
+        err,
+        PromptRunError::ReasoningBudgetExceeded {
+            limit: 500,
+            used: 700,
+        }
+    );
+
+    let result = run_prompt_with_backend(
+        &backend,
+        PromptRunParams::new("/tmp", "x").with_max_reasoning_tokens(700),
+    )
+    .await
+    .expect("usage at the cap is allowed");
+    assert_eq!(result.assistant_text, "ok");
+}
@@ -7,3 +7,5 @@ mod command_exec;
 mod thread_api;
 // Integration: prompt-run lifecycle end-to-end behavior.
 mod run_prompt;
+// Unit: prompt orchestration over a fake PromptBackend (no process).
+mod backend;
@@ -1,4 +1,5 @@
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use serde_json::{json, Value};
@@ -14,6 +15,7 @@ fn maps_turn_start_params_to_wire_shape() {
             },
             InputItem::LocalImage {
                 path: "/tmp/a.png".to_owned(),
+                mime_type: Some("image/png".to_owned()),
             },
         ],
         cwd: Some("/tmp".to_owned()),
@@ -29,6 +31,10 @@ fn maps_turn_start_params_to_wire_shape() {
         summary: Some("brief".to_owned()),
         personality: Some(Personality::Pragmatic),
         output_schema: Some(json!({"type":"object"})),
+        extra_params: json!({"model": "ignored", "collaborationMode": "plan"})
+            .as_object()
+            .cloned()
+            .expect("extra params object"),
     };
 
     let wire = turn_start_params_to_wire("thr_1", &params);
@@ -37,6 +43,7 @@ fn maps_turn_start_params_to_wire_shape() {
     assert_eq!(wire["input"][0]["text"], "hello");
     assert_eq!(wire["input"][1]["type"], "localImage");
     assert_eq!(wire["input"][1]["path"], "/tmp/a.png");
+    assert_eq!(wire["input"][1]["mimeType"], "image/png");
     assert_eq!(wire["approvalPolicy"], "never");
     assert_eq!(wire["sandboxPolicy"]["type"], "workspaceWrite");
     assert_eq!(wire["sandboxPolicy"]["writableRoots"][0], "/tmp");
@@ -45,6 +52,22 @@ fn maps_turn_start_params_to_wire_shape() {
     assert_eq!(wire["serviceTier"], "fast");
     assert_eq!(wire["personality"], "pragmatic");
     assert_eq!(wire["outputSchema"]["type"], "object");
+    assert_eq!(wire["model"], "gpt-5", "typed fields win over extra params");
+    assert_eq!(wire["collaborationMode"], "plan");
+}
+
+#[test]
+fn turn_extra_params_reject_runtime_owned_keys() {
+    for key in ["threadId", "input"] {
+        let extra = json!({ key: "x" }).as_object().cloned().expect("object");
+        let err = validate_turn_extra_params(&extra).expect_err("reserved key must fail");
+        assert!(err.to_string().contains(key), "{err}");
+    }
+    let extra = json!({"collaborationMode": "plan"})
+        .as_object()
+        .cloned()
+        .expect("object");
+    validate_turn_extra_params(&extra).expect("unreserved key passes");
 }
 
 #[test]
@@ -140,6 +163,7 @@ fn builds_prompt_input_with_at_path_attachment() {
             path: "README.md".to_owned(),
             placeholder: None,
         }],
+        &AttachmentPlaceholderStrategy::UsePath,
     );
     assert_eq!(input.len(), 1);
     match &input[0] {
@@ -156,6 +180,75 @@ fn builds_prompt_input_with_at_path_attachment() {
     }
 }
 
+#[test]
+fn attachment_placeholder_strategy_fills_missing_placeholders() {
+    let attachments = [
+        PromptAttachment::AtPath {
+            path: "/long/path/to/README.md".to_owned(),
+            placeholder: None,
+        },
+        PromptAttachment::AtPath {
+            path: "src/lib.rs".to_owned(),
+            placeholder: Some("entry".to_owned()),
+        },
+    ];
+    let text_elements = |strategy: &AttachmentPlaceholderStrategy| match build_prompt_inputs(
+        "summarize",
+        &attachments,
+        strategy,
+    )
+    .remove(0)
+    {
+        InputItem::TextWithElements {
+            text,
+            text_elements,
+        } => {
+            assert_eq!(text, "summarize\n@/long/path/to/README.md\n@src/lib.rs");
+            text_elements
+        }
+        other => panic!("unexpected input variant: {other:?}"),
+    };
+
+    let by_path = text_elements(&AttachmentPlaceholderStrategy::UsePath);
+    assert_eq!(by_path[0].placeholder, None);
+    assert_eq!(by_path[1].placeholder.as_deref(), Some("entry"));
+
+    let by_stem = text_elements(&AttachmentPlaceholderStrategy::FileStem);
+    assert_eq!(by_stem[0].placeholder.as_deref(), Some("README"));
+    assert_eq!(by_stem[0].byte_range, by_path[0].byte_range);
+    assert_eq!(by_stem[1].placeholder.as_deref(), Some("entry"));
+
+    let custom = text_elements(&AttachmentPlaceholderStrategy::Custom(Arc::new(|path| {
+        path.rsplit('/').next().unwrap_or(path).to_uppercase()
+    })));
+    assert_eq!(custom[0].placeholder.as_deref(), Some("README.MD"));
+    assert_eq!(custom[1].placeholder.as_deref(), Some("entry"));
+}
+
+#[test]
+fn facade_id_parsers_cover_raw_result_shapes() {
+    use crate::runtime::{parse_thread_id, parse_turn_id};
+
+    assert_eq!(
+        parse_thread_id(&json!({"thread": {"id": "thr_nested"}})).as_deref(),
+        Some("thr_nested")
+    );
+    assert_eq!(
+        parse_thread_id(&json!({"threadId": "thr_flat"})).as_deref(),
+        Some("thr_flat")
+    );
+    assert_eq!(
+        parse_turn_id(&json!({"turn": {"id": "turn_nested"}, "turnId": "turn_flat"})).as_deref(),
+        Some("turn_nested")
+    );
+    assert_eq!(
+        parse_turn_id(&json!({"turnId": "turn_flat"})).as_deref(),
+        Some("turn_flat")
+    );
+    assert_eq!(parse_turn_id(&json!({"turnId": " padded "})), None);
+    assert_eq!(parse_thread_id(&json!({"id": "thr_bare"})), None);
+}
+
 #[test]
 fn parses_policy_and_effort_from_str() {
     assert_eq!(
@@ -190,6 +283,85 @@ fn parses_policy_and_effort_from_str() {
     );
 }
 
+#[test]
+fn turn_start_params_from_prompt_matches_prompt_run_mapping() {
+    let p = PromptRunParams::new("/work", "review")
+        .with_model("gpt-5")
+        .with_approval_policy(ApprovalPolicy::OnRequest)
+        .with_attachment(PromptAttachment::AtPath {
+            path: "docs/README.md".to_owned(),
+            placeholder: None,
+        })
+        .with_extra_turn_param("custom", json!(1));
+
+    let params = TurnStartParams::from_prompt(&p, &AttachmentPlaceholderStrategy::FileStem);
+    assert_eq!(params.cwd.as_deref(), Some("/work"));
+    assert_eq!(params.model.as_deref(), Some("gpt-5"));
+    assert_eq!(params.effort, Some(DEFAULT_REASONING_EFFORT));
+    assert_eq!(params.approval_policy, Some(ApprovalPolicy::OnRequest));
+    assert_eq!(params.extra_params.get("custom"), Some(&json!(1)));
+    match &params.input[0] {
+        InputItem::TextWithElements {
+            text,
+            text_elements,
+        } => {
+            assert_eq!(text, "review\n@docs/README.md");
+            assert_eq!(text_elements[0].placeholder.as_deref(), Some("README"));
+        }
+        other => panic!("unexpected input: {other:?}"),
+    }
+}
+
+#[test]
+fn with_prompt_reuses_a_template_across_runs() {
+    let base = PromptRunParams::new("/work", "")
+        .with_model("gpt-5")
+        .with_approval_policy(ApprovalPolicy::OnRequest);
+
+    let first = base.clone().with_prompt("question 1");
+    let second = base.clone().with_prompt(String::from("question 2"));
+
+    assert_eq!(first.prompt, "question 1");
+    assert_eq!(second.prompt, "question 2");
+    assert_eq!(base.prompt, "");
+    assert_eq!(
+        PromptRunParams {
+            prompt: String::new(),
+            ..second
+        },
+        base
+    );
+}
+
+#[test]
+fn thread_rollback_response_falls_back_to_extra_count_without_turn_ids() {
+    let thread = json!({
+        "id": "thr_rb",
+        "cliVersion": "0.104.0",
+        "createdAt": 1700000000,
+        "cwd": "/tmp",
+        "modelProvider": "openai",
+        "path": "/tmp/threads/thr_rb.jsonl",
+        "preview": "hello",
+        "source": "app-server",
+        "turns": [],
+        "updatedAt": 1700000001,
+        "rolledBackTurns": 2
+    });
+    let legacy: ThreadRollbackResponse =
+        serde_json::from_value(json!({ "thread": thread })).expect("parse legacy rollback");
+    assert!(legacy.rolled_back_turn_ids.is_empty());
+    assert_eq!(legacy.rolled_back_count(), Some(2));
+
+    let typed: ThreadRollbackResponse = serde_json::from_value(json!({
+        "thread": thread,
+        "rolledBackTurnIds": ["turn_a"]
+    }))
+    .expect("parse typed rollback");
+    assert_eq!(typed.rolled_back_turn_ids, vec!["turn_a"]);
+    assert_eq!(typed.rolled_back_count(), Some(1));
+}
+
 #[test]
 fn parses_thread_item_payload_variants() {
     let agent: ThreadItemView = serde_json::from_value(json!({
@@ -223,6 +395,122 @@ fn parses_thread_item_payload_variants() {
         other => panic!("unexpected payload: {other:?}"),
     }
 
+    let mcp_json = json!({
+        "id": "item_m",
+        "type": "mcpToolCall",
+        "server": "github",
+        "tool": "search_issues",
+        "status": "completed",
+        "arguments": {"query": "is:open"},
+        "result": {"content": [{"type": "text", "text": "3 issues"}]},
+        "durationMs": 42,
+        "futureField": true
+    });
+    let mcp: ThreadItemView = serde_json::from_value(mcp_json).expect("parse mcp item");
+    assert_eq!(mcp.item_type, ThreadItemType::McpToolCall);
+    match &mcp.payload {
+        ThreadItemPayloadView::McpToolCall(data) => {
+            assert_eq!(
+                (data.server.as_str(), data.tool.as_str()),
+                ("github", "search_issues")
+            );
+            assert_eq!(data.status, "completed");
+            assert_eq!(data.arguments, Some(json!({"query": "is:open"})));
+            assert_eq!(data.duration_ms, Some(42));
+            assert_eq!(data.error, None);
+            assert_eq!(data.extra.get("futureField"), Some(&json!(true)));
+        }
+        other => panic!("unexpected payload: {other:?}"),
+    }
+    let reparsed: ThreadItemView =
+        serde_json::from_value(serde_json::to_value(&mcp).expect("serialize mcp item"))
+            .expect("reparse mcp item");
+    assert_eq!(reparsed, mcp);
+
+    let search: ThreadItemView = serde_json::from_value(json!({
+        "id": "item_w",
+        "type": "webSearch",
+        "query": "rust tokio select",
+        "results": [{"title": "tokio::select!", "url": "https://docs.rs/tokio"}],
+        "action": {"type": "search"}
+    }))
+    .expect("parse web search item");
+    assert_eq!(search.item_type, ThreadItemType::WebSearch);
+    match &search.payload {
+        ThreadItemPayloadView::WebSearch(data) => {
+            assert_eq!(data.query, "rust tokio select");
+            assert_eq!(data.results.len(), 1);
+            assert_eq!(data.extra.get("action"), Some(&json!({"type": "search"})));
+        }
+        other => panic!("unexpected payload: {other:?}"),
+    }
+    let reparsed: ThreadItemView =
+        serde_json::from_value(serde_json::to_value(&search).expect("serialize search item"))
+            .expect("reparse search item");
+    assert_eq!(reparsed, search);
+
+    let image: ThreadItemView = serde_json::from_value(json!({
+        "id": "item_i",
+        "type": "imageView",
+        "path": "/tmp/screenshot.png"
+    }))
+    .expect("parse image view item");
+    assert_eq!(image.item_type, ThreadItemType::ImageView);
+    match &image.payload {
+        ThreadItemPayloadView::ImageView(data) => {
+            assert_eq!(data.url_or_path, "/tmp/screenshot.png");
+            assert_eq!(data.alt, None);
+        }
+        other => panic!("unexpected payload: {other:?}"),
+    }
+    let remote_image: ThreadItemView = serde_json::from_value(json!({
+        "id": "item_j",
+        "type": "imageView",
+        "url": "https://example.com/chart.png",
+        "alt": "chart"
+    }))
+    .expect("parse remote image view item");
+    match &remote_image.payload {
+        ThreadItemPayloadView::ImageView(data) => {
+            assert_eq!(data.url_or_path, "https://example.com/chart.png");
+            assert_eq!(data.alt.as_deref(), Some("chart"));
+        }
+        other => panic!("unexpected payload: {other:?}"),
+    }
+
+    let entered: ThreadItemView = serde_json::from_value(json!({
+        "id": "item_r1",
+        "type": "enteredReviewMode",
+        "review": "current changes",
+        "target": {"type": "uncommittedChanges"}
+    }))
+    .expect("parse entered review mode item");
+    assert_eq!(entered.item_type, ThreadItemType::EnteredReviewMode);
+    match &entered.payload {
+        ThreadItemPayloadView::EnteredReviewMode(data) => {
+            assert_eq!(data.review, "current changes");
+            assert_eq!(
+                data.extra.get("target"),
+                Some(&json!({"type": "uncommittedChanges"}))
+            );
+        }
+        other => panic!("unexpected payload: {other:?}"),
+    }
+    let reparsed: ThreadItemView =
+        serde_json::from_value(serde_json::to_value(&entered).expect("serialize review item"))
+            .expect("reparse review item");
+    assert_eq!(reparsed, entered);
+    let exited: ThreadItemView = serde_json::from_value(json!({
+        "id": "item_r2",
+        "type": "exitedReviewMode",
+        "review": "current changes"
+    }))
+    .expect("parse exited review mode item");
+    assert!(matches!(
+        exited.payload,
+        ThreadItemPayloadView::ExitedReviewMode(ref data) if data.review == "current changes"
+    ));
+
     let unknown: ThreadItemView = serde_json::from_value(json!({
         "id": "item_u",
         "type": "futureType",
@@ -249,6 +537,7 @@ async fn validate_prompt_attachments_rejects_missing_path() {
             path: "definitely_missing_file_12345.txt".to_owned(),
             placeholder: None,
         }],
+        &AttachmentLimits::default(),
     )
     .await
     .expect_err("must fail");
@@ -260,6 +549,24 @@ async fn validate_prompt_attachments_rejects_missing_path() {
     }
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn validate_prompt_rejects_blank_prompt_without_attachments() {
+    for prompt in ["", "  \n\t"] {
+        let err = validate_prompt(
+            &PromptRunParams::new("/tmp", prompt),
+            &AttachmentLimits::default(),
+        )
+        .await
+        .expect_err("blank prompt must fail");
+        assert!(matches!(err, PromptRunError::EmptyPrompt), "{err:?}");
+    }
+
+    let image_only = PromptRunParams::new("/tmp", "").attach_image_url("https://example.com/a.png");
+    validate_prompt(&image_only, &AttachmentLimits::default())
+        .await
+        .expect("attachments alone are input");
+}
+
 #[test]
 fn prompt_run_params_defaults_are_explicit() {
     let params = PromptRunParams::new("/work", "hello");
@@ -272,9 +579,10 @@ fn prompt_run_params_defaults_are_explicit() {
         SandboxPolicy::Preset(SandboxPreset::ReadOnly)
     );
     assert!(!params.privileged_escalation_approved);
-    assert_eq!(params.timeout, Duration::from_secs(120));
+    assert_eq!(params.timeout, None);
     assert_eq!(params.output_schema, None);
     assert!(params.attachments.is_empty());
+    assert_eq!(params.metadata, Value::Null);
 }
 
 #[test]
@@ -309,7 +617,7 @@ fn prompt_run_params_builder_overrides_defaults() {
         })
     );
     assert!(params.privileged_escalation_approved);
-    assert_eq!(params.timeout, Duration::from_secs(30));
+    assert_eq!(params.timeout, Some(Duration::from_secs(30)));
     assert_eq!(
         params.output_schema,
         Some(json!({"type":"object","required":["answer"]}))
@@ -383,6 +691,77 @@ fn maps_thread_start_params_to_wire_shape() {
     assert_eq!(wire["ephemeral"], true);
 }
 
+#[test]
+fn raw_sandbox_policy_is_sent_verbatim_on_thread_and_turn_start() {
+    let raw = json!({
+        "type": "workspaceWrite",
+        "writableRoots": ["/work"],
+        "excludeTmpdirEnvVar": true,
+        "vendorExtension": {"level": 2}
+    });
+    let thread_wire = thread_start_params_to_wire(&ThreadStartParams {
+        sandbox_policy: Some(SandboxPolicy::Raw(raw.clone())),
+        ..ThreadStartParams::default()
+    });
+    assert_eq!(thread_wire["sandboxPolicy"], raw);
+
+    let turn_wire = turn_start_params_to_wire(
+        "thr_1",
+        &TurnStartParams {
+            sandbox_policy: Some(SandboxPolicy::Raw(raw.clone())),
+            ..TurnStartParams::default()
+        },
+    );
+    assert_eq!(turn_wire["sandboxPolicy"], raw);
+}
+
+#[test]
+fn raw_sandbox_policy_goes_through_privileged_escalation_guard() {
+    use super::super::wire::{validate_thread_start_security, validate_turn_start_security};
+
+    let turn = |raw: Value, approved: bool| TurnStartParams {
+        cwd: Some("/work".to_owned()),
+        approval_policy: Some(ApprovalPolicy::OnRequest),
+        sandbox_policy: Some(SandboxPolicy::Raw(raw)),
+        privileged_escalation_approved: approved,
+        ..TurnStartParams::default()
+    };
+    for raw in [
+        json!({"type": "dangerFullAccess"}),
+        json!({"type": "workspaceWrite", "writableRoots": ["/work"]}),
+        json!({"type": "somethingNew"}),
+    ] {
+        let err = validate_turn_start_security(&turn(raw.clone(), false), false)
+            .expect_err("raw privileged policy without opt-in");
+        assert_eq!(
+            err,
+            RpcError::InvalidRequest(
+                "privileged sandbox requires explicit escalation approval".to_owned()
+            ),
+            "{raw}"
+        );
+        validate_turn_start_security(&turn(raw, true), false).expect("opted in with scope");
+    }
+    validate_turn_start_security(&turn(json!({"type": "readOnly"}), false), false)
+        .expect("raw read-only needs no opt-in");
+    assert!(matches!(
+        validate_turn_start_security(&turn(json!("danger-full-access"), true), false),
+        Err(RpcError::InvalidRequest(_))
+    ));
+
+    let err = validate_thread_start_security(
+        &ThreadStartParams {
+            cwd: Some("/work".to_owned()),
+            approval_policy: Some(ApprovalPolicy::OnRequest),
+            sandbox_policy: Some(SandboxPolicy::Raw(json!({"type": "dangerFullAccess"}))),
+            ..ThreadStartParams::default()
+        },
+        false,
+    )
+    .expect_err("raw privileged thread policy without opt-in");
+    assert!(matches!(err, RpcError::InvalidRequest(_)));
+}
+
 #[test]
 fn maps_thread_resume_overrides_to_supported_subset() {
     let params = ThreadStartParams {
@@ -416,3 +795,351 @@ fn maps_thread_resume_overrides_to_supported_subset() {
     assert!(!wire.contains_key("serviceName"));
     assert!(!wire.contains_key("ephemeral"));
 }
+
+#[test]
+fn thread_export_flattens_turn_items_to_text_or_summary() {
+    let thread: ThreadView = serde_json::from_value(json!({
+        "id": "thr_export",
+        "cliVersion": "1.0.0",
+        "createdAt": 1,
+        "cwd": "/work",
+        "modelProvider": "openai",
+        "path": "/tmp/thr_export.jsonl",
+        "preview": "hello",
+        "source": "app-server",
+        "updatedAt": 2,
+        "model": "gpt-5-codex",
+        "turns": [{
+            "id": "turn_1",
+            "status": "completed",
+            "items": [
+                {"id": "item_user", "type": "userMessage", "content": [{"type": "text", "text": "hi"}]},
+                {"id": "item_reason", "type": "reasoning", "summary": ["plan", "act"]},
+                {"id": "item_cmd", "type": "commandExecution", "command": "ls", "commandActions": [], "cwd": "/work", "status": "completed", "exitCode": 0},
+                {"id": "item_agent", "type": "agentMessage", "text": "done"}
+            ]
+        }]
+    }))
+    .expect("thread view");
+
+    let export = ThreadExport::from_view(&thread);
+    assert_eq!(export.thread_id, "thr_export");
+    assert_eq!(export.model.as_deref(), Some("gpt-5-codex"));
+    assert_eq!(export.turns.len(), 1);
+    assert_eq!(export.turns[0].status, ThreadTurnStatus::Completed);
+    let texts: Vec<(ThreadItemType, &str)> = export.turns[0]
+        .items
+        .iter()
+        .map(|item| (item.item_type.clone(), item.text_or_summary.as_str()))
+        .collect();
+    assert_eq!(
+        texts,
+        vec![
+            (ThreadItemType::UserMessage, "hi"),
+            (ThreadItemType::Reasoning, "plan\nact"),
+            (ThreadItemType::CommandExecution, "$ ls [completed, exit 0]"),
+            (ThreadItemType::AgentMessage, "done"),
+        ]
+    );
+
+    let wire = serde_json::to_value(&export).expect("serialize export");
+    assert_eq!(wire["threadId"], "thr_export");
+    assert_eq!(wire["turns"][0]["items"][3]["type"], "agentMessage");
+    assert_eq!(wire["turns"][0]["items"][3]["textOrSummary"], "done");
+}
+
+#[test]
+fn thread_view_git_info_parsed_tolerates_missing_and_extra_fields() {
+    let mut thread: ThreadView = serde_json::from_value(json!({
+        "id": "thr_git",
+        "cliVersion": "1.0.0",
+        "createdAt": 1,
+        "cwd": "/work",
+        "gitInfo": {"sha": "abc123", "branch": "main", "originUrl": "git@host:repo", "stash": 2},
+        "modelProvider": "openai",
+        "path": "/tmp/thr_git.jsonl",
+        "preview": "",
+        "source": "app-server",
+        "turns": [],
+        "updatedAt": 2
+    }))
+    .expect("thread view");
+
+    let git = thread.git_info_parsed().expect("git info");
+    assert_eq!(git.branch.as_deref(), Some("main"));
+    assert_eq!(git.commit.as_deref(), Some("abc123"));
+    assert_eq!(git.dirty, None);
+    assert_eq!(git.origin_url.as_deref(), Some("git@host:repo"));
+    assert_eq!(git.extra.get("stash"), Some(&json!(2)));
+    assert_eq!(thread.git_info.as_ref().expect("raw kept")["sha"], "abc123");
+
+    thread.git_info = Some(json!({"commit": "def456", "dirty": true}));
+    let git = thread.git_info_parsed().expect("git info");
+    assert_eq!(git.commit.as_deref(), Some("def456"));
+    assert_eq!(git.dirty, Some(true));
+    assert_eq!(git.branch, None);
+
+    thread.git_info = Some(Value::Null);
+    assert_eq!(thread.git_info_parsed(), None);
+    thread.git_info = None;
+    assert_eq!(thread.git_info_parsed(), None);
+}
+
+#[test]
+fn sniff_image_mime_recognizes_supported_magic_bytes() {
+    use super::super::attachment_validation::sniff_image_mime;
+
+    assert_eq!(
+        sniff_image_mime(b"\x89PNG\r\n\x1a\n\0\0\0\x0d"),
+        Some("image/png")
+    );
+    assert_eq!(sniff_image_mime(b"\xff\xd8\xff\xe0"), Some("image/jpeg"));
+    assert_eq!(sniff_image_mime(b"GIF89a\x01\0"), Some("image/gif"));
+    assert_eq!(sniff_image_mime(b"RIFF\x24\0\0\0WEBP"), Some("image/webp"));
+    assert_eq!(sniff_image_mime(b"RIFF\x24\0\0\0WAVE"), None);
+    assert_eq!(sniff_image_mime(b"hello world"), None);
+    assert_eq!(sniff_image_mime(b""), None);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn local_image_attachments_are_sniffed_and_annotated() {
+    use super::super::attachment_validation::annotate_local_image_mime_types;
+
+    let dir = std::env::temp_dir().join(format!("attachment_sniff_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    std::fs::write(dir.join("shot.png"), b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR").expect("write png");
+    std::fs::write(dir.join("notes.txt"), b"plain text, not an image").expect("write txt");
+    let cwd = dir.to_string_lossy().to_string();
+
+    validate_prompt_attachments(
+        &cwd,
+        &[PromptAttachment::LocalImage {
+            path: "shot.png".to_owned(),
+        }],
+        &AttachmentLimits::default(),
+    )
+    .await
+    .expect("png attachment is valid");
+
+    let err = validate_prompt_attachments(
+        &cwd,
+        &[PromptAttachment::LocalImage {
+            path: "notes.txt".to_owned(),
+        }],
+        &AttachmentLimits::default(),
+    )
+    .await
+    .expect_err("text file must be rejected as image");
+    match err {
+        PromptRunError::UnsupportedAttachmentType { path, detected } => {
+            assert!(path.ends_with("notes.txt"));
+            assert_eq!(detected, "text/plain");
+        }
+        other => panic!("unexpected error: {other:?}"),
+    }
+
+    let mut input = build_prompt_inputs(
+        "look",
+        &[PromptAttachment::LocalImage {
+            path: "shot.png".to_owned(),
+        }],
+        &AttachmentPlaceholderStrategy::UsePath,
+    );
+    annotate_local_image_mime_types(&cwd, &mut input).await;
+    assert_eq!(
+        input[1],
+        InputItem::LocalImage {
+            path: "shot.png".to_owned(),
+            mime_type: Some("image/png".to_owned()),
+        }
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn inline_local_images_replace_paths_with_data_urls_under_byte_cap() {
+    use super::super::attachment_validation::inline_local_images;
+
+    let dir = std::env::temp_dir().join(format!("attachment_inline_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    std::fs::write(dir.join("dot.gif"), b"GIF89a").expect("write gif");
+    let cwd = dir.to_string_lossy().to_string();
+    let build = || {
+        build_prompt_inputs(
+            "look",
+            &[PromptAttachment::LocalImage {
+                path: "dot.gif".to_owned(),
+            }],
+            &AttachmentPlaceholderStrategy::UsePath,
+        )
+    };
+
+    let mut input = build();
+    inline_local_images(&cwd, &mut input, &AttachmentLimits::default())
+        .await
+        .expect("inline image");
+    assert_eq!(
+        input[1],
+        InputItem::ImageUrl {
+            url: "data:image/gif;base64,R0lGODlh".to_owned(),
+        }
+    );
+
+    let mut input = build();
+    let err = inline_local_images(
+        &cwd,
+        &mut input,
+        &AttachmentLimits {
+            max_count: None,
+            max_bytes: Some(5),
+        },
+    )
+    .await
+    .expect_err("byte cap applies to inlined bytes");
+    assert_eq!(
+        err,
+        PromptRunError::AttachmentLimitExceeded {
+            unit: "bytes",
+            limit: 5,
+            actual: 6,
+        }
+    );
+
+    std::fs::write(dir.join("notes.txt"), b"plain text").expect("write text");
+    let mut input = build_prompt_inputs(
+        "look",
+        &[
+            PromptAttachment::AtPath {
+                path: "notes.txt".to_owned(),
+                placeholder: None,
+            },
+            PromptAttachment::LocalImage {
+                path: "notes.txt".to_owned(),
+            },
+        ],
+        &AttachmentPlaceholderStrategy::UsePath,
+    );
+    let mention = input[1].clone();
+    let err = inline_local_images(&cwd, &mut input, &AttachmentLimits::default())
+        .await
+        .expect_err("non-image bytes are rejected when inlined");
+    assert!(
+        matches!(err, PromptRunError::UnsupportedAttachmentType { ref detected, .. } if detected == "text/plain"),
+        "unexpected error: {err:?}"
+    );
+    assert_eq!(input[1], mention, "@path mentions are never inlined");
+
+    std::fs::remove_dir_all(&dir).expect("cleanup temp dir");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn validate_prompt_attachments_enforces_configured_limits() {
+    let dir = std::env::temp_dir().join(format!("attachment_limits_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    std::fs::write(dir.join("a.txt"), [b'a'; 600]).expect("write a");
+    std::fs::write(dir.join("b.txt"), [b'b'; 600]).expect("write b");
+    let cwd = dir.to_string_lossy().to_string();
+    let attachments = ["a.txt", "b.txt"].map(|path| PromptAttachment::AtPath {
+        path: path.to_owned(),
+        placeholder: None,
+    });
+
+    validate_prompt_attachments(&cwd, &attachments, &AttachmentLimits::default())
+        .await
+        .expect("no limits configured");
+
+    let err = validate_prompt_attachments(
+        &cwd,
+        &attachments,
+        &AttachmentLimits {
+            max_count: Some(1),
+            max_bytes: None,
+        },
+    )
+    .await
+    .expect_err("count limit");
+    assert_eq!(
+        err,
+        PromptRunError::AttachmentLimitExceeded {
+            unit: "attachments",
+            limit: 1,
+            actual: 2,
+        }
+    );
+
+    let err = validate_prompt_attachments(
+        &cwd,
+        &attachments,
+        &AttachmentLimits {
+            max_count: Some(2),
+            max_bytes: Some(1_000),
+        },
+    )
+    .await
+    .expect_err("byte limit");
+    assert_eq!(
+        err,
+        PromptRunError::AttachmentLimitExceeded {
+            unit: "bytes",
+            limit: 1_000,
+            actual: 1_200,
+        }
+    );
+
+    validate_prompt_attachments(
+        &cwd,
+        &attachments,
+        &AttachmentLimits {
+            max_count: Some(2),
+            max_bytes: Some(1_200),
+        },
+    )
+    .await
+    .expect("limits are inclusive");
+
+    std::fs::remove_dir_all(&dir).expect("cleanup temp dir");
+}
+
+#[test]
+fn turn_usage_estimates_cost_from_pricing_table() {
+    let usage = TurnUsage::from_token_usage(
+        &json!({
+            "total": {"inputTokens": 9_000_000, "outputTokens": 9_000_000},
+            "last": {
+                "inputTokens": 1_000_000,
+                "cachedInputTokens": 400_000,
+                "outputTokens": 200_000,
+                "reasoningOutputTokens": 50_000
+            }
+        }),
+        Some("gpt-5"),
+    )
+    .expect("usage");
+    assert_eq!(usage.input_tokens, 1_000_000);
+    assert_eq!(usage.cached_input_tokens, 400_000);
+    assert_eq!(usage.reasoning_output_tokens, 50_000);
+
+    let cost = usage
+        .estimated_cost(&PricingTable::default())
+        .expect("gpt-5 is priced by default");
+    assert!((cost - (0.6 * 1.25 + 0.4 * 0.125 + 0.2 * 10.0)).abs() < 1e-9);
+
+    let custom = PricingTable::empty().with_model("gpt-5", ModelPricing::new(1.0, 1.0, 1.0));
+    assert!((usage.estimated_cost(&custom).expect("override") - 1.2).abs() < 1e-9);
+
+    let unknown = TurnUsage {
+        model: Some("in-house-model".to_owned()),
+        ..usage.clone()
+    };
+    assert_eq!(unknown.estimated_cost(&PricingTable::default()), None);
+    let server_default = TurnUsage {
+        model: None,
+        ..usage
+    };
+    assert_eq!(
+        server_default.estimated_cost(&PricingTable::default()),
+        None
+    );
+    assert_eq!(TurnUsage::from_token_usage(&json!({}), Some("gpt-5")), None);
+}
@@ -2,22 +2,33 @@ use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use crate::plugin::{HookAction, HookContext, HookIssue, HookIssueClass, HookPhase, PreHook};
-use crate::runtime::{RuntimeConfig, RuntimeHookConfig};
-use serde_json::{json, Value};
+use crate::plugin::{
+    AutoApproval, HookAction, HookContext, HookIssue, HookIssueClass, HookPhase, PostHook, PreHook,
+    ShutdownToken,
+};
+use crate::runtime::rpc_contract::methods;
+use crate::runtime::{
+    InMemoryPromptCache, PromptCache, PromptCacheKey, RuntimeConfig, RuntimeHookConfig,
+    SequentialIdGenerator,
+};
+use serde_json::{json, Map, Value};
 use tokio::time::sleep;
 
 use super::super::*;
 use super::support::{
-    python_api_mock_process, python_session_mutation_probe_process,
+    python_api_mock_process, python_run_prompt_mock_process, python_session_mutation_probe_process,
     spawn_run_prompt_cross_thread_noise_runtime, spawn_run_prompt_effort_probe_runtime,
     spawn_run_prompt_error_runtime, spawn_run_prompt_interrupt_probe_runtime,
     spawn_run_prompt_lagged_cancelled_runtime, spawn_run_prompt_lagged_completion_runtime,
     spawn_run_prompt_lagged_completion_slow_thread_read_runtime,
     spawn_run_prompt_mutation_probe_runtime, spawn_run_prompt_runtime,
-    spawn_run_prompt_runtime_with_hooks, spawn_run_prompt_streaming_timeout_runtime,
-    spawn_run_prompt_turn_failed_runtime, MetadataCapturePostHook, PhasePatchPreHook,
-    RecordingPostHook, RecordingPreHook,
+    spawn_run_prompt_runtime_with_hooks, spawn_run_prompt_runtime_with_id_generator,
+    spawn_run_prompt_runtime_with_prompt_cache, spawn_run_prompt_same_thread_interleaved_runtime,
+    spawn_run_prompt_streaming_timeout_runtime,
+    spawn_run_prompt_streaming_timeout_runtime_with_default,
+    spawn_run_prompt_streaming_timeout_runtime_with_max_turns,
+    spawn_run_prompt_turn_failed_runtime, AssistantTextCapturePostHook, MetadataCapturePostHook,
+    PhasePatchPreHook, RecordingPostHook, RecordingPreHook, ShutdownCapturePostHook,
 };
 
 #[derive(Clone)]
@@ -25,6 +36,85 @@ struct CaptureCwdPreHook {
     cwd_values: Arc<Mutex<Vec<Option<String>>>>,
 }
 
+#[derive(Clone)]
+struct CaptureCorrelationPreHook {
+    correlation_ids: Arc<Mutex<Vec<String>>>,
+}
+
+impl PreHook for CaptureCorrelationPreHook {
+    fn name(&self) -> &'static str {
+        "capture_correlation"
+    }
+
+    fn call<'a>(
+        &'a self,
+        ctx: &'a HookContext,
+    ) -> crate::plugin::HookFuture<'a, Result<HookAction, HookIssue>> {
+        Box::pin(async move {
+            self.correlation_ids
+                .lock()
+                .expect("correlation lock")
+                .push(ctx.correlation_id.clone());
+            Ok(HookAction::Noop)
+        })
+    }
+}
+
+#[derive(Clone)]
+struct RespondPreHook {
+    phase: HookPhase,
+    text: &'static str,
+}
+
+impl PreHook for RespondPreHook {
+    fn name(&self) -> &'static str {
+        "respond"
+    }
+
+    fn call<'a>(
+        &'a self,
+        ctx: &'a HookContext,
+    ) -> crate::plugin::HookFuture<'a, Result<HookAction, HookIssue>> {
+        Box::pin(async move {
+            if ctx.phase == self.phase {
+                Ok(HookAction::Respond {
+                    text: self.text.to_owned(),
+                })
+            } else {
+                Ok(HookAction::Noop)
+            }
+        })
+    }
+}
+
+/// (phase, main_status, assistant_text) per post hook call.
+type CapturedMainStatuses = Arc<Mutex<Vec<(HookPhase, Option<String>, Option<String>)>>>;
+
+#[derive(Clone)]
+struct MainStatusCapturePostHook {
+    statuses: CapturedMainStatuses,
+}
+
+impl PostHook for MainStatusCapturePostHook {
+    fn name(&self) -> &'static str {
+        "capture_main_status"
+    }
+
+    fn call<'a>(
+        &'a self,
+        ctx: &'a HookContext,
+    ) -> crate::plugin::HookFuture<'a, Result<(), HookIssue>> {
+        Box::pin(async move {
+            self.statuses.lock().expect("statuses lock").push((
+                ctx.phase,
+                ctx.main_status.clone(),
+                ctx.assistant_text.clone(),
+            ));
+            Ok(())
+        })
+    }
+}
+
 struct Lcg(u64);
 
 impl Lcg {
@@ -72,8 +162,18 @@ async fn run_prompt_returns_assistant_text() {
             sandbox_policy: SandboxPolicy::Preset(SandboxPreset::ReadOnly),
             privileged_escalation_approved: false,
             attachments: vec![],
-            timeout: Duration::from_secs(2),
+            timeout: Some(Duration::from_secs(2)),
             output_schema: None,
+            drop_effort_on_reject: false,
+            metadata: Value::Null,
+            steer_from: None,
+            extra_turn_params: Map::new(),
+            resend_model_on_resume: false,
+            inline_local_images: false,
+            assistant_item_separator: "\n".to_owned(),
+            fail_on_command_error: false,
+            auto_approve_all: false,
+            max_reasoning_tokens: None,
         })
         .await
         .expect("run prompt");
@@ -140,6 +240,20 @@ async fn run_prompt_in_thread_reuses_existing_thread_id() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_rejects_empty_prompt_before_any_rpc() {
+    let runtime = spawn_run_prompt_runtime().await;
+
+    let err = runtime
+        .run_prompt(PromptRunParams::new("/tmp", " "))
+        .await
+        .expect_err("empty prompt");
+    assert!(matches!(err, PromptRunError::EmptyPrompt), "{err:?}");
+    assert!(runtime.state_snapshot().threads.is_empty());
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn run_prompt_hook_order_is_pre_then_post() {
     let events = Arc::new(Mutex::new(Vec::<String>::new()));
@@ -181,6 +295,117 @@ async fn run_prompt_hook_order_is_pre_then_post() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_skips_pre_hooks_outside_their_phases() {
+    let scoped_events = Arc::new(Mutex::new(Vec::<String>::new()));
+    let all_events = Arc::new(Mutex::new(Vec::<String>::new()));
+    let hooks = RuntimeHookConfig::new()
+        .with_pre_hook_for_phases(
+            Arc::new(RecordingPreHook {
+                name: "pre_turn_only",
+                events: scoped_events.clone(),
+                fail_phase: None,
+            }),
+            &[HookPhase::PreTurn],
+        )
+        .with_pre_hook(Arc::new(RecordingPreHook {
+            name: "pre_all",
+            events: all_events.clone(),
+            fail_phase: None,
+        }));
+    let runtime = spawn_run_prompt_runtime_with_hooks(hooks).await;
+
+    runtime
+        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("run prompt");
+
+    assert_eq!(
+        scoped_events.lock().expect("events lock").as_slice(),
+        &["pre:PreTurn".to_owned()]
+    );
+    assert_eq!(
+        all_events.lock().expect("events lock").as_slice(),
+        &["pre:PreRun".to_owned(), "pre:PreTurn".to_owned()]
+    );
+
+    let run_scoped_events = Arc::new(Mutex::new(Vec::<String>::new()));
+    let run_hooks = RuntimeHookConfig::new().with_pre_hook_for_phases(
+        Arc::new(RecordingPreHook {
+            name: "run_pre_run_only",
+            events: run_scoped_events.clone(),
+            fail_phase: None,
+        }),
+        &[HookPhase::PreRun],
+    );
+    runtime
+        .run_prompt_with_hooks(PromptRunParams::new("/tmp", "say ok"), Some(&run_hooks))
+        .await
+        .expect("run prompt with scoped hooks");
+    assert_eq!(
+        run_scoped_events.lock().expect("events lock").as_slice(),
+        &["pre:PreRun".to_owned()]
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_hook_correlation_ids_replay_with_sequential_generator() {
+    async fn captured_ids() -> Vec<String> {
+        let correlation_ids = Arc::new(Mutex::new(Vec::<String>::new()));
+        let hooks = RuntimeHookConfig::new().with_pre_hook(Arc::new(CaptureCorrelationPreHook {
+            correlation_ids: correlation_ids.clone(),
+        }));
+        let runtime = spawn_run_prompt_runtime_with_id_generator(
+            hooks,
+            Arc::new(SequentialIdGenerator::new()),
+        )
+        .await;
+        for _ in 0..2 {
+            runtime
+                .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+                .await
+                .expect("run prompt");
+        }
+        runtime.shutdown().await.expect("shutdown");
+        let ids = correlation_ids.lock().expect("correlation lock").clone();
+        ids
+    }
+
+    let first = captured_ids().await;
+    assert_eq!(first, ["id-1", "id-1", "id-2", "id-2"]);
+    assert_eq!(
+        captured_ids().await,
+        first,
+        "fresh generator must replay ids"
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_post_hooks_observe_shutdown_token_cancellation() {
+    let tokens = Arc::new(Mutex::new(Vec::new()));
+    let hooks = RuntimeHookConfig::new().with_post_hook(Arc::new(ShutdownCapturePostHook {
+        name: "shutdown_capture",
+        tokens: Arc::clone(&tokens),
+    }));
+    let runtime = spawn_run_prompt_runtime_with_hooks(hooks).await;
+
+    runtime
+        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("run prompt");
+    let captured = tokens.lock().expect("tokens lock").clone();
+    assert_eq!(captured.len(), 2, "PostTurn and PostRun must both fire");
+    assert!(captured.iter().all(|token| !token.is_cancelled()));
+
+    runtime.shutdown().await.expect("shutdown");
+    assert!(captured.iter().all(ShutdownToken::is_cancelled));
+    tokio::time::timeout(Duration::from_millis(100), captured[0].cancelled())
+        .await
+        .expect("cancelled() resolves after shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn run_prompt_pre_hooks_receive_working_directory_not_prompt_text() {
     let cwd_values = Arc::new(Mutex::new(Vec::<Option<String>>::new()));
@@ -246,6 +471,155 @@ async fn run_prompt_hook_failure_is_fail_open_with_report() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_hook_issues_are_published_on_live_stream() {
+    let hooks = RuntimeHookConfig::new().with_pre_hook(Arc::new(RecordingPreHook {
+        name: "pre_fail",
+        events: Arc::new(Mutex::new(Vec::new())),
+        fail_phase: Some(HookPhase::PreRun),
+    }));
+    let runtime = spawn_run_prompt_runtime_with_hooks(hooks).await;
+    let mut live_rx = runtime.subscribe_live();
+
+    runtime
+        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("run prompt");
+
+    let mut reports = Vec::new();
+    while let Ok(envelope) = live_rx.try_recv() {
+        if envelope.method.as_deref() == Some(methods::RUNTIME_HOOKS_REPORT) {
+            reports.push(envelope);
+        }
+    }
+    assert_eq!(reports.len(), 1);
+    let params = &reports[0].json["params"];
+    assert!(params["correlationId"]
+        .as_str()
+        .is_some_and(|id| !id.is_empty()));
+    assert_eq!(params["issues"][0]["hook_name"], "pre_fail");
+    assert_eq!(
+        params["issues"].as_array().map(Vec::len),
+        Some(runtime.hook_report_snapshot().issues.len())
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_fires_session_hooks_on_implicit_start_when_enabled() {
+    let events = Arc::new(Mutex::new(Vec::<String>::new()));
+    let hooks = RuntimeHookConfig::new()
+        .with_pre_hook(Arc::new(RecordingPreHook {
+            name: "pre_session_fail",
+            events: events.clone(),
+            fail_phase: Some(HookPhase::PreSessionStart),
+        }))
+        .with_post_hook(Arc::new(RecordingPostHook {
+            name: "post_all",
+            events: events.clone(),
+            fail_phase: None,
+        }))
+        .with_session_hooks_on_implicit_start(true);
+    let runtime = spawn_run_prompt_runtime_with_hooks(hooks).await;
+
+    let result = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("run prompt");
+
+    assert_eq!(result.assistant_text, "ok-from-run-prompt");
+    assert_eq!(
+        events.lock().expect("events lock").as_slice(),
+        &[
+            "pre:PreRun".to_owned(),
+            "pre:PreSessionStart".to_owned(),
+            "post:PostSessionStart".to_owned(),
+            "pre:PreTurn".to_owned(),
+            "post:PostTurn".to_owned(),
+            "post:PostRun".to_owned(),
+        ]
+    );
+    let report = runtime.hook_report_snapshot();
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].hook_name, "pre_session_fail");
+    assert_eq!(report.issues[0].phase, HookPhase::PreSessionStart);
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_pre_run_respond_skips_thread_and_turn() {
+    let events = Arc::new(Mutex::new(Vec::<String>::new()));
+    let statuses = Arc::new(Mutex::new(Vec::new()));
+    let hooks = RuntimeHookConfig::new()
+        .with_pre_hook(Arc::new(RespondPreHook {
+            phase: HookPhase::PreRun,
+            text: "cached answer",
+        }))
+        .with_pre_hook(Arc::new(RecordingPreHook {
+            name: "pre_after_respond",
+            events: events.clone(),
+            fail_phase: None,
+        }))
+        .with_post_hook(Arc::new(MainStatusCapturePostHook {
+            statuses: statuses.clone(),
+        }));
+    let runtime = spawn_run_prompt_runtime_with_hooks(hooks).await;
+
+    let result = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("run prompt");
+
+    assert_eq!(result.assistant_text, "cached answer");
+    assert!(result.turn_id.starts_with("short_circuit_"));
+    assert_eq!(result.thread_id, result.turn_id);
+    assert!(events.lock().expect("events lock").is_empty());
+    assert_eq!(
+        statuses.lock().expect("statuses lock").as_slice(),
+        &[(HookPhase::PostRun, Some("short_circuited".to_owned()), None)]
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_pre_turn_respond_keeps_thread_and_skips_turn_start() {
+    let statuses = Arc::new(Mutex::new(Vec::new()));
+    let hooks = RuntimeHookConfig::new()
+        .with_pre_hook(Arc::new(RespondPreHook {
+            phase: HookPhase::PreTurn,
+            text: "local answer",
+        }))
+        .with_post_hook(Arc::new(MainStatusCapturePostHook {
+            statuses: statuses.clone(),
+        }));
+    let runtime = spawn_run_prompt_runtime_with_hooks(hooks).await;
+
+    let result = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("run prompt");
+
+    assert_eq!(result.assistant_text, "local answer");
+    assert_eq!(result.thread_id, "thr_prompt");
+    assert!(result.turn_id.starts_with("short_circuit_"));
+    assert_eq!(
+        statuses.lock().expect("statuses lock").as_slice(),
+        &[
+            (
+                HookPhase::PostTurn,
+                Some("short_circuited".to_owned()),
+                Some("local answer".to_owned())
+            ),
+            (HookPhase::PostRun, Some("short_circuited".to_owned()), None),
+        ]
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn thread_start_and_resume_emit_session_hook_phases() {
     let events = Arc::new(Mutex::new(Vec::<String>::new()));
@@ -336,147 +710,975 @@ async fn run_prompt_applies_pre_mutations_for_prompt_model_attachment_and_metada
         }));
     let runtime = spawn_run_prompt_mutation_probe_runtime(hooks).await;
 
-    let result = runtime
-        .run_prompt(PromptRunParams::new("/tmp", "original prompt"))
+    let result = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "original prompt"))
+        .await
+        .expect("run prompt");
+    let payload: Value =
+        serde_json::from_str(&result.assistant_text).expect("decode probe payload");
+    assert_eq!(payload["threadModel"], json!("model-pre-run"));
+    assert_eq!(payload["turnModel"], json!("model-pre-turn"));
+    assert_eq!(payload["text"], json!("patched-in-pre-turn"));
+    assert_eq!(payload["itemTypes"], json!(["text", "image", "skill"]),);
+
+    let post_turn_metadata = {
+        let captured = metadata_events.lock().expect("metadata lock");
+        captured
+            .iter()
+            .find(|(phase, _)| *phase == HookPhase::PostTurn)
+            .map(|(_, metadata)| metadata.clone())
+            .expect("post-turn metadata")
+    };
+    assert_eq!(post_turn_metadata["from_pre_run"], json!(true));
+    assert_eq!(post_turn_metadata["from_pre_turn"], json!(1));
+
+    assert!(
+        runtime.hook_report_snapshot().is_clean(),
+        "valid mutations should not produce issues"
+    );
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_in_thread_omits_model_on_resume_unless_requested() {
+    let runtime = spawn_run_prompt_mutation_probe_runtime(RuntimeHookConfig::new()).await;
+
+    let result = runtime
+        .run_prompt_in_thread(
+            "thr_existing",
+            PromptRunParams::new("/tmp", "continue").with_model("model-session"),
+        )
+        .await
+        .expect("run prompt in thread");
+    let payload: Value =
+        serde_json::from_str(&result.assistant_text).expect("decode probe payload");
+    assert_eq!(payload["resumeOverrides"]["cwd"], json!("/tmp"));
+    assert!(
+        payload["resumeOverrides"].get("model").is_none(),
+        "resume must not re-send model by default: {payload}"
+    );
+    assert_eq!(payload["turnModel"], json!("model-session"));
+
+    let result = runtime
+        .run_prompt_in_thread(
+            "thr_existing",
+            PromptRunParams::new("/tmp", "continue")
+                .with_model("model-switch")
+                .resend_model_on_resume(),
+        )
+        .await
+        .expect("run prompt in thread with model resend");
+    let payload: Value =
+        serde_json::from_str(&result.assistant_text).expect("decode probe payload");
+    assert_eq!(payload["resumeOverrides"]["model"], json!("model-switch"));
+    assert_eq!(payload["turnModel"], json!("model-switch"));
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_metadata_seeds_hook_context_from_first_phase() {
+    let metadata_events = Arc::new(Mutex::new(Vec::<(HookPhase, Value)>::new()));
+    let hooks = RuntimeHookConfig::new()
+        .with_pre_hook(Arc::new(PhasePatchPreHook {
+            name: "metadata_patch",
+            patches: vec![(
+                HookPhase::PreTurn,
+                crate::plugin::HookPatch {
+                    metadata_delta: json!({"from_pre_turn": 1}),
+                    ..crate::plugin::HookPatch::default()
+                },
+            )],
+        }))
+        .with_post_hook(Arc::new(MetadataCapturePostHook {
+            name: "metadata_capture",
+            metadata: metadata_events.clone(),
+        }));
+    let runtime = spawn_run_prompt_runtime_with_hooks(hooks).await;
+
+    runtime
+        .run_prompt(
+            PromptRunParams::new("/tmp", "say ok")
+                .with_metadata(json!({"tenant": "acme", "feature": "triage"})),
+        )
+        .await
+        .expect("run prompt");
+
+    let captured = metadata_events.lock().expect("metadata lock").clone();
+    assert_eq!(
+        captured,
+        vec![
+            (
+                HookPhase::PostTurn,
+                json!({"tenant": "acme", "feature": "triage", "from_pre_turn": 1})
+            ),
+            (
+                HookPhase::PostRun,
+                json!({"tenant": "acme", "feature": "triage", "from_pre_turn": 1})
+            ),
+        ]
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[derive(Debug, Default)]
+struct EnvelopeRecordingSink {
+    envelopes: Mutex<Vec<crate::runtime::events::Envelope>>,
+}
+
+impl crate::runtime::sink::EventSink for EnvelopeRecordingSink {
+    fn on_envelope<'a>(
+        &'a self,
+        envelope: &'a crate::runtime::events::Envelope,
+    ) -> crate::runtime::sink::EventSinkFuture<'a> {
+        Box::pin(async move {
+            self.envelopes
+                .lock()
+                .expect("envelope lock")
+                .push(envelope.clone());
+            Ok(())
+        })
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_metadata_reaches_event_sink_for_its_turn() {
+    let sink = Arc::new(EnvelopeRecordingSink::default());
+    let mut cfg = RuntimeConfig::new(python_run_prompt_mock_process());
+    cfg.event_sink = Some(sink.clone());
+    let runtime = Runtime::spawn_local(cfg).await.expect("spawn runtime");
+
+    let result = runtime
+        .run_prompt(
+            PromptRunParams::new("/tmp", "say ok")
+                .with_metadata(json!({"tenant": "acme", "feature": "triage"})),
+        )
+        .await
+        .expect("run prompt");
+    runtime.shutdown().await.expect("shutdown");
+
+    let envelopes = sink.envelopes.lock().expect("envelope lock").clone();
+    let records: Vec<_> = envelopes
+        .iter()
+        .filter(|envelope| envelope.method.as_deref() == Some(methods::RUNTIME_RUN_METADATA))
+        .collect();
+    assert_eq!(records.len(), 1, "one metadata record per run turn");
+    let record = records[0];
+    assert_eq!(record.thread_id.as_deref(), Some(result.thread_id.as_str()));
+    assert_eq!(record.turn_id.as_deref(), Some(result.turn_id.as_str()));
+    assert_eq!(
+        record.json["params"],
+        json!({
+            "threadId": result.thread_id,
+            "turnId": result.turn_id,
+            "metadata": {"tenant": "acme", "feature": "triage"},
+        })
+    );
+    assert!(
+        envelopes.windows(2).all(|pair| pair[0].seq < pair[1].seq),
+        "metadata record keeps the sink in seq order"
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_clean_hook_report_reaches_sink_and_lossless_in_seq_order() {
+    let sink = Arc::new(EnvelopeRecordingSink::default());
+    let mut cfg = RuntimeConfig::new(python_run_prompt_mock_process());
+    cfg.event_sink = Some(sink.clone());
+    cfg.hooks = RuntimeHookConfig::new().with_post_hook(Arc::new(RecordingPostHook {
+        name: "post_clean",
+        events: Arc::new(Mutex::new(Vec::new())),
+        fail_phase: None,
+    }));
+    let runtime = Runtime::spawn_local(cfg).await.expect("spawn runtime");
+    let mut lossless_rx = runtime.subscribe_live_lossless();
+
+    runtime
+        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("run prompt");
+    assert!(runtime.hook_report_snapshot().is_clean());
+    runtime.shutdown().await.expect("shutdown");
+
+    let mut lossless = Vec::new();
+    while let Ok(envelope) = lossless_rx.try_recv() {
+        lossless.push(envelope);
+    }
+    let sunk = sink.envelopes.lock().expect("envelope lock").clone();
+    for envelopes in [&lossless, &sunk] {
+        let reports: Vec<_> = envelopes
+            .iter()
+            .filter(|envelope| envelope.method.as_deref() == Some(methods::RUNTIME_HOOKS_REPORT))
+            .collect();
+        assert_eq!(reports.len(), 1, "clean runs still publish one report");
+        assert_eq!(reports[0].json["params"]["issues"], json!([]));
+        assert!(envelopes.windows(2).all(|pair| pair[0].seq < pair[1].seq));
+    }
+    let lossless_seqs: Vec<_> = lossless.iter().map(|envelope| envelope.seq).collect();
+    let sunk_seqs: Vec<_> = sunk
+        .iter()
+        .map(|envelope| envelope.seq)
+        .filter(|seq| lossless_seqs.first().is_some_and(|first| seq >= first))
+        .collect();
+    assert_eq!(
+        lossless_seqs, sunk_seqs,
+        "no channel skips the report's seq"
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_ignores_invalid_hook_attachment_with_fail_open() {
+    let patches = vec![(
+        HookPhase::PreTurn,
+        crate::plugin::HookPatch {
+            prompt_override: None,
+            model_override: None,
+            add_attachments: vec![crate::plugin::HookAttachment::LocalImage {
+                path: "definitely_missing_image_for_hook_test.png".to_owned(),
+            }],
+            metadata_delta: Value::Null,
+        },
+    )];
+    let hooks = RuntimeHookConfig::new().with_pre_hook(Arc::new(PhasePatchPreHook {
+        name: "bad_attachment_patch",
+        patches,
+    }));
+    let runtime = spawn_run_prompt_mutation_probe_runtime(hooks).await;
+
+    let result = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "prompt"))
+        .await
+        .expect("main run should continue");
+    let payload: Value =
+        serde_json::from_str(&result.assistant_text).expect("decode probe payload");
+    assert_eq!(payload["itemTypes"], json!(["text"]));
+    let report = runtime.hook_report_snapshot();
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].hook_name, "bad_attachment_patch");
+    assert_eq!(report.issues[0].class, HookIssueClass::Validation);
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn pre_session_mutation_restricts_prompt_and_attachments_but_allows_model_and_metadata() {
+    let patches = vec![(
+        HookPhase::PreSessionStart,
+        crate::plugin::HookPatch {
+            prompt_override: Some("not-allowed".to_owned()),
+            model_override: Some("model-from-session-hook".to_owned()),
+            add_attachments: vec![crate::plugin::HookAttachment::ImageUrl {
+                url: "https://example.com/ignored.png".to_owned(),
+            }],
+            metadata_delta: json!({"session_key": "session_value"}),
+        },
+    )];
+    let metadata_events = Arc::new(Mutex::new(Vec::<(HookPhase, Value)>::new()));
+    let hooks = RuntimeHookConfig::new()
+        .with_pre_hook(Arc::new(PhasePatchPreHook {
+            name: "session_patch",
+            patches,
+        }))
+        .with_post_hook(Arc::new(MetadataCapturePostHook {
+            name: "session_metadata_capture",
+            metadata: metadata_events.clone(),
+        }));
+    let cfg = RuntimeConfig::new(python_session_mutation_probe_process()).with_hooks(hooks);
+    let runtime = Runtime::spawn_local(cfg).await.expect("spawn runtime");
+
+    let thread = runtime
+        .thread_start(ThreadStartParams::default())
+        .await
+        .expect("thread start");
+    assert_eq!(thread.thread_id, "thr_model-from-session-hook");
+
+    let report = runtime.hook_report_snapshot();
+    assert_eq!(report.issues.len(), 2);
+    assert!(report
+        .issues
+        .iter()
+        .all(|issue| issue.class == HookIssueClass::Validation));
+    assert_eq!(report.issues[0].phase, HookPhase::PreSessionStart);
+    assert_eq!(report.issues[1].phase, HookPhase::PreSessionStart);
+
+    let post_session_metadata = {
+        let captured = metadata_events.lock().expect("metadata lock");
+        captured
+            .iter()
+            .find(|(phase, _)| *phase == HookPhase::PostSessionStart)
+            .map(|(_, metadata)| metadata.clone())
+            .expect("post-session metadata")
+    };
+    assert_eq!(post_session_metadata["session_key"], json!("session_value"));
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_ignores_cross_thread_events_for_same_turn_id() {
+    let runtime = spawn_run_prompt_cross_thread_noise_runtime().await;
+    let result = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("run prompt");
+
+    assert_eq!(result.thread_id, "thr_prompt");
+    assert_eq!(result.turn_id, "turn_prompt");
+    assert_eq!(result.assistant_text, "ok-from-run-prompt");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_in_thread_steer_from_anchors_follow_up_turn() {
+    let runtime = spawn_run_prompt_runtime().await;
+
+    let result = runtime
+        .run_prompt_in_thread(
+            "thr_prompt",
+            PromptRunParams::new("/tmp", "follow up").with_steer_from("turn_prompt"),
+        )
+        .await
+        .expect("steered run prompt");
+    assert_eq!(result.turn_id, "turn_steered");
+    assert_eq!(result.assistant_text, "ok-from-run-prompt");
+
+    let outcome = runtime
+        .loaded_thread_handle("thr_prompt")
+        .turn_steer_outcome(
+            "turn_prompt",
+            vec![InputItem::Text {
+                text: "again".to_owned(),
+            }],
+        )
+        .await
+        .expect("turn steer outcome");
+    assert_eq!(outcome.turn_id, "turn_steered");
+    assert!(outcome.was_new_turn);
+
+    let err = runtime
+        .run_prompt_in_thread(
+            "thr_prompt",
+            PromptRunParams::new("/tmp", "follow up").with_steer_from("turn_old"),
+        )
+        .await
+        .expect_err("stale steer must fail");
+    match err {
+        PromptRunError::StaleSteerTurn {
+            expected_turn_id,
+            message,
+        } => {
+            assert_eq!(expected_turn_id, "turn_old");
+            assert!(message.contains("does not match"), "{message}");
+        }
+        other => panic!("expected stale steer error, got {other:?}"),
+    }
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn thread_fork_and_prompt_runs_on_the_fork() {
+    let runtime = spawn_run_prompt_runtime().await;
+
+    let (fork_id, result) = runtime
+        .thread_fork_and_prompt("thr_prompt", PromptRunParams::new("/tmp", "variant"))
+        .await
+        .expect("fork and prompt");
+    assert_eq!(fork_id, "thr_fork_of_thr_prompt");
+    assert_eq!(result.thread_id, fork_id);
+    assert_eq!(result.assistant_text, "ok-from-run-prompt");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_on_fork_returns_result_and_fork_handle() {
+    let runtime = spawn_run_prompt_runtime().await;
+
+    let (result, fork) = runtime
+        .run_prompt_on_fork("thr_prompt", PromptRunParams::new("/tmp", "variant"))
+        .await
+        .expect("run on fork");
+    assert_eq!(fork.thread_id, "thr_fork_of_thr_prompt");
+    assert_eq!(result.thread_id, fork.thread_id);
+    assert_eq!(result.assistant_text, "ok-from-run-prompt");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_batch_returns_results_in_input_order() {
+    let runtime = spawn_run_prompt_runtime().await;
+    let cancel = ShutdownToken::new();
+
+    let results = runtime
+        .run_prompt_batch(
+            vec![
+                PromptRunParams::new("/tmp", "one"),
+                PromptRunParams::new("/tmp", "two"),
+                PromptRunParams::new("/tmp", "three"),
+            ],
+            2,
+            &cancel,
+        )
+        .await;
+    assert_eq!(results.len(), 3);
+    for result in results {
+        assert_eq!(
+            result.expect("batch item").assistant_text,
+            "ok-from-run-prompt"
+        );
+    }
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_batch_cancel_interrupts_in_flight_and_short_circuits_pending() {
+    let runtime = spawn_run_prompt_interrupt_probe_runtime().await;
+    let mut live_rx = runtime.subscribe_live();
+    let cancel = ShutdownToken::new();
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        sleep(Duration::from_millis(200)).await;
+        trigger.cancel();
+    });
+
+    let started = Instant::now();
+    let results = runtime
+        .run_prompt_batch(
+            (0..4)
+                .map(|_| {
+                    PromptRunParams::new("/tmp", "never finishes")
+                        .with_timeout(Duration::from_secs(30))
+                })
+                .collect(),
+            2,
+            &cancel,
+        )
+        .await;
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(results.len(), 4);
+    assert!(results
+        .iter()
+        .all(|result| matches!(result, Err(PromptRunError::Cancelled))));
+
+    let seen = tokio::time::timeout(Duration::from_secs(2), async {
+        loop {
+            let envelope = live_rx.recv().await.expect("live closed");
+            if envelope.method.as_deref() == Some("probe/interruptSeen") {
+                return envelope;
+            }
+        }
+    })
+    .await
+    .expect("cancel must interrupt an in-flight turn");
+    assert_eq!(seen.turn_id.as_deref(), Some("turn_interrupt_probe"));
+    assert_eq!(seen.json["params"]["reason"], "cancelled");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn thread_pool_run_prompt_uses_prewarmed_thread() {
+    let runtime = spawn_run_prompt_runtime().await;
+    let pool = ThreadPool::new(&runtime, ThreadStartParams::default(), 1);
+    pool.fill().await.expect("fill pool");
+    assert_eq!(pool.ready_count(), 1);
+
+    let result = pool
+        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("pooled run prompt");
+    assert_eq!(result.thread_id, "thr_prompt");
+    assert_eq!(result.assistant_text, "ok-from-run-prompt");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn thread_pool_run_prompt_rejects_settings_conflicting_with_the_pool() {
+    let runtime = spawn_run_prompt_runtime().await;
+    let pinned = ThreadStartParams {
+        model: Some("gpt-pool".to_owned()),
+        cwd: Some("/tmp".to_owned()),
+        ..ThreadStartParams::default()
+    };
+    let pool = ThreadPool::new(&runtime, pinned, 1);
+    pool.fill().await.expect("fill pool");
+
+    let err = pool
+        .run_prompt(PromptRunParams::new("/tmp", "say ok").with_model("gpt-other"))
+        .await
+        .expect_err("conflicting model must be rejected");
+    assert!(
+        matches!(&err, PromptRunError::Rpc(RpcError::InvalidRequest(message)) if message.contains("model")),
+        "unexpected error: {err:?}"
+    );
+    let err = pool
+        .run_prompt(PromptRunParams::new("/var", "say ok"))
+        .await
+        .expect_err("conflicting cwd must be rejected");
+    assert!(
+        matches!(&err, PromptRunError::Rpc(RpcError::InvalidRequest(message)) if message.contains("cwd")),
+        "unexpected error: {err:?}"
+    );
+    assert_eq!(
+        pool.ready_count(),
+        1,
+        "rejected runs must not take a thread"
+    );
+
+    pool.run_prompt(PromptRunParams::new("/tmp", "say ok").with_model("gpt-pool"))
+        .await
+        .expect("matching settings run");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn thread_pool_forwards_its_hooks_to_thread_start_and_run_prompt() {
+    let runtime = spawn_run_prompt_runtime().await;
+    let events = Arc::new(Mutex::new(Vec::<String>::new()));
+    let hooks = RuntimeHookConfig::new().with_pre_hook(Arc::new(RecordingPreHook {
+        name: "pool_pre",
+        events: events.clone(),
+        fail_phase: None,
+    }));
+    let pool = ThreadPool::new(&runtime, ThreadStartParams::default(), 0).with_hooks(hooks);
+
+    pool.run_prompt(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("pooled run prompt");
+
+    let seen = events.lock().expect("events lock").clone();
+    assert_eq!(
+        seen,
+        vec![
+            "pre:PreSessionStart".to_owned(),
+            "pre:PreRun".to_owned(),
+            "pre:PreTurn".to_owned(),
+        ]
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_fail_on_command_error_fails_completed_turn() {
+    let runtime = spawn_run_prompt_runtime().await;
+
+    let lenient = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "run failing command"))
+        .await
+        .expect("failed command is ignored by default");
+    assert_eq!(lenient.assistant_text, "ok-from-run-prompt");
+
+    let err = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "run failing command").fail_on_command_error(true))
+        .await
+        .expect_err("failed command must fail the run");
+    assert_eq!(
+        err,
+        PromptRunError::CommandFailed {
+            command: "cargo test".to_owned(),
+            exit_code: Some(101),
+        }
+    );
+
+    let passing = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "say ok").fail_on_command_error(true))
+        .await
+        .expect("turn without failed commands succeeds");
+    assert_eq!(passing.assistant_text, "ok-from-run-prompt");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_max_reasoning_tokens_fails_run_over_budget() {
+    let runtime = spawn_run_prompt_runtime().await;
+
+    let uncapped = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "think at length"))
+        .await
+        .expect("reasoning is unbounded by default");
+    assert_eq!(uncapped.assistant_text, "ok-from-run-prompt");
+
+    let within = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "think at length").with_max_reasoning_tokens(900))
+        .await
+        .expect("usage at the cap is allowed");
+    assert_eq!(within.assistant_text, "ok-from-run-prompt");
+
+    let err = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "think at length").with_max_reasoning_tokens(500))
+        .await
+        .expect_err("usage over the cap must fail the run");
+    assert_eq!(
+        err,
+        PromptRunError::ReasoningBudgetExceeded {
+            limit: 500,
+            used: 900,
+        }
+    );
+
+    let budgeted = PromptRunParams::new("/tmp", "think at length").with_max_reasoning_tokens(500);
+    assert_eq!(PromptCacheKey::for_params(&budgeted), None);
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_max_reasoning_tokens_sums_steps_and_checks_trailing_usage() {
+    let runtime = spawn_run_prompt_runtime().await;
+
+    let err = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "think in steps").with_max_reasoning_tokens(500))
+        .await
+        .expect_err("per-step usage must add up toward the cap");
+    assert_eq!(
+        err,
+        PromptRunError::ReasoningBudgetExceeded {
+            limit: 500,
+            used: 600,
+        }
+    );
+
+    let err = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "think late").with_max_reasoning_tokens(500))
+        .await
+        .expect_err("usage reported after completion must be enforced");
+    assert_eq!(
+        err,
+        PromptRunError::ReasoningBudgetExceeded {
+            limit: 500,
+            used: 900,
+        }
+    );
+
+    let within = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "think late").with_max_reasoning_tokens(900))
+        .await
+        .expect("trailing usage at the cap is allowed");
+    assert_eq!(within.assistant_text, "ok-from-run-prompt");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_stream_enforces_max_reasoning_tokens() {
+    let runtime = spawn_run_prompt_runtime().await;
+    let thread = runtime
+        .thread_start(ThreadStartParams::default())
+        .await
+        .expect("thread start");
+    let stream_with = |prompt: &'static str, limit: u64| {
+        let runtime = runtime.clone();
+        let thread_id = thread.thread_id.clone();
+        async move {
+            runtime
+                .run_prompt_on_loaded_thread_stream_with_hooks(
+                    &thread_id,
+                    PromptRunParams::new("/tmp", prompt).with_max_reasoning_tokens(limit),
+                    None,
+                )
+                .await
+                .expect("start stream")
+                .finish()
+                .await
+        }
+    };
+
+    for (prompt, why) in [
+        ("think at length", "usage during the stream"),
+        ("think late", "usage trailing completion"),
+    ] {
+        let err = stream_with(prompt, 500).await.expect_err(why);
+        assert_eq!(
+            err,
+            PromptRunError::ReasoningBudgetExceeded {
+                limit: 500,
+                used: 900,
+            },
+            "{why}"
+        );
+    }
+
+    let within = stream_with("think at length", 900)
+        .await
+        .expect("usage at the cap is allowed");
+    assert_eq!(within.assistant_text, "ok-from-run-prompt");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_auto_approve_all_accepts_its_turn_and_reports_each_approval() {
+    let runtime = spawn_run_prompt_runtime().await;
+
+    let result = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "needs approval").auto_approve_all(true))
+        .await
+        .expect("auto-approved run");
+    assert_eq!(result.assistant_text, "approval:accept");
+    assert_eq!(runtime.metrics_snapshot().approvals_auto_approved, 1);
+    assert_eq!(
+        runtime.hook_report_snapshot().auto_approvals,
+        vec![AutoApproval {
+            thread_id: "thr_prompt".to_owned(),
+            turn_id: "turn_prompt".to_owned(),
+            method: methods::ITEM_COMMAND_EXECUTION_REQUEST_APPROVAL.to_owned(),
+        }]
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_full_collects_reasoning_commands_file_changes_and_usage() {
+    let runtime = spawn_run_prompt_runtime().await;
+
+    let full = runtime
+        .run_prompt_full(PromptRunParams::new("/tmp", "show the full picture"))
+        .await
+        .expect("run prompt full");
+
+    assert_eq!(full.run.thread_id, "thr_prompt");
+    assert_eq!(full.run.turn_id, "turn_prompt");
+    assert_eq!(full.run.assistant_text, "ok-from-run-prompt");
+    assert_eq!(full.reasoning_text, "inspect the repo");
+    assert_eq!(full.commands.len(), 1);
+    assert_eq!(full.commands[0].command, "ls");
+    assert_eq!(full.commands[0].exit_code, Some(0));
+    assert_eq!(full.file_changes.len(), 1);
+    assert_eq!(full.file_changes[0].id, "item_patch");
+    assert_eq!(
+        full.usage,
+        Some(json!({"total": {"inputTokens": 12, "outputTokens": 3}}))
+    );
+    assert_eq!(
+        full.turn_usage,
+        Some(TurnUsage {
+            model: None,
+            input_tokens: 12,
+            output_tokens: 3,
+            ..TurnUsage::default()
+        })
+    );
+
+    let plain = runtime
+        .run_prompt_full(PromptRunParams::new("/tmp", "say ok"))
+        .await
+        .expect("run prompt full without extras");
+    assert_eq!(plain.reasoning_text, "");
+    assert!(plain.commands.is_empty());
+    assert!(plain.file_changes.is_empty());
+    assert_eq!(plain.usage, None);
+    assert_eq!(plain.turn_usage, None);
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_collects_events_on_either_side_of_turn_start_result() {
+    let runtime = spawn_run_prompt_runtime().await;
+
+    // Default mock branch writes turn/started, deltas, and turn/completed before the RPC result.
+    let events_first = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
         .await
-        .expect("run prompt");
-    let payload: Value =
-        serde_json::from_str(&result.assistant_text).expect("decode probe payload");
-    assert_eq!(payload["threadModel"], json!("model-pre-run"));
-    assert_eq!(payload["turnModel"], json!("model-pre-turn"));
-    assert_eq!(payload["text"], json!("patched-in-pre-turn"));
-    assert_eq!(payload["itemTypes"], json!(["text", "image", "skill"]),);
+        .expect("events before turn/start result");
+    assert_eq!(events_first.assistant_text, "ok-from-run-prompt");
 
-    let post_turn_metadata = {
-        let captured = metadata_events.lock().expect("metadata lock");
-        captured
-            .iter()
-            .find(|(phase, _)| *phase == HookPhase::PostTurn)
-            .map(|(_, metadata)| metadata.clone())
-            .expect("post-turn metadata")
+    let result_first = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "respond before events"))
+        .await
+        .expect("turn/start result before events");
+    assert_eq!(result_first.assistant_text, "ok-from-run-prompt");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_empty_turn_reports_diagnostics() {
+    let runtime = spawn_run_prompt_runtime().await;
+    let expected = TurnDiagnostics {
+        items_seen: 0,
+        methods_seen: vec!["turn/started".to_owned(), "turn/completed".to_owned()],
+        deltas_seen: 0,
+        recovered_from_lag: false,
+        failed_command: None,
     };
-    assert_eq!(post_turn_metadata["from_pre_run"], json!(true));
-    assert_eq!(post_turn_metadata["from_pre_turn"], json!(1));
 
-    assert!(
-        runtime.hook_report_snapshot().is_clean(),
-        "valid mutations should not produce issues"
+    let err = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "emit no items"))
+        .await
+        .expect_err("empty turn must fail");
+    assert_eq!(err, PromptRunError::EmptyAssistantText(expected.clone()));
+    assert_eq!(
+        err.to_string(),
+        "assistant text is empty: items=0 deltas=0 methods=[turn/started, turn/completed]"
     );
+
+    let thread = runtime
+        .thread_start(ThreadStartParams::default())
+        .await
+        .expect("thread start");
+    let stream_err = runtime
+        .run_prompt_on_loaded_thread_stream_with_hooks(
+            &thread.thread_id,
+            PromptRunParams::new("/tmp", "emit no items"),
+            None,
+        )
+        .await
+        .expect("start stream")
+        .finish()
+        .await
+        .expect_err("empty streamed turn must fail");
+    assert_eq!(stream_err, PromptRunError::EmptyAssistantText(expected));
+
     runtime.shutdown().await.expect("shutdown");
 }
 
 #[tokio::test(flavor = "current_thread")]
-async fn run_prompt_ignores_invalid_hook_attachment_with_fail_open() {
-    let patches = vec![(
-        HookPhase::PreTurn,
-        crate::plugin::HookPatch {
-            prompt_override: None,
-            model_override: None,
-            add_attachments: vec![crate::plugin::HookAttachment::LocalImage {
-                path: "definitely_missing_image_for_hook_test.png".to_owned(),
-            }],
-            metadata_delta: Value::Null,
-        },
-    )];
-    let hooks = RuntimeHookConfig::new().with_pre_hook(Arc::new(PhasePatchPreHook {
-        name: "bad_attachment_patch",
-        patches,
-    }));
-    let runtime = spawn_run_prompt_mutation_probe_runtime(hooks).await;
-
+async fn run_prompt_simple_sends_default_effort() {
+    let runtime = spawn_run_prompt_effort_probe_runtime().await;
     let result = runtime
-        .run_prompt(PromptRunParams::new("/tmp", "prompt"))
+        .run_prompt_simple("/tmp", "probe effort")
         .await
-        .expect("main run should continue");
-    let payload: Value =
-        serde_json::from_str(&result.assistant_text).expect("decode probe payload");
-    assert_eq!(payload["itemTypes"], json!(["text"]));
-    let report = runtime.hook_report_snapshot();
-    assert_eq!(report.issues.len(), 1);
-    assert_eq!(report.issues[0].hook_name, "bad_attachment_patch");
-    assert_eq!(report.issues[0].class, HookIssueClass::Validation);
+        .expect("run prompt simple");
+
+    assert_eq!(result.assistant_text, "medium");
 
     runtime.shutdown().await.expect("shutdown");
 }
 
 #[tokio::test(flavor = "current_thread")]
-async fn pre_session_mutation_restricts_prompt_and_attachments_but_allows_model_and_metadata() {
-    let patches = vec![(
-        HookPhase::PreSessionStart,
-        crate::plugin::HookPatch {
-            prompt_override: Some("not-allowed".to_owned()),
-            model_override: Some("model-from-session-hook".to_owned()),
-            add_attachments: vec![crate::plugin::HookAttachment::ImageUrl {
-                url: "https://example.com/ignored.png".to_owned(),
-            }],
-            metadata_delta: json!({"session_key": "session_value"}),
-        },
-    )];
-    let metadata_events = Arc::new(Mutex::new(Vec::<(HookPhase, Value)>::new()));
-    let hooks = RuntimeHookConfig::new()
-        .with_pre_hook(Arc::new(PhasePatchPreHook {
-            name: "session_patch",
-            patches,
-        }))
-        .with_post_hook(Arc::new(MetadataCapturePostHook {
-            name: "session_metadata_capture",
-            metadata: metadata_events.clone(),
-        }));
-    let cfg = RuntimeConfig::new(python_session_mutation_probe_process()).with_hooks(hooks);
+async fn run_prompt_rejects_attachments_over_configured_count() {
+    let cfg = RuntimeConfig::new(super::support::python_run_prompt_mock_process())
+        .with_max_attachments(1);
     let runtime = Runtime::spawn_local(cfg).await.expect("spawn runtime");
+    let params = PromptRunParams::new("/tmp", "two images")
+        .attach_image_url("https://example.com/a.png")
+        .attach_image_url("https://example.com/b.png");
+
+    let err = runtime
+        .run_prompt(params)
+        .await
+        .expect_err("second attachment exceeds the cap");
+    assert_eq!(
+        err,
+        PromptRunError::AttachmentLimitExceeded {
+            unit: "attachments",
+            limit: 1,
+            actual: 2,
+        }
+    );
+    assert!(runtime.state_snapshot().threads.is_empty());
+
+    runtime.shutdown().await.expect("shutdown");
+}
 
+#[tokio::test(flavor = "current_thread")]
+async fn concurrent_turns_on_one_thread_keep_items_separate() {
+    let runtime = spawn_run_prompt_same_thread_interleaved_runtime().await;
     let thread = runtime
         .thread_start(ThreadStartParams::default())
         .await
         .expect("thread start");
-    assert_eq!(thread.thread_id, "thr_model-from-session-hook");
 
-    let report = runtime.hook_report_snapshot();
-    assert_eq!(report.issues.len(), 2);
-    assert!(report
-        .issues
-        .iter()
-        .all(|issue| issue.class == HookIssueClass::Validation));
-    assert_eq!(report.issues[0].phase, HookPhase::PreSessionStart);
-    assert_eq!(report.issues[1].phase, HookPhase::PreSessionStart);
+    let (first, second) = tokio::join!(
+        runtime.run_prompt_on_loaded_thread_with_hooks(
+            &thread.thread_id,
+            PromptRunParams::new("/tmp", "first"),
+            None,
+        ),
+        runtime.run_prompt_on_loaded_thread_with_hooks(
+            &thread.thread_id,
+            PromptRunParams::new("/tmp", "second"),
+            None,
+        ),
+    );
+    let first = first.expect("first turn");
+    let second = second.expect("second turn");
+    assert_eq!(
+        (first.turn_id.as_str(), first.assistant_text.as_str()),
+        ("turn_1", "first-reply")
+    );
+    assert_eq!(
+        (second.turn_id.as_str(), second.assistant_text.as_str()),
+        ("turn_2", "second-reply")
+    );
 
-    let post_session_metadata = {
-        let captured = metadata_events.lock().expect("metadata lock");
-        captured
-            .iter()
-            .find(|(phase, _)| *phase == HookPhase::PostSessionStart)
-            .map(|(_, metadata)| metadata.clone())
-            .expect("post-session metadata")
-    };
-    assert_eq!(post_session_metadata["session_key"], json!("session_value"));
+    let state = runtime.state_snapshot();
+    let projected = &state.threads["thr_shared"];
+    assert_eq!(
+        projected.turns["turn_1"].items["item_1"].text_accum,
+        "first-reply"
+    );
+    assert_eq!(
+        projected.turns["turn_2"].items["item_2"].text_accum,
+        "second-reply"
+    );
+    assert_eq!(projected.active_turn, None);
 
     runtime.shutdown().await.expect("shutdown");
 }
 
 #[tokio::test(flavor = "current_thread")]
-async fn run_prompt_ignores_cross_thread_events_for_same_turn_id() {
-    let runtime = spawn_run_prompt_cross_thread_noise_runtime().await;
-    let result = runtime
-        .run_prompt(PromptRunParams::new("/tmp", "say ok"))
+async fn run_prompt_no_effort_omits_effort_field() {
+    let runtime = spawn_run_prompt_effort_probe_runtime().await;
+    let omitted = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "probe effort").no_effort())
         .await
-        .expect("run prompt");
+        .expect("run prompt without effort");
+    assert_eq!(omitted.assistant_text, "missing");
 
-    assert_eq!(result.thread_id, "thr_prompt");
-    assert_eq!(result.turn_id, "turn_prompt");
-    assert_eq!(result.assistant_text, "ok-from-run-prompt");
+    let restored = runtime
+        .run_prompt(
+            PromptRunParams::new("/tmp", "probe effort")
+                .no_effort()
+                .with_effort(ReasoningEffort::Low),
+        )
+        .await
+        .expect("run prompt with effort restored");
+    assert_eq!(restored.assistant_text, "low");
 
     runtime.shutdown().await.expect("shutdown");
 }
 
 #[tokio::test(flavor = "current_thread")]
-async fn run_prompt_simple_sends_default_effort() {
+async fn run_prompt_surfaces_effort_rejection_without_opt_in() {
     let runtime = spawn_run_prompt_effort_probe_runtime().await;
-    let result = runtime
-        .run_prompt_simple("/tmp", "probe effort")
+    let err = runtime
+        .run_prompt(
+            PromptRunParams::new("/tmp", "reject effort").with_timeout(Duration::from_secs(2)),
+        )
         .await
-        .expect("run prompt simple");
+        .expect_err("effort rejection must surface");
+    assert!(matches!(
+        err,
+        PromptRunError::Rpc(RpcError::ServerError(ref object)) if object.code == -32602
+    ));
 
-    assert_eq!(result.assistant_text, "medium");
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_retries_without_effort_when_opted_in() {
+    let runtime = spawn_run_prompt_effort_probe_runtime().await;
+    let result = runtime
+        .run_prompt(
+            PromptRunParams::new("/tmp", "reject effort")
+                .with_timeout(Duration::from_secs(2))
+                .allow_effort_drop_on_reject(),
+        )
+        .await
+        .expect("retry without effort");
+    assert_eq!(result.assistant_text, "missing");
 
     runtime.shutdown().await.expect("shutdown");
 }
@@ -494,8 +1696,18 @@ async fn run_prompt_preserves_explicit_effort() {
             sandbox_policy: SandboxPolicy::Preset(SandboxPreset::ReadOnly),
             privileged_escalation_approved: false,
             attachments: vec![],
-            timeout: Duration::from_secs(2),
+            timeout: Some(Duration::from_secs(2)),
             output_schema: None,
+            drop_effort_on_reject: false,
+            metadata: Value::Null,
+            steer_from: None,
+            extra_turn_params: Map::new(),
+            resend_model_on_resume: false,
+            inline_local_images: false,
+            assistant_item_separator: "\n".to_owned(),
+            fail_on_command_error: false,
+            auto_approve_all: false,
+            max_reasoning_tokens: None,
         })
         .await
         .expect("run prompt");
@@ -545,8 +1757,18 @@ async fn randomized_user_like_prompt_paths_remain_stable() {
                     sandbox_policy: SandboxPolicy::Preset(SandboxPreset::ReadOnly),
                     privileged_escalation_approved: false,
                     attachments: vec![],
-                    timeout: Duration::from_secs(2),
+                    timeout: Some(Duration::from_secs(2)),
                     output_schema: None,
+                    drop_effort_on_reject: false,
+                    metadata: Value::Null,
+                    steer_from: None,
+                    extra_turn_params: Map::new(),
+                    resend_model_on_resume: false,
+                    inline_local_images: false,
+                    assistant_item_separator: "\n".to_owned(),
+                    fail_on_command_error: false,
+                    auto_approve_all: false,
+                    max_reasoning_tokens: None,
                 })
                 .await
                 .expect("full prompt must succeed"),
@@ -570,8 +1792,18 @@ async fn randomized_user_like_prompt_paths_remain_stable() {
                         sandbox_policy: SandboxPolicy::Preset(SandboxPreset::ReadOnly),
                         privileged_escalation_approved: false,
                         attachments: vec![],
-                        timeout: Duration::from_secs(2),
+                        timeout: Some(Duration::from_secs(2)),
                         output_schema: None,
+                        drop_effort_on_reject: false,
+                        metadata: Value::Null,
+                        steer_from: None,
+                        extra_turn_params: Map::new(),
+                        resend_model_on_resume: false,
+                        inline_local_images: false,
+                        assistant_item_separator: "\n".to_owned(),
+                        fail_on_command_error: false,
+                        auto_approve_all: false,
+                        max_reasoning_tokens: None,
                     })
                     .await
                     .expect("effort probe prompt must succeed");
@@ -611,8 +1843,18 @@ async fn run_prompt_surfaces_turn_error_when_text_is_empty() {
             sandbox_policy: SandboxPolicy::Preset(SandboxPreset::ReadOnly),
             privileged_escalation_approved: false,
             attachments: vec![],
-            timeout: Duration::from_secs(2),
+            timeout: Some(Duration::from_secs(2)),
             output_schema: None,
+            drop_effort_on_reject: false,
+            metadata: Value::Null,
+            steer_from: None,
+            extra_turn_params: Map::new(),
+            resend_model_on_resume: false,
+            inline_local_images: false,
+            assistant_item_separator: "\n".to_owned(),
+            fail_on_command_error: false,
+            auto_approve_all: false,
+            max_reasoning_tokens: None,
         })
         .await
         .expect_err("run prompt must fail");
@@ -646,8 +1888,18 @@ async fn run_prompt_surfaces_turn_failed_with_context() {
             sandbox_policy: SandboxPolicy::Preset(SandboxPreset::ReadOnly),
             privileged_escalation_approved: false,
             attachments: vec![],
-            timeout: Duration::from_secs(2),
+            timeout: Some(Duration::from_secs(2)),
             output_schema: None,
+            drop_effort_on_reject: false,
+            metadata: Value::Null,
+            steer_from: None,
+            extra_turn_params: Map::new(),
+            resend_model_on_resume: false,
+            inline_local_images: false,
+            assistant_item_separator: "\n".to_owned(),
+            fail_on_command_error: false,
+            auto_approve_all: false,
+            max_reasoning_tokens: None,
         })
         .await
         .expect_err("run prompt must fail");
@@ -686,6 +1938,162 @@ async fn run_prompt_timeout_uses_absolute_deadline_under_streaming_deltas() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_queues_turns_beyond_max_concurrent_turns() {
+    let runtime = spawn_run_prompt_streaming_timeout_runtime_with_max_turns(1).await;
+    let params = PromptRunParams::new("/tmp", "timeout probe").with_timeout(Duration::from_secs(1));
+
+    let first = tokio::spawn({
+        let runtime = runtime.clone();
+        let params = params.clone();
+        async move { runtime.run_prompt(params).await }
+    });
+    wait_for_turn_metrics(&runtime, 0, 1).await;
+
+    let second = tokio::spawn({
+        let runtime = runtime.clone();
+        async move { runtime.run_prompt(params).await }
+    });
+    wait_for_turn_metrics(&runtime, 1, 1).await;
+
+    let first = first.await.expect("join first");
+    assert!(matches!(first, Err(PromptRunError::Timeout(_))));
+    wait_for_turn_metrics(&runtime, 0, 1).await;
+
+    let second = second.await.expect("join second");
+    assert!(matches!(second, Err(PromptRunError::Timeout(_))));
+    wait_for_turn_metrics(&runtime, 0, 0).await;
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+async fn wait_for_turn_metrics(runtime: &Runtime, queued: u64, active: u64) {
+    let deadline = Instant::now() + Duration::from_secs(3);
+    loop {
+        let metrics = runtime.metrics_snapshot();
+        if metrics.turns_queued == queued && metrics.turns_active == active {
+            return;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "expected queued={queued} active={active}, got queued={} active={}",
+            metrics.turns_queued,
+            metrics.turns_active
+        );
+        sleep(Duration::from_millis(5)).await;
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_serves_read_only_runs_from_prompt_cache() {
+    let cache = Arc::new(InMemoryPromptCache::new());
+    let runtime = spawn_run_prompt_runtime_with_prompt_cache(cache.clone()).await;
+
+    let params = PromptRunParams::new("/tmp", "say ok").with_timeout(Duration::from_secs(2));
+    let key = PromptCacheKey::for_params(&params).expect("read-only run is cacheable");
+    let first = runtime.run_prompt(params.clone()).await.expect("miss");
+    assert_eq!(first.assistant_text, "ok-from-run-prompt");
+    assert_eq!(cache.get(&key), Some(first));
+
+    let seeded = PromptRunResult {
+        thread_id: "thr_cached".to_owned(),
+        turn_id: "turn_cached".to_owned(),
+        assistant_text: "from-cache".to_owned(),
+        schema_valid: None,
+    };
+    cache.put(key.clone(), &seeded);
+    let hit = runtime.run_prompt(params.clone()).await.expect("hit");
+    assert_eq!(hit, seeded);
+
+    let other_effort = params.clone().with_effort(ReasoningEffort::Low);
+    let miss = runtime.run_prompt(other_effort).await.expect("effort miss");
+    assert_eq!(miss.assistant_text, "ok-from-run-prompt");
+
+    let strict = params.clone().fail_on_command_error(true);
+    assert_ne!(PromptCacheKey::for_params(&strict), Some(key.clone()));
+    let miss = runtime.run_prompt(strict).await.expect("strict miss");
+    assert_eq!(miss.assistant_text, "ok-from-run-prompt");
+
+    let other_separator = params.clone().with_assistant_item_separator("\n\n");
+    assert_ne!(
+        PromptCacheKey::for_params(&other_separator),
+        Some(key.clone())
+    );
+    let miss = runtime
+        .run_prompt(other_separator)
+        .await
+        .expect("separator miss");
+    assert_eq!(miss.assistant_text, "ok-from-run-prompt");
+
+    let approving = params.with_approval_policy(ApprovalPolicy::OnRequest);
+    assert_eq!(PromptCacheKey::for_params(&approving), None);
+    let bypass = runtime.run_prompt(approving).await.expect("bypass");
+    assert_eq!(bypass.assistant_text, "ok-from-run-prompt");
+    assert_eq!(cache.len(), 4);
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn prompt_cache_runs_pre_run_hooks_and_skips_short_circuits() {
+    let cache = Arc::new(InMemoryPromptCache::new());
+    let runtime = spawn_run_prompt_runtime_with_prompt_cache(cache.clone()).await;
+    let params = PromptRunParams::new("/tmp", "say ok").with_timeout(Duration::from_secs(2));
+
+    let respond = RuntimeHookConfig::new().with_pre_hook(Arc::new(RespondPreHook {
+        phase: HookPhase::PreRun,
+        text: "from hook",
+    }));
+    let short_circuited = runtime
+        .run_prompt_with_hooks(params.clone(), Some(&respond))
+        .await
+        .expect("short circuit");
+    assert_eq!(short_circuited.assistant_text, "from hook");
+    assert!(cache.is_empty(), "hook short-circuits must not be cached");
+
+    let miss = runtime.run_prompt(params.clone()).await.expect("miss");
+    assert_eq!(cache.len(), 1);
+
+    let events = Arc::new(Mutex::new(Vec::<String>::new()));
+    let recording = RuntimeHookConfig::new().with_pre_hook(Arc::new(RecordingPreHook {
+        name: "pre_on_hit",
+        events: events.clone(),
+        fail_phase: None,
+    }));
+    let hit = runtime
+        .run_prompt_with_hooks(params, Some(&recording))
+        .await
+        .expect("hit");
+    assert_eq!(hit, miss);
+    assert_eq!(
+        events.lock().expect("events lock").as_slice(),
+        &["pre:PreRun".to_owned()]
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn run_prompt_without_timeout_uses_runtime_default_prompt_timeout() {
+    let default_timeout = Duration::from_millis(150);
+    let runtime = spawn_run_prompt_streaming_timeout_runtime_with_default(default_timeout).await;
+
+    let err = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "timeout probe"))
+        .await
+        .expect_err("run prompt must timeout on the runtime default");
+    assert!(matches!(err, PromptRunError::Timeout(d) if d == default_timeout));
+
+    let explicit_timeout = Duration::from_millis(100);
+    let err = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "timeout probe").with_timeout(explicit_timeout))
+        .await
+        .expect_err("run prompt must timeout on the explicit value");
+    assert!(matches!(err, PromptRunError::Timeout(d) if d == explicit_timeout));
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn run_prompt_recovers_when_live_stream_lags_past_terminal_event() {
     let runtime = spawn_run_prompt_lagged_completion_runtime().await;
@@ -761,6 +2169,7 @@ async fn run_prompt_timeout_emits_turn_interrupt_request() {
             && envelope.thread_id.as_deref() == Some("thr_interrupt_probe")
             && envelope.turn_id.as_deref() == Some("turn_interrupt_probe")
         {
+            assert_eq!(envelope.json["params"]["reason"], "timeout");
             saw_interrupt = true;
             break;
         }
@@ -773,6 +2182,53 @@ async fn run_prompt_timeout_emits_turn_interrupt_request() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn post_turn_hooks_observe_final_assistant_text() {
+    let texts = Arc::new(Mutex::new(Vec::<(HookPhase, Option<String>)>::new()));
+    let hooks = RuntimeHookConfig::new().with_post_hook(Arc::new(AssistantTextCapturePostHook {
+        name: "text_capture",
+        texts: Arc::clone(&texts),
+    }));
+    let runtime = spawn_run_prompt_runtime_with_hooks(hooks).await;
+
+    let direct = runtime
+        .run_prompt(PromptRunParams::new("/tmp", "direct"))
+        .await
+        .expect("run prompt");
+    let thread = runtime
+        .thread_start(ThreadStartParams::default())
+        .await
+        .expect("thread start");
+    let streamed = runtime
+        .run_prompt_on_loaded_thread_stream_with_hooks(
+            &thread.thread_id,
+            PromptRunParams::new("/tmp", "streamed"),
+            None,
+        )
+        .await
+        .expect("start prompt stream")
+        .finish()
+        .await
+        .expect("finish prompt stream");
+
+    let captured = texts.lock().expect("texts lock").clone();
+    let post_turn: Vec<Option<String>> = captured
+        .iter()
+        .filter(|(phase, _)| *phase == HookPhase::PostTurn)
+        .map(|(_, text)| text.clone())
+        .collect();
+    assert_eq!(
+        post_turn,
+        vec![Some(direct.assistant_text), Some(streamed.assistant_text)]
+    );
+    assert!(captured
+        .iter()
+        .filter(|(phase, _)| *phase != HookPhase::PostTurn)
+        .all(|(_, text)| text.is_none()));
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn prompt_stream_drop_runs_post_turn_hooks() {
     let events = Arc::new(Mutex::new(Vec::<String>::new()));
@@ -818,3 +2274,27 @@ async fn prompt_stream_drop_runs_post_turn_hooks() {
 
     runtime.shutdown().await.expect("shutdown");
 }
+
+#[cfg(feature = "blocking")]
+#[test]
+fn blocking_runtime_runs_prompt_without_caller_executor() {
+    let runtime = Runtime::spawn_blocking(RuntimeConfig::new(
+        super::support::python_run_prompt_mock_process(),
+    ))
+    .expect("spawn blocking runtime");
+    let result = runtime
+        .run_prompt_blocking(PromptRunParams::new("/tmp", "hello blocking"))
+        .expect("run prompt blocking");
+    assert_eq!(result.assistant_text, "ok-from-run-prompt");
+    runtime.shutdown_blocking().expect("shutdown blocking");
+}
+
+#[cfg(feature = "blocking")]
+#[tokio::test(flavor = "current_thread")]
+async fn blocking_runtime_rejects_calls_from_async_context() {
+    let err = Runtime::spawn_blocking(RuntimeConfig::new(
+        super::support::python_run_prompt_mock_process(),
+    ))
+    .expect_err("nested reactor must be rejected");
+    assert_eq!(err, crate::runtime::RuntimeError::BlockingInAsyncContext);
+}
@@ -1,7 +1,7 @@
 use std::sync::{Arc, Mutex};
 
 use crate::plugin::{
-    HookAction, HookContext, HookIssue, HookIssueClass, HookPhase, PostHook, PreHook,
+    HookAction, HookContext, HookIssue, HookIssueClass, HookPhase, PostHook, PreHook, ShutdownToken,
 };
 use serde_json::Value;
 
@@ -122,3 +122,55 @@ impl PostHook for MetadataCapturePostHook {
         })
     }
 }
+
+pub(crate) type CapturedAssistantTexts = Arc<Mutex<Vec<(HookPhase, Option<String>)>>>;
+
+#[derive(Clone)]
+pub(crate) struct AssistantTextCapturePostHook {
+    pub(crate) name: &'static str,
+    pub(crate) texts: CapturedAssistantTexts,
+}
+
+impl PostHook for AssistantTextCapturePostHook {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn call<'a>(
+        &'a self,
+        ctx: &'a HookContext,
+    ) -> crate::plugin::HookFuture<'a, Result<(), HookIssue>> {
+        Box::pin(async move {
+            self.texts
+                .lock()
+                .expect("texts lock")
+                .push((ctx.phase, ctx.assistant_text.clone()));
+            Ok(())
+        })
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ShutdownCapturePostHook {
+    pub(crate) name: &'static str,
+    pub(crate) tokens: Arc<Mutex<Vec<ShutdownToken>>>,
+}
+
+impl PostHook for ShutdownCapturePostHook {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn call<'a>(
+        &'a self,
+        ctx: &'a HookContext,
+    ) -> crate::plugin::HookFuture<'a, Result<(), HookIssue>> {
+        Box::pin(async move {
+            self.tokens
+                .lock()
+                .expect("tokens lock")
+                .push(ctx.shutdown().clone());
+            Ok(())
+        })
+    }
+}
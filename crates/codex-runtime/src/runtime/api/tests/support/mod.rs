@@ -2,16 +2,23 @@ mod hook_fixtures;
 mod process_fixtures;
 
 pub(crate) use hook_fixtures::{
-    MetadataCapturePostHook, PhasePatchPreHook, RecordingPostHook, RecordingPreHook,
+    AssistantTextCapturePostHook, MetadataCapturePostHook, PhasePatchPreHook, RecordingPostHook,
+    RecordingPreHook, ShutdownCapturePostHook,
 };
+pub(crate) use process_fixtures::python_run_prompt_mock_process;
 pub(crate) use process_fixtures::{
     python_api_mock_process, python_session_mutation_probe_process, spawn_mock_runtime,
-    spawn_run_prompt_cross_thread_noise_runtime, spawn_run_prompt_effort_probe_runtime,
-    spawn_run_prompt_error_runtime, spawn_run_prompt_interrupt_probe_runtime,
-    spawn_run_prompt_lagged_cancelled_runtime, spawn_run_prompt_lagged_completion_runtime,
+    spawn_model_list_missing_runtime, spawn_run_prompt_cross_thread_noise_runtime,
+    spawn_run_prompt_effort_probe_runtime, spawn_run_prompt_error_runtime,
+    spawn_run_prompt_interrupt_probe_runtime, spawn_run_prompt_lagged_cancelled_runtime,
+    spawn_run_prompt_lagged_completion_runtime,
     spawn_run_prompt_lagged_completion_slow_thread_read_runtime,
     spawn_run_prompt_mutation_probe_runtime, spawn_run_prompt_runtime,
-    spawn_run_prompt_runtime_with_hooks, spawn_run_prompt_streaming_timeout_runtime,
-    spawn_run_prompt_turn_failed_runtime, spawn_thread_resume_mismatched_id_runtime,
-    spawn_thread_resume_missing_id_runtime,
+    spawn_run_prompt_runtime_with_hooks, spawn_run_prompt_runtime_with_id_generator,
+    spawn_run_prompt_runtime_with_prompt_cache, spawn_run_prompt_same_thread_interleaved_runtime,
+    spawn_run_prompt_streaming_timeout_runtime,
+    spawn_run_prompt_streaming_timeout_runtime_with_default,
+    spawn_run_prompt_streaming_timeout_runtime_with_max_turns,
+    spawn_run_prompt_turn_failed_runtime, spawn_thread_resume_id_change_accepting_runtime,
+    spawn_thread_resume_mismatched_id_runtime, spawn_thread_resume_missing_id_runtime,
 };
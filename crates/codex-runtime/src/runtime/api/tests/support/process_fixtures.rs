@@ -1,11 +1,19 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use crate::runtime::core::Runtime;
-use crate::runtime::{RuntimeConfig, RuntimeHookConfig, StdioProcessSpec};
+use crate::runtime::{
+    IdGenerator, PromptCache, ResumeIdChangeObserver, RuntimeConfig, RuntimeHookConfig,
+    StdioProcessSpec,
+};
 
 pub(crate) fn python_api_mock_process() -> StdioProcessSpec {
     let script = r#"
 import json
 import sys
 
+metadata_store = {}
+
 for line in sys.stdin:
     line = line.strip()
     if not line:
@@ -54,12 +62,23 @@ for line in sys.stdin:
         out = {"id": rpc_id, "result": {"threadId": params.get("threadId", "thr_resume")}}
     elif method == "thread/fork":
         out = {"id": rpc_id, "result": {"threadId": "thr_forked"}}
-    elif method == "thread/archive":
-        out = {"id": rpc_id, "result": {"ok": True, "threadId": params.get("threadId")}}
+    elif method in ("thread/archive", "thread/unarchive"):
+        if params.get("threadId") == "thr_missing":
+            out = {"id": rpc_id, "error": {"code": -32602, "message": "thread not found"}}
+        else:
+            out = {"id": rpc_id, "result": {"ok": True, "threadId": params.get("threadId")}}
+    elif method == "thread/read" and params.get("threadId") == "thr_missing":
+        out = {"id": rpc_id, "error": {"code": -32602, "message": "thread not found"}}
     elif method == "thread/read":
         thread = make_thread(params.get("threadId", "thr_read"))
         thread["turnsIncluded"] = bool(params.get("includeTurns"))
-        if params.get("includeTurns"):
+        if params.get("includeTurns") and thread["id"] == "thr_many_turns":
+            # Old-server behavior: the lastNTurns hint is echoed but not applied.
+            thread["lastNTurnsSeen"] = params.get("lastNTurns")
+            thread["turns"] = [
+                make_turn(f"turn_many_{index}", "completed", []) for index in range(1, 5)
+            ]
+        elif params.get("includeTurns"):
             thread["turns"] = [
                 make_turn(
                     "turn_read_1",
@@ -73,8 +92,24 @@ for line in sys.stdin:
                 "thread": thread
             },
         }
+    elif method in ("thread/setMetadata", "thread/getMetadata") and str(
+        params.get("threadId", "")
+    ).startswith("thr_legacy"):
+        out = {"id": rpc_id, "error": {"code": -32601, "message": f"unknown method {method}"}}
+    elif method == "thread/setMetadata":
+        metadata_store[params.get("threadId")] = params.get("metadata")
+        out = {"id": rpc_id, "result": {}}
+    elif method == "thread/getMetadata":
+        out = {"id": rpc_id, "result": {"metadata": metadata_store.get(params.get("threadId"))}}
+    elif method == "model/list":
+        if params.get("cursor") == "models_page_2":
+            out = {"id": rpc_id, "result": {"data": [{"id": "gpt-mini", "model": "gpt-mini"}], "nextCursor": None}}
+        else:
+            out = {"id": rpc_id, "result": {"data": [{"id": "gpt-main", "model": "gpt-main"}], "nextCursor": "models_page_2"}}
     elif method == "thread/list":
         thread = make_thread("thr_list")
+        if "thr_list" in metadata_store:
+            thread["metadata"] = metadata_store["thr_list"]
         thread["archivedFilter"] = params.get("archived")
         thread["sortKey"] = params.get("sortKey")
         thread["providerCount"] = len(params.get("modelProviders") or [])
@@ -114,7 +149,10 @@ for line in sys.stdin:
         out = {
             "id": rpc_id,
             "result": {
-                "thread": thread
+                "thread": thread,
+                "rolledBackTurnIds": [
+                    f"turn_rolled_{i}" for i in range(params.get("numTurns") or 0)
+                ],
             },
         }
     elif method == "skills/list":
@@ -224,6 +262,7 @@ for line in sys.stdin:
 pub(crate) fn python_run_prompt_mock_process() -> StdioProcessSpec {
     let script = r#"
 import json
+import time
 import sys
 
 for line in sys.stdin:
@@ -261,19 +300,70 @@ for line in sys.stdin:
         sys.stdout.flush()
         continue
 
+    if method == "thread/fork":
+        forked_id = "thr_fork_of_" + params.get("threadId", "unknown")
+        sys.stdout.write(json.dumps({"id": rpc_id, "result": {"thread": {"id": forked_id}}}) + "\n")
+        sys.stdout.flush()
+        continue
+
     if method == "turn/start":
         thread_id = params.get("threadId", "thr_prompt")
-        turn_id = "turn_prompt"
+        expected_turn_id = params.get("expectedTurnId")
+        if expected_turn_id is not None and expected_turn_id != "turn_prompt":
+            sys.stdout.write(json.dumps({"id": rpc_id, "error": {"code": -32600, "message": f"expectedTurnId {expected_turn_id} does not match active turn turn_prompt"}}) + "\n")
+            sys.stdout.flush()
+            continue
+        turn_id = "turn_steered" if expected_turn_id is not None else "turn_prompt"
         assistant_text = "ok-from-run-prompt"
         if params.get("outputSchema") is not None:
             assistant_text = json.dumps(params.get("outputSchema"), sort_keys=True)
+        input_items = params.get("input") or [{}]
+        if "emit no items" in str(input_items[0].get("text", "")):
+            sys.stdout.write(json.dumps({"method":"turn/started","params":{"threadId":thread_id,"turnId":turn_id}}) + "\n")
+            sys.stdout.write(json.dumps({"method":"turn/completed","params":{"threadId":thread_id,"turnId":turn_id}}) + "\n")
+            sys.stdout.write(json.dumps({"id": rpc_id, "result": {"turn": {"id": turn_id}}}) + "\n")
+            sys.stdout.flush()
+            continue
+        if "respond before events" in str(input_items[0].get("text", "")):
+            sys.stdout.write(json.dumps({"id": rpc_id, "result": {"turn": {"id": turn_id}}}) + "\n")
+            sys.stdout.flush()
+            rpc_id = None
         sys.stdout.write(json.dumps({"method":"turn/started","params":{"threadId":thread_id,"turnId":turn_id}}) + "\n")
+        if "needs approval" in str(input_items[0].get("text", "")):
+            # Ask before answering turn/start: the run learns its turn id only afterwards.
+            sys.stdout.write(json.dumps({"id":"appr_1","method":"item/commandExecution/requestApproval","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_cmd"}}) + "\n")
+            sys.stdout.write(json.dumps({"id": rpc_id, "result": {"turn": {"id": turn_id}}}) + "\n")
+            rpc_id = None
+            sys.stdout.flush()
+            for reply in sys.stdin:
+                reply = json.loads(reply)
+                if reply.get("id") == "appr_1":
+                    assistant_text = "approval:" + str((reply.get("result") or {}).get("decision"))
+                    break
+        if "run failing command" in str(input_items[0].get("text", "")):
+            sys.stdout.write(json.dumps({"method":"item/started","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_cmd","itemType":"commandExecution"}}) + "\n")
+            sys.stdout.write(json.dumps({"method":"item/completed","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_cmd","item":{"type":"commandExecution","id":"item_cmd","command":"cargo test","status":"completed","exitCode":101}}}) + "\n")
+        if "think in steps" in str(input_items[0].get("text", "")):
+            for _ in range(2):
+                sys.stdout.write(json.dumps({"method":"thread/tokenUsage/updated","params":{"threadId":thread_id,"turnId":turn_id,"tokenUsage":{"last":{"inputTokens":6,"outputTokens":320,"reasoningOutputTokens":300}}}}) + "\n")
+        if "think at length" in str(input_items[0].get("text", "")):
+            sys.stdout.write(json.dumps({"method":"thread/tokenUsage/updated","params":{"threadId":thread_id,"turnId":turn_id,"tokenUsage":{"total":{"inputTokens":12,"outputTokens":950,"reasoningOutputTokens":900}}}}) + "\n")
+        if "full picture" in str(input_items[0].get("text", "")):
+            sys.stdout.write(json.dumps({"method":"item/completed","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_reason","item":{"type":"reasoning","id":"item_reason","summary":["inspect the repo"],"content":[]}}}) + "\n")
+            sys.stdout.write(json.dumps({"method":"item/completed","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_ls","item":{"type":"commandExecution","id":"item_ls","command":"ls","commandActions":[],"cwd":"/tmp","status":"completed","exitCode":0}}}) + "\n")
+            sys.stdout.write(json.dumps({"method":"item/completed","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_patch","item":{"type":"fileChange","id":"item_patch","changes":[{"path":"README.md","kind":"update"}],"status":"completed"}}}) + "\n")
+            sys.stdout.write(json.dumps({"method":"thread/tokenUsage/updated","params":{"threadId":thread_id,"turnId":turn_id,"tokenUsage":{"total":{"inputTokens":12,"outputTokens":3}}}}) + "\n")
         sys.stdout.write(json.dumps({"method":"item/started","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_prompt","itemType":"agentMessage"}}) + "\n")
         sys.stdout.write(json.dumps({"method":"item/agentMessage/delta","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_prompt","delta":assistant_text}}) + "\n")
         sys.stdout.write(json.dumps({"method":"item/completed","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_prompt","item":{"type":"agent_message","text":assistant_text}}}) + "\n")
         sys.stdout.write(json.dumps({"method":"turn/completed","params":{"threadId":thread_id,"turnId":turn_id}}) + "\n")
-        sys.stdout.write(json.dumps({"id": rpc_id, "result": {"turn": {"id": turn_id}}}) + "\n")
+        if rpc_id is not None:
+            sys.stdout.write(json.dumps({"id": rpc_id, "result": {"turn": {"id": turn_id}}}) + "\n")
         sys.stdout.flush()
+        if "think late" in str(input_items[0].get("text", "")):
+            time.sleep(0.05)
+            sys.stdout.write(json.dumps({"method":"thread/tokenUsage/updated","params":{"threadId":thread_id,"turnId":turn_id,"tokenUsage":{"last":{"inputTokens":12,"outputTokens":950,"reasoningOutputTokens":900}}}}) + "\n")
+            sys.stdout.flush()
         continue
 
     sys.stdout.write(json.dumps({"id": rpc_id, "result": {"echoMethod": method, "params": params}}) + "\n")
@@ -342,6 +432,73 @@ for line in sys.stdin:
     crate::test_fixtures::python_inline_process(script)
 }
 
+/// Two turns on one thread: the first turn/start is held open until the second
+/// arrives, then both turns stream interleaved deltas and complete out of order.
+pub(crate) fn python_run_prompt_same_thread_interleaved_process() -> StdioProcessSpec {
+    let script = r#"
+import json
+import sys
+
+def emit(method, params):
+    sys.stdout.write(json.dumps({"method": method, "params": params}) + "\n")
+
+turn_count = 0
+
+for line in sys.stdin:
+    line = line.strip()
+    if not line:
+        continue
+    try:
+        msg = json.loads(line)
+    except Exception:
+        continue
+
+    method = msg.get("method")
+    rpc_id = msg.get("id")
+    params = msg.get("params") or {}
+
+    if method == "initialize" and rpc_id is not None:
+        sys.stdout.write(json.dumps({"id": rpc_id, "result": {"ready": True}}) + "\n")
+        sys.stdout.flush()
+        continue
+
+    if rpc_id is None:
+        continue
+
+    if method == "thread/start":
+        sys.stdout.write(json.dumps({"id": rpc_id, "result": {"thread": {"id": "thr_shared"}}}) + "\n")
+        emit("thread/started", {"threadId": "thr_shared"})
+        sys.stdout.flush()
+        continue
+
+    if method == "turn/start":
+        thread_id = params.get("threadId", "thr_shared")
+        turn_count += 1
+        turn_id = f"turn_{turn_count}"
+        sys.stdout.write(json.dumps({"id": rpc_id, "result": {"turn": {"id": turn_id}}}) + "\n")
+        emit("turn/started", {"threadId": thread_id, "turnId": turn_id})
+        if turn_count == 2:
+            for turn, item in (("turn_1", "item_1"), ("turn_2", "item_2")):
+                emit("item/started", {"threadId": thread_id, "turnId": turn, "itemId": item, "itemType": "agentMessage"})
+            for turn, item, delta in (
+                ("turn_2", "item_2", "second-"),
+                ("turn_1", "item_1", "first-"),
+                ("turn_2", "item_2", "reply"),
+                ("turn_1", "item_1", "reply"),
+            ):
+                emit("item/agentMessage/delta", {"threadId": thread_id, "turnId": turn, "itemId": item, "delta": delta})
+            emit("turn/completed", {"threadId": thread_id, "turnId": "turn_2"})
+            emit("turn/completed", {"threadId": thread_id, "turnId": "turn_1"})
+        sys.stdout.flush()
+        continue
+
+    sys.stdout.write(json.dumps({"id": rpc_id, "result": {"echoMethod": method, "params": params}}) + "\n")
+    sys.stdout.flush()
+"#;
+
+    crate::test_fixtures::python_inline_process(script)
+}
+
 pub(crate) fn python_run_prompt_error_mock_process() -> StdioProcessSpec {
     let script = r#"
 import json
@@ -478,6 +635,11 @@ for line in sys.stdin:
         thread_id = params.get("threadId", "thr_effort_probe")
         turn_id = "turn_effort_probe"
         effort = params.get("effort", "missing")
+        input_items = params.get("input") or [{}]
+        if "reject effort" in str(input_items[0].get("text", "")) and "effort" in params:
+            sys.stdout.write(json.dumps({"id": rpc_id, "error": {"code": -32602, "message": "unsupported parameter: effort"}}) + "\n")
+            sys.stdout.flush()
+            continue
         sys.stdout.write(json.dumps({"method":"turn/started","params":{"threadId":thread_id,"turnId":turn_id}}) + "\n")
         sys.stdout.write(json.dumps({"method":"item/started","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_effort_probe","itemType":"agentMessage"}}) + "\n")
         sys.stdout.write(json.dumps({"method":"item/agentMessage/delta","params":{"threadId":thread_id,"turnId":turn_id,"itemId":"item_effort_probe","delta":str(effort)}}) + "\n")
@@ -500,6 +662,7 @@ import json
 import sys
 
 thread_model = {}
+resume_overrides = {}
 
 for line in sys.stdin:
     line = line.strip()
@@ -530,6 +693,13 @@ for line in sys.stdin:
         sys.stdout.flush()
         continue
 
+    if method == "thread/resume":
+        thread_id = params.get("threadId", "thr_mutation_probe")
+        resume_overrides[thread_id] = params.get("overrides") or {}
+        sys.stdout.write(json.dumps({"id": rpc_id, "result": {"thread": {"id": thread_id}}}) + "\n")
+        sys.stdout.flush()
+        continue
+
     if method == "turn/start":
         thread_id = params.get("threadId", "thr_mutation_probe")
         turn_id = "turn_mutation_probe"
@@ -547,6 +717,7 @@ for line in sys.stdin:
             "turnModel": params.get("model"),
             "text": text_value,
             "itemTypes": item_types,
+            "resumeOverrides": resume_overrides.get(thread_id),
         }
         message = json.dumps(payload, sort_keys=True)
         sys.stdout.write(json.dumps({"method":"turn/started","params":{"threadId":thread_id,"turnId":turn_id}}) + "\n")
@@ -691,7 +862,7 @@ for line in sys.stdin:
         if rpc_id is None:
             # Interrupt must be an RPC request; ignore notifications.
             continue
-        sys.stdout.write(json.dumps({"method":"probe/interruptSeen","params":{"threadId":params.get("threadId"),"turnId":params.get("turnId")}}) + "\n")
+        sys.stdout.write(json.dumps({"method":"probe/interruptSeen","params":{"threadId":params.get("threadId"),"turnId":params.get("turnId"),"reason":params.get("reason")}}) + "\n")
         sys.stdout.write(json.dumps({"id": rpc_id, "result": {"ok": True}}) + "\n")
         sys.stdout.flush()
         continue
@@ -1048,6 +1219,55 @@ for line in sys.stdin:
     crate::test_fixtures::python_inline_process(script)
 }
 
+pub(crate) fn python_model_list_missing_process() -> StdioProcessSpec {
+    let script = r#"
+import json
+import sys
+
+archived = []
+
+for line in sys.stdin:
+    line = line.strip()
+    if not line:
+        continue
+    try:
+        msg = json.loads(line)
+    except Exception:
+        continue
+
+    method = msg.get("method")
+    rpc_id = msg.get("id")
+    params = msg.get("params") or {}
+
+    if method == "initialize" and rpc_id is not None:
+        sys.stdout.write(json.dumps({"id": rpc_id, "result": {"ready": True}}) + "\n")
+        sys.stdout.flush()
+        continue
+
+    if rpc_id is None:
+        continue
+
+    if method == "model/list":
+        out = {"id": rpc_id, "error": {"code": -32601, "message": f"unknown method {method}"}}
+    elif method == "thread/start" and params.get("model") == "gpt-retired":
+        out = {"id": rpc_id, "error": {"code": -32602, "message": "model gpt-retired is not available"}}
+    elif method == "thread/start":
+        out = {"id": rpc_id, "result": {"thread": {"id": "thr_probe"}, "ephemeral": params.get("ephemeral")}}
+    elif method == "thread/archive":
+        archived.append(params.get("threadId"))
+        out = {"id": rpc_id, "result": {}}
+    elif method == "probe/archived":
+        out = {"id": rpc_id, "result": {"archived": archived}}
+    else:
+        out = {"id": rpc_id, "result": {"echoMethod": method, "params": params}}
+
+    sys.stdout.write(json.dumps(out) + "\n")
+    sys.stdout.flush()
+"#;
+
+    crate::test_fixtures::python_inline_process(script)
+}
+
 pub(crate) async fn spawn_mock_runtime() -> Runtime {
     let cfg = RuntimeConfig::new(python_api_mock_process());
     Runtime::spawn_local(cfg).await.expect("spawn runtime")
@@ -1063,6 +1283,11 @@ pub(crate) async fn spawn_run_prompt_cross_thread_noise_runtime() -> Runtime {
     Runtime::spawn_local(cfg).await.expect("spawn runtime")
 }
 
+pub(crate) async fn spawn_run_prompt_same_thread_interleaved_runtime() -> Runtime {
+    let cfg = RuntimeConfig::new(python_run_prompt_same_thread_interleaved_process());
+    Runtime::spawn_local(cfg).await.expect("spawn runtime")
+}
+
 pub(crate) async fn spawn_run_prompt_error_runtime() -> Runtime {
     let cfg = RuntimeConfig::new(python_run_prompt_error_mock_process());
     Runtime::spawn_local(cfg).await.expect("spawn runtime")
@@ -1088,6 +1313,22 @@ pub(crate) async fn spawn_run_prompt_streaming_timeout_runtime() -> Runtime {
     Runtime::spawn_local(cfg).await.expect("spawn runtime")
 }
 
+pub(crate) async fn spawn_run_prompt_streaming_timeout_runtime_with_default(
+    default_prompt_timeout: Duration,
+) -> Runtime {
+    let cfg = RuntimeConfig::new(python_run_prompt_streaming_timeout_process())
+        .with_default_prompt_timeout(default_prompt_timeout);
+    Runtime::spawn_local(cfg).await.expect("spawn runtime")
+}
+
+pub(crate) async fn spawn_run_prompt_streaming_timeout_runtime_with_max_turns(
+    max_concurrent_turns: usize,
+) -> Runtime {
+    let cfg = RuntimeConfig::new(python_run_prompt_streaming_timeout_process())
+        .with_max_concurrent_turns(max_concurrent_turns);
+    Runtime::spawn_local(cfg).await.expect("spawn runtime")
+}
+
 pub(crate) async fn spawn_run_prompt_interrupt_probe_runtime() -> Runtime {
     let cfg = RuntimeConfig::new(python_run_prompt_interrupt_probe_process());
     Runtime::spawn_local(cfg).await.expect("spawn runtime")
@@ -1103,6 +1344,15 @@ pub(crate) async fn spawn_thread_resume_mismatched_id_runtime() -> Runtime {
     Runtime::spawn_local(cfg).await.expect("spawn runtime")
 }
 
+pub(crate) async fn spawn_thread_resume_id_change_accepting_runtime(
+    observer: ResumeIdChangeObserver,
+) -> Runtime {
+    let cfg = RuntimeConfig::new(python_thread_resume_mismatched_id_process())
+        .with_accept_resume_id_change(true)
+        .with_resume_id_change_observer(observer);
+    Runtime::spawn_local(cfg).await.expect("spawn runtime")
+}
+
 pub(crate) async fn spawn_run_prompt_lagged_completion_runtime() -> Runtime {
     let mut cfg = RuntimeConfig::new(python_run_prompt_lagged_completion_process());
     cfg.live_channel_capacity = 1;
@@ -1122,7 +1372,29 @@ pub(crate) async fn spawn_run_prompt_lagged_cancelled_runtime() -> Runtime {
     Runtime::spawn_local(cfg).await.expect("spawn runtime")
 }
 
+pub(crate) async fn spawn_run_prompt_runtime_with_prompt_cache(
+    prompt_cache: Arc<dyn PromptCache>,
+) -> Runtime {
+    let cfg = RuntimeConfig::new(python_run_prompt_mock_process()).with_prompt_cache(prompt_cache);
+    Runtime::spawn_local(cfg).await.expect("spawn runtime")
+}
+
 pub(crate) async fn spawn_run_prompt_runtime_with_hooks(hooks: RuntimeHookConfig) -> Runtime {
     let cfg = RuntimeConfig::new(python_run_prompt_mock_process()).with_hooks(hooks);
     Runtime::spawn_local(cfg).await.expect("spawn runtime")
 }
+
+pub(crate) async fn spawn_run_prompt_runtime_with_id_generator(
+    hooks: RuntimeHookConfig,
+    id_generator: Arc<dyn IdGenerator>,
+) -> Runtime {
+    let cfg = RuntimeConfig::new(python_run_prompt_mock_process())
+        .with_hooks(hooks)
+        .with_id_generator(id_generator);
+    Runtime::spawn_local(cfg).await.expect("spawn runtime")
+}
+
+pub(crate) async fn spawn_model_list_missing_runtime() -> Runtime {
+    let cfg = RuntimeConfig::new(python_model_list_missing_process());
+    Runtime::spawn_local(cfg).await.expect("spawn runtime")
+}
@@ -1,12 +1,16 @@
 use crate::runtime::errors::RpcError;
 use crate::runtime::events::extract_skills_changed_notification;
-use serde_json::json;
+use crate::runtime::RuntimeConfig;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::time::timeout;
 
 use super::super::*;
 use super::support::{
-    spawn_mock_runtime, spawn_thread_resume_mismatched_id_runtime,
+    python_api_mock_process, spawn_mock_runtime, spawn_model_list_missing_runtime,
+    spawn_run_prompt_interrupt_probe_runtime, spawn_run_prompt_same_thread_interleaved_runtime,
+    spawn_thread_resume_id_change_accepting_runtime, spawn_thread_resume_mismatched_id_runtime,
     spawn_thread_resume_missing_id_runtime,
 };
 
@@ -61,6 +65,7 @@ async fn typed_thread_and_turn_roundtrip() {
     assert_eq!(turn.thread_id, "thr_typed");
     assert_eq!(turn.turn_id, "turn_typed");
 
+    #[allow(deprecated)]
     let steered = thread
         .turn_steer(
             &turn.turn_id,
@@ -71,6 +76,22 @@ async fn typed_thread_and_turn_roundtrip() {
         .await
         .expect("turn steer");
     assert_eq!(steered, "turn_typed");
+    let outcome = thread
+        .turn_steer_outcome(
+            &turn.turn_id,
+            vec![InputItem::Text {
+                text: "continue".to_owned(),
+            }],
+        )
+        .await
+        .expect("turn steer outcome");
+    assert_eq!(
+        outcome,
+        SteerOutcome {
+            turn_id: "turn_typed".to_owned(),
+            was_new_turn: false,
+        }
+    );
 
     thread
         .turn_interrupt(&turn.turn_id)
@@ -129,6 +150,51 @@ async fn thread_start_rejects_privileged_sandbox_without_non_never_approval() {
     .await;
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn allow_unrestricted_sandbox_accepts_never_approval_but_keeps_scope_and_opt_in() {
+    let cfg = RuntimeConfig::new(python_api_mock_process()).allow_unrestricted_sandbox(true);
+    let runtime = Runtime::spawn_local(cfg).await.expect("spawn runtime");
+    let params = |cwd: Option<&str>, approved: bool| ThreadStartParams {
+        cwd: cwd.map(ToOwned::to_owned),
+        approval_policy: Some(ApprovalPolicy::Never),
+        sandbox_policy: Some(SandboxPolicy::Preset(SandboxPreset::DangerFullAccess)),
+        privileged_escalation_approved: approved,
+        ..ThreadStartParams::default()
+    };
+
+    let thread = runtime
+        .thread_start(params(Some("/tmp"), true))
+        .await
+        .expect("never approval allowed for unrestricted sandbox");
+    thread
+        .turn_start(TurnStartParams {
+            input: vec![InputItem::Text {
+                text: "hi".to_owned(),
+            }],
+            cwd: Some("/tmp".to_owned()),
+            approval_policy: Some(ApprovalPolicy::Never),
+            sandbox_policy: Some(SandboxPolicy::Preset(SandboxPreset::DangerFullAccess)),
+            privileged_escalation_approved: true,
+            ..TurnStartParams::default()
+        })
+        .await
+        .expect("turn with never approval allowed for unrestricted sandbox");
+    assert_invalid_request(
+        runtime
+            .thread_start(params(None, true))
+            .await
+            .expect_err("scope still required"),
+    );
+    assert_invalid_request(
+        runtime
+            .thread_start(params(Some("/tmp"), false))
+            .await
+            .expect_err("explicit opt-in still required"),
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn thread_start_rejects_privileged_sandbox_without_scope() {
     assert_thread_start_rejects_privileged_sandbox(
@@ -236,6 +302,32 @@ async fn thread_resume_requires_matching_thread_id() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn thread_resume_accepts_changed_id_when_configured() {
+    let migrations = Arc::new(Mutex::new(Vec::<(String, String)>::new()));
+    let sink = Arc::clone(&migrations);
+    let runtime =
+        spawn_thread_resume_id_change_accepting_runtime(Arc::new(move |requested, resumed| {
+            sink.lock()
+                .expect("migrations lock")
+                .push((requested.to_owned(), resumed.to_owned()));
+        }))
+        .await;
+
+    let thread = runtime
+        .thread_resume("thr_expected", ThreadStartParams::default())
+        .await
+        .expect("changed resume id must be accepted when configured");
+
+    assert_eq!(thread.thread_id, "thr_unexpected");
+    assert_eq!(
+        *migrations.lock().expect("migrations lock"),
+        vec![("thr_expected".to_owned(), "thr_unexpected".to_owned())]
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn turn_start_rejects_empty_input() {
     let runtime = spawn_mock_runtime().await;
@@ -269,6 +361,147 @@ async fn runtime_archive_and_interrupt_wrappers_work() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn thread_metadata_roundtrips_and_surfaces_in_thread_list() {
+    let runtime = spawn_mock_runtime().await;
+    let labels = json!({"projectId": "p-42", "owner": "ops"});
+
+    assert_eq!(
+        runtime
+            .thread_get_metadata("thr_list")
+            .await
+            .expect("get before set"),
+        Value::Null
+    );
+    runtime
+        .thread_set_metadata("thr_list", labels.clone())
+        .await
+        .expect("set metadata");
+    assert_eq!(
+        runtime
+            .thread_get_metadata("thr_list")
+            .await
+            .expect("get metadata"),
+        labels
+    );
+
+    let listed = runtime
+        .thread_list(ThreadListParams::default())
+        .await
+        .expect("thread list");
+    assert_eq!(listed.data[0].metadata(), Some(&labels));
+
+    let err = runtime
+        .thread_set_metadata("thr_list", json!(["not", "an", "object"]))
+        .await
+        .expect_err("non-object metadata must be rejected");
+    assert_invalid_request(err);
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn thread_metadata_falls_back_to_local_state_without_server_support() {
+    let runtime = spawn_mock_runtime().await;
+    let labels = json!({"owner": "ops"});
+
+    runtime
+        .thread_set_metadata("thr_legacy", labels.clone())
+        .await
+        .expect("fallback set");
+    assert_eq!(
+        runtime
+            .thread_get_metadata("thr_legacy")
+            .await
+            .expect("fallback get"),
+        labels
+    );
+    assert_eq!(
+        runtime.state_snapshot().threads["thr_legacy"].metadata,
+        Some(labels)
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn runtime_check_model_pages_through_model_list() {
+    let runtime = spawn_mock_runtime().await;
+
+    assert_eq!(
+        runtime.check_model("gpt-mini").await.expect("check listed"),
+        ModelStatus::Available
+    );
+    let status = runtime
+        .check_model("gpt-unknown")
+        .await
+        .expect("check unlisted");
+    assert!(!status.is_available());
+    assert_eq!(
+        status,
+        ModelStatus::Unavailable {
+            reason: "model 'gpt-unknown' is not listed by model/list".to_owned(),
+        }
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn runtime_check_model_falls_back_to_probe_thread_without_model_list() {
+    let runtime = spawn_model_list_missing_runtime().await;
+
+    assert_eq!(
+        runtime
+            .check_model("gpt-main")
+            .await
+            .expect("probe accepted"),
+        ModelStatus::Available
+    );
+    let archived = runtime
+        .call_raw("probe/archived", json!({}))
+        .await
+        .expect("archived probe");
+    assert_eq!(archived["archived"], json!(["thr_probe"]));
+
+    match runtime
+        .check_model("gpt-retired")
+        .await
+        .expect("probe rejected")
+    {
+        ModelStatus::Unavailable { reason } => {
+            assert!(reason.contains("gpt-retired is not available"), "{reason}");
+        }
+        other => panic!("unexpected status: {other:?}"),
+    }
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn runtime_thread_read_last_n_turns_is_sent_and_applied_when_server_ignores_it() {
+    let runtime = spawn_mock_runtime().await;
+
+    let read = runtime
+        .thread_read(ThreadReadParams {
+            thread_id: "thr_many_turns".to_owned(),
+            include_turns: Some(true),
+            last_n_turns: Some(2),
+        })
+        .await
+        .expect("thread read");
+    assert_eq!(read.thread.extra.get("lastNTurnsSeen"), Some(&json!(2)));
+    let turn_ids: Vec<&str> = read
+        .thread
+        .turns
+        .iter()
+        .map(|turn| turn.id.as_str())
+        .collect();
+    assert_eq!(turn_ids, ["turn_many_3", "turn_many_4"]);
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn runtime_thread_read_rejects_empty_thread_id_by_known_validation() {
     let runtime = spawn_mock_runtime().await;
@@ -277,6 +510,7 @@ async fn runtime_thread_read_rejects_empty_thread_id_by_known_validation() {
         .thread_read(ThreadReadParams {
             thread_id: String::new(),
             include_turns: Some(true),
+            last_n_turns: None,
         })
         .await
         .expect_err("thread read must reject empty thread id");
@@ -310,6 +544,58 @@ async fn runtime_turn_interrupt_rejects_empty_ids_by_known_validation() {
     runtime.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn runtime_export_thread_flattens_turns() {
+    let runtime = spawn_mock_runtime().await;
+
+    let export = runtime
+        .export_thread("thr_export")
+        .await
+        .expect("export thread");
+    assert_eq!(export.thread_id, "thr_export");
+    assert_eq!(export.turns.len(), 1);
+    assert_eq!(export.turns[0].id, "turn_read_1");
+    assert_eq!(export.turns[0].status, ThreadTurnStatus::Completed);
+    assert_eq!(
+        export.turns[0].items,
+        vec![ThreadExportItem {
+            item_type: ThreadItemType::AgentMessage,
+            text_or_summary: "ok".to_owned(),
+        }]
+    );
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn thread_pool_hands_out_prewarmed_threads_and_replenishes() {
+    let runtime = spawn_mock_runtime().await;
+
+    let thread = runtime
+        .prewarm_thread(ThreadStartParams::default())
+        .await
+        .expect("prewarm thread");
+    assert_eq!(thread.thread_id, "thr_typed");
+
+    let pool = ThreadPool::new(&runtime, ThreadStartParams::default(), 2);
+    assert_eq!(pool.ready_count(), 0);
+    pool.fill().await.expect("fill pool");
+    assert_eq!(pool.ready_count(), 2);
+
+    let thread = pool.acquire().await.expect("acquire pooled thread");
+    assert_eq!(thread.thread_id, "thr_typed");
+    timeout(Duration::from_secs(2), async {
+        while pool.ready_count() < pool.target_size() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("pool replenished in background");
+    assert_eq!(pool.ready_count(), 2);
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn runtime_thread_read_list_loaded_and_rollback_wrappers_work() {
     let runtime = spawn_mock_runtime().await;
@@ -318,6 +604,7 @@ async fn runtime_thread_read_list_loaded_and_rollback_wrappers_work() {
         .thread_read(ThreadReadParams {
             thread_id: "thr_typed".to_owned(),
             include_turns: Some(true),
+            last_n_turns: None,
         })
         .await
         .expect("thread read");
@@ -371,6 +658,16 @@ async fn runtime_thread_read_list_loaded_and_rollback_wrappers_work() {
     assert_eq!(loaded.data, vec!["thr_loaded_1".to_owned()]);
     assert_eq!(loaded.next_cursor.as_deref(), Some("loaded_cursor"));
 
+    let handles = runtime
+        .loaded_thread_handles(ThreadLoadedListParams {
+            cursor: Some("loaded_cursor".to_owned()),
+            limit: Some(1),
+        })
+        .await
+        .expect("loaded thread handles");
+    let handle_ids: Vec<&str> = handles.iter().map(|h| h.thread_id.as_str()).collect();
+    assert_eq!(handle_ids, vec!["thr_loaded_1"]);
+
     let rollback = runtime
         .thread_rollback(ThreadRollbackParams {
             thread_id: "thr_typed".to_owned(),
@@ -383,6 +680,11 @@ async fn runtime_thread_read_list_loaded_and_rollback_wrappers_work() {
         rollback.thread.extra.get("rolledBackTurns"),
         Some(&json!(3))
     );
+    assert_eq!(
+        rollback.rolled_back_turn_ids,
+        vec!["turn_rolled_0", "turn_rolled_1", "turn_rolled_2"]
+    );
+    assert_eq!(rollback.rolled_back_count(), Some(3));
     assert_eq!(rollback.thread.turns.len(), 1);
     assert_eq!(rollback.thread.turns[0].status, ThreadTurnStatus::Failed);
     assert_eq!(
@@ -451,3 +753,234 @@ async fn live_stream_exposes_skills_changed_notification() {
 
     runtime.shutdown().await.expect("shutdown");
 }
+
+#[tokio::test(flavor = "current_thread")]
+async fn thread_archive_many_returns_per_id_results_in_input_order() {
+    let runtime = spawn_mock_runtime().await;
+    let ids: Vec<String> = (0..10)
+        .map(|index| format!("thr_bulk_{index}"))
+        .chain(["thr_missing".to_owned()])
+        .collect();
+
+    let archived = runtime.thread_archive_many(&ids).await;
+    assert_eq!(
+        archived
+            .iter()
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>(),
+        ids
+    );
+    assert!(archived[..10].iter().all(|(_, result)| result.is_ok()));
+    assert!(matches!(archived[10].1, Err(RpcError::ServerError(_))));
+
+    runtime
+        .thread_unarchive("thr_bulk_0")
+        .await
+        .expect("thread unarchive");
+    let restored = runtime.thread_unarchive_many(&ids[..2]).await;
+    assert!(restored.iter().all(|(_, result)| result.is_ok()));
+    assert!(runtime.thread_archive_many(&[]).await.is_empty());
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn thread_read_many_returns_per_id_results_in_input_order() {
+    let runtime = spawn_mock_runtime().await;
+    let ids: Vec<String> = ["thr_read_a", "thr_missing", "thr_read_b", "thr_read_c"]
+        .map(str::to_owned)
+        .to_vec();
+    let params = ids
+        .iter()
+        .map(|thread_id| ThreadReadParams {
+            thread_id: thread_id.clone(),
+            include_turns: Some(true),
+            last_n_turns: None,
+        })
+        .collect();
+
+    let reads = runtime.thread_read_many(params, 2).await;
+    assert_eq!(
+        reads.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>(),
+        ids
+    );
+    for (thread_id, result) in reads.iter().filter(|(id, _)| id != "thr_missing") {
+        let read = result.as_ref().expect("thread read");
+        assert_eq!(&read.thread.id, thread_id);
+        assert_eq!(read.thread.turns.len(), 1);
+    }
+    assert!(matches!(reads[1].1, Err(RpcError::ServerError(_))));
+    assert!(runtime.thread_read_many(Vec::new(), 0).await.is_empty());
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn bounded_ordered_many_reports_panicked_tasks_as_internal_errors() {
+    let results =
+        super::super::thread_api::bounded_ordered_many(vec![1, 2, 3], 2, |n| async move {
+            if n == 2 {
+                panic!("bulk task {n} exploded");
+            }
+            Ok::<_, RpcError>(n * 10)
+        })
+        .await;
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0], Ok(10));
+    match &results[1] {
+        Err(RpcError::Internal(message)) => assert!(message.contains("panic"), "{message}"),
+        other => panic!("expected internal error, got {other:?}"),
+    }
+    assert_eq!(results[2], Ok(30));
+}
+
+fn guarded_turn_params() -> TurnStartParams {
+    TurnStartParams {
+        input: vec![InputItem::Text {
+            text: "long running".to_owned(),
+        }],
+        ..TurnStartParams::default()
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn turn_guard_drop_sends_best_effort_interrupt() {
+    let runtime = spawn_run_prompt_interrupt_probe_runtime().await;
+    let mut live_rx = runtime.subscribe_live();
+    let thread = runtime
+        .thread_start(ThreadStartParams::default())
+        .await
+        .expect("thread start");
+
+    let guard = thread
+        .turn_start_guarded(guarded_turn_params())
+        .await
+        .expect("guarded turn start");
+    assert_eq!(guard.thread_id(), "thr_interrupt_probe");
+    assert_eq!(guard.turn_id(), "turn_interrupt_probe");
+    drop(guard);
+
+    let seen = timeout(Duration::from_secs(2), async {
+        loop {
+            let envelope = live_rx.recv().await.expect("live closed");
+            if envelope.method.as_deref() == Some("probe/interruptSeen") {
+                return envelope;
+            }
+        }
+    })
+    .await
+    .expect("interrupt must be sent on guard drop");
+    assert_eq!(seen.thread_id.as_deref(), Some("thr_interrupt_probe"));
+    assert_eq!(seen.turn_id.as_deref(), Some("turn_interrupt_probe"));
+    assert_eq!(seen.json["params"]["reason"], "cancelled");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn turn_interrupt_with_reason_sends_reason_field() {
+    let runtime = spawn_run_prompt_interrupt_probe_runtime().await;
+    let mut live_rx = runtime.subscribe_live();
+
+    runtime
+        .turn_interrupt_with_reason("thr_interrupt_probe", "turn_interrupt_probe", "policy")
+        .await
+        .expect("interrupt with reason");
+
+    let seen = timeout(Duration::from_secs(2), async {
+        loop {
+            let envelope = live_rx.recv().await.expect("live closed");
+            if envelope.method.as_deref() == Some("probe/interruptSeen") {
+                return envelope;
+            }
+        }
+    })
+    .await
+    .expect("interrupt must reach the server");
+    assert_eq!(seen.json["params"]["reason"], "policy");
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn turn_guard_disarm_keeps_turn_running() {
+    let runtime = spawn_run_prompt_interrupt_probe_runtime().await;
+    let mut live_rx = runtime.subscribe_live();
+    let thread = runtime
+        .thread_start(ThreadStartParams::default())
+        .await
+        .expect("thread start");
+
+    let turn = thread
+        .turn_start_guarded(guarded_turn_params())
+        .await
+        .expect("guarded turn start")
+        .disarm();
+    assert_eq!(turn.turn_id, "turn_interrupt_probe");
+
+    tokio::task::yield_now().await;
+    runtime
+        .call_raw("probe/barrier", json!({}))
+        .await
+        .expect("barrier call");
+    while let Ok(envelope) = live_rx.try_recv() {
+        assert_ne!(
+            envelope.method.as_deref(),
+            Some("probe/interruptSeen"),
+            "disarmed guard must not interrupt"
+        );
+    }
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn thread_wait_quiescent_waits_for_every_in_progress_turn() {
+    let runtime = spawn_run_prompt_same_thread_interleaved_runtime().await;
+    runtime
+        .thread_wait_quiescent("thr_unknown", Duration::from_millis(10))
+        .await
+        .expect("unknown thread is quiescent");
+
+    let thread = runtime
+        .thread_start(ThreadStartParams::default())
+        .await
+        .expect("thread start");
+    let text_turn = || TurnStartParams {
+        input: vec![InputItem::Text {
+            text: "hi".to_owned(),
+        }],
+        ..TurnStartParams::default()
+    };
+    thread.turn_start(text_turn()).await.expect("first turn");
+    timeout(Duration::from_secs(2), async {
+        while runtime
+            .state_snapshot()
+            .threads
+            .get("thr_shared")
+            .is_none_or(|state| state.in_progress_turn_ids().is_empty())
+        {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("first turn projected as in progress");
+
+    let err = runtime
+        .thread_wait_quiescent("thr_shared", Duration::from_millis(50))
+        .await
+        .expect_err("turn_1 is still running");
+    assert_eq!(err, RpcError::Timeout);
+
+    // The mock completes both turns once the second one starts.
+    let waiter = runtime.thread_wait_quiescent("thr_shared", Duration::from_secs(2));
+    let (waited, second) = tokio::join!(waiter, thread.turn_start(text_turn()));
+    second.expect("second turn");
+    waited.expect("thread settles");
+    assert!(runtime.state_snapshot().threads["thr_shared"]
+        .in_progress_turn_ids()
+        .is_empty());
+
+    runtime.shutdown().await.expect("shutdown");
+}
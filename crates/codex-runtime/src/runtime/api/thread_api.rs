@@ -1,24 +1,85 @@
+use std::collections::HashMap;
+use std::future::Future;
 use std::time::Duration;
 
 use crate::plugin::{BlockReason, HookPhase};
 use serde_json::{Map, Value};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinSet;
 
 use crate::runtime::core::Runtime;
+use crate::runtime::detached_task::{current_detached_task_plan, spawn_detached_task};
 use crate::runtime::errors::RpcError;
 use crate::runtime::hooks::RuntimeHookConfig;
 use crate::runtime::rpc_contract::{methods, RpcValidationMode};
+use crate::runtime::state::TurnStatus;
+use crate::runtime::turn_lifecycle::{
+    interrupt_turn_best_effort_with_timeout, INTERRUPT_REASON_CANCELLED,
+};
 use crate::runtime::turn_output::{parse_thread_id, parse_turn_id};
 
 use super::flow::{
     apply_pre_hook_actions_to_session, result_status, HookContextInput, HookExecutionState,
     SessionMutationState,
 };
+use super::prompt_run::INTERRUPT_RPC_TIMEOUT;
 use super::wire::{
     deserialize_result, input_item_to_wire, serialize_params, thread_overrides_to_wire,
-    turn_start_params_to_wire, validate_turn_start_security,
+    turn_start_params_to_wire, validate_turn_extra_params, validate_turn_start_security,
 };
 use super::*;
 
+/// Upper bound on concurrent RPCs issued by bulk thread helpers.
+const THREAD_BULK_CONCURRENCY: usize = 8;
+
+impl TurnGuard {
+    /// Borrow the guarded turn identity.
+    pub fn turn(&self) -> &TurnHandle {
+        &self.turn
+    }
+
+    pub fn turn_id(&self) -> &str {
+        &self.turn.turn_id
+    }
+
+    pub fn thread_id(&self) -> &str {
+        &self.turn.thread_id
+    }
+
+    /// Release the turn without interrupting it.
+    /// Allocation: none. Complexity: O(1).
+    pub fn disarm(mut self) -> TurnHandle {
+        self.armed = false;
+        self.turn.clone()
+    }
+}
+
+impl Drop for TurnGuard {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let runtime = self.runtime.clone();
+        let fallback_runtime = runtime.clone();
+        let thread_id = self.turn.thread_id.clone();
+        let turn_id = self.turn.turn_id.clone();
+        spawn_detached_task(
+            async move {
+                interrupt_turn_best_effort_with_timeout(
+                    &runtime,
+                    &thread_id,
+                    &turn_id,
+                    INTERRUPT_REASON_CANCELLED,
+                    INTERRUPT_RPC_TIMEOUT,
+                )
+                .await;
+            },
+            current_detached_task_plan("turn_guard_interrupt"),
+            move || fallback_runtime.record_detached_task_init_failed(),
+        );
+    }
+}
+
 impl ThreadHandle {
     pub fn runtime(&self) -> &crate::runtime::core::Runtime {
         &self.runtime
@@ -26,7 +87,8 @@ impl ThreadHandle {
 
     pub async fn turn_start(&self, p: TurnStartParams) -> Result<TurnHandle, RpcError> {
         ensure_turn_input_not_empty(&p.input)?;
-        validate_turn_start_security(&p)?;
+        validate_turn_start_security(&p, self.runtime.allow_unrestricted_sandbox())?;
+        validate_turn_extra_params(&p.extra_params)?;
 
         let response = self
             .runtime
@@ -46,14 +108,41 @@ impl ThreadHandle {
         })
     }
 
+    /// Start a turn that is interrupted (best effort) when the returned guard drops.
+    /// Opt-in cancel-on-drop; plain `turn_start` stays fire-and-forget.
+    pub async fn turn_start_guarded(&self, p: TurnStartParams) -> Result<TurnGuard, RpcError> {
+        let turn = self.turn_start(p).await?;
+        Ok(TurnGuard {
+            turn,
+            runtime: self.runtime.clone(),
+            armed: true,
+        })
+    }
+
     /// Start a follow-up turn anchored to an expected previous turn id.
     /// Allocation: JSON params + input item wire objects.
     /// Complexity: O(n), n = input item count.
+    #[deprecated(note = "use `turn_steer_outcome`, which reports whether a new turn started")]
     pub async fn turn_steer(
         &self,
         expected_turn_id: &str,
         input: Vec<InputItem>,
     ) -> Result<super::TurnId, RpcError> {
+        Ok(self
+            .turn_steer_outcome(expected_turn_id, input)
+            .await?
+            .turn_id)
+    }
+
+    /// Steer with follow-up input anchored to `expected_turn_id`. `was_new_turn` is set
+    /// when the server answers with a different turn id than the expected one.
+    /// Allocation: JSON params + input item wire objects.
+    /// Complexity: O(n), n = input item count.
+    pub async fn turn_steer_outcome(
+        &self,
+        expected_turn_id: &str,
+        input: Vec<InputItem>,
+    ) -> Result<SteerOutcome, RpcError> {
         ensure_turn_input_not_empty(&input)?;
 
         let mut params = Map::<String, Value>::new();
@@ -70,10 +159,14 @@ impl ThreadHandle {
             .runtime
             .call_validated(methods::TURN_START, Value::Object(params))
             .await?;
-        parse_turn_id(&response).ok_or_else(|| {
+        let turn_id = parse_turn_id(&response).ok_or_else(|| {
             RpcError::InvalidRequest(format!(
                 "turn/start(steer) missing turn id in result: {response}"
             ))
+        })?;
+        Ok(SteerOutcome {
+            was_new_turn: turn_id != expected_turn_id,
+            turn_id,
         })
     }
 
@@ -116,7 +209,7 @@ impl Runtime {
             scoped_hooks,
         )
         .await;
-        self.publish_hook_report(hook_state.report);
+        self.publish_hook_report(&hook_state.correlation_id, hook_state.report);
         result
     }
 
@@ -151,11 +244,11 @@ impl Runtime {
             scoped_hooks,
         )
         .await;
-        self.publish_hook_report(hook_state.report);
+        self.publish_hook_report(&hook_state.correlation_id, hook_state.report);
         result
     }
 
-    async fn prepare_session_start_hooks(
+    pub(super) async fn prepare_session_start_hooks(
         &self,
         p: ThreadStartParams,
         thread_id: Option<&str>,
@@ -200,7 +293,7 @@ impl Runtime {
         Ok((p, hook_state, cwd, model))
     }
 
-    async fn finalize_session_start_hooks(
+    pub(super) async fn finalize_session_start_hooks(
         &self,
         hook_state: &mut HookExecutionState,
         cwd: Option<&str>,
@@ -223,6 +316,7 @@ impl Runtime {
                 thread_id: post_thread_id,
                 turn_id: None,
                 main_status: Some(result_status(result)),
+                assistant_text: None,
             },
             scoped_hooks,
         )
@@ -235,7 +329,7 @@ impl Runtime {
         p: ThreadStartParams,
     ) -> Result<ThreadHandle, RpcError> {
         let p = super::escalate_approval_if_tool_hooks(self, p);
-        super::wire::validate_thread_start_security(&p)?;
+        super::wire::validate_thread_start_security(&p, self.allow_unrestricted_sandbox())?;
         let mut params = Map::<String, Value>::new();
         params.insert("threadId".to_owned(), Value::String(thread_id.to_owned()));
         let overrides = thread_overrides_to_wire(&p);
@@ -251,7 +345,7 @@ impl Runtime {
                 "thread/resume missing thread id in result: {response}"
             ))
         })?;
-        if resumed != thread_id {
+        if resumed != thread_id && !self.accept_resume_id_change(thread_id, &resumed) {
             return Err(RpcError::InvalidRequest(format!(
                 "thread/resume returned mismatched thread id: requested={thread_id} actual={resumed}"
             )));
@@ -283,21 +377,161 @@ impl Runtime {
     /// Allocation: one JSON object with thread id.
     /// Complexity: O(1).
     pub async fn thread_archive(&self, thread_id: &str) -> Result<(), RpcError> {
+        self.thread_id_call(methods::THREAD_ARCHIVE, thread_id)
+            .await
+    }
+
+    /// Restore one archived thread.
+    /// Allocation: one JSON object with thread id.
+    /// Complexity: O(1).
+    pub async fn thread_unarchive(&self, thread_id: &str) -> Result<(), RpcError> {
+        self.thread_id_call(methods::THREAD_UNARCHIVE, thread_id)
+            .await
+    }
+
+    /// Archive many threads with bounded concurrency.
+    /// Results keep input order; one failure does not stop the others.
+    /// Allocation: one task + result slot per id. Complexity: O(n), n = id count.
+    pub async fn thread_archive_many(
+        &self,
+        thread_ids: &[String],
+    ) -> Vec<(String, Result<(), RpcError>)> {
+        self.thread_id_call_many(methods::THREAD_ARCHIVE, thread_ids)
+            .await
+    }
+
+    /// Unarchive many threads with bounded concurrency.
+    /// Results keep input order; one failure does not stop the others.
+    /// Allocation: one task + result slot per id. Complexity: O(n), n = id count.
+    pub async fn thread_unarchive_many(
+        &self,
+        thread_ids: &[String],
+    ) -> Vec<(String, Result<(), RpcError>)> {
+        self.thread_id_call_many(methods::THREAD_UNARCHIVE, thread_ids)
+            .await
+    }
+
+    /// Attach durable labels (project id, owner, ...) to a thread via `thread/setMetadata`.
+    /// `metadata` must be a JSON object and replaces any previous value.
+    /// When the server answers MethodNotFound, the labels are kept in the local
+    /// `ThreadState::metadata` instead: process-local, absent from `thread_list`, and
+    /// subject to state projection pruning.
+    /// Allocation: one params object. Complexity: O(n), n = metadata size.
+    pub async fn thread_set_metadata(
+        &self,
+        thread_id: &str,
+        metadata: Value,
+    ) -> Result<(), RpcError> {
+        if !metadata.is_object() {
+            return Err(RpcError::InvalidRequest(format!(
+                "{} metadata must be a JSON object",
+                methods::THREAD_SET_METADATA
+            )));
+        }
         let mut params = Map::<String, Value>::new();
         params.insert("threadId".to_owned(), Value::String(thread_id.to_owned()));
-        let _ = self
-            .call_validated(methods::THREAD_ARCHIVE, Value::Object(params))
-            .await?;
+        params.insert("metadata".to_owned(), metadata.clone());
+        match self
+            .call_validated(methods::THREAD_SET_METADATA, Value::Object(params))
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(RpcError::MethodNotFound(_)) => {
+                self.set_local_thread_metadata(thread_id, metadata);
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Read thread labels via `thread/getMetadata` (`Value::Null` when none are set).
+    /// Falls back to the local `ThreadState::metadata` when the server answers MethodNotFound.
+    /// Allocation: one params object + cloned metadata. Complexity: O(n), n = metadata size.
+    pub async fn thread_get_metadata(&self, thread_id: &str) -> Result<Value, RpcError> {
+        let mut params = Map::<String, Value>::new();
+        params.insert("threadId".to_owned(), Value::String(thread_id.to_owned()));
+        match self
+            .call_validated(methods::THREAD_GET_METADATA, Value::Object(params))
+            .await
+        {
+            Ok(result) => Ok(result.get("metadata").cloned().unwrap_or(Value::Null)),
+            Err(RpcError::MethodNotFound(_)) => Ok(self
+                .state_snapshot()
+                .threads
+                .get(thread_id)
+                .and_then(|thread| thread.metadata.clone())
+                .unwrap_or(Value::Null)),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn thread_id_call(&self, method: &'static str, thread_id: &str) -> Result<(), RpcError> {
+        let mut params = Map::<String, Value>::new();
+        params.insert("threadId".to_owned(), Value::String(thread_id.to_owned()));
+        let _ = self.call_validated(method, Value::Object(params)).await?;
         Ok(())
     }
 
+    async fn thread_id_call_many(
+        &self,
+        method: &'static str,
+        thread_ids: &[String],
+    ) -> Vec<(String, Result<(), RpcError>)> {
+        let results =
+            bounded_ordered_many(thread_ids.to_vec(), THREAD_BULK_CONCURRENCY, |thread_id| {
+                let runtime = self.clone();
+                async move { runtime.thread_id_call(method, &thread_id).await }
+            })
+            .await;
+        thread_ids.iter().cloned().zip(results).collect()
+    }
+
     /// Read one thread by id.
+    /// With `last_n_turns`, at most the last N turns are returned even when the server
+    /// ignores the hint and sends them all.
     /// Allocation: serialized params + decoded response object.
     /// Complexity: O(n), n = thread payload size.
     pub async fn thread_read(&self, p: ThreadReadParams) -> Result<ThreadReadResponse, RpcError> {
         let params = serialize_params(methods::THREAD_READ, &p)?;
         let response = self.call_validated(methods::THREAD_READ, params).await?;
-        deserialize_result(methods::THREAD_READ, response)
+        let mut read: ThreadReadResponse = deserialize_result(methods::THREAD_READ, response)?;
+        if let Some(last_n) = p.last_n_turns {
+            let turns = &mut read.thread.turns;
+            let excess = turns.len().saturating_sub(last_n as usize);
+            turns.drain(..excess);
+        }
+        Ok(read)
+    }
+
+    /// Read many threads with at most `concurrency` (min 1) reads in flight.
+    /// Results keep input order; one failed read does not fail the others.
+    /// Allocation: one task + result slot per read. Complexity: O(n), n = total payload size.
+    pub async fn thread_read_many(
+        &self,
+        params: Vec<ThreadReadParams>,
+        concurrency: usize,
+    ) -> Vec<(ThreadId, Result<ThreadReadResponse, RpcError>)> {
+        let thread_ids: Vec<ThreadId> = params.iter().map(|p| p.thread_id.clone()).collect();
+        let results = bounded_ordered_many(params, concurrency, |p| {
+            let runtime = self.clone();
+            async move { runtime.thread_read(p).await }
+        })
+        .await;
+        thread_ids.into_iter().zip(results).collect()
+    }
+
+    /// Export one thread with its turns in a compact, serializable shape.
+    /// Side effects: sends one thread/read RPC with `includeTurns=true`.
+    /// Allocation: decoded thread payload + one String per item. Complexity: O(n), n = thread payload size.
+    pub async fn export_thread(&self, thread_id: &str) -> Result<ThreadExport, RpcError> {
+        let response = self
+            .thread_read(ThreadReadParams {
+                thread_id: thread_id.to_owned(),
+                include_turns: Some(true),
+                last_n_turns: None,
+            })
+            .await?;
+        Ok(ThreadExport::from_view(&response.thread))
     }
 
     /// List persisted threads with optional filters and pagination.
@@ -323,6 +557,22 @@ impl Runtime {
         deserialize_result(methods::THREAD_LOADED_LIST, response)
     }
 
+    /// List one page of loaded threads as handles, without resuming them.
+    /// Handles are only usable for threads the server still has loaded; any other
+    /// thread id must go through `thread_resume` first.
+    /// Allocation: one handle per returned id. Complexity: O(n), n = returned ids.
+    pub async fn loaded_thread_handles(
+        &self,
+        p: ThreadLoadedListParams,
+    ) -> Result<Vec<ThreadHandle>, RpcError> {
+        let loaded = self.thread_loaded_list(p).await?;
+        Ok(loaded
+            .data
+            .iter()
+            .map(|thread_id| self.loaded_thread_handle(thread_id))
+            .collect())
+    }
+
     /// List skills for one or more working directories.
     /// Allocation: serialized params + decoded inventory payload.
     /// Complexity: O(n), n = number of returned cwd entries + skill metadata size.
@@ -353,7 +603,26 @@ impl Runtime {
         let _ = self
             .call_validated(
                 methods::TURN_INTERRUPT,
-                interrupt_params(thread_id, turn_id),
+                interrupt_params(thread_id, turn_id, None),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Interrupt one in-flight turn and tell the server why (e.g. `"user-cancel"`, `"policy"`).
+    /// Internal interrupts send `"timeout"` (deadline hit) or `"cancelled"` (dropped stream/guard).
+    /// Allocation: one JSON object with thread + turn id + reason.
+    /// Complexity: O(1).
+    pub async fn turn_interrupt_with_reason(
+        &self,
+        thread_id: &str,
+        turn_id: &str,
+        reason: &str,
+    ) -> Result<(), RpcError> {
+        let _ = self
+            .call_validated(
+                methods::TURN_INTERRUPT,
+                interrupt_params(thread_id, turn_id, Some(reason)),
             )
             .await?;
         Ok(())
@@ -367,23 +636,116 @@ impl Runtime {
         thread_id: &str,
         turn_id: &str,
         timeout_duration: Duration,
+    ) -> Result<(), RpcError> {
+        self.turn_interrupt_with_reason_and_timeout(thread_id, turn_id, None, timeout_duration)
+            .await
+    }
+
+    pub(crate) async fn turn_interrupt_with_reason_and_timeout(
+        &self,
+        thread_id: &str,
+        turn_id: &str,
+        reason: Option<&str>,
+        timeout_duration: Duration,
     ) -> Result<(), RpcError> {
         let _ = self
             .call_validated_with_mode_and_timeout(
                 methods::TURN_INTERRUPT,
-                interrupt_params(thread_id, turn_id),
+                interrupt_params(thread_id, turn_id, reason),
                 RpcValidationMode::KnownMethods,
                 timeout_duration,
             )
             .await?;
         Ok(())
     }
+
+    /// Wait until the thread's projected state has no `InProgress` turns.
+    /// Threads the runtime has not seen count as quiescent. Fails with `RpcError::Timeout`
+    /// when `timeout_duration` elapses and `RpcError::TransportClosed` if the live stream ends.
+    /// Side effects: none; re-checks `state_snapshot` after every live envelope.
+    /// Allocation: one live subscription. Complexity: O(e * t), e = envelopes seen, t = turn count.
+    pub async fn thread_wait_quiescent(
+        &self,
+        thread_id: &str,
+        timeout_duration: Duration,
+    ) -> Result<(), RpcError> {
+        // Subscribe before the first check so a turn finishing in between is not missed.
+        let mut live_rx = self.subscribe_live();
+        let deadline = tokio::time::Instant::now() + timeout_duration;
+        loop {
+            if self.thread_is_quiescent(thread_id) {
+                return Ok(());
+            }
+            match tokio::time::timeout_at(deadline, live_rx.recv()).await {
+                Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) => {}
+                Ok(Err(RecvError::Closed)) => return Err(RpcError::TransportClosed),
+                Err(_) => return Err(RpcError::Timeout),
+            }
+        }
+    }
+
+    fn thread_is_quiescent(&self, thread_id: &str) -> bool {
+        match self.state_snapshot().threads.get(thread_id) {
+            Some(thread) => thread
+                .turns
+                .values()
+                .all(|turn| turn.status != TurnStatus::InProgress),
+            None => true,
+        }
+    }
 }
 
-fn interrupt_params(thread_id: &str, turn_id: &str) -> Value {
+/// Run `f` over `inputs` with at most `concurrency` (min 1) tasks in flight.
+/// Results keep input order; a task that panicked or was aborted yields
+/// `RpcError::Internal` carrying its join error.
+/// Allocation: one task + result slot per input. Complexity: O(n), n = input count.
+pub(super) async fn bounded_ordered_many<I, T, F, Fut>(
+    inputs: Vec<I>,
+    concurrency: usize,
+    f: F,
+) -> Vec<Result<T, RpcError>>
+where
+    F: Fn(I) -> Fut,
+    Fut: Future<Output = Result<T, RpcError>> + Send + 'static,
+    T: Send + 'static,
+{
+    let concurrency = concurrency.max(1);
+    let mut slots: Vec<Option<Result<T, RpcError>>> = (0..inputs.len()).map(|_| None).collect();
+    let mut task_indexes = HashMap::with_capacity(concurrency);
+    let mut pending = inputs.into_iter().enumerate();
+    let mut tasks = JoinSet::new();
+    loop {
+        while tasks.len() < concurrency {
+            let Some((index, input)) = pending.next() else {
+                break;
+            };
+            let handle = tasks.spawn(f(input));
+            task_indexes.insert(handle.id(), index);
+        }
+        let (id, result) = match tasks.join_next_with_id().await {
+            Some(Ok((id, result))) => (id, result),
+            Some(Err(err)) => (err.id(), Err(RpcError::Internal(err.to_string()))),
+            None => break,
+        };
+        if let Some(index) = task_indexes.remove(&id) {
+            slots[index] = Some(result);
+        }
+    }
+    slots
+        .into_iter()
+        .map(|slot| {
+            slot.unwrap_or_else(|| Err(RpcError::Internal("bulk task lost its result".to_owned())))
+        })
+        .collect()
+}
+
+fn interrupt_params(thread_id: &str, turn_id: &str, reason: Option<&str>) -> Value {
     let mut params = Map::<String, Value>::new();
     params.insert("threadId".to_owned(), Value::String(thread_id.to_owned()));
     params.insert("turnId".to_owned(), Value::String(turn_id.to_owned()));
+    if let Some(reason) = reason {
+        params.insert("reason".to_owned(), Value::String(reason.to_owned()));
+    }
     Value::Object(params)
 }
 
@@ -0,0 +1,217 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use arc_swap::ArcSwapOption;
+
+use crate::runtime::core::Runtime;
+use crate::runtime::detached_task::{current_detached_task_plan, spawn_detached_task};
+use crate::runtime::errors::RpcError;
+use crate::runtime::hooks::RuntimeHookConfig;
+
+use super::{PromptRunError, PromptRunParams, PromptRunResult, ThreadHandle, ThreadStartParams};
+
+impl Runtime {
+    /// Start a thread ahead of its first prompt so the `thread/start` round-trip
+    /// happens during idle time. Same semantics as `thread_start`, including hooks.
+    /// Side effects: one thread/start RPC. Allocation: one ThreadHandle. Complexity: O(1).
+    pub async fn prewarm_thread(
+        &self,
+        params: ThreadStartParams,
+    ) -> Result<ThreadHandle, RpcError> {
+        self.thread_start(params).await
+    }
+}
+
+/// Pool of pre-started threads sharing one `ThreadStartParams` template.
+/// `acquire` hands out a ready thread (or starts one inline when the pool is empty)
+/// and replenishes the pool in the background. Cloning shares the same pool.
+#[derive(Clone)]
+pub struct ThreadPool {
+    inner: Arc<ThreadPoolInner>,
+}
+
+struct ThreadPoolInner {
+    runtime: Runtime,
+    params: ThreadStartParams,
+    target_size: usize,
+    /// Scoped hooks for pooled thread starts and runs, on top of runtime hooks.
+    hooks: ArcSwapOption<RuntimeHookConfig>,
+    state: Mutex<PoolState>,
+}
+
+struct PoolState {
+    ready: VecDeque<ThreadHandle>,
+    /// thread/start calls reserved but not yet resolved.
+    starting: usize,
+}
+
+impl std::fmt::Debug for ThreadPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThreadPool")
+            .field("target_size", &self.inner.target_size)
+            .field("ready", &self.ready_count())
+            .finish()
+    }
+}
+
+impl ThreadPool {
+    /// Create an empty pool; call `fill` to pre-start threads.
+    /// Allocation: one Arc + queue of `target_size`. Complexity: O(1).
+    pub fn new(runtime: &Runtime, params: ThreadStartParams, target_size: usize) -> Self {
+        Self {
+            inner: Arc::new(ThreadPoolInner {
+                runtime: runtime.clone(),
+                params,
+                target_size,
+                hooks: ArcSwapOption::new(None),
+                state: Mutex::new(PoolState {
+                    ready: VecDeque::with_capacity(target_size),
+                    starting: 0,
+                }),
+            }),
+        }
+    }
+
+    /// Run `hooks` (on top of runtime hooks) for pooled thread starts and `run_prompt`.
+    /// Shared by every clone of the pool; threads already started keep their start hooks.
+    pub fn with_hooks(self, hooks: RuntimeHookConfig) -> Self {
+        self.inner.hooks.store(Some(Arc::new(hooks)));
+        self
+    }
+
+    pub fn target_size(&self) -> usize {
+        self.inner.target_size
+    }
+
+    /// Number of started threads waiting to be handed out.
+    pub fn ready_count(&self) -> usize {
+        lock_state(&self.inner).ready.len()
+    }
+
+    /// Start threads until the pool holds `target_size` ready handles.
+    /// Side effects: up to `target_size` thread/start RPCs, issued sequentially.
+    /// Complexity: O(k), k = missing thread count.
+    pub async fn fill(&self) -> Result<(), RpcError> {
+        fill_pool(&self.inner).await
+    }
+
+    /// Take one ready thread, starting a fresh one inline when the pool is empty.
+    /// Schedules background replenishment back to `target_size`.
+    /// Side effects: at most one inline thread/start RPC. Complexity: O(1).
+    pub async fn acquire(&self) -> Result<ThreadHandle, RpcError> {
+        let pooled = lock_state(&self.inner).ready.pop_front();
+        let thread = match pooled {
+            Some(thread) => thread,
+            None => start_pooled_thread(&self.inner).await?,
+        };
+        self.replenish_in_background();
+        Ok(thread)
+    }
+
+    /// Run one prompt on a pooled thread, with the pool's hooks.
+    /// `p`'s thread-level settings (model, cwd, approval, sandbox) must agree with the
+    /// settings the pool's `ThreadStartParams` pins; a conflict is rejected with
+    /// `RpcError::InvalidRequest` before a thread is taken. Unpinned settings and
+    /// turn-level ones (effort, attachments) come from `p`.
+    /// Side effects: same as `run_prompt`, minus the thread/start round-trip when a thread is ready.
+    pub async fn run_prompt(&self, p: PromptRunParams) -> Result<PromptRunResult, PromptRunError> {
+        if let Some(field) = conflicting_thread_setting(&self.inner.params, &p) {
+            return Err(RpcError::InvalidRequest(format!(
+                "thread pool run_prompt: {field} conflicts with the pool's ThreadStartParams"
+            ))
+            .into());
+        }
+        let thread = self.acquire().await?;
+        let hooks = self.inner.hooks.load_full();
+        self.inner
+            .runtime
+            .run_prompt_on_loaded_thread_with_hooks(&thread.thread_id, p, hooks.as_deref())
+            .await
+    }
+
+    fn replenish_in_background(&self) {
+        if self.inner.target_size == 0 {
+            return;
+        }
+        let inner = Arc::clone(&self.inner);
+        let fallback_runtime = inner.runtime.clone();
+        spawn_detached_task(
+            async move {
+                if let Err(err) = fill_pool(&inner).await {
+                    tracing::warn!(error = %err, "thread pool replenish failed");
+                }
+            },
+            current_detached_task_plan("thread_pool_replenish"),
+            move || fallback_runtime.record_detached_task_init_failed(),
+        );
+    }
+}
+
+/// Start threads until ready + in-flight starts reach the target.
+/// In-flight starts are reserved up front so concurrent fills never overshoot.
+async fn fill_pool(inner: &ThreadPoolInner) -> Result<(), RpcError> {
+    loop {
+        {
+            let mut state = lock_state(inner);
+            if state.ready.len() + state.starting >= inner.target_size {
+                return Ok(());
+            }
+            state.starting += 1;
+        }
+        let reservation = StartReservation(inner);
+        let started = start_pooled_thread(inner).await;
+        drop(reservation);
+        lock_state(inner).ready.push_back(started?);
+    }
+}
+
+async fn start_pooled_thread(inner: &ThreadPoolInner) -> Result<ThreadHandle, RpcError> {
+    let hooks = inner.hooks.load_full();
+    inner
+        .runtime
+        .thread_start_with_hooks(inner.params.clone(), hooks.as_deref())
+        .await
+}
+
+/// First thread-level setting pinned by the pool template that `p` would contradict.
+fn conflicting_thread_setting(
+    pinned: &ThreadStartParams,
+    p: &PromptRunParams,
+) -> Option<&'static str> {
+    if pinned.model.is_some() && p.model.is_some() && pinned.model != p.model {
+        return Some("model");
+    }
+    if pinned.cwd.as_deref().is_some_and(|cwd| cwd != p.cwd) {
+        return Some("cwd");
+    }
+    if pinned
+        .approval_policy
+        .is_some_and(|policy| policy != p.approval_policy)
+    {
+        return Some("approval_policy");
+    }
+    if pinned
+        .sandbox_policy
+        .as_ref()
+        .is_some_and(|policy| *policy != p.sandbox_policy)
+    {
+        return Some("sandbox_policy");
+    }
+    None
+}
+
+/// Releases one `starting` slot on drop, so a cancelled fill never leaks a reservation.
+struct StartReservation<'a>(&'a ThreadPoolInner);
+
+impl Drop for StartReservation<'_> {
+    fn drop(&mut self) {
+        lock_state(self.0).starting -= 1;
+    }
+}
+
+fn lock_state(inner: &ThreadPoolInner) -> MutexGuard<'_, PoolState> {
+    match inner.state.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
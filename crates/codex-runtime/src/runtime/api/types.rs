@@ -5,6 +5,7 @@ mod command_exec;
 mod input;
 mod policies;
 mod skills;
+mod thread_export;
 mod thread_ops;
 mod thread_views;
 
@@ -15,7 +16,9 @@ pub use command_exec::{
     CommandExecWriteParams, CommandExecWriteResponse,
 };
 pub use input::TextElement;
-pub use input::{ByteRange, InputItem, PromptAttachment, ThreadId, TurnId};
+pub use input::{
+    AttachmentPlaceholderStrategy, ByteRange, InputItem, PromptAttachment, ThreadId, TurnId,
+};
 pub(crate) use policies::{
     sandbox_policy_to_wire_value, summarize_sandbox_policy, summarize_sandbox_policy_wire_value,
 };
@@ -28,13 +31,16 @@ pub use skills::{
     SkillToolDependency, SkillsListEntry, SkillsListExtraRootsForCwd, SkillsListParams,
     SkillsListResponse,
 };
+pub(crate) use thread_export::item_text_or_summary;
+pub use thread_export::{ThreadExport, ThreadExportItem, ThreadExportTurn};
 pub use thread_ops::{
-    ThreadHandle, ThreadListParams, ThreadListResponse, ThreadListSortKey, ThreadLoadedListParams,
-    ThreadLoadedListResponse, ThreadReadParams, ThreadRollbackParams, ThreadRollbackResponse,
-    ThreadStartParams, TurnHandle, TurnStartParams,
+    SteerOutcome, ThreadHandle, ThreadListParams, ThreadListResponse, ThreadListSortKey,
+    ThreadLoadedListParams, ThreadLoadedListResponse, ThreadReadParams, ThreadRollbackParams,
+    ThreadRollbackResponse, ThreadStartParams, TurnGuard, TurnHandle, TurnStartParams,
 };
 pub use thread_views::{
-    ThreadAgentMessageItemView, ThreadCommandExecutionItemView, ThreadItemPayloadView,
-    ThreadItemType, ThreadItemView, ThreadReadResponse, ThreadTurnErrorView, ThreadTurnStatus,
-    ThreadTurnView, ThreadView,
+    ThreadAgentMessageItemView, ThreadCommandExecutionItemView, ThreadGitInfo,
+    ThreadImageViewItemView, ThreadItemPayloadView, ThreadItemType, ThreadItemView,
+    ThreadMcpToolCallItemView, ThreadReadResponse, ThreadReviewModeItemView, ThreadTurnErrorView,
+    ThreadTurnStatus, ThreadTurnView, ThreadView, ThreadWebSearchItemView,
 };
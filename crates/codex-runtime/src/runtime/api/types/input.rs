@@ -1,3 +1,6 @@
+use std::path::Path;
+use std::sync::Arc;
+
 pub type ThreadId = String;
 pub type TurnId = String;
 pub type ItemId = String;
@@ -16,6 +19,8 @@ pub enum InputItem {
     },
     LocalImage {
         path: String,
+        /// Sniffed image MIME type; filled by prompt runs, optional for raw turns.
+        mime_type: Option<String>,
     },
     Skill {
         name: String,
@@ -52,3 +57,41 @@ pub enum PromptAttachment {
         path: String,
     },
 }
+
+/// How `@path` attachments without an explicit placeholder get one.
+/// The prompt text always keeps `@path`, so byte ranges stay exact; only the
+/// `placeholder` label changes.
+#[derive(Clone, Default)]
+pub enum AttachmentPlaceholderStrategy {
+    /// Leave the placeholder unset; clients fall back to the path itself.
+    #[default]
+    UsePath,
+    /// File name without extension, e.g. `/long/path/to/README.md` -> `README`.
+    FileStem,
+    /// Caller-provided mapping from path to placeholder.
+    Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl AttachmentPlaceholderStrategy {
+    /// Placeholder for `path`, or `None` for `UsePath`.
+    /// Allocation: one String for FileStem/Custom. Complexity: O(path length).
+    pub fn placeholder_for(&self, path: &str) -> Option<String> {
+        match self {
+            Self::UsePath => None,
+            Self::FileStem => Path::new(path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned()),
+            Self::Custom(derive) => Some(derive(path)),
+        }
+    }
+}
+
+impl std::fmt::Debug for AttachmentPlaceholderStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UsePath => f.write_str("UsePath"),
+            Self::FileStem => f.write_str("FileStem"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
@@ -76,7 +76,7 @@ impl FromStr for ApprovalPolicy {
     }
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ReasoningEffort {
     #[serde(rename = "low")]
     Low,
@@ -157,6 +157,9 @@ impl SandboxPreset {
 #[derive(Clone, Debug, PartialEq)]
 pub enum SandboxPolicy {
     Preset(SandboxPreset),
+    /// Sent verbatim as `sandboxPolicy`. Still guarded like presets: any object whose
+    /// `type` is not `readOnly` (unknown types included) counts as privileged and needs
+    /// `privileged_escalation_approved`; non-objects and missing `type` are rejected.
     Raw(Value),
 }
 
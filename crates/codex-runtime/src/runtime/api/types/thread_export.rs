@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::input::{ThreadId, TurnId};
+use super::thread_views::{
+    ThreadItemPayloadView, ThreadItemType, ThreadItemView, ThreadTurnStatus, ThreadTurnView,
+    ThreadView,
+};
+
+/// Compact, self-contained conversation export for one thread.
+/// Stable shape intended for bug reports and clipboard sharing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadExport {
+    pub thread_id: ThreadId,
+    #[serde(default)]
+    pub model: Option<String>,
+    pub turns: Vec<ThreadExportTurn>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadExportTurn {
+    pub id: TurnId,
+    pub status: ThreadTurnStatus,
+    pub items: Vec<ThreadExportItem>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadExportItem {
+    #[serde(rename = "type")]
+    pub item_type: ThreadItemType,
+    pub text_or_summary: String,
+}
+
+impl ThreadExport {
+    /// Flatten one thread view into the compact export shape.
+    /// Pure transform. Allocation: one String per item. Complexity: O(n), n = item payload size.
+    pub fn from_view(thread: &ThreadView) -> Self {
+        Self {
+            thread_id: thread.id.clone(),
+            model: thread
+                .extra
+                .get("model")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned),
+            turns: thread
+                .turns
+                .iter()
+                .map(ThreadExportTurn::from_view)
+                .collect(),
+        }
+    }
+}
+
+impl ThreadExportTurn {
+    fn from_view(turn: &ThreadTurnView) -> Self {
+        Self {
+            id: turn.id.clone(),
+            status: turn.status,
+            items: turn.items.iter().map(ThreadExportItem::from_view).collect(),
+        }
+    }
+}
+
+impl ThreadExportItem {
+    fn from_view(item: &ThreadItemView) -> Self {
+        Self {
+            item_type: item.item_type.clone(),
+            text_or_summary: item_text_or_summary(&item.payload),
+        }
+    }
+}
+
+pub(crate) fn item_text_or_summary(payload: &ThreadItemPayloadView) -> String {
+    match payload {
+        ThreadItemPayloadView::AgentMessage(data) => data.text.clone(),
+        ThreadItemPayloadView::CommandExecution(data) => match data.exit_code {
+            Some(exit_code) => format!("$ {} [{}, exit {exit_code}]", data.command, data.status),
+            None => format!("$ {} [{}]", data.command, data.status),
+        },
+        ThreadItemPayloadView::McpToolCall(data) => {
+            format!("mcp {}/{} [{}]", data.server, data.tool, data.status)
+        }
+        ThreadItemPayloadView::WebSearch(data) => format!("search: {}", data.query),
+        ThreadItemPayloadView::ImageView(data) => match &data.alt {
+            Some(alt) => format!("image {} ({alt})", data.url_or_path),
+            None => format!("image {}", data.url_or_path),
+        },
+        ThreadItemPayloadView::EnteredReviewMode(data) => {
+            format!("review started: {}", data.review)
+        }
+        ThreadItemPayloadView::ExitedReviewMode(data) => format!("review ended: {}", data.review),
+        ThreadItemPayloadView::Unknown(fields) => ["text", "summary", "content"]
+            .iter()
+            .filter_map(|key| fields.get(*key))
+            .map(flatten_text_value)
+            .find(|text| !text.is_empty())
+            .unwrap_or_default(),
+    }
+}
+
+/// Collapse a string or array of strings/`{text}` objects into one newline-joined String.
+fn flatten_text_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                Value::String(text) => Some(text.as_str()),
+                Value::Object(fields) => fields.get("text").and_then(Value::as_str),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::str::FromStr;
 
 use crate::runtime::core::Runtime;
@@ -21,6 +21,9 @@ pub struct TurnStartParams {
     pub summary: Option<String>,
     pub personality: Option<Personality>,
     pub output_schema: Option<Value>,
+    /// Extra `turn/start` wire fields for server features without a typed field yet.
+    /// Typed fields win on key conflicts; `threadId` and `input` are rejected.
+    pub extra_params: Map<String, Value>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -29,6 +32,35 @@ pub struct TurnHandle {
     pub thread_id: ThreadId,
 }
 
+/// Result of `ThreadHandle::turn_steer_outcome`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SteerOutcome {
+    /// Turn that receives the steering input.
+    pub turn_id: TurnId,
+    /// The server answered with a turn other than the expected one, so new output
+    /// belongs to `turn_id`, not to the anchored turn.
+    pub was_new_turn: bool,
+}
+
+/// RAII wrapper from `ThreadHandle::turn_start_guarded`.
+/// Dropping an armed guard fires a best-effort `turn/interrupt` for the turn.
+/// Call `disarm` once the turn has finished or should keep running.
+#[must_use = "dropping a TurnGuard interrupts the turn"]
+pub struct TurnGuard {
+    pub(in crate::runtime::api) turn: TurnHandle,
+    pub(in crate::runtime::api) runtime: Runtime,
+    pub(in crate::runtime::api) armed: bool,
+}
+
+impl std::fmt::Debug for TurnGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TurnGuard")
+            .field("turn", &self.turn)
+            .field("armed", &self.armed)
+            .finish()
+    }
+}
+
 #[derive(Clone)]
 pub struct ThreadHandle {
     pub thread_id: ThreadId,
@@ -66,6 +98,10 @@ pub struct ThreadReadParams {
     pub thread_id: ThreadId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub include_turns: Option<bool>,
+    /// Ask for only the most recent N turns (sent as `lastNTurns`). Servers that ignore
+    /// it return every turn; `thread_read` then keeps the last N client-side.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_n_turns: Option<u32>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -145,6 +181,25 @@ pub struct ThreadRollbackParams {
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ThreadRollbackResponse {
     pub thread: ThreadView,
+    /// Ids of the removed turns, oldest first. Empty when the server only reports a count.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rolled_back_turn_ids: Vec<TurnId>,
+}
+
+impl ThreadRollbackResponse {
+    /// Number of removed turns: the typed id list when present, else the
+    /// `rolledBackTurns` count from `thread.extra`.
+    /// Allocation: none. Complexity: O(1).
+    pub fn rolled_back_count(&self) -> Option<u64> {
+        if !self.rolled_back_turn_ids.is_empty() {
+            return Some(self.rolled_back_turn_ids.len() as u64);
+        }
+        self.thread
+            .extra
+            .get("rolledBackTurns")
+            .and_then(Value::as_u64)
+    }
 }
@@ -115,10 +115,72 @@ pub struct ThreadCommandExecutionItemView {
     pub extra: Map<String, Value>,
 }
 
+/// `mcpToolCall` item: one tool invocation on an MCP server.
+/// Field names follow the app-server wire shape (`arguments`/`result`/`error`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadMcpToolCallItemView {
+    pub server: String,
+    pub tool: String,
+    pub status: String,
+    /// Tool input as sent to the server.
+    #[serde(default)]
+    pub arguments: Option<Value>,
+    /// Tool output; set once the call completed.
+    #[serde(default)]
+    pub result: Option<Value>,
+    #[serde(default)]
+    pub error: Option<Value>,
+    #[serde(default)]
+    pub duration_ms: Option<i64>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `webSearch` item: one web search issued by the agent.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadWebSearchItemView {
+    pub query: String,
+    /// Search results as reported by the server (empty when none were attached).
+    #[serde(default)]
+    pub results: Vec<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `imageView` item: one image the agent looked at.
+/// Read from wire `path` (or `url`); serialized back as `path`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadImageViewItemView {
+    #[serde(rename = "path", alias = "url")]
+    pub url_or_path: String,
+    #[serde(default)]
+    pub alt: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `enteredReviewMode` / `exitedReviewMode` item: a review-mode transition.
+/// `review` describes what is being reviewed; mode parameters stay in `extra`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadReviewModeItemView {
+    pub review: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ThreadItemPayloadView {
     AgentMessage(ThreadAgentMessageItemView),
     CommandExecution(ThreadCommandExecutionItemView),
+    McpToolCall(ThreadMcpToolCallItemView),
+    WebSearch(ThreadWebSearchItemView),
+    ImageView(ThreadImageViewItemView),
+    EnteredReviewMode(ThreadReviewModeItemView),
+    ExitedReviewMode(ThreadReviewModeItemView),
     Unknown(Map<String, Value>),
 }
 
@@ -141,6 +203,19 @@ impl Serialize for ThreadItemView {
             ThreadItemPayloadView::CommandExecution(data) => {
                 serde_json::to_value(data).map_err(serde::ser::Error::custom)?
             }
+            ThreadItemPayloadView::McpToolCall(data) => {
+                serde_json::to_value(data).map_err(serde::ser::Error::custom)?
+            }
+            ThreadItemPayloadView::WebSearch(data) => {
+                serde_json::to_value(data).map_err(serde::ser::Error::custom)?
+            }
+            ThreadItemPayloadView::ImageView(data) => {
+                serde_json::to_value(data).map_err(serde::ser::Error::custom)?
+            }
+            ThreadItemPayloadView::EnteredReviewMode(data)
+            | ThreadItemPayloadView::ExitedReviewMode(data) => {
+                serde_json::to_value(data).map_err(serde::ser::Error::custom)?
+            }
             ThreadItemPayloadView::Unknown(extra) => Value::Object(extra.clone()),
         };
         let Value::Object(mut fields) = object else {
@@ -188,6 +263,31 @@ impl<'de> Deserialize<'de> for ThreadItemView {
                         .map_err(serde::de::Error::custom)?;
                 ThreadItemPayloadView::CommandExecution(data)
             }
+            ThreadItemType::McpToolCall => {
+                let data: ThreadMcpToolCallItemView = serde_json::from_value(Value::Object(fields))
+                    .map_err(serde::de::Error::custom)?;
+                ThreadItemPayloadView::McpToolCall(data)
+            }
+            ThreadItemType::WebSearch => {
+                let data: ThreadWebSearchItemView = serde_json::from_value(Value::Object(fields))
+                    .map_err(serde::de::Error::custom)?;
+                ThreadItemPayloadView::WebSearch(data)
+            }
+            ThreadItemType::ImageView => {
+                let data: ThreadImageViewItemView = serde_json::from_value(Value::Object(fields))
+                    .map_err(serde::de::Error::custom)?;
+                ThreadItemPayloadView::ImageView(data)
+            }
+            ThreadItemType::EnteredReviewMode => {
+                let data: ThreadReviewModeItemView = serde_json::from_value(Value::Object(fields))
+                    .map_err(serde::de::Error::custom)?;
+                ThreadItemPayloadView::EnteredReviewMode(data)
+            }
+            ThreadItemType::ExitedReviewMode => {
+                let data: ThreadReviewModeItemView = serde_json::from_value(Value::Object(fields))
+                    .map_err(serde::de::Error::custom)?;
+                ThreadItemPayloadView::ExitedReviewMode(data)
+            }
             _ => ThreadItemPayloadView::Unknown(fields),
         };
 
@@ -224,6 +324,23 @@ pub struct ThreadTurnView {
     pub extra: Map<String, Value>,
 }
 
+/// Typed view of `ThreadView::git_info`. Every field is optional; `commit` also
+/// accepts the server's `sha` spelling, and unknown fields land in `extra`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThreadGitInfo {
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default, alias = "sha")]
+    pub commit: Option<String>,
+    #[serde(default)]
+    pub dirty: Option<bool>,
+    #[serde(default)]
+    pub origin_url: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ThreadView {
@@ -243,6 +360,22 @@ pub struct ThreadView {
     pub extra: Map<String, Value>,
 }
 
+impl ThreadView {
+    /// Labels attached via `thread/setMetadata`, when the server returns them.
+    /// Complexity: O(log n), n = extra field count.
+    pub fn metadata(&self) -> Option<&Value> {
+        self.extra.get("metadata").filter(|value| !value.is_null())
+    }
+
+    /// Decodes `git_info`; `None` when absent, null, or not an object with
+    /// well-typed known fields. The raw value stays in `git_info`.
+    /// Allocation: one ThreadGitInfo clone of the value. Complexity: O(n), n = git_info size.
+    pub fn git_info_parsed(&self) -> Option<ThreadGitInfo> {
+        let value = self.git_info.as_ref().filter(|value| value.is_object())?;
+        ThreadGitInfo::deserialize(value).ok()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ThreadReadResponse {
     pub thread: ThreadView,
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Token counts of one turn, read from a `thread/tokenUsage/updated` payload.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TurnUsage {
+    /// Model the run requested; `None` when the server default was used.
+    pub model: Option<String>,
+    /// Prompt tokens, including `cached_input_tokens`.
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    /// Completion tokens, including `reasoning_output_tokens`.
+    pub output_tokens: u64,
+    pub reasoning_output_tokens: u64,
+}
+
+impl TurnUsage {
+    /// Parse a `tokenUsage` object: the `last` breakdown when present, else `total`,
+    /// else the object itself. `None` when neither input nor output counts are present.
+    /// Allocation: one String for `model`. Complexity: O(1).
+    pub fn from_token_usage(token_usage: &Value, model: Option<&str>) -> Option<Self> {
+        let counts = token_usage
+            .get("last")
+            .or_else(|| token_usage.get("total"))
+            .unwrap_or(token_usage);
+        let count = |key: &str| counts.get(key).and_then(Value::as_u64);
+        let input_tokens = count("inputTokens");
+        let output_tokens = count("outputTokens");
+        if input_tokens.is_none() && output_tokens.is_none() {
+            return None;
+        }
+        Some(Self {
+            model: model.map(ToOwned::to_owned),
+            input_tokens: input_tokens.unwrap_or(0),
+            cached_input_tokens: count("cachedInputTokens").unwrap_or(0),
+            output_tokens: output_tokens.unwrap_or(0),
+            reasoning_output_tokens: count("reasoningOutputTokens").unwrap_or(0),
+        })
+    }
+
+    /// Estimated spend in USD. `None` when `model` is unset or missing from `pricing`.
+    /// Cached input tokens bill at the cached rate, the rest of the input at the input
+    /// rate; reasoning tokens are part of `output_tokens`.
+    /// Allocation: none. Complexity: O(1).
+    pub fn estimated_cost(&self, pricing: &PricingTable) -> Option<f64> {
+        let rates = pricing.get(self.model.as_deref()?)?;
+        let cached = self.cached_input_tokens.min(self.input_tokens);
+        let uncached = self.input_tokens - cached;
+        let usd_millionths = uncached as f64 * rates.input_per_million
+            + cached as f64 * rates.cached_input_per_million
+            + self.output_tokens as f64 * rates.output_per_million;
+        Some(usd_millionths / 1_000_000.0)
+    }
+}
+
+/// USD rates per one million tokens for one model.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    pub cached_input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+impl ModelPricing {
+    pub fn new(
+        input_per_million: f64,
+        cached_input_per_million: f64,
+        output_per_million: f64,
+    ) -> Self {
+        Self {
+            input_per_million,
+            cached_input_per_million,
+            output_per_million,
+        }
+    }
+}
+
+/// Per-model rates for `TurnUsage::estimated_cost`, keyed by exact model name.
+/// `default()` ships a small built-in table (list prices at release time); override or
+/// extend it with `with_model`, or start from `empty()`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PricingTable {
+    models: HashMap<String, ModelPricing>,
+}
+
+impl PricingTable {
+    /// Table without any model.
+    /// Allocation: none. Complexity: O(1).
+    pub fn empty() -> Self {
+        Self {
+            models: HashMap::new(),
+        }
+    }
+
+    /// Add or replace the rates of one model.
+    /// Allocation: one String key. Complexity: O(1) amortized.
+    pub fn with_model(mut self, model: impl Into<String>, pricing: ModelPricing) -> Self {
+        self.models.insert(model.into(), pricing);
+        self
+    }
+
+    /// Allocation: none. Complexity: O(1).
+    pub fn get(&self, model: &str) -> Option<ModelPricing> {
+        self.models.get(model).copied()
+    }
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        Self::empty()
+            .with_model("gpt-5", ModelPricing::new(1.25, 0.125, 10.0))
+            .with_model("gpt-5-codex", ModelPricing::new(1.25, 0.125, 10.0))
+            .with_model("gpt-5-mini", ModelPricing::new(0.25, 0.025, 2.0))
+            .with_model("gpt-5-nano", ModelPricing::new(0.05, 0.005, 0.4))
+    }
+}
@@ -5,9 +5,9 @@ use crate::runtime::errors::RpcError;
 use crate::runtime::rpc_contract::payload_summary;
 
 use super::{
-    sandbox_policy_to_wire_value, summarize_sandbox_policy, ApprovalPolicy, ByteRange,
-    CommandExecParams, InputItem, PromptAttachment, TextElement, ThreadStartParams,
-    TurnStartParams,
+    sandbox_policy_to_wire_value, summarize_sandbox_policy, ApprovalPolicy,
+    AttachmentPlaceholderStrategy, ByteRange, CommandExecParams, InputItem, PromptAttachment,
+    TextElement, ThreadStartParams, TurnStartParams,
 };
 
 pub(super) fn serialize_params<T: Serialize>(method: &str, params: &T) -> Result<Value, RpcError> {
@@ -32,42 +32,69 @@ pub(super) fn deserialize_result<T: DeserializeOwned>(
 /// 1) explicit opt-in (`privileged_escalation_approved`)
 /// 2) non-never approval policy
 /// 3) explicit execution scope (`cwd` or writable roots)
-pub(super) fn validate_thread_start_security(p: &ThreadStartParams) -> Result<(), RpcError> {
+///
+/// `allow_never_approval` (`RuntimeConfig::allow_unrestricted_sandbox`) waives only 2).
+pub(super) fn validate_thread_start_security(
+    p: &ThreadStartParams,
+    allow_never_approval: bool,
+) -> Result<(), RpcError> {
     validate_privileged_sandbox_security(
         p.sandbox_policy.as_ref(),
         p.privileged_escalation_approved,
         p.approval_policy,
         p.cwd.as_deref(),
+        allow_never_approval,
     )
 }
 
 /// Enforce privileged sandbox escalation policy (SEC-004) for turn/start.
-pub(super) fn validate_turn_start_security(p: &TurnStartParams) -> Result<(), RpcError> {
+pub(super) fn validate_turn_start_security(
+    p: &TurnStartParams,
+    allow_never_approval: bool,
+) -> Result<(), RpcError> {
     validate_privileged_sandbox_security(
         p.sandbox_policy.as_ref(),
         p.privileged_escalation_approved,
         p.approval_policy,
         p.cwd.as_deref(),
+        allow_never_approval,
     )
 }
 
+/// Wire keys the runtime owns on `turn/start`; extra params may not set them.
+const RESERVED_TURN_START_KEYS: [&str; 2] = ["threadId", "input"];
+
+/// Reject extra turn params that would override runtime-owned wire keys.
+/// Allocation: none on success. Complexity: O(1).
+pub(super) fn validate_turn_extra_params(extra: &Map<String, Value>) -> Result<(), RpcError> {
+    match RESERVED_TURN_START_KEYS
+        .iter()
+        .find(|key| extra.contains_key(**key))
+    {
+        Some(key) => Err(RpcError::InvalidRequest(format!(
+            "turn/start extra params must not override `{key}`"
+        ))),
+        None => Ok(()),
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum PrivilegedSandboxViolation {
-    ExplicitApprovalRequired,
-    NonNeverApprovalPolicyRequired,
-    ExplicitScopeRequired,
+    MissingEscalationApproval,
+    NeverApprovalPolicy,
+    MissingExplicitScope,
 }
 
 impl PrivilegedSandboxViolation {
     fn message(self) -> &'static str {
         match self {
-            Self::ExplicitApprovalRequired => {
+            Self::MissingEscalationApproval => {
                 "privileged sandbox requires explicit escalation approval"
             }
-            Self::NonNeverApprovalPolicyRequired => {
+            Self::NeverApprovalPolicy => {
                 "privileged sandbox requires non-never approval policy"
             }
-            Self::ExplicitScopeRequired => {
+            Self::MissingExplicitScope => {
                 "privileged sandbox requires explicit scope via cwd or writable roots"
             }
         }
@@ -94,12 +121,14 @@ fn validate_privileged_sandbox_security(
     privileged_escalation_approved: bool,
     approval_policy: Option<ApprovalPolicy>,
     cwd: Option<&str>,
+    allow_never_approval: bool,
 ) -> Result<(), RpcError> {
     match check_privileged_sandbox_security(
         sandbox_policy,
         privileged_escalation_approved,
         approval_policy,
         cwd,
+        allow_never_approval,
     ) {
         Ok(()) => Ok(()),
         Err(error) => Err(RpcError::InvalidRequest(error.into_message())),
@@ -111,6 +140,7 @@ fn check_privileged_sandbox_security(
     privileged_escalation_approved: bool,
     approval_policy: Option<ApprovalPolicy>,
     cwd: Option<&str>,
+    allow_never_approval: bool,
 ) -> Result<(), PrivilegedSandboxSecurityError> {
     let Some(sandbox_policy) = sandbox_policy else {
         return Ok(());
@@ -122,17 +152,19 @@ fn check_privileged_sandbox_security(
     }
     if !privileged_escalation_approved {
         return Err(PrivilegedSandboxSecurityError::Violation(
-            PrivilegedSandboxViolation::ExplicitApprovalRequired,
+            PrivilegedSandboxViolation::MissingEscalationApproval,
         ));
     }
-    if approval_policy.unwrap_or(ApprovalPolicy::Never) == ApprovalPolicy::Never {
+    if !allow_never_approval
+        && approval_policy.unwrap_or(ApprovalPolicy::Never) == ApprovalPolicy::Never
+    {
         return Err(PrivilegedSandboxSecurityError::Violation(
-            PrivilegedSandboxViolation::NonNeverApprovalPolicyRequired,
+            PrivilegedSandboxViolation::NeverApprovalPolicy,
         ));
     }
     if !has_explicit_scope(cwd, policy_summary.has_non_empty_writable_roots()) {
         return Err(PrivilegedSandboxSecurityError::Violation(
-            PrivilegedSandboxViolation::ExplicitScopeRequired,
+            PrivilegedSandboxViolation::MissingExplicitScope,
         ));
     }
     Ok(())
@@ -269,6 +301,9 @@ pub(super) fn turn_start_params_to_wire(thread_id: &str, p: &TurnStartParams) ->
             .map(|per| Value::String(per.as_wire().to_owned())),
     );
     insert_if_some(&mut params, "outputSchema", p.output_schema.clone());
+    for (key, value) in &p.extra_params {
+        params.entry(key.clone()).or_insert_with(|| value.clone());
+    }
 
     Value::Object(params)
 }
@@ -361,10 +396,12 @@ pub(super) fn command_exec_params_to_wire(p: &CommandExecParams) -> Value {
 }
 
 /// Build input items for one prompt execution.
+/// `@path` attachments without a placeholder get one from `placeholders`.
 /// Allocation: O(n), n = prompt length + attachment count.
 pub(super) fn build_prompt_inputs(
     prompt: &str,
     attachments: &[PromptAttachment],
+    placeholders: &AttachmentPlaceholderStrategy,
 ) -> Vec<InputItem> {
     let mut text = prompt.to_owned();
     let mut text_elements = Vec::<TextElement>::new();
@@ -373,13 +410,19 @@ pub(super) fn build_prompt_inputs(
     for attachment in attachments {
         match attachment {
             PromptAttachment::AtPath { path, placeholder } => {
-                append_at_path_mention(&mut text, &mut text_elements, path, placeholder.as_deref());
+                let placeholder = placeholder
+                    .clone()
+                    .or_else(|| placeholders.placeholder_for(path));
+                append_at_path_mention(&mut text, &mut text_elements, path, placeholder);
             }
             PromptAttachment::ImageUrl { url } => {
                 tail_items.push(InputItem::ImageUrl { url: url.clone() });
             }
             PromptAttachment::LocalImage { path } => {
-                tail_items.push(InputItem::LocalImage { path: path.clone() });
+                tail_items.push(InputItem::LocalImage {
+                    path: path.clone(),
+                    mime_type: None,
+                });
             }
             PromptAttachment::Skill { name, path } => {
                 tail_items.push(InputItem::Skill {
@@ -410,7 +453,7 @@ fn append_at_path_mention(
     text: &mut String,
     text_elements: &mut Vec<TextElement>,
     path: &str,
-    placeholder: Option<&str>,
+    placeholder: Option<String>,
 ) {
     if !text.is_empty() && !text.ends_with('\n') {
         text.push('\n');
@@ -423,7 +466,7 @@ fn append_at_path_mention(
 
     text_elements.push(TextElement {
         byte_range: ByteRange { start, end },
-        placeholder: placeholder.map(ToOwned::to_owned),
+        placeholder,
     });
 }
 
@@ -452,9 +495,12 @@ pub(super) fn input_item_to_wire(item: &InputItem) -> Value {
             value.insert("type".to_owned(), Value::String("image".to_owned()));
             value.insert("url".to_owned(), Value::String(url.clone()));
         }
-        InputItem::LocalImage { path } => {
+        InputItem::LocalImage { path, mime_type } => {
             value.insert("type".to_owned(), Value::String("localImage".to_owned()));
             value.insert("path".to_owned(), Value::String(path.clone()));
+            if let Some(mime_type) = mime_type {
+                value.insert("mimeType".to_owned(), Value::String(mime_type.clone()));
+            }
         }
         InputItem::Skill { name, path } => {
             value.insert("type".to_owned(), Value::String("skill".to_owned()));
@@ -498,7 +544,7 @@ fn insert_if_some(params: &mut Map<String, Value>, key: &'static str, value: Opt
 // ── Prompt → thread/turn param transformations ────────────────────────────
 // Pure functions: no self, no side effects. Allocation: one struct per call.
 
-use super::{PromptRunParams, ReasoningEffort};
+use super::PromptRunParams;
 
 /// Build ThreadStartParams from a prompt run request.
 /// Allocation: String clones for model + cwd. Complexity: O(1).
@@ -513,26 +559,38 @@ pub(super) fn thread_start_params_from_prompt(p: &PromptRunParams) -> ThreadStar
     }
 }
 
-/// Build TurnStartParams from a prompt run request with explicit effort.
+/// Build TurnStartParams from a prompt run request; `effort: None` omits the wire field.
 /// Allocation: Vec<InputItem> (O(n) attachments) + String clones. Complexity: O(n).
 pub(super) fn turn_start_params_from_prompt(
     p: &PromptRunParams,
-    effort: ReasoningEffort,
+    placeholders: &AttachmentPlaceholderStrategy,
 ) -> TurnStartParams {
     TurnStartParams {
-        input: build_prompt_inputs(&p.prompt, &p.attachments),
+        input: build_prompt_inputs(&p.prompt, &p.attachments, placeholders),
         cwd: Some(p.cwd.clone()),
         approval_policy: Some(p.approval_policy),
         sandbox_policy: Some(p.sandbox_policy.clone()),
         privileged_escalation_approved: p.privileged_escalation_approved,
         model: p.model.clone(),
-        effort: Some(effort),
+        effort: p.effort,
         summary: None,
         output_schema: p.output_schema.clone(),
+        extra_params: p.extra_turn_params.clone(),
         ..TurnStartParams::default()
     }
 }
 
+impl TurnStartParams {
+    /// Build `turn/start` params from prompt-style params with the same input and
+    /// policy mapping `run_prompt` uses, for callers driving `ThreadHandle::turn_start`
+    /// themselves. Pass the runtime's `AttachmentPlaceholderStrategy` to match its labels.
+    /// Not applied here: local image MIME sniffing, inlining, and steer routing.
+    /// Allocation: Vec<InputItem> + String clones. Complexity: O(n), n = prompt + attachments.
+    pub fn from_prompt(p: &PromptRunParams, placeholders: &AttachmentPlaceholderStrategy) -> Self {
+        turn_start_params_from_prompt(p, placeholders)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde::Deserialize;
@@ -586,6 +644,7 @@ mod tests {
             false,
             Some(ApprovalPolicy::OnRequest),
             Some("/tmp"),
+            false,
         )
         .expect_err("missing explicit opt-in must fail");
         assert!(matches!(err, RpcError::InvalidRequest(_)));
@@ -595,6 +654,7 @@ mod tests {
             true,
             Some(ApprovalPolicy::OnRequest),
             Some("/tmp"),
+            false,
         )
         .expect("privileged sandbox with explicit approval and scope should pass");
     }
@@ -602,15 +662,15 @@ mod tests {
     #[test]
     fn privileged_sandbox_violation_messages_are_data_driven() {
         assert_eq!(
-            PrivilegedSandboxViolation::ExplicitApprovalRequired.message(),
+            PrivilegedSandboxViolation::MissingEscalationApproval.message(),
             "privileged sandbox requires explicit escalation approval"
         );
         assert_eq!(
-            PrivilegedSandboxViolation::NonNeverApprovalPolicyRequired.message(),
+            PrivilegedSandboxViolation::NeverApprovalPolicy.message(),
             "privileged sandbox requires non-never approval policy"
         );
         assert_eq!(
-            PrivilegedSandboxViolation::ExplicitScopeRequired.message(),
+            PrivilegedSandboxViolation::MissingExplicitScope.message(),
             "privileged sandbox requires explicit scope via cwd or writable roots"
         );
     }
@@ -623,13 +683,48 @@ mod tests {
             true,
             Some(ApprovalPolicy::OnRequest),
             None,
+            false,
         )
         .expect_err("missing scope must fail");
         assert_eq!(
             err,
             PrivilegedSandboxSecurityError::Violation(
-                PrivilegedSandboxViolation::ExplicitScopeRequired
+                PrivilegedSandboxViolation::MissingExplicitScope
+            )
+        );
+    }
+
+    #[test]
+    fn unrestricted_sandbox_waives_only_the_non_never_approval_requirement() {
+        let sandbox_policy = SandboxPolicy::Preset(SandboxPreset::DangerFullAccess);
+        let check = |approved: bool, cwd: Option<&str>, allow_never_approval: bool| {
+            check_privileged_sandbox_security(
+                Some(&sandbox_policy),
+                approved,
+                Some(ApprovalPolicy::Never),
+                cwd,
+                allow_never_approval,
             )
+        };
+
+        assert_eq!(
+            check(true, Some("/work"), false),
+            Err(PrivilegedSandboxSecurityError::Violation(
+                PrivilegedSandboxViolation::NeverApprovalPolicy
+            ))
+        );
+        check(true, Some("/work"), true).expect("never approval allowed with explicit opt-out");
+        assert_eq!(
+            check(false, Some("/work"), true),
+            Err(PrivilegedSandboxSecurityError::Violation(
+                PrivilegedSandboxViolation::MissingEscalationApproval
+            ))
+        );
+        assert_eq!(
+            check(true, None, true),
+            Err(PrivilegedSandboxSecurityError::Violation(
+                PrivilegedSandboxViolation::MissingExplicitScope
+            ))
         );
     }
 }
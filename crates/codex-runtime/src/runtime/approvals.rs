@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Map, Value};
 
+use crate::runtime::errors::RpcErrorObject;
 use crate::runtime::rpc_contract::methods;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -28,14 +32,117 @@ pub enum TimeoutAction {
     Error,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+/// Direct reply produced for one server request without queueing it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApprovalDecision {
+    /// Send this value as the JSON-RPC result.
+    Result(Value),
+    /// Send this JSON-RPC error.
+    Error(RpcErrorObject),
+}
+
+/// Typed result payload for `Runtime::respond_approval_ok`.
+/// Each constructor builds the shape its server-request method expects, so the
+/// common replies cannot fail payload validation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApprovalResult(Value);
+
+impl ApprovalResult {
+    /// `{"decision": decision}` for command-execution and file-change approvals
+    /// (`accept`, `acceptForSession`, `decline`, `cancel`, ...).
+    /// Allocation: one String + result object. Complexity: O(1).
+    pub fn approval(decision: impl Into<String>) -> Self {
+        Self(json!({ "decision": decision.into() }))
+    }
+
+    /// `{"answers": {question_id: {"answers": [...]}}}` for `item/tool/requestUserInput`.
+    /// Allocation: one object per question. Complexity: O(n), n = total answer count.
+    pub fn user_input(answers: impl IntoIterator<Item = (String, Vec<String>)>) -> Self {
+        let answers: Map<String, Value> = answers
+            .into_iter()
+            .map(|(question_id, answers)| (question_id, json!({ "answers": answers })))
+            .collect();
+        Self(json!({ "answers": answers }))
+    }
+
+    /// `{"success": success, "contentItems": [...]}` for `item/tool/call`.
+    /// Allocation: one result object. Complexity: O(1) (items are moved).
+    pub fn tool_call(success: bool, content_items: Vec<Value>) -> Self {
+        Self(json!({ "success": success, "contentItems": content_items }))
+    }
+
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+
+    pub fn into_value(self) -> Value {
+        self.0
+    }
+}
+
+impl From<ApprovalResult> for Value {
+    fn from(result: ApprovalResult) -> Self {
+        result.0
+    }
+}
+
+/// Fallback for server-request methods the runtime does not know.
+/// Returning `None` defers to `auto_decline_unknown`.
+/// Called synchronously on the dispatcher task: it must not block or wait on I/O, or every
+/// inbound message (RPC responses included) stalls behind it. Hand slow work to a queue and
+/// return `None`.
+pub type UnknownServerRequestHandler =
+    Arc<dyn Fn(&ServerRequest) -> Option<ApprovalDecision> + Send + Sync>;
+
+#[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerRequestConfig {
     pub default_timeout_ms: u64,
     pub on_timeout: TimeoutAction,
     pub auto_decline_unknown: bool,
+    /// Per-method result payloads sent when a request times out or is auto-declined.
+    /// Consulted before the built-in shapes and `on_timeout`, so custom server-request
+    /// methods can define their own decline payload.
+    #[serde(default)]
+    pub decline_payloads: HashMap<String, Value>,
+    /// Runs on the dispatcher task for unknown methods before decline/queue routing;
+    /// must not block (see `UnknownServerRequestHandler`). Not serialized.
+    #[serde(skip)]
+    pub unknown_handler: Option<UnknownServerRequestHandler>,
+}
+
+impl std::fmt::Debug for ServerRequestConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerRequestConfig")
+            .field("default_timeout_ms", &self.default_timeout_ms)
+            .field("on_timeout", &self.on_timeout)
+            .field("auto_decline_unknown", &self.auto_decline_unknown)
+            .field("decline_payloads", &self.decline_payloads)
+            .field(
+                "unknown_handler",
+                &self.unknown_handler.as_ref().map(|_| ".."),
+            )
+            .finish()
+    }
+}
+
+/// Handlers compare by identity (`Arc::ptr_eq`).
+impl PartialEq for ServerRequestConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.default_timeout_ms == other.default_timeout_ms
+            && self.on_timeout == other.on_timeout
+            && self.auto_decline_unknown == other.auto_decline_unknown
+            && self.decline_payloads == other.decline_payloads
+            && match (&self.unknown_handler, &other.unknown_handler) {
+                (None, None) => true,
+                (Some(left), Some(right)) => Arc::ptr_eq(left, right),
+                _ => false,
+            }
+    }
 }
 
+impl Eq for ServerRequestConfig {}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum ServerRequestRoute {
@@ -49,10 +156,28 @@ impl Default for ServerRequestConfig {
             default_timeout_ms: 30_000,
             on_timeout: TimeoutAction::Decline,
             auto_decline_unknown: true,
+            decline_payloads: HashMap::new(),
+            unknown_handler: None,
         }
     }
 }
 
+impl ServerRequestConfig {
+    /// Register a custom decline result payload for one server-request method.
+    /// Allocation: one String + map insert. Complexity: O(1) amortized.
+    pub fn with_decline_payload(mut self, method: impl Into<String>, payload: Value) -> Self {
+        self.decline_payloads.insert(method.into(), payload);
+        self
+    }
+
+    /// Handle unknown server-request methods with custom logic (see `unknown_handler`).
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_unknown_handler(mut self, handler: UnknownServerRequestHandler) -> Self {
+        self.unknown_handler = Some(handler);
+        self
+    }
+}
+
 /// Pure classifier for known server-request methods.
 /// Allocation: none. Complexity: O(1).
 pub fn is_known_server_request_method(method: &str) -> bool {
@@ -66,6 +191,17 @@ pub fn is_known_server_request_method(method: &str) -> bool {
     )
 }
 
+/// True for the command-execution and file-change approval methods, the only server
+/// requests counted by approval metrics and covered by auto-approval.
+/// Allocation: none. Complexity: O(1).
+pub(crate) fn is_approval_method(method: &str) -> bool {
+    matches!(
+        method,
+        methods::ITEM_COMMAND_EXECUTION_REQUEST_APPROVAL
+            | methods::ITEM_FILE_CHANGE_REQUEST_APPROVAL
+    )
+}
+
 /// Decide whether a server request should be queued or auto-declined.
 /// Allocation: none. Complexity: O(1).
 pub fn route_server_request(method: &str, auto_decline_unknown: bool) -> ServerRequestRoute {
@@ -80,6 +216,25 @@ pub fn route_server_request(method: &str, auto_decline_unknown: bool) -> ServerR
 mod tests {
     use super::*;
 
+    #[test]
+    fn approval_result_constructors_build_method_payloads() {
+        assert_eq!(
+            ApprovalResult::approval("accept").into_value(),
+            json!({"decision": "accept"})
+        );
+        assert_eq!(
+            Value::from(ApprovalResult::user_input([(
+                "q1".to_owned(),
+                vec!["alice".to_owned()]
+            )])),
+            json!({"answers": {"q1": {"answers": ["alice"]}}})
+        );
+        assert_eq!(
+            ApprovalResult::tool_call(false, Vec::new()).as_value(),
+            &json!({"success": false, "contentItems": []})
+        );
+    }
+
     #[test]
     fn routes_known_method_to_queue() {
         let route = route_server_request("item/fileChange/requestApproval", true);
@@ -49,19 +49,29 @@ impl Default for CompatibilityGuard {
 pub(super) fn validate_runtime_compatibility(
     runtime: &Runtime,
     guard: &CompatibilityGuard,
+) -> Result<(), ClientError> {
+    check_initialize_compatibility(runtime.server_user_agent().as_deref(), guard)
+}
+
+/// Apply `guard` to the `userAgent` of an `initialize` result. Shared by `Client::connect`
+/// and the runtime handshake (`RuntimeConfig::compatibility_guard`).
+/// Allocation: product String + error strings on failure. Complexity: O(n), n = agent length.
+pub(crate) fn check_initialize_compatibility(
+    user_agent: Option<&str>,
+    guard: &CompatibilityGuard,
 ) -> Result<(), ClientError> {
     if !guard.require_initialize_user_agent && guard.min_codex_version.is_none() {
         return Ok(());
     }
 
-    let Some(user_agent) = runtime.server_user_agent() else {
+    let Some(user_agent) = user_agent else {
         if guard.require_initialize_user_agent {
             return Err(ClientError::MissingInitializeUserAgent);
         }
         return Ok(());
     };
-    let (product, version) = parse_initialize_user_agent(&user_agent)
-        .ok_or_else(|| ClientError::InvalidInitializeUserAgent(user_agent.clone()))?;
+    let (product, version) = parse_initialize_user_agent(user_agent)
+        .ok_or_else(|| ClientError::InvalidInitializeUserAgent(user_agent.to_owned()))?;
     let is_codex_product = product.starts_with("Codex ");
 
     if is_codex_product {
@@ -70,7 +80,7 @@ pub(super) fn validate_runtime_compatibility(
                 return Err(ClientError::IncompatibleCodexVersion {
                     detected: version.to_string(),
                     required: min_required.to_string(),
-                    user_agent,
+                    user_agent: user_agent.to_owned(),
                 });
             }
         }
@@ -79,7 +89,7 @@ pub(super) fn validate_runtime_compatibility(
     Ok(())
 }
 
-pub(super) fn parse_initialize_user_agent(value: &str) -> Option<(String, SemVerTriplet)> {
+pub(crate) fn parse_initialize_user_agent(value: &str) -> Option<(String, SemVerTriplet)> {
     let slash = value.find('/')?;
     let product = value.get(..slash)?.trim().to_owned();
     if product.is_empty() {
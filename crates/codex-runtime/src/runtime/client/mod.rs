@@ -13,9 +13,10 @@ mod session;
 pub use compat_guard::{CompatibilityGuard, SemVerTriplet};
 pub use config::ClientConfig;
 pub use profile::{RunProfile, SessionConfig};
-pub use session::Session;
+pub use session::{Session, SessionPrompt};
 
 use compat_guard::validate_runtime_compatibility;
+pub(crate) use compat_guard::{check_initialize_compatibility, parse_initialize_user_agent};
 use profile::{prepared_prompt_run_from_profile, session_thread_start_params};
 
 #[derive(Clone)]
@@ -178,11 +179,6 @@ pub enum ClientError {
     Runtime(#[from] RuntimeError),
 }
 
-#[cfg(test)]
-fn parse_initialize_user_agent(value: &str) -> Option<(String, SemVerTriplet)> {
-    compat_guard::parse_initialize_user_agent(value)
-}
-
 #[cfg(test)]
 fn session_prompt_params(config: &SessionConfig, prompt: impl Into<String>) -> PromptRunParams {
     profile::session_prompt_params(config, prompt)
@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use std::sync::Arc;
 use std::time::Duration;
 
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 use crate::plugin::{PostHook, PreHook};
 use crate::runtime::api::{
@@ -10,6 +10,7 @@ use crate::runtime::api::{
     SandboxPreset, ThreadStartParams, DEFAULT_REASONING_EFFORT,
 };
 use crate::runtime::hooks::RuntimeHookConfig;
+use crate::runtime::turn_output::DEFAULT_ASSISTANT_ITEM_SEPARATOR;
 
 #[derive(Clone, Debug, PartialEq)]
 struct ProfileCore {
@@ -19,7 +20,7 @@ struct ProfileCore {
     sandbox_policy: SandboxPolicy,
     privileged_escalation_approved: bool,
     attachments: Vec<PromptAttachment>,
-    timeout: Duration,
+    timeout: Option<Duration>,
     output_schema: Option<Value>,
     hooks: RuntimeHookConfig,
 }
@@ -33,7 +34,7 @@ impl Default for ProfileCore {
             sandbox_policy: SandboxPolicy::Preset(SandboxPreset::ReadOnly),
             privileged_escalation_approved: false,
             attachments: Vec::new(),
-            timeout: Duration::from_secs(120),
+            timeout: None,
             output_schema: None,
             hooks: RuntimeHookConfig::default(),
         }
@@ -82,6 +83,16 @@ impl ProfileCore {
             attachments: self.attachments,
             timeout: self.timeout,
             output_schema: self.output_schema,
+            drop_effort_on_reject: false,
+            metadata: Value::Null,
+            steer_from: None,
+            extra_turn_params: Map::new(),
+            resend_model_on_resume: false,
+            inline_local_images: false,
+            assistant_item_separator: DEFAULT_ASSISTANT_ITEM_SEPARATOR.to_owned(),
+            fail_on_command_error: false,
+            auto_approve_all: false,
+            max_reasoning_tokens: None,
         }
     }
 
@@ -170,10 +181,10 @@ macro_rules! impl_profile_builder_methods {
             self
         }
 
-        /// Set timeout.
+        /// Set the turn timeout; unset, runs use `RuntimeConfig::default_prompt_timeout`.
         /// Allocation: none. Complexity: O(1).
         pub fn with_timeout(mut self, timeout: Duration) -> Self {
-            self.timeout = timeout;
+            self.timeout = Some(timeout);
             self
         }
 
@@ -272,7 +283,8 @@ pub struct RunProfile {
     /// Explicit opt-in gate for privileged sandbox usage (SEC-004).
     pub privileged_escalation_approved: bool,
     pub attachments: Vec<PromptAttachment>,
-    pub timeout: Duration,
+    /// Turn timeout. `None` (the default) uses `RuntimeConfig::default_prompt_timeout`.
+    pub timeout: Option<Duration>,
     pub output_schema: Option<Value>,
     pub hooks: RuntimeHookConfig,
 }
@@ -290,9 +302,44 @@ impl RunProfile {
         Self::default()
     }
 
+    /// Named profile: read-only sandbox, approval `never`. Same as `new()`.
+    /// Allocation: none. Complexity: O(1).
+    pub fn safe() -> Self {
+        Self::default()
+    }
+
+    /// Named profile: workspace-write sandbox (no extra roots, no network), approval
+    /// `on-failure`; the run cwd scopes the writable workspace.
+    /// Does not opt in to privileged escalation (SEC-004): chain
+    /// `.allow_privileged_escalation()` explicitly before running it.
+    /// Allocation: one empty Vec. Complexity: O(1).
+    pub fn autonomous() -> Self {
+        Self::default()
+            .with_approval_policy(ApprovalPolicy::OnFailure)
+            .with_sandbox_policy(SandboxPolicy::Preset(SandboxPreset::WorkspaceWrite {
+                writable_roots: Vec::new(),
+                network_access: false,
+            }))
+    }
+
     impl_profile_builder_methods!();
 }
 
+impl PromptRunParams {
+    /// Build prompt-run params from a reusable profile: model, effort, approval, sandbox,
+    /// escalation opt-in, attachments, timeout, and output schema are taken from it.
+    /// Profile hooks are not part of `PromptRunParams`; run through `Client`/`Session`
+    /// (or `RuntimeConfig::with_hooks`) to apply them.
+    /// Allocation: clones profile Strings/vectors + cwd/prompt Strings. Complexity: O(n), n = field sizes.
+    pub fn from_profile(
+        profile: &RunProfile,
+        cwd: impl Into<String>,
+        prompt: impl Into<String>,
+    ) -> Self {
+        ProfileCore::from(profile.clone()).into_prompt_params(cwd.into(), prompt.into())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct SessionConfig {
     pub cwd: String,
@@ -303,7 +350,8 @@ pub struct SessionConfig {
     /// Explicit opt-in gate for privileged sandbox usage (SEC-004).
     pub privileged_escalation_approved: bool,
     pub attachments: Vec<PromptAttachment>,
-    pub timeout: Duration,
+    /// Turn timeout. `None` (the default) uses `RuntimeConfig::default_prompt_timeout`.
+    pub timeout: Option<Duration>,
     pub output_schema: Option<Value>,
     pub hooks: RuntimeHookConfig,
 }
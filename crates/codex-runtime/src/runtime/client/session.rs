@@ -1,12 +1,17 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
+use serde_json::Value;
 use tokio::sync::Mutex;
 
-use crate::runtime::api::{PromptRunError, PromptRunParams, PromptRunResult, PromptRunStream};
+use crate::runtime::api::{
+    ApprovalPolicy, PromptAttachment, PromptRunError, PromptRunParams, PromptRunResult,
+    PromptRunStream, ReasoningEffort, SandboxPolicy,
+};
 use crate::runtime::core::Runtime;
 use crate::runtime::errors::RpcError;
-use crate::runtime::hooks::merge_hook_configs;
+use crate::runtime::hooks::{merge_hook_configs, RuntimeHookConfig};
 
 use super::profile::{prepared_prompt_run_from_profile, session_prepared_prompt_run};
 use super::{RunProfile, SessionConfig};
@@ -130,14 +135,21 @@ impl Session {
         &self,
         prompt: impl Into<String>,
     ) -> Result<PromptRunStream, PromptRunError> {
-        self.state.ensure_open_for_prompt()?;
         let prepared = session_prepared_prompt_run(&self.config, prompt);
+        self.stream_with(prepared.params, prepared.hooks.as_ref())
+            .await
+    }
+
+    /// Shared by `ask_stream` and `SessionPrompt::stream`: open check, then a scoped stream
+    /// on the session thread with `hooks`.
+    async fn stream_with(
+        &self,
+        params: PromptRunParams,
+        hooks: &RuntimeHookConfig,
+    ) -> Result<PromptRunStream, PromptRunError> {
+        self.state.ensure_open_for_prompt()?;
         self.runtime
-            .run_prompt_on_loaded_thread_stream_with_hooks(
-                &self.thread_id,
-                prepared.params,
-                Some(prepared.hooks.as_ref()),
-            )
+            .run_prompt_on_loaded_thread_stream_with_hooks(&self.thread_id, params, Some(hooks))
             .await
     }
 
@@ -151,6 +163,16 @@ impl Session {
         self.ask_stream(prompt).await?.finish().await
     }
 
+    /// Start one prompt builder seeded with the session defaults.
+    /// Per-call overrides apply only to the built turn; session defaults stay untouched.
+    /// Allocation: PromptRunParams clone payloads (cwd/model/sandbox/attachments). Complexity: O(n), n = attachment count + prompt length.
+    pub fn prompt(&self, prompt: impl Into<String>) -> SessionPrompt<'_> {
+        SessionPrompt {
+            session: self,
+            params: session_prepared_prompt_run(&self.config, prompt).params,
+        }
+    }
+
     /// Continue this session with one prompt while overriding selected turn options.
     /// Side effects: sends turn/start RPC calls on one already-loaded thread.
     /// Allocation: depends on caller-provided params. Complexity: O(1) wrapper.
@@ -217,3 +239,81 @@ impl Session {
         permit.store_result(result)
     }
 }
+
+/// One prompt on a [`Session`] with optional per-call overrides.
+/// Built by [`Session::prompt`]; starts from the session defaults.
+#[must_use = "call run() or stream() to send the prompt"]
+pub struct SessionPrompt<'a> {
+    session: &'a Session,
+    params: PromptRunParams,
+}
+
+impl SessionPrompt<'_> {
+    /// Override model for this prompt only.
+    /// Allocation: one String. Complexity: O(model length).
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.params = self.params.with_model(model);
+        self
+    }
+
+    /// Override reasoning effort for this prompt only.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_effort(mut self, effort: ReasoningEffort) -> Self {
+        self.params = self.params.with_effort(effort);
+        self
+    }
+
+    /// Override approval policy for this prompt only.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_approval_policy(mut self, approval_policy: ApprovalPolicy) -> Self {
+        self.params = self.params.with_approval_policy(approval_policy);
+        self
+    }
+
+    /// Override sandbox policy for this prompt only.
+    /// Allocation: depends on payload move/clone at callsite. Complexity: O(1).
+    pub fn with_sandbox_policy(mut self, sandbox_policy: SandboxPolicy) -> Self {
+        self.params = self.params.with_sandbox_policy(sandbox_policy);
+        self
+    }
+
+    /// Override timeout for this prompt only.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.params = self.params.with_timeout(timeout);
+        self
+    }
+
+    /// Override output schema for this prompt only.
+    pub fn with_output_schema(mut self, output_schema: Value) -> Self {
+        self.params = self.params.with_output_schema(output_schema);
+        self
+    }
+
+    /// Add one attachment on top of the session default attachments.
+    /// Allocation: amortized O(1) push. Complexity: O(1).
+    pub fn with_attachment(mut self, attachment: PromptAttachment) -> Self {
+        self.params = self.params.with_attachment(attachment);
+        self
+    }
+
+    /// Borrow the merged prompt params that will be sent.
+    /// Allocation: none. Complexity: O(1).
+    pub fn params(&self) -> &PromptRunParams {
+        &self.params
+    }
+
+    /// Send the prompt on the session thread and wait for the final result.
+    /// Side effects: sends turn/start RPC calls on one already-loaded thread.
+    pub async fn run(self) -> Result<PromptRunResult, PromptRunError> {
+        self.session.ask_with(self.params).await
+    }
+
+    /// Send the prompt on the session thread and receive scoped typed turn events.
+    /// Side effects: sends turn/start RPC calls on one already-loaded thread.
+    pub async fn stream(self) -> Result<PromptRunStream, PromptRunError> {
+        self.session
+            .stream_with(self.params, &self.session.config.hooks)
+            .await
+    }
+}
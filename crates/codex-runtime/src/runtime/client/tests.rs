@@ -16,7 +16,9 @@ use crate::runtime::api::{
     SandboxPreset,
 };
 use crate::runtime::hooks::RuntimeHookConfig;
-use crate::runtime::{InitializeCapabilities, PromptRunParams};
+use crate::runtime::{
+    InitializeCapabilities, PromptRunError, PromptRunParams, Runtime, RuntimeConfig,
+};
 
 #[derive(Debug)]
 struct TempDir {
@@ -834,7 +836,7 @@ fn session_config_defaults_are_explicit() {
         SandboxPolicy::Preset(SandboxPreset::ReadOnly)
     );
     assert!(!cfg.privileged_escalation_approved);
-    assert_eq!(cfg.timeout, Duration::from_secs(120));
+    assert_eq!(cfg.timeout, None);
     assert!(cfg.attachments.is_empty());
 }
 
@@ -849,11 +851,48 @@ fn run_profile_defaults_are_explicit() {
         SandboxPolicy::Preset(SandboxPreset::ReadOnly)
     );
     assert!(!profile.privileged_escalation_approved);
-    assert_eq!(profile.timeout, Duration::from_secs(120));
+    assert_eq!(profile.timeout, None);
     assert_eq!(profile.output_schema, None);
     assert!(profile.attachments.is_empty());
 }
 
+#[test]
+fn prompt_run_params_from_named_profiles_apply_policy_defaults() {
+    let safe = PromptRunParams::from_profile(&RunProfile::safe(), "/work", "inspect");
+    assert_eq!(safe.cwd, "/work");
+    assert_eq!(safe.prompt, "inspect");
+    assert_eq!(safe.approval_policy, ApprovalPolicy::Never);
+    assert_eq!(
+        safe.sandbox_policy,
+        SandboxPolicy::Preset(SandboxPreset::ReadOnly)
+    );
+    assert!(!safe.privileged_escalation_approved);
+
+    let unapproved = PromptRunParams::from_profile(&RunProfile::autonomous(), "/work", "fix it");
+    assert!(!unapproved.privileged_escalation_approved);
+
+    let profile = RunProfile::autonomous()
+        .with_model("gpt-main")
+        .with_effort(ReasoningEffort::High)
+        .allow_privileged_escalation();
+    let autonomous = PromptRunParams::from_profile(&profile, "/work", "fix it");
+    assert_eq!(autonomous.model.as_deref(), Some("gpt-main"));
+    assert_eq!(autonomous.effort, Some(ReasoningEffort::High));
+    assert_eq!(autonomous.approval_policy, ApprovalPolicy::OnFailure);
+    assert_eq!(
+        autonomous.sandbox_policy,
+        SandboxPolicy::Preset(SandboxPreset::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: false,
+        })
+    );
+    assert!(autonomous.privileged_escalation_approved);
+    assert_eq!(
+        autonomous,
+        profile_to_prompt_params("/work".to_owned(), "fix it", profile)
+    );
+}
+
 #[test]
 fn client_config_initialize_capabilities_are_explicit() {
     let cfg = ClientConfig::new();
@@ -965,7 +1004,7 @@ fn session_config_from_profile_maps_all_fields() {
         })
     );
     assert!(cfg.privileged_escalation_approved);
-    assert_eq!(cfg.timeout, Duration::from_secs(33));
+    assert_eq!(cfg.timeout, Some(Duration::from_secs(33)));
     assert_eq!(
         cfg.output_schema,
         Some(json!({"type":"object","properties":{"ok":{"type":"boolean"}}}))
@@ -1012,7 +1051,7 @@ fn session_prompt_params_maps_config_and_prompt() {
         })
     );
     assert!(params.privileged_escalation_approved);
-    assert_eq!(params.timeout, Duration::from_secs(33));
+    assert_eq!(params.timeout, Some(Duration::from_secs(33)));
     assert_eq!(
         params.output_schema,
         Some(json!({"type":"object","required":["answer"]}))
@@ -1054,7 +1093,7 @@ fn profile_to_prompt_params_maps_profile_and_input() {
         })
     );
     assert!(params.privileged_escalation_approved);
-    assert_eq!(params.timeout, Duration::from_secs(15));
+    assert_eq!(params.timeout, Some(Duration::from_secs(15)));
     assert_eq!(
         params.output_schema,
         Some(json!({"type":"object","properties":{"text":{"type":"string"}}}))
@@ -1068,6 +1107,45 @@ fn profile_to_prompt_params_maps_profile_and_input() {
     );
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn session_ask_without_timeout_uses_runtime_default_prompt_timeout() {
+    let script = r#"
+import json
+import sys
+
+for line in sys.stdin:
+    msg = json.loads(line)
+    rpc_id = msg.get("id")
+    if rpc_id is None:
+        continue
+    if msg.get("method") == "turn/start":
+        thread_id = msg["params"]["threadId"]
+        sys.stdout.write(json.dumps({"id": rpc_id, "result": {"turn": {"id": "turn_hang"}}}) + "\n")
+        sys.stdout.write(json.dumps({"method":"turn/started","params":{"threadId":thread_id,"turnId":"turn_hang"}}) + "\n")
+    else:
+        sys.stdout.write(json.dumps({"id": rpc_id, "result": {}}) + "\n")
+    sys.stdout.flush()
+"#;
+    let default_timeout = Duration::from_millis(150);
+    let runtime = Runtime::spawn_local(
+        RuntimeConfig::new(crate::test_fixtures::python_inline_process(script))
+            .with_default_prompt_timeout(default_timeout),
+    )
+    .await
+    .expect("runtime spawn");
+    let config = SessionConfig::new("/tmp");
+    assert_eq!(config.timeout, None);
+    let session = super::Session::new(runtime.clone(), "thr_session".to_owned(), config);
+
+    let err = session
+        .ask("never finishes")
+        .await
+        .expect_err("session run must time out on the runtime default");
+    assert!(matches!(err, PromptRunError::Timeout(d) if d == default_timeout));
+
+    runtime.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn session_ask_propagates_output_schema_to_turn_start() {
     let schema = json!({
@@ -1137,6 +1215,54 @@ async fn session_ask_stream_yields_scoped_events_and_final_result() {
     client.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn session_prompt_stream_reports_first_partial_schema_violation() {
+    let (temp, client) = connect_mock_client(
+        "runtime_client_session_stream_schema_violation",
+        ClientConfig::new(),
+    )
+    .await;
+
+    let session = client
+        .start_session(SessionConfig::new(temp_cwd(&temp)))
+        .await
+        .expect("start session");
+
+    let seen = Arc::new(Mutex::new(Vec::<String>::new()));
+    let observed = Arc::clone(&seen);
+    // The mock echoes the schema itself as the assistant delta, which this schema rejects.
+    let mut stream = session
+        .prompt("ignored")
+        .with_output_schema(json!({
+            "type": "object",
+            "properties": {"answer": {"type": "string"}},
+            "additionalProperties": false
+        }))
+        .stream()
+        .await
+        .expect("prompt stream")
+        .with_schema_violation_observer(Arc::new(move |violation| {
+            observed
+                .lock()
+                .expect("violation lock")
+                .push(violation.to_owned());
+        }));
+
+    let first = stream
+        .recv()
+        .await
+        .expect("stream recv")
+        .expect("first event");
+    assert!(matches!(first, PromptRunStreamEvent::AgentMessageDelta(_)));
+    assert_eq!(
+        *seen.lock().expect("violation lock"),
+        vec!["$: unexpected property 'additionalProperties'".to_owned()]
+    );
+    drop(stream);
+
+    client.shutdown().await.expect("shutdown");
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn session_ask_wait_finishes_scoped_stream_without_manual_loop() {
     let (temp, client) =
@@ -1327,6 +1453,115 @@ async fn session_ask_with_accepts_prompt_run_params() {
     client.shutdown().await.expect("shutdown");
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn session_run_applies_session_defaults() {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "result": {"type": "string"}
+        }
+    });
+    let (temp, client) =
+        connect_mock_client("runtime_client_session_run", ClientConfig::new()).await;
+
+    let session = client
+        .start_session(SessionConfig::new(temp_cwd(&temp)).with_output_schema(schema.clone()))
+        .await
+        .expect("start session");
+    let out = session.ask("schema-session").await.expect("ask");
+    assert_eq!(out.thread_id, "thr_client");
+    assert_eq!(out.schema_valid, Some(true));
+    let echoed: serde_json::Value =
+        serde_json::from_str(&out.assistant_text).expect("assistant text must echo schema");
+    assert_eq!(echoed, schema);
+
+    session.close().await.expect("close");
+    client.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn session_prompt_overrides_apply_only_to_one_call() {
+    let default_schema = json!({"type": "object"});
+    let override_schema = json!({
+        "type": "object",
+        "properties": {
+            "override": {"type": "string"}
+        }
+    });
+    let (temp, client) =
+        connect_mock_client("runtime_client_session_prompt", ClientConfig::new()).await;
+
+    let session = client
+        .start_session(
+            SessionConfig::new(temp_cwd(&temp))
+                .with_model("gpt-session")
+                .with_output_schema(default_schema.clone()),
+        )
+        .await
+        .expect("start session");
+
+    let prompt = session
+        .prompt("override")
+        .with_effort(ReasoningEffort::Low)
+        .with_output_schema(override_schema.clone());
+    assert_eq!(prompt.params().cwd, temp_cwd(&temp));
+    assert_eq!(prompt.params().model.as_deref(), Some("gpt-session"));
+    assert_eq!(prompt.params().effort, Some(ReasoningEffort::Low));
+    let out = prompt.run().await.expect("prompt run");
+    let echoed: serde_json::Value =
+        serde_json::from_str(&out.assistant_text).expect("assistant text must echo schema");
+    assert_eq!(echoed, override_schema);
+
+    let out = session.ask("default").await.expect("ask");
+    let echoed: serde_json::Value =
+        serde_json::from_str(&out.assistant_text).expect("assistant text must echo schema");
+    assert_eq!(echoed, default_schema);
+
+    session.close().await.expect("close");
+    let err = session
+        .prompt("closed")
+        .run()
+        .await
+        .expect_err("closed session must reject prompt");
+    assert!(matches!(err, crate::runtime::PromptRunError::Rpc(_)));
+    client.shutdown().await.expect("shutdown");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn session_result_reports_schema_compliance() {
+    let (temp, client) =
+        connect_mock_client("runtime_client_schema_valid", ClientConfig::new()).await;
+
+    let session = client
+        .start_session(SessionConfig::new(temp_cwd(&temp)))
+        .await
+        .expect("start session");
+    let out = session.ask("no-schema").await.expect("ask");
+    assert_eq!(out.schema_valid, None);
+
+    // The mock echoes the schema itself, which lacks the required `answer` key.
+    let out = session
+        .prompt("schema")
+        .with_output_schema(json!({"type": "object", "required": ["answer"]}))
+        .run()
+        .await
+        .expect("prompt run");
+    assert_eq!(out.schema_valid, Some(false));
+
+    let mut stream = session
+        .prompt("schema-stream")
+        .with_output_schema(json!({"type": "object", "required": ["type"]}))
+        .stream()
+        .await
+        .expect("prompt stream");
+    while stream.recv().await.expect("stream recv").is_some() {}
+    let out = stream.finish().await.expect("stream finish");
+    assert_eq!(out.schema_valid, Some(true));
+
+    session.close().await.expect("close");
+    client.shutdown().await.expect("shutdown");
+}
+
 #[test]
 fn runtime_module_reexports_thread_types_documented_in_api_reference() {
     let _thread_start = crate::runtime::ThreadStartParams::default();
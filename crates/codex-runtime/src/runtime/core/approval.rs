@@ -4,9 +4,14 @@ use tokio::sync::mpsc;
 use crate::runtime::approvals::ServerRequest;
 use crate::runtime::errors::{RpcErrorObject, RuntimeError};
 
-use super::dispatch::{send_rpc_error, send_rpc_result, validate_server_request_result_payload};
+use crate::runtime::metrics::ApprovalOutcome;
+
+use super::dispatch::{
+    approval_outcome_for_result, send_rpc_error, send_rpc_result, validate_approval_edit,
+    validate_server_request_result_payload,
+};
 use super::state_projection::state_remove_pending_server_request;
-use super::{PendingServerRequestEntry, Runtime};
+use super::{PendingServerRequestEntry, Runtime, APPROVAL_EDITED_PARAMS_CAPABILITY};
 
 impl Runtime {
     pub async fn take_server_request_rx(
@@ -21,19 +26,63 @@ impl Runtime {
             .ok_or(RuntimeError::ServerRequestReceiverTaken)
     }
 
+    /// Answer one queued server request with a JSON-RPC result.
+    /// Accepts a raw `Value` or a typed `ApprovalResult`; the payload is validated
+    /// against the request method before it is sent.
     pub async fn respond_approval_ok(
         &self,
         approval_id: &str,
-        result: Value,
+        result: impl Into<Value>,
     ) -> Result<(), RuntimeError> {
+        let result = result.into();
         let entry = self
             .take_pending_server_request_entry(approval_id, |entry| {
                 validate_server_request_result_payload(&entry.method, &result)
             })
             .await?;
+        self.inner
+            .metrics
+            .record_approval_outcome(&entry.method, approval_outcome_for_result(&result));
         send_rpc_result(&self.inner, &entry.rpc_id, result).await
     }
 
+    /// Approve one queued command/file-change request with edited params ("approve with
+    /// changes"), sent as `{"decision":"accept","editedParams":...}`. The edit is checked
+    /// against the request method and the original params (see `validate_approval_edit`).
+    /// Fails closed with `RuntimeError::Unsupported` unless the server declared
+    /// `approvalEditedParams` at initialize; the request then stays pending.
+    /// Allocation: one result object. Complexity: O(p), p = edited params size.
+    pub async fn respond_approval_with_edit(
+        &self,
+        approval_id: &str,
+        edited_params: Value,
+    ) -> Result<(), RuntimeError> {
+        if !self.server_supports_approval_edits() {
+            return Err(RuntimeError::Unsupported(format!(
+                "server did not declare capabilities.{APPROVAL_EDITED_PARAMS_CAPABILITY}"
+            )));
+        }
+        let state = self.state_snapshot();
+        let entry = self
+            .take_pending_server_request_entry(approval_id, |entry| {
+                let original = state
+                    .pending_server_requests
+                    .get(&entry.rpc_key)
+                    .map(|pending| &pending.params);
+                validate_approval_edit(&entry.method, original, &edited_params)
+            })
+            .await?;
+        self.inner
+            .metrics
+            .record_approval_outcome(&entry.method, ApprovalOutcome::Approved);
+        send_rpc_result(
+            &self.inner,
+            &entry.rpc_id,
+            json!({ "decision": "accept", "editedParams": edited_params }),
+        )
+        .await
+    }
+
     pub async fn respond_approval_err(
         &self,
         approval_id: &str,
@@ -42,6 +91,9 @@ impl Runtime {
         let entry = self
             .take_pending_server_request_entry(approval_id, |_| Ok(()))
             .await?;
+        self.inner
+            .metrics
+            .record_approval_outcome(&entry.method, ApprovalOutcome::Declined);
         send_rpc_error(
             &self.inner,
             &entry.rpc_id,
@@ -0,0 +1,85 @@
+use tokio::runtime::{Builder, Handle};
+
+use crate::runtime::api::{PromptRunError, PromptRunParams, PromptRunResult};
+use crate::runtime::errors::RuntimeError;
+
+use super::{Runtime, RuntimeConfig};
+
+/// Synchronous facade over `Runtime` for callers without an async executor.
+///
+/// Owns a private current-thread tokio reactor plus the `Runtime` spawned on it.
+/// Background work (transport reader, dispatcher, supervisor) only makes progress
+/// while one of the `*_blocking` calls is driving the reactor.
+///
+/// Every method must be called from plain synchronous code: calling from inside an
+/// existing tokio runtime returns `RuntimeError::BlockingInAsyncContext` instead of
+/// nesting reactors (which would panic).
+pub struct BlockingRuntime {
+    reactor: tokio::runtime::Runtime,
+    runtime: Runtime,
+}
+
+impl std::fmt::Debug for BlockingRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockingRuntime")
+            .field("initialized", &self.runtime.is_initialized())
+            .finish()
+    }
+}
+
+impl Runtime {
+    /// Create a dedicated current-thread reactor and spawn a runtime on it.
+    /// Must not be called from an async context; see `BlockingRuntime`.
+    /// Side effects: builds one tokio reactor, spawns the app-server child process.
+    /// Allocation: reactor + runtime state. Complexity: O(1) plus process startup.
+    pub fn spawn_blocking(cfg: RuntimeConfig) -> Result<BlockingRuntime, RuntimeError> {
+        ensure_sync_context()?;
+        let reactor = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| {
+                RuntimeError::Internal(format!("blocking reactor build failed: {err}"))
+            })?;
+        let runtime = reactor.block_on(Runtime::spawn_local(cfg))?;
+        Ok(BlockingRuntime { reactor, runtime })
+    }
+}
+
+impl BlockingRuntime {
+    /// Async runtime driven by this facade. Futures built from it must be run
+    /// through `block_on`, not on another executor.
+    pub fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    /// Drive `future` to completion on the owned reactor.
+    /// Must not be called from an async context.
+    /// Complexity: whatever `future` costs; blocks the calling thread.
+    pub fn block_on<F: std::future::Future>(&self, future: F) -> Result<F::Output, RuntimeError> {
+        ensure_sync_context()?;
+        Ok(self.reactor.block_on(future))
+    }
+
+    /// Blocking form of `Runtime::run_prompt`.
+    /// Side effects: same as `run_prompt`; blocks the calling thread until the turn ends.
+    pub fn run_prompt_blocking(
+        &self,
+        p: PromptRunParams,
+    ) -> Result<PromptRunResult, PromptRunError> {
+        self.block_on(self.runtime.run_prompt(p))?
+    }
+
+    /// Blocking form of `Runtime::shutdown`; drops the reactor afterwards.
+    /// Side effects: stops the child process and all background tasks.
+    pub fn shutdown_blocking(self) -> Result<(), RuntimeError> {
+        self.block_on(self.runtime.shutdown())?
+    }
+}
+
+/// Reject calls made from inside a tokio runtime: nested `block_on` panics.
+fn ensure_sync_context() -> Result<(), RuntimeError> {
+    match Handle::try_current() {
+        Ok(_) => Err(RuntimeError::BlockingInAsyncContext),
+        Err(_) => Ok(()),
+    }
+}
@@ -6,11 +6,19 @@ use std::sync::Arc;
 use serde_json::{json, Value};
 use tokio::time::Duration;
 
+use crate::runtime::api::AttachmentPlaceholderStrategy;
 use crate::runtime::approvals::ServerRequestConfig;
+use crate::runtime::client::CompatibilityGuard;
 use crate::runtime::hooks::RuntimeHookConfig;
+use crate::runtime::id::IdGenerator;
+use crate::runtime::prompt_cache::PromptCache;
 use crate::runtime::sink::EventSink;
 use crate::runtime::state::StateProjectionLimits;
-use crate::runtime::transport::{StdioProcessSpec, StdioTransportConfig};
+use crate::runtime::transport::{RawFrameObserver, StdioProcessSpec, StdioTransportConfig};
+
+/// Callback fired as `(requested_thread_id, resumed_thread_id)` when `thread/resume`
+/// returns a different id and `RuntimeConfig::accept_resume_id_change` is set.
+pub type ResumeIdChangeObserver = Arc<dyn Fn(&str, &str) + Send + Sync>;
 
 // ── Supervisor ────────────────────────────────────────────────────────────
 
@@ -27,13 +35,17 @@ pub enum RestartPolicy {
 }
 
 /// Configuration for the process supervisor lifecycle.
-/// Copy type — zero allocation.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Allocation: only the optional post-restart health check payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SupervisorConfig {
     pub restart: RestartPolicy,
     pub shutdown_flush_timeout_ms: u64,
     pub shutdown_terminate_grace_ms: u64,
     pub restart_budget_reset_ms: u64,
+    /// JSON-RPC `(method, params)` issued after a restart handshake.
+    /// Only a successful response marks the connection `Running`; a failure
+    /// counts as a failed restart attempt against `max_restarts`.
+    pub post_restart_healthcheck: Option<(String, Value)>,
 }
 
 impl Default for SupervisorConfig {
@@ -43,10 +55,24 @@ impl Default for SupervisorConfig {
             shutdown_flush_timeout_ms: 500,
             shutdown_terminate_grace_ms: 750,
             restart_budget_reset_ms: 30_000,
+            post_restart_healthcheck: None,
         }
     }
 }
 
+impl SupervisorConfig {
+    /// Require a successful `method` call after each restart before declaring recovery.
+    /// Allocation: method String + params Value. Complexity: O(1).
+    pub fn with_post_restart_healthcheck(
+        mut self,
+        method: impl Into<String>,
+        params: Value,
+    ) -> Self {
+        self.post_restart_healthcheck = Some((method.into(), params));
+        self
+    }
+}
+
 /// Initialize capability switches exposed to the child app-server.
 /// Copy type — zero allocation.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -81,6 +107,19 @@ pub struct RuntimeConfig {
     pub transport: StdioTransportConfig,
     pub supervisor: SupervisorConfig,
     pub rpc_response_timeout: Duration,
+    /// Deadline for the `initialize` response during spawn and restarts.
+    pub initialize_timeout: Duration,
+    /// Max serialized size of the `initialize` result; larger results fail the handshake.
+    pub max_initialize_result_bytes: usize,
+    /// Checked against the `initialize` result's `userAgent` on every handshake (spawn and
+    /// restarts); a mismatch fails it like an `initialize` error. `None` (default) = no check.
+    pub compatibility_guard: Option<CompatibilityGuard>,
+    /// Extra `spawn_local` attempts after a transient spawn/initialize failure. Default 0.
+    pub spawn_retries: u32,
+    /// Delay between `spawn_local` attempts.
+    pub spawn_retry_backoff: Duration,
+    /// Prompt-run timeout used when `PromptRunParams::timeout` is `None`.
+    pub default_prompt_timeout: Duration,
     pub server_requests: ServerRequestConfig,
     pub initialize_params: Value,
     pub live_channel_capacity: usize,
@@ -88,6 +127,27 @@ pub struct RuntimeConfig {
     pub event_sink: Option<Arc<dyn EventSink>>,
     pub event_sink_channel_capacity: usize,
     pub state_projection_limits: StateProjectionLimits,
+    pub id_generator: Option<Arc<dyn IdGenerator>>,
+    /// Result cache for read-only `run_prompt` calls; `None` disables caching.
+    pub prompt_cache: Option<Arc<dyn PromptCache>>,
+    pub attachment_placeholder_strategy: AttachmentPlaceholderStrategy,
+    /// Max attachments per prompt run (after hook patches). `None` = unlimited.
+    pub max_attachments: Option<usize>,
+    /// Max summed size of local attachment files per prompt run. `None` = unlimited.
+    pub max_attachment_bytes: Option<u64>,
+    /// Accept a `thread/resume` result whose id differs from the requested one and
+    /// continue on the returned id. `false` (default) fails with `RpcError::InvalidRequest`.
+    pub accept_resume_id_change: bool,
+    pub resume_id_change_observer: Option<ResumeIdChangeObserver>,
+    /// Protocol debugging tap: sees every stdio frame exactly as it crossed the wire.
+    /// `None` (default) adds no per-frame work.
+    pub raw_frame_observer: Option<RawFrameObserver>,
+    /// Max prompt turns in flight at once; further turns wait in FIFO order before
+    /// `turn/start`. `None` (default) = unlimited. Must be > 0 when set.
+    pub max_concurrent_turns: Option<usize>,
+    /// Let privileged sandboxes run with `ApprovalPolicy::Never` (unattended CI).
+    /// Explicit escalation opt-in and scope are still required. Default `false`.
+    pub allow_unrestricted_sandbox: bool,
 }
 
 impl RuntimeConfig {
@@ -100,6 +160,12 @@ impl RuntimeConfig {
             transport: StdioTransportConfig::default(),
             supervisor: SupervisorConfig::default(),
             rpc_response_timeout: Duration::from_secs(30),
+            initialize_timeout: Duration::from_secs(10),
+            max_initialize_result_bytes: 64 * 1024,
+            compatibility_guard: None,
+            spawn_retries: 0,
+            spawn_retry_backoff: Duration::from_millis(500),
+            default_prompt_timeout: Duration::from_secs(120),
             server_requests: ServerRequestConfig::default(),
             initialize_params: json!({
                 "clientInfo": {
@@ -114,6 +180,16 @@ impl RuntimeConfig {
             event_sink: None,
             event_sink_channel_capacity: 1024,
             state_projection_limits: StateProjectionLimits::default(),
+            id_generator: None,
+            prompt_cache: None,
+            attachment_placeholder_strategy: AttachmentPlaceholderStrategy::UsePath,
+            max_attachments: None,
+            max_attachment_bytes: None,
+            accept_resume_id_change: false,
+            resume_id_change_observer: None,
+            raw_frame_observer: None,
+            max_concurrent_turns: None,
+            allow_unrestricted_sandbox: false,
         }
     }
 
@@ -124,6 +200,115 @@ impl RuntimeConfig {
         self
     }
 
+    /// Replace the built-in id source (e.g. `SequentialIdGenerator` for reproducible tests).
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_id_generator(mut self, id_generator: Arc<dyn IdGenerator>) -> Self {
+        self.id_generator = Some(id_generator);
+        self
+    }
+
+    /// Serve eligible read-only prompt runs from `prompt_cache` (see `PromptCacheKey::for_params`).
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_prompt_cache(mut self, prompt_cache: Arc<dyn PromptCache>) -> Self {
+        self.prompt_cache = Some(prompt_cache);
+        self
+    }
+
+    /// Choose how prompt `@path` attachments without a placeholder get one.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_attachment_placeholder_strategy(
+        mut self,
+        strategy: AttachmentPlaceholderStrategy,
+    ) -> Self {
+        self.attachment_placeholder_strategy = strategy;
+        self
+    }
+
+    /// Cap the number of attachments per prompt run.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_max_attachments(mut self, max_attachments: usize) -> Self {
+        self.max_attachments = Some(max_attachments);
+        self
+    }
+
+    /// Cap the summed size of local attachment files (`@path`, skill, local image) per
+    /// prompt run. Image URLs count as zero bytes.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_max_attachment_bytes(mut self, max_attachment_bytes: u64) -> Self {
+        self.max_attachment_bytes = Some(max_attachment_bytes);
+        self
+    }
+
+    /// Accept (`true`) or reject (`false`, default) resumed threads that come back
+    /// under a different id.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_accept_resume_id_change(mut self, accept: bool) -> Self {
+        self.accept_resume_id_change = accept;
+        self
+    }
+
+    /// Observe accepted resume id migrations; only fires when
+    /// `accept_resume_id_change` is set.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_resume_id_change_observer(mut self, observer: ResumeIdChangeObserver) -> Self {
+        self.resume_id_change_observer = Some(observer);
+        self
+    }
+
+    /// Observe raw inbound/outbound frames, including lines that fail to parse.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_raw_frame_observer(mut self, observer: RawFrameObserver) -> Self {
+        self.raw_frame_observer = Some(observer);
+        self
+    }
+
+    /// Cap prompt turns in flight; extra `run_prompt*` turns queue until a slot frees.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_max_concurrent_turns(mut self, max_concurrent_turns: usize) -> Self {
+        self.max_concurrent_turns = Some(max_concurrent_turns);
+        self
+    }
+
+    /// Drop the "non-never approval" requirement of the privileged sandbox guard, so
+    /// trusted automation can run `DangerFullAccess` without being asked. Still needs
+    /// `privileged_escalation_approved` and a cwd/writable-roots scope; spawn logs a warning.
+    /// Allocation: none. Complexity: O(1).
+    pub fn allow_unrestricted_sandbox(mut self, allow: bool) -> Self {
+        self.allow_unrestricted_sandbox = allow;
+        self
+    }
+
+    /// Override how long spawn waits for the `initialize` response.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_initialize_timeout(mut self, initialize_timeout: Duration) -> Self {
+        self.initialize_timeout = initialize_timeout;
+        self
+    }
+
+    /// Enforce `guard` during the `initialize` handshake instead of after spawn.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_compatibility_guard(mut self, guard: CompatibilityGuard) -> Self {
+        self.compatibility_guard = Some(guard);
+        self
+    }
+
+    /// Retry the whole spawn + `initialize` sequence up to `retries` times, `backoff`
+    /// apart, when the child fails to start, disconnects, or does not answer in time.
+    /// An `initialize` error response is not retried.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_spawn_retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.spawn_retries = retries;
+        self.spawn_retry_backoff = backoff;
+        self
+    }
+
+    /// Override the timeout applied to prompt runs that leave `timeout` unset.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_default_prompt_timeout(mut self, default_prompt_timeout: Duration) -> Self {
+        self.default_prompt_timeout = default_prompt_timeout;
+        self
+    }
+
     /// Override initialize capability switches while preserving other init params.
     pub fn with_initialize_capabilities(mut self, capabilities: InitializeCapabilities) -> Self {
         set_initialize_capabilities(&mut self.initialize_params, capabilities);
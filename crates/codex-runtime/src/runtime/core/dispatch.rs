@@ -7,13 +7,14 @@ use tokio::sync::mpsc;
 use tokio::time::{interval, Duration, MissedTickBehavior};
 use uuid::Uuid;
 
-use crate::plugin::{HookContext, HookPhase, HookReport};
+use crate::plugin::{AutoApproval, HookContext, HookPhase, HookReport};
 use crate::runtime::approvals::{
-    route_server_request, ServerRequest, ServerRequestRoute, TimeoutAction,
+    is_approval_method, is_known_server_request_method, route_server_request, ApprovalDecision,
+    ServerRequest, ServerRequestRoute, TimeoutAction,
 };
 use crate::runtime::errors::RuntimeError;
 use crate::runtime::events::{Direction, Envelope, JsonRpcId, MsgKind};
-use crate::runtime::metrics::RuntimeMetrics;
+use crate::runtime::metrics::{ApprovalOutcome, RuntimeMetrics};
 use crate::runtime::rpc::{extract_message_metadata, map_rpc_error};
 use crate::runtime::rpc_contract::methods;
 use crate::runtime::sink::EventSink;
@@ -28,7 +29,11 @@ use super::{PendingServerRequestEntry, RuntimeInner};
 
 const APPROVAL_TIMEOUT_SWEEP_INTERVAL: Duration = Duration::from_millis(50);
 
-pub(super) async fn dispatcher_loop(inner: Arc<RuntimeInner>, mut read_rx: mpsc::Receiver<Value>) {
+pub(super) async fn dispatcher_loop(
+    inner: Arc<RuntimeInner>,
+    mut read_rx: mpsc::Receiver<Value>,
+    epoch: u64,
+) {
     let mut timeout_sweep = interval(APPROVAL_TIMEOUT_SWEEP_INTERVAL);
     timeout_sweep.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
@@ -53,33 +58,22 @@ pub(super) async fn dispatcher_loop(inner: Arc<RuntimeInner>, mut read_rx: mpsc:
                         Ok(json.get("result").cloned().unwrap_or(Value::Null))
                     };
 
-                    if let Some(tx) = inner.io.pending.lock().await.remove(&id) {
+                    if let Some(entry) = inner.io.pending.lock().await.remove(&id) {
                         inner.metrics.dec_pending_rpc();
-                        let _ = tx.send(response);
+                        let _ = entry.tx.send(response);
                     }
                 }
             }
             MsgKind::ServerRequest => {
                 if let (Some(id), Some(method)) = (request_id, metadata.method.as_deref()) {
                     let params = json.get("params").cloned().unwrap_or(Value::Null);
-                    match route_server_request(
-                        method,
-                        inner.spec.server_request_cfg.auto_decline_unknown,
-                    ) {
-                        ServerRequestRoute::AutoDecline => {
-                            let _ = respond_with_timeout_policy(&inner, &id, method).await;
-                        }
-                        ServerRequestRoute::Queue => {
-                            queue_server_request(&inner, id, method, params).await;
-                        }
-                    }
+                    handle_server_request(&inner, id, method, params).await;
                 }
             }
             MsgKind::Notification | MsgKind::Unknown => {}
         }
 
-        let seq = inner.counters.next_seq.fetch_add(1, Ordering::Relaxed) + 1;
-        let envelope = Envelope {
+        emit_envelope(&inner, |seq| Envelope {
             seq,
             ts_millis: now_millis(),
             direction: Direction::Inbound,
@@ -90,12 +84,7 @@ pub(super) async fn dispatcher_loop(inner: Arc<RuntimeInner>, mut read_rx: mpsc:
             turn_id: metadata.turn_id,
             item_id: metadata.item_id,
             json: Arc::new(json),
-        };
-        state_apply_envelope(&inner, &envelope);
-        route_event_sink(&inner, &envelope);
-        if inner.io.live_tx.send(envelope).is_err() {
-            inner.metrics.record_broadcast_send_failed();
-        }
+        });
             }
             _ = timeout_sweep.tick() => {
                 expire_pending_server_requests(&inner).await;
@@ -104,9 +93,56 @@ pub(super) async fn dispatcher_loop(inner: Arc<RuntimeInner>, mut read_rx: mpsc:
     }
 
     resolve_transport_closed_pending(&inner).await;
+    inner
+        .counters
+        .closed_dispatcher_epoch
+        .store(epoch, Ordering::Release);
     inner.io.transport_closed_signal.notify_one();
 }
 
+/// Allocate the next seq and hand the built envelope to state, sink, lossless and live
+/// channels. `emit_order` is held throughout, so envelopes synthesized by the runtime
+/// outside the dispatcher still reach every channel in seq order.
+/// Allocation: see `route_event_sink` and `route_lossless_live`.
+pub(super) fn emit_envelope(inner: &Arc<RuntimeInner>, build: impl FnOnce(u64) -> Envelope) {
+    let _order = match inner.io.emit_order.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let seq = inner.counters.next_seq.fetch_add(1, Ordering::Relaxed) + 1;
+    let envelope = build(seq);
+    state_apply_envelope(inner, &envelope);
+    route_event_sink(inner, &envelope);
+    route_lossless_live(inner, &envelope);
+    if inner.io.live_tx.send(envelope).is_err() {
+        inner.metrics.record_broadcast_send_failed();
+    }
+}
+
+/// Hand one envelope to every lossless subscriber's forwarding queue without waiting.
+/// Each subscriber's own task moves envelopes into its bounded receiver, so a stalled
+/// consumer only grows its queue. Closed queues are pruned.
+/// Allocation: one envelope clone per subscriber. Complexity: O(s), s = lossless subscriber count.
+fn route_lossless_live(inner: &Arc<RuntimeInner>, envelope: &Envelope) {
+    let mut saw_closed = false;
+    {
+        let subscribers = match inner.io.lossless_live_txs.read() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        for tx in subscribers.iter() {
+            saw_closed |= tx.send(envelope.clone()).is_err();
+        }
+    }
+
+    if saw_closed {
+        match inner.io.lossless_live_txs.write() {
+            Ok(mut guard) => guard.retain(|tx| !tx.is_closed()),
+            Err(poisoned) => poisoned.into_inner().retain(|tx| !tx.is_closed()),
+        }
+    }
+}
+
 async fn expire_pending_server_requests(inner: &Arc<RuntimeInner>) {
     let now = now_millis();
     let expired: Vec<PendingServerRequestEntry> = {
@@ -125,6 +161,9 @@ async fn expire_pending_server_requests(inner: &Arc<RuntimeInner>) {
 
     for entry in expired {
         inner.metrics.dec_pending_server_request();
+        inner
+            .metrics
+            .record_approval_outcome(&entry.method, ApprovalOutcome::TimedOut);
         state_remove_pending_server_request(inner, &entry.rpc_key);
         let _ = respond_with_timeout_policy(inner, &entry.rpc_id, &entry.method).await;
     }
@@ -166,6 +205,10 @@ async fn queue_server_request(
     method: &str,
     params: Value,
 ) {
+    if let Some(result) = maybe_auto_approve(inner, method, &params) {
+        let _ = send_rpc_result(inner, &rpc_id, result).await;
+        return;
+    }
     if let Some(result) = maybe_run_pre_tool_use_hooks(inner, method, &params).await {
         let _ = send_rpc_result(inner, &rpc_id, result).await;
         return;
@@ -176,16 +219,28 @@ async fn queue_server_request(
     let deadline = compute_deadline_millis(now, inner.spec.server_request_cfg.default_timeout_ms);
     let rpc_key = jsonrpc_state_key(&rpc_id);
 
-    inner.io.pending_server_requests.lock().await.insert(
-        approval_id.clone(),
-        PendingServerRequestEntry {
-            rpc_id,
-            rpc_key: rpc_key.clone(),
-            method: method.to_owned(),
-            created_at_millis: now,
-            deadline_millis: deadline,
-        },
-    );
+    {
+        let mut pending = inner.io.pending_server_requests.lock().await;
+        // Re-check under the lock: a turn registered since the first check only sweeps
+        // entries already in the map (see `register_auto_approve_turn`).
+        if let Some(result) = maybe_auto_approve(inner, method, &params) {
+            drop(pending);
+            let _ = send_rpc_result(inner, &rpc_id, result).await;
+            return;
+        }
+        pending.insert(
+            approval_id.clone(),
+            PendingServerRequestEntry {
+                rpc_id,
+                rpc_key: rpc_key.clone(),
+                method: method.to_owned(),
+                thread_id: param_str(&params, "threadId"),
+                turn_id: param_str(&params, "turnId"),
+                created_at_millis: now,
+                deadline_millis: deadline,
+            },
+        );
+    }
     inner.metrics.inc_pending_server_request();
     state_insert_pending_server_request(
         inner,
@@ -218,6 +273,9 @@ async fn queue_server_request(
                 .remove(&approval_id);
             if let Some(pending) = pending {
                 inner.metrics.dec_pending_server_request();
+                inner
+                    .metrics
+                    .record_approval_outcome(&pending.method, ApprovalOutcome::AutoDeclined);
                 state_remove_pending_server_request(inner, &pending.rpc_key);
                 let _ = respond_with_timeout_policy(inner, &pending.rpc_id, &pending.method).await;
             }
@@ -225,6 +283,175 @@ async fn queue_server_request(
     }
 }
 
+/// Answer or queue one inbound server request: the unknown-method handler first, then
+/// decline/queue routing. Runs on the dispatcher task, so nothing here may block.
+/// Allocation: see `queue_server_request`. Complexity: O(1) plus handler cost.
+async fn handle_server_request(
+    inner: &Arc<RuntimeInner>,
+    id: JsonRpcId,
+    method: &str,
+    params: Value,
+) {
+    inner.metrics.record_approval_requested(method);
+    if let Some(decision) = run_unknown_server_request_handler(inner, method, &params) {
+        let _ = respond_with_decision(inner, &id, method, decision).await;
+        return;
+    }
+    match route_server_request(method, inner.spec.server_request_cfg.auto_decline_unknown) {
+        ServerRequestRoute::AutoDecline => {
+            inner
+                .metrics
+                .record_approval_outcome(method, ApprovalOutcome::AutoDeclined);
+            let _ = respond_with_timeout_policy(inner, &id, method).await;
+        }
+        ServerRequestRoute::Queue => {
+            queue_server_request(inner, id, method, params).await;
+        }
+    }
+}
+
+/// Ask `ServerRequestConfig::unknown_handler` to answer an unknown method directly.
+/// `None` when the method is known, no handler is set, or the handler defers.
+/// Allocation: one ServerRequest (uuid + params clone) when a handler runs. Complexity: O(1).
+fn run_unknown_server_request_handler(
+    inner: &Arc<RuntimeInner>,
+    method: &str,
+    params: &Value,
+) -> Option<ApprovalDecision> {
+    let handler = inner.spec.server_request_cfg.unknown_handler.as_ref()?;
+    if is_known_server_request_method(method) {
+        return None;
+    }
+    handler(&ServerRequest {
+        approval_id: Uuid::new_v4().to_string(),
+        method: method.to_owned(),
+        params: params.clone(),
+    })
+}
+
+async fn respond_with_decision(
+    inner: &Arc<RuntimeInner>,
+    rpc_id: &JsonRpcId,
+    method: &str,
+    decision: ApprovalDecision,
+) -> Result<(), RuntimeError> {
+    match decision {
+        ApprovalDecision::Result(result) => {
+            inner
+                .metrics
+                .record_approval_outcome(method, approval_outcome_for_result(&result));
+            send_rpc_result(inner, rpc_id, result).await
+        }
+        ApprovalDecision::Error(err) => {
+            inner
+                .metrics
+                .record_approval_outcome(method, ApprovalOutcome::Declined);
+            send_rpc_error(
+                inner,
+                rpc_id,
+                json!({
+                    "code": err.code,
+                    "message": err.message,
+                    "data": err.data
+                }),
+            )
+            .await
+        }
+    }
+}
+
+/// Accept command/file-change approvals for the `(thread, turn)` registered by an
+/// `auto_approve_all` run, recording each one for the run's hook report.
+/// Other server requests (user input, tool calls) carry no accept decision and still queue.
+/// Allocation: key pair + one audit record when matched. Complexity: O(1).
+fn maybe_auto_approve(inner: &Arc<RuntimeInner>, method: &str, params: &Value) -> Option<Value> {
+    if !is_approval_method(method) {
+        return None;
+    }
+    let thread_id = params.get("threadId").and_then(Value::as_str)?;
+    let turn_id = params.get("turnId").and_then(Value::as_str)?;
+    {
+        let mut guard = match inner.io.auto_approve_turns.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let approvals = guard.get_mut(&(thread_id.to_owned(), turn_id.to_owned()))?;
+        approvals.push(AutoApproval {
+            thread_id: thread_id.to_owned(),
+            turn_id: turn_id.to_owned(),
+            method: method.to_owned(),
+        });
+    }
+    inner
+        .metrics
+        .record_approval_outcome(method, ApprovalOutcome::AutoApproved);
+    tracing::info!(thread_id, turn_id, method, "auto-approved server request");
+    Some(json!({"decision": "accept"}))
+}
+
+/// Register the auto-approve scope for `(thread_id, turn_id)` and accept that turn's approvals
+/// already queued: the server may ask before the `turn/start` response reaches the run.
+/// Swept requests may already sit in the server request channel; answering them later fails
+/// as not found.
+/// Allocation: two Strings + one record per swept request. Complexity: O(q), q = queued.
+pub(super) async fn register_auto_approve_turn(
+    inner: &Arc<RuntimeInner>,
+    thread_id: &str,
+    turn_id: &str,
+) {
+    let swept: Vec<PendingServerRequestEntry> = {
+        let mut pending = inner.io.pending_server_requests.lock().await;
+        let mut scopes = match inner.io.auto_approve_turns.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let records = scopes
+            .entry((thread_id.to_owned(), turn_id.to_owned()))
+            .or_default();
+        let approval_ids: Vec<String> = pending
+            .iter()
+            .filter(|(_, entry)| {
+                is_approval_method(&entry.method)
+                    && entry.thread_id.as_deref() == Some(thread_id)
+                    && entry.turn_id.as_deref() == Some(turn_id)
+            })
+            .map(|(approval_id, _)| approval_id.clone())
+            .collect();
+        approval_ids
+            .iter()
+            .filter_map(|approval_id| pending.remove(approval_id))
+            .inspect(|entry| {
+                records.push(AutoApproval {
+                    thread_id: thread_id.to_owned(),
+                    turn_id: turn_id.to_owned(),
+                    method: entry.method.clone(),
+                })
+            })
+            .collect()
+    };
+    for entry in swept {
+        inner.metrics.dec_pending_server_request();
+        state_remove_pending_server_request(inner, &entry.rpc_key);
+        inner
+            .metrics
+            .record_approval_outcome(&entry.method, ApprovalOutcome::AutoApproved);
+        tracing::info!(
+            thread_id,
+            turn_id,
+            method = entry.method.as_str(),
+            "auto-approved queued server request"
+        );
+        let _ = send_rpc_result(inner, &entry.rpc_id, json!({"decision": "accept"})).await;
+    }
+}
+
+fn param_str(params: &Value, key: &str) -> Option<String> {
+    params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned)
+}
+
 async fn maybe_run_pre_tool_use_hooks(
     inner: &Arc<RuntimeInner>,
     method: &str,
@@ -254,8 +481,10 @@ async fn maybe_run_pre_tool_use_hooks(
         correlation_id: format!("tu-{}", Uuid::new_v4()),
         ts_ms: now_millis(),
         metadata: Value::Null,
+        assistant_text: None,
         tool_name: tool_use_hooks::extract_tool_name(method, params),
         tool_input: tool_use_hooks::extract_tool_input(params),
+        shutdown: inner.hooks.shutdown_token(),
     };
 
     let mut report = HookReport::default();
@@ -265,8 +494,18 @@ async fn maybe_run_pre_tool_use_hooks(
     }
 
     Some(match decision {
-        Ok(()) => json!({"decision": "accept"}),
-        Err(_) => json!({"decision": "decline"}),
+        Ok(()) => {
+            inner
+                .metrics
+                .record_approval_outcome(method, ApprovalOutcome::Approved);
+            json!({"decision": "accept"})
+        }
+        Err(_) => {
+            inner
+                .metrics
+                .record_approval_outcome(method, ApprovalOutcome::Declined);
+            json!({"decision": "decline"})
+        }
     })
 }
 
@@ -299,6 +538,10 @@ async fn respond_with_timeout_policy(
     rpc_id: &JsonRpcId,
     method: &str,
 ) -> Result<(), RuntimeError> {
+    if let Some(payload) = inner.spec.server_request_cfg.decline_payloads.get(method) {
+        return send_rpc_result(inner, rpc_id, payload.clone()).await;
+    }
+
     // auth refresh has its own error path regardless of the configured on_timeout policy:
     // the client must handle the error explicitly rather than receive a synthetic decline payload.
     if method == methods::ACCOUNT_CHATGPT_AUTH_TOKENS_REFRESH {
@@ -330,6 +573,22 @@ pub(super) fn validate_server_request_result_payload(
     }
 }
 
+/// Classify a client-supplied server-request result for approval metrics.
+/// Decline-like decisions (`decline`, `cancel`, `denied`, `abort`) count as declined;
+/// everything else (accept variants, answers, tool results) counts as approved.
+/// Pure function. Allocation: none. Complexity: O(1).
+pub(super) fn approval_outcome_for_result(result: &Value) -> ApprovalOutcome {
+    let decision = match result.get("decision") {
+        Some(Value::String(decision)) => decision.as_str(),
+        Some(Value::Object(obj)) => obj.keys().next().map(String::as_str).unwrap_or_default(),
+        _ => return ApprovalOutcome::Approved,
+    };
+    match decision {
+        "decline" | "cancel" | "denied" | "abort" => ApprovalOutcome::Declined,
+        _ => ApprovalOutcome::Approved,
+    }
+}
+
 fn validate_approval_payload(method: &str, result: &Value) -> Result<(), RuntimeError> {
     match result.get("decision") {
         Some(Value::String(_)) => Ok(()),
@@ -340,6 +599,65 @@ fn validate_approval_payload(method: &str, result: &Value) -> Result<(), Runtime
     }
 }
 
+/// Identity fields an approval edit may repeat but never change.
+const APPROVAL_EDIT_IDENTITY_KEYS: [&str; 3] = ["threadId", "turnId", "itemId"];
+
+/// Validate "approve with changes" params against the request they replace.
+/// Only command/file-change approvals accept edits; identity fields must match `original`
+/// when both carry them, and a command edit needs a non-empty `command` (string or
+/// string array) plus an optional string `cwd`.
+/// Pure function. Allocation: none on success. Complexity: O(k), k = command parts.
+pub(super) fn validate_approval_edit(
+    method: &str,
+    original: Option<&Value>,
+    edited: &Value,
+) -> Result<(), RuntimeError> {
+    if !matches!(
+        method,
+        methods::ITEM_COMMAND_EXECUTION_REQUEST_APPROVAL
+            | methods::ITEM_FILE_CHANGE_REQUEST_APPROVAL
+    ) {
+        return Err(RuntimeError::Internal(format!(
+            "approval edits are not supported for {method}"
+        )));
+    }
+    let obj = require_object(edited, "invalid approval edit: expected object")?;
+    if obj.is_empty() {
+        return Err(RuntimeError::Internal(
+            "invalid approval edit: empty params".to_owned(),
+        ));
+    }
+    for key in APPROVAL_EDIT_IDENTITY_KEYS {
+        let before = original.and_then(|original| original.get(key));
+        if let (Some(before), Some(after)) = (before, obj.get(key)) {
+            if before != after {
+                return Err(RuntimeError::Internal(format!(
+                    "invalid approval edit for {method}: {key} cannot change"
+                )));
+            }
+        }
+    }
+    if method != methods::ITEM_COMMAND_EXECUTION_REQUEST_APPROVAL {
+        return Ok(());
+    }
+    let command_ok = match obj.get("command") {
+        Some(Value::String(command)) => !command.trim().is_empty(),
+        Some(Value::Array(parts)) => !parts.is_empty() && parts.iter().all(|part| part.is_string()),
+        _ => false,
+    };
+    if !command_ok {
+        return Err(RuntimeError::Internal(format!(
+            "invalid approval edit for {method}: command must be a non-empty string or string array"
+        )));
+    }
+    if !matches!(obj.get("cwd"), None | Some(Value::String(_))) {
+        return Err(RuntimeError::Internal(format!(
+            "invalid approval edit for {method}: cwd must be a string"
+        )));
+    }
+    Ok(())
+}
+
 fn validate_request_user_input_payload(result: &Value) -> Result<(), RuntimeError> {
     let obj = require_object(result, "invalid requestUserInput payload: expected object")?;
     if !matches!(obj.get("answers"), Some(Value::Object(_))) {
@@ -453,6 +771,7 @@ async fn send_rpc_message(
 fn jsonrpc_id_to_value(id: &JsonRpcId) -> Value {
     match id {
         JsonRpcId::Number(v) => Value::Number((*v).into()),
+        JsonRpcId::Signed(v) => Value::Number((*v).into()),
         JsonRpcId::Text(v) => Value::String(v.clone()),
     }
 }
@@ -460,6 +779,7 @@ fn jsonrpc_id_to_value(id: &JsonRpcId) -> Value {
 fn jsonrpc_state_key(id: &JsonRpcId) -> String {
     match id {
         JsonRpcId::Number(v) => format!("n:{v}"),
+        JsonRpcId::Signed(v) => format!("n:{v}"),
         JsonRpcId::Text(v) => format!("s:{v}"),
     }
 }
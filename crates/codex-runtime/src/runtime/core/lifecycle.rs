@@ -5,30 +5,104 @@ use std::time::Duration;
 use serde_json::json;
 use serde_json::Value;
 
-use crate::runtime::errors::RuntimeError;
-use crate::runtime::state::ConnectionState;
-use crate::runtime::transport::StdioTransport;
+use crate::runtime::client::check_initialize_compatibility;
+use crate::runtime::errors::{RpcError, RuntimeError};
+use crate::runtime::rpc_contract::methods;
+use crate::runtime::state::{ConnectionState, TurnStatus};
+use crate::runtime::transport::{StdioProcessSpec, StdioTransport};
 
 use super::dispatch::dispatcher_loop;
 use super::rpc_io::{call_raw_inner, notify_raw_inner, resolve_transport_closed_pending};
-use super::state_projection::state_set_connection;
+use super::state_projection::{state_set_connection, state_snapshot_arc};
+use super::supervisor::start_supervisor_task;
 use super::RuntimeInner;
 
-pub(super) async fn spawn_connection_generation(
+/// Poll interval while `reconfigure` waits for in-progress turns to end.
+const RECONFIGURE_DRAIN_POLL: Duration = Duration::from_millis(20);
+
+/// First generation for `spawn_local`: on a transient failure (child spawn error,
+/// initialize timeout or disconnect, `initialized` write failure) the whole spawn +
+/// handshake sequence is retried up to `retries` times, `backoff` apart. An `initialize`
+/// error response or an oversized result is deterministic and fails at once.
+pub(super) async fn spawn_initial_generation(
     inner: &Arc<RuntimeInner>,
-    generation: u64,
+    retries: u32,
+    backoff: Duration,
 ) -> Result<(), RuntimeError> {
+    let mut attempt = 0u32;
+    loop {
+        match try_attach_connection_generation(inner).await {
+            Ok(()) => {
+                mark_generation_running(inner, 0);
+                return Ok(());
+            }
+            Err(failure) if failure.transient && attempt < retries => {
+                attempt += 1;
+                tracing::warn!(
+                    "spawn attempt {attempt} of {} failed, retrying in {}ms: {}",
+                    retries.saturating_add(1),
+                    backoff.as_millis(),
+                    failure.error
+                );
+                tokio::time::sleep(backoff).await;
+            }
+            Err(failure) => return Err(failure.error),
+        }
+    }
+}
+
+/// Spawn the child and complete the initialize handshake without marking it `Running`.
+/// Callers finish with `mark_generation_running` or `detach_generation`.
+pub(super) async fn attach_connection_generation(
+    inner: &Arc<RuntimeInner>,
+) -> Result<(), RuntimeError> {
+    try_attach_connection_generation(inner)
+        .await
+        .map_err(|failure| failure.error)
+}
+
+/// Failed attach, tagged with whether repeating the same attach may succeed.
+struct AttachFailure {
+    error: RuntimeError,
+    transient: bool,
+}
+
+impl AttachFailure {
+    fn transient(error: RuntimeError) -> Self {
+        Self {
+            error,
+            transient: true,
+        }
+    }
+
+    fn deterministic(error: RuntimeError) -> Self {
+        Self {
+            error,
+            transient: false,
+        }
+    }
+}
+
+async fn try_attach_connection_generation(inner: &Arc<RuntimeInner>) -> Result<(), AttachFailure> {
     if inner.counters.shutting_down.load(Ordering::Acquire) {
-        return Err(RuntimeError::TransportClosed);
+        return Err(AttachFailure::deterministic(RuntimeError::TransportClosed));
     }
 
     state_set_connection(inner, ConnectionState::Starting);
     set_initialize_result(inner, None);
 
-    let mut transport =
-        StdioTransport::spawn(inner.spec.process.clone(), inner.spec.transport_cfg).await?;
-    let read_rx = transport.take_read_rx()?;
-    let outbound_tx = transport.write_tx()?;
+    let mut transport = StdioTransport::spawn_with_metrics(
+        process_spec(inner),
+        inner.spec.transport_cfg,
+        Some(Arc::clone(&inner.metrics)),
+        inner.spec.raw_frame_observer.clone(),
+    )
+    .await
+    .map_err(AttachFailure::transient)?;
+    let read_rx = transport
+        .take_read_rx()
+        .map_err(AttachFailure::deterministic)?;
+    let outbound_tx = transport.write_tx().map_err(AttachFailure::deterministic)?;
 
     inner.io.outbound_tx.store(Some(Arc::new(outbound_tx)));
 
@@ -38,7 +112,12 @@ pub(super) async fn spawn_connection_generation(
     }
 
     let dispatcher_inner = Arc::clone(inner);
-    let dispatcher_task = tokio::spawn(dispatcher_loop(dispatcher_inner, read_rx));
+    let epoch = inner
+        .counters
+        .dispatcher_epoch
+        .fetch_add(1, Ordering::AcqRel)
+        .saturating_add(1);
+    let dispatcher_task = tokio::spawn(dispatcher_loop(dispatcher_inner, read_rx, epoch));
     inner
         .tasks
         .dispatcher_task
@@ -51,34 +130,197 @@ pub(super) async fn spawn_connection_generation(
         inner,
         "initialize",
         inner.spec.initialize_params.clone(),
-        inner.spec.rpc_response_timeout,
+        inner.spec.initialize_timeout,
     )
     .await
     {
         Ok(value) => value,
+        Err(RpcError::Timeout) => {
+            let phase = format!(
+                "initialize handshake timed out after {}ms",
+                inner.spec.initialize_timeout.as_millis()
+            );
+            return Err(AttachFailure::transient(
+                fail_spawn_generation_with_detach(
+                    inner,
+                    &phase,
+                    RuntimeError::Internal("no initialize response".to_owned()),
+                )
+                .await,
+            ));
+        }
         Err(err) => {
-            return Err(fail_spawn_generation_with_detach(
+            let transient = err == RpcError::TransportClosed;
+            let error = fail_spawn_generation_with_detach(
                 inner,
                 "initialize handshake failed",
                 RuntimeError::Internal(err.to_string()),
             )
-            .await);
+            .await;
+            return Err(AttachFailure { error, transient });
         }
     };
+    let initialize_result_bytes = initialize_result.to_string().len();
+    if initialize_result_bytes > inner.spec.max_initialize_result_bytes {
+        return Err(AttachFailure::deterministic(
+            fail_spawn_generation_with_detach(
+                inner,
+                "initialize handshake failed",
+                RuntimeError::Internal(format!(
+                    "initialize result too large: {initialize_result_bytes} bytes > {} bytes",
+                    inner.spec.max_initialize_result_bytes
+                )),
+            )
+            .await,
+        ));
+    }
+    if let Some(guard) = inner.spec.compatibility_guard.as_ref() {
+        let user_agent = initialize_result.get("userAgent").and_then(Value::as_str);
+        if let Err(err) = check_initialize_compatibility(user_agent, guard) {
+            let error = match detach_generation(inner).await {
+                Ok(()) => RuntimeError::Incompatible(err.to_string()),
+                Err(detach_err) => RuntimeError::Internal(format!(
+                    "initialize compatibility check failed: {err}; detach failed: {detach_err}"
+                )),
+            };
+            return Err(AttachFailure::deterministic(error));
+        }
+    }
     if let Err(err) = notify_raw_inner(inner, "initialized", json!({})).await {
-        return Err(
+        return Err(AttachFailure::transient(
             fail_spawn_generation_with_detach(inner, "initialized notify failed", err).await,
-        );
+        ));
     }
     set_initialize_result(inner, Some(initialize_result));
+    Ok(())
+}
 
+pub(super) fn mark_generation_running(inner: &Arc<RuntimeInner>, generation: u64) {
+    inner.counters.generation_start_seq.store(
+        inner.counters.next_seq.load(Ordering::Acquire),
+        Ordering::Release,
+    );
     inner
         .counters
         .generation
         .store(generation, Ordering::Release);
     inner.counters.initialized.store(true, Ordering::Release);
     state_set_connection(inner, ConnectionState::Running { generation });
-    Ok(())
+}
+
+/// Drain in-progress turns, then swap the process spec and respawn as the next generation.
+/// `draining` stays set until the swap ends, so no turn can start on the outgoing child.
+/// Holds the generation lock so the supervisor neither restarts concurrently nor mistakes
+/// the swap for a crash. On spawn failure the previous spec is restored and respawned.
+pub(super) async fn reconfigure_runtime(
+    inner: &Arc<RuntimeInner>,
+    new_process: StdioProcessSpec,
+) -> Result<(), RuntimeError> {
+    inner.counters.draining.store(true, Ordering::Release);
+    let result = match drain_in_progress_turns(inner).await {
+        Ok(()) => swap_generation_process(inner, new_process).await,
+        Err(err) => Err(err),
+    };
+    inner.counters.draining.store(false, Ordering::Release);
+
+    // Relaunch a supervisor that gave up (Dead) only after releasing the generation lock:
+    // shutdown joins the supervisor while holding its task slot.
+    if !inner.counters.shutting_down.load(Ordering::Acquire) {
+        let mut supervisor_guard = inner.tasks.supervisor_task.lock().await;
+        if supervisor_guard
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            supervisor_guard.take();
+            drop(supervisor_guard);
+            start_supervisor_task(inner).await;
+        }
+    }
+    result
+}
+
+async fn swap_generation_process(
+    inner: &Arc<RuntimeInner>,
+    new_process: StdioProcessSpec,
+) -> Result<(), RuntimeError> {
+    let _generation_guard = inner.tasks.generation_lock.lock().await;
+    if inner.counters.shutting_down.load(Ordering::Acquire) {
+        return Err(RuntimeError::TransportClosed);
+    }
+    let previous_process = replace_process_spec(inner, new_process);
+    inner.counters.initialized.store(false, Ordering::Release);
+    if let Err(err) = detach_generation(inner).await {
+        replace_process_spec(inner, previous_process);
+        return Err(err);
+    }
+
+    let generation = inner
+        .counters
+        .generation
+        .load(Ordering::Acquire)
+        .saturating_add(1);
+    let result = match attach_connection_generation(inner).await {
+        Ok(()) => Ok(()),
+        Err(err) => {
+            replace_process_spec(inner, previous_process);
+            if let Err(rollback_err) = attach_connection_generation(inner).await {
+                state_set_connection(inner, ConnectionState::Dead);
+                return Err(RuntimeError::Internal(format!(
+                    "reconfigure failed: {err}; rollback failed: {rollback_err}"
+                )));
+            }
+            Err(err)
+        }
+    };
+    mark_generation_running(inner, generation);
+    result
+}
+
+/// Wait, at most `default_prompt_timeout`, until the current generation has no `turn/start`
+/// in flight and no in-progress turn. Turns last seen on an earlier generation are ignored:
+/// their child is gone.
+async fn drain_in_progress_turns(inner: &Arc<RuntimeInner>) -> Result<(), RuntimeError> {
+    let deadline = tokio::time::Instant::now() + inner.spec.default_prompt_timeout;
+    loop {
+        if inner.counters.shutting_down.load(Ordering::Acquire) {
+            return Err(RuntimeError::TransportClosed);
+        }
+        let turn_start_in_flight = inner
+            .io
+            .pending
+            .lock()
+            .await
+            .values()
+            .any(|entry| entry.method == methods::TURN_START);
+        let generation_start_seq = inner.counters.generation_start_seq.load(Ordering::Acquire);
+        let state = state_snapshot_arc(inner);
+        let turn_in_progress = state.threads.values().any(|thread| {
+            thread.turns.values().any(|turn| {
+                turn.status == TurnStatus::InProgress && turn.last_seq > generation_start_seq
+            })
+        });
+        if !turn_start_in_flight && !turn_in_progress {
+            return Ok(());
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(RuntimeError::Timeout);
+        }
+        tokio::time::sleep(RECONFIGURE_DRAIN_POLL).await;
+    }
+}
+
+fn process_spec(inner: &Arc<RuntimeInner>) -> StdioProcessSpec {
+    match inner.spec.process.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+fn replace_process_spec(inner: &Arc<RuntimeInner>, process: StdioProcessSpec) -> StdioProcessSpec {
+    match inner.spec.process.write() {
+        Ok(mut guard) => std::mem::replace(&mut *guard, process),
+        Err(poisoned) => std::mem::replace(&mut *poisoned.into_inner(), process),
+    }
 }
 
 pub(super) async fn detach_generation(inner: &Arc<RuntimeInner>) -> Result<(), RuntimeError> {
@@ -87,6 +329,7 @@ pub(super) async fn detach_generation(inner: &Arc<RuntimeInner>) -> Result<(), R
 
 pub(super) async fn shutdown_runtime(inner: &Arc<RuntimeInner>) -> Result<(), RuntimeError> {
     inner.counters.shutting_down.store(true, Ordering::Release);
+    inner.hooks.signal_shutdown();
     inner.counters.initialized.store(false, Ordering::Release);
     state_set_connection(inner, ConnectionState::ShuttingDown);
     inner.io.shutdown_signal.notify_waiters();
@@ -3,29 +3,43 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::sync::RwLock;
 
-use crate::plugin::{BlockReason, HookContext, HookReport};
+use crate::plugin::{AutoApproval, BlockReason, HookContext, HookReport, ShutdownToken};
 use arc_swap::ArcSwapOption;
 use serde_json::Value;
 use tokio::sync::{broadcast, mpsc, oneshot, Mutex, Notify};
 use tokio::task::JoinHandle;
-use tokio::time::Duration;
+use tokio::time::{Duration, Instant};
 
+use crate::runtime::api::{AttachmentLimits, AttachmentPlaceholderStrategy};
 #[cfg(test)]
 use crate::runtime::approvals::TimeoutAction;
 use crate::runtime::approvals::{ServerRequest, ServerRequestConfig};
+use crate::runtime::client::{parse_initialize_user_agent, CompatibilityGuard, SemVerTriplet};
+use crate::runtime::detached_task::{current_detached_task_plan, spawn_detached_task};
 use crate::runtime::errors::{RpcError, RuntimeError};
-use crate::runtime::events::{Envelope, JsonRpcId};
+use crate::runtime::events::{Direction, Envelope, JsonRpcId, MsgKind};
 use crate::runtime::hooks::{HookKernel, PreHookDecision, RuntimeHookConfig};
+use crate::runtime::id::{IdGenerator, IdKind};
 use crate::runtime::metrics::{RuntimeMetrics, RuntimeMetricsSnapshot};
+use crate::runtime::prompt_cache::PromptCache;
+use crate::runtime::rpc_contract::{methods, ValidationObserver};
 use crate::runtime::runtime_validation::validate_runtime_capacities;
-#[cfg(test)]
-use crate::runtime::state::ConnectionState;
-use crate::runtime::state::{RuntimeState, StateProjectionLimits};
-use crate::runtime::transport::{StdioProcessSpec, StdioTransport, StdioTransportConfig};
+use crate::runtime::state::{
+    ConnectionState, HealthStatus, RuntimeLifecycle, RuntimeState, StateProjectionLimits,
+};
+use crate::runtime::transport::{
+    RawFrameObserver, StdioProcessSpec, StdioTransport, StdioTransportConfig,
+};
+use crate::runtime::turn_limiter::{TurnLimiter, TurnSlot};
 
 type PendingResult = Result<Value, RpcError>;
 
+/// `initialize` result capability a server sets to honor `editedParams` in approval answers.
+const APPROVAL_EDITED_PARAMS_CAPABILITY: &str = "approvalEditedParams";
+
 mod approval;
+#[cfg(feature = "blocking")]
+mod blocking;
 mod config;
 mod dispatch;
 pub(crate) mod io_policy;
@@ -35,17 +49,30 @@ mod rpc_io;
 mod state_projection;
 mod supervisor;
 
-pub use config::{InitializeCapabilities, RestartPolicy, RuntimeConfig, SupervisorConfig};
-use dispatch::event_sink_loop;
-use lifecycle::{shutdown_runtime, spawn_connection_generation};
-use state_projection::state_snapshot_arc;
+#[cfg(feature = "blocking")]
+pub use blocking::BlockingRuntime;
+pub use config::{
+    InitializeCapabilities, RestartPolicy, ResumeIdChangeObserver, RuntimeConfig, SupervisorConfig,
+};
+use dispatch::{event_sink_loop, register_auto_approve_turn};
+use lifecycle::{reconfigure_runtime, shutdown_runtime, spawn_initial_generation};
+pub use rpc::PendingRpc;
+use state_projection::{state_set_thread_metadata, state_snapshot_arc};
 use supervisor::start_supervisor_task;
 
+struct PendingRpcEntry {
+    method: String,
+    started_at: Instant,
+    tx: oneshot::Sender<PendingResult>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct PendingServerRequestEntry {
     rpc_id: JsonRpcId,
     rpc_key: String,
     method: String,
+    thread_id: Option<String>,
+    turn_id: Option<String>,
     created_at_millis: i64,
     deadline_millis: i64,
 }
@@ -53,29 +80,60 @@ struct PendingServerRequestEntry {
 struct RuntimeCounters {
     initialized: AtomicBool,
     shutting_down: AtomicBool,
+    /// Set while `reconfigure` drains turns and swaps the child; new `turn/start` calls
+    /// are rejected meanwhile.
+    draining: AtomicBool,
     generation: AtomicU64,
+    /// `next_seq` when the current generation came up; older turns belong to dead children.
+    generation_start_seq: AtomicU64,
+    /// Id of the most recently spawned dispatcher; bumped once per attach.
+    dispatcher_epoch: AtomicU64,
+    /// Epoch of the last dispatcher that signalled transport close.
+    closed_dispatcher_epoch: AtomicU64,
     next_rpc_id: AtomicU64,
     next_seq: AtomicU64,
+    /// Source of `hk-N` correlation ids; kept apart from `next_seq` so ids never consume seqs.
+    next_hook_correlation: AtomicU64,
 }
 
 struct RuntimeSpec {
-    process: StdioProcessSpec,
+    process: RwLock<StdioProcessSpec>,
     transport_cfg: StdioTransportConfig,
     initialize_params: Value,
     supervisor_cfg: SupervisorConfig,
     rpc_response_timeout: Duration,
+    initialize_timeout: Duration,
+    max_initialize_result_bytes: usize,
+    compatibility_guard: Option<CompatibilityGuard>,
+    default_prompt_timeout: Duration,
     server_request_cfg: ServerRequestConfig,
     state_projection_limits: StateProjectionLimits,
+    live_channel_capacity: usize,
+    id_generator: Option<Arc<dyn IdGenerator>>,
+    prompt_cache: Option<Arc<dyn PromptCache>>,
+    attachment_placeholder_strategy: AttachmentPlaceholderStrategy,
+    attachment_limits: AttachmentLimits,
+    accept_resume_id_change: bool,
+    resume_id_change_observer: Option<ResumeIdChangeObserver>,
+    raw_frame_observer: Option<RawFrameObserver>,
+    turn_limiter: TurnLimiter,
+    allow_unrestricted_sandbox: bool,
 }
 
 struct RuntimeIo {
-    pending: Mutex<HashMap<u64, oneshot::Sender<PendingResult>>>,
+    pending: Mutex<HashMap<u64, PendingRpcEntry>>,
     outbound_tx: ArcSwapOption<mpsc::Sender<Value>>,
     live_tx: broadcast::Sender<Envelope>,
+    lossless_live_txs: RwLock<Vec<mpsc::UnboundedSender<Envelope>>>,
     pending_server_requests: Mutex<HashMap<String, PendingServerRequestEntry>>,
+    /// `(thread_id, turn_id)` of active runs with `PromptRunParams::auto_approve_all`,
+    /// mapped to the approvals accepted so far for the run's hook report.
+    auto_approve_turns: RwLock<HashMap<(String, String), Vec<AutoApproval>>>,
     server_request_tx: mpsc::Sender<ServerRequest>,
     server_request_rx: Mutex<Option<mpsc::Receiver<ServerRequest>>>,
     event_sink_tx: Option<mpsc::Sender<Envelope>>,
+    /// Held while a seq is allocated and its envelope routed; see `dispatch::emit_envelope`.
+    emit_order: std::sync::Mutex<()>,
     transport_closed_signal: Notify,
     shutdown_signal: Notify,
 }
@@ -85,6 +143,8 @@ struct RuntimeTasks {
     supervisor_task: Mutex<Option<JoinHandle<()>>>,
     dispatcher_task: Mutex<Option<JoinHandle<()>>>,
     transport: Mutex<Option<StdioTransport>>,
+    /// Serializes generation swaps between the supervisor and `reconfigure`.
+    generation_lock: Mutex<()>,
 }
 
 struct RuntimeSnapshots {
@@ -95,6 +155,21 @@ struct RuntimeSnapshots {
 #[derive(Clone)]
 pub struct Runtime {
     inner: Arc<RuntimeInner>,
+    _handle: Arc<HandleGuard>,
+}
+
+/// Shared by every user-facing `Runtime` clone but not by background tasks,
+/// which keep `RuntimeInner` alive on their own.
+struct HandleGuard {
+    hook_shutdown: ShutdownToken,
+}
+
+impl Drop for HandleGuard {
+    fn drop(&mut self) {
+        // Last handle gone without an explicit shutdown: hooks still holding a
+        // cloned context must not wait on a runtime nobody can reach anymore.
+        self.hook_shutdown.cancel();
+    }
 }
 
 struct RuntimeInner {
@@ -105,6 +180,7 @@ struct RuntimeInner {
     snapshots: RuntimeSnapshots,
     metrics: Arc<RuntimeMetrics>,
     hooks: HookKernel,
+    validation_observer: RwLock<Option<ValidationObserver>>,
 }
 
 impl Runtime {
@@ -115,6 +191,12 @@ impl Runtime {
             transport,
             supervisor,
             rpc_response_timeout,
+            initialize_timeout,
+            max_initialize_result_bytes,
+            compatibility_guard,
+            spawn_retries,
+            spawn_retry_backoff,
+            default_prompt_timeout,
             server_requests,
             initialize_params,
             live_channel_capacity,
@@ -122,6 +204,16 @@ impl Runtime {
             event_sink,
             event_sink_channel_capacity,
             state_projection_limits,
+            id_generator,
+            prompt_cache,
+            attachment_placeholder_strategy,
+            max_attachments,
+            max_attachment_bytes,
+            accept_resume_id_change,
+            resume_id_change_observer,
+            raw_frame_observer,
+            max_concurrent_turns,
+            allow_unrestricted_sandbox,
         } = cfg;
 
         validate_runtime_capacities(
@@ -131,9 +223,19 @@ impl Runtime {
             event_sink_channel_capacity,
             rpc_response_timeout,
         )?;
+        crate::runtime::runtime_validation::validate_initialize_limits(
+            initialize_timeout,
+            max_initialize_result_bytes,
+        )?;
         crate::runtime::runtime_validation::validate_state_projection_limits(
             &state_projection_limits,
         )?;
+        crate::runtime::runtime_validation::validate_max_concurrent_turns(max_concurrent_turns)?;
+        if allow_unrestricted_sandbox {
+            tracing::warn!(
+                "allow_unrestricted_sandbox is set: privileged sandboxes may run with approval policy `never`"
+            );
+        }
 
         let (live_tx, _) = broadcast::channel(live_channel_capacity);
         let (server_request_tx, server_request_rx) = mpsc::channel(server_request_channel_capacity);
@@ -147,32 +249,62 @@ impl Runtime {
             None => (None, None),
         };
 
+        let hooks = HookKernel::new(hooks);
+        let handle = Arc::new(HandleGuard {
+            hook_shutdown: hooks.shutdown_token(),
+        });
         let runtime = Self {
+            _handle: handle,
             inner: Arc::new(RuntimeInner {
                 counters: RuntimeCounters {
                     initialized: AtomicBool::new(false),
                     shutting_down: AtomicBool::new(false),
+                    draining: AtomicBool::new(false),
                     generation: AtomicU64::new(0),
+                    generation_start_seq: AtomicU64::new(0),
+                    dispatcher_epoch: AtomicU64::new(0),
+                    closed_dispatcher_epoch: AtomicU64::new(0),
                     next_rpc_id: AtomicU64::new(1),
                     next_seq: AtomicU64::new(0),
+                    next_hook_correlation: AtomicU64::new(0),
                 },
                 spec: RuntimeSpec {
-                    process,
+                    process: RwLock::new(process),
                     transport_cfg: transport,
                     initialize_params,
                     supervisor_cfg: supervisor,
                     rpc_response_timeout,
+                    initialize_timeout,
+                    max_initialize_result_bytes,
+                    compatibility_guard,
+                    default_prompt_timeout,
                     server_request_cfg: server_requests,
                     state_projection_limits,
+                    live_channel_capacity,
+                    id_generator,
+                    prompt_cache,
+                    attachment_placeholder_strategy,
+                    attachment_limits: AttachmentLimits {
+                        max_count: max_attachments,
+                        max_bytes: max_attachment_bytes,
+                    },
+                    accept_resume_id_change,
+                    resume_id_change_observer,
+                    raw_frame_observer,
+                    turn_limiter: TurnLimiter::new(max_concurrent_turns, Arc::clone(&metrics)),
+                    allow_unrestricted_sandbox,
                 },
                 io: RuntimeIo {
                     pending: Mutex::new(HashMap::new()),
                     outbound_tx: ArcSwapOption::new(None),
                     live_tx,
+                    lossless_live_txs: RwLock::new(Vec::new()),
                     pending_server_requests: Mutex::new(HashMap::new()),
+                    auto_approve_turns: RwLock::new(HashMap::new()),
                     server_request_tx,
                     server_request_rx: Mutex::new(Some(server_request_rx)),
                     event_sink_tx,
+                    emit_order: std::sync::Mutex::new(()),
                     transport_closed_signal: Notify::new(),
                     shutdown_signal: Notify::new(),
                 },
@@ -181,34 +313,110 @@ impl Runtime {
                     supervisor_task: Mutex::new(None),
                     dispatcher_task: Mutex::new(None),
                     transport: Mutex::new(None),
+                    generation_lock: Mutex::new(()),
                 },
                 snapshots: RuntimeSnapshots {
                     state: RwLock::new(Arc::new(RuntimeState::default())),
                     initialize_result: RwLock::new(None),
                 },
                 metrics,
-                hooks: HookKernel::new(hooks),
+                hooks,
+                validation_observer: RwLock::new(None),
             }),
         };
 
-        spawn_connection_generation(&runtime.inner, 0).await?;
+        spawn_initial_generation(&runtime.inner, spawn_retries, spawn_retry_backoff).await?;
         start_supervisor_task(&runtime.inner).await;
 
         Ok(runtime)
     }
 
+    /// Subscribe to inbound envelopes in ingest order (strictly increasing `seq`).
+    /// Slow receivers lag and skip envelopes; the skipped `seq` range shows what was missed.
+    /// Allocation: one broadcast receiver. Complexity: O(1).
     pub fn subscribe_live(&self) -> broadcast::Receiver<Envelope> {
         self.inner.io.live_tx.subscribe()
     }
 
+    /// Subscribe to inbound envelopes without lag loss.
+    /// Unlike `subscribe_live`, nothing is dropped: a per-subscriber forwarding task spills
+    /// envelopes into an unbounded queue while the receiver is full, so a stalled receiver
+    /// never blocks dispatch but grows memory until it drains. Drop the receiver to unsubscribe.
+    /// Allocation: one mpsc channel of `live_channel_capacity`, one unbounded queue and one
+    /// forwarding task. Complexity: O(1).
+    pub fn subscribe_live_lossless(&self) -> mpsc::Receiver<Envelope> {
+        let (spill_tx, mut spill_rx) = mpsc::unbounded_channel::<Envelope>();
+        let (tx, rx) = mpsc::channel(self.inner.spec.live_channel_capacity);
+        spawn_detached_task(
+            async move {
+                while let Some(envelope) = spill_rx.recv().await {
+                    if tx.send(envelope).await.is_err() {
+                        break;
+                    }
+                }
+            },
+            current_detached_task_plan("lossless_live_forward"),
+            || {},
+        );
+        match self.inner.io.lossless_live_txs.write() {
+            Ok(mut guard) => guard.push(spill_tx),
+            Err(poisoned) => poisoned.into_inner().push(spill_tx),
+        }
+        rx
+    }
+
     pub fn is_initialized(&self) -> bool {
         self.inner.counters.initialized.load(Ordering::Acquire)
     }
 
+    /// Whether this runtime accepts new work. Unlike `ConnectionState`, which tracks the
+    /// child process, this stays `Running` across supervisor restarts and reports
+    /// `Draining` while `reconfigure` waits for turns, so callers can stop starting work.
+    /// Allocation: none. Complexity: O(1).
+    pub fn lifecycle_state(&self) -> RuntimeLifecycle {
+        let counters = &self.inner.counters;
+        if state_snapshot_arc(&self.inner).connection == ConnectionState::Dead {
+            RuntimeLifecycle::Dead
+        } else if counters.shutting_down.load(Ordering::Acquire) {
+            RuntimeLifecycle::ShuttingDown
+        } else if counters.draining.load(Ordering::Acquire) {
+            RuntimeLifecycle::Draining
+        } else {
+            RuntimeLifecycle::Running
+        }
+    }
+
+    /// Health summary for readiness probes: connection phase, lifecycle flags, and the
+    /// pending/turn gauges of `metrics_snapshot`. See `HealthStatus::is_serving`.
+    /// Allocation: none beyond the metrics snapshot. Complexity: O(1).
+    pub fn health(&self) -> HealthStatus {
+        let (connection, generation) = state_snapshot_arc(&self.inner).connection.phase();
+        let lifecycle = self.lifecycle_state();
+        let metrics = self.metrics_snapshot();
+        HealthStatus {
+            connection,
+            generation,
+            dead: lifecycle == RuntimeLifecycle::Dead,
+            draining: lifecycle == RuntimeLifecycle::Draining,
+            shutting_down: lifecycle == RuntimeLifecycle::ShuttingDown,
+            uptime_millis: metrics.uptime_millis,
+            pending_rpc_count: metrics.pending_rpc_count,
+            pending_server_request_count: metrics.pending_server_request_count,
+            turns_active: metrics.turns_active,
+            turns_queued: metrics.turns_queued,
+        }
+    }
+
     pub fn state_snapshot(&self) -> Arc<RuntimeState> {
         state_snapshot_arc(&self.inner)
     }
 
+    /// False once the state projection evicted a thread (see `StateProjectionLimits::max_threads`);
+    /// `thread_read` is then needed for full history.
+    pub fn state_is_complete(&self) -> bool {
+        self.state_snapshot().is_complete()
+    }
+
     pub fn initialize_result_snapshot(&self) -> Option<Value> {
         match self.inner.snapshots.initialize_result.read() {
             Ok(guard) => guard.clone(),
@@ -222,10 +430,37 @@ impl Runtime {
             .and_then(|value| value.as_str().map(ToOwned::to_owned))
     }
 
+    /// Version from the `initialize` result's `userAgent` (`<product>/<major>.<minor>.<patch>`);
+    /// `None` before the handshake or when the agent is missing or unparsable.
+    /// Allocation: agent String + product String. Complexity: O(n), n = agent length.
+    pub fn server_version(&self) -> Option<SemVerTriplet> {
+        let user_agent = self.server_user_agent()?;
+        parse_initialize_user_agent(&user_agent).map(|(_, version)| version)
+    }
+
+    /// True when the `initialize` result declares `capabilities.approvalEditedParams: true`,
+    /// i.e. the server honors `editedParams` in approval responses.
+    /// Allocation: clones the initialize result. Complexity: O(n), n = result size.
+    pub fn server_supports_approval_edits(&self) -> bool {
+        self.initialize_result_snapshot()
+            .and_then(|result| {
+                result
+                    .get("capabilities")?
+                    .get(APPROVAL_EDITED_PARAMS_CAPABILITY)?
+                    .as_bool()
+            })
+            .unwrap_or(false)
+    }
+
     pub fn metrics_snapshot(&self) -> RuntimeMetricsSnapshot {
         self.inner.metrics.snapshot(now_millis())
     }
 
+    /// Wait for a `max_concurrent_turns` slot; hold the returned guard until the turn ends.
+    pub(crate) async fn acquire_turn_slot(&self) -> TurnSlot {
+        self.inner.spec.turn_limiter.acquire().await
+    }
+
     pub(crate) fn record_detached_task_init_failed(&self) {
         self.inner.metrics.record_detached_task_init_failed();
     }
@@ -243,6 +478,11 @@ impl Runtime {
         self.inner.hooks.register(hooks);
     }
 
+    /// Shutdown token handed to hooks through `HookContext::shutdown`.
+    pub(crate) fn hook_shutdown_token(&self) -> ShutdownToken {
+        self.inner.hooks.shutdown_token()
+    }
+
     pub(crate) fn hooks_enabled(&self) -> bool {
         self.inner.hooks.is_enabled()
     }
@@ -280,19 +520,169 @@ impl Runtime {
             .clear_thread_scoped_pre_tool_use_hooks(thread_id);
     }
 
+    /// Accept approval requests for `turn_id` on `thread_id` until `take_turn_auto_approvals`,
+    /// including ones already queued. Requests for other turns of the same thread still queue.
+    /// Allocation: two Strings + one record per swept request. Complexity: O(q), q = queued.
+    pub(crate) async fn register_turn_auto_approve(&self, thread_id: &str, turn_id: &str) {
+        register_auto_approve_turn(&self.inner, thread_id, turn_id).await;
+    }
+
+    /// Stop auto-approving `turn_id` and return the approvals accepted for it.
+    /// Allocation: one key pair. Complexity: O(1).
+    pub(crate) fn take_turn_auto_approvals(
+        &self,
+        thread_id: &str,
+        turn_id: &str,
+    ) -> Vec<AutoApproval> {
+        let mut guard = match self.inner.io.auto_approve_turns.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        guard
+            .remove(&(thread_id.to_owned(), turn_id.to_owned()))
+            .unwrap_or_default()
+    }
+
+    /// Publish `auto_approvals` as a hook report of their own, for runs without hook state.
+    /// Allocation: one correlation id. Complexity: O(a), a = approval count.
+    pub(crate) fn publish_auto_approvals(&self, auto_approvals: Vec<AutoApproval>) {
+        if auto_approvals.is_empty() {
+            return;
+        }
+        let correlation_id = self.next_hook_correlation_id();
+        self.publish_hook_report(
+            &correlation_id,
+            HookReport {
+                issues: Vec::new(),
+                auto_approvals,
+            },
+        );
+    }
+
     pub(crate) fn hooks_enabled_with(&self, scoped_hooks: Option<&RuntimeHookConfig>) -> bool {
         self.hooks_enabled() || scoped_hooks.is_some_and(|hooks| !hooks.is_empty())
     }
 
+    /// True when `run_prompt` should fire session hooks for its implicit thread start.
+    pub(crate) fn session_hooks_on_implicit_start_with(
+        &self,
+        scoped_hooks: Option<&RuntimeHookConfig>,
+    ) -> bool {
+        self.inner.hooks.session_hooks_on_implicit_start()
+            || scoped_hooks.is_some_and(|hooks| hooks.session_hooks_on_implicit_start)
+    }
+
+    /// Record thread metadata in the local state projection (server fallback path).
+    pub(crate) fn set_local_thread_metadata(&self, thread_id: &str, metadata: Value) {
+        state_set_thread_metadata(&self.inner, thread_id, metadata);
+    }
+
+    pub(crate) fn attachment_placeholder_strategy(&self) -> &AttachmentPlaceholderStrategy {
+        &self.inner.spec.attachment_placeholder_strategy
+    }
+
+    pub(crate) fn prompt_cache(&self) -> Option<&Arc<dyn PromptCache>> {
+        self.inner.spec.prompt_cache.as_ref()
+    }
+
+    pub(crate) fn attachment_limits(&self) -> &AttachmentLimits {
+        &self.inner.spec.attachment_limits
+    }
+
+    /// Effective prompt-run timeout: the caller's value, else the runtime default.
+    pub(crate) fn resolve_prompt_timeout(&self, timeout: Option<Duration>) -> Duration {
+        timeout.unwrap_or(self.inner.spec.default_prompt_timeout)
+    }
+
+    /// Decide whether a resumed thread may continue under `resumed` instead of
+    /// `requested`; notifies the configured observer when the change is accepted.
+    /// Complexity: O(1) plus observer cost.
+    pub(crate) fn accept_resume_id_change(&self, requested: &str, resumed: &str) -> bool {
+        if !self.inner.spec.accept_resume_id_change {
+            return false;
+        }
+        if let Some(observer) = &self.inner.spec.resume_id_change_observer {
+            observer(requested, resumed);
+        }
+        true
+    }
+
+    /// `RuntimeConfig::allow_unrestricted_sandbox`: privileged sandboxes may use `Never` approval.
+    pub(crate) fn allow_unrestricted_sandbox(&self) -> bool {
+        self.inner.spec.allow_unrestricted_sandbox
+    }
+
     pub(crate) fn next_hook_correlation_id(&self) -> String {
-        let seq = self.inner.counters.next_seq.fetch_add(1, Ordering::AcqRel) + 1;
-        format!("hk-{seq}")
+        if let Some(id_generator) = &self.inner.spec.id_generator {
+            return id_generator.next_id(IdKind::HookCorrelation);
+        }
+        let id = self
+            .inner
+            .counters
+            .next_hook_correlation
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        format!("hk-{id}")
     }
 
-    pub(crate) fn publish_hook_report(&self, report: HookReport) {
+    /// Store `report` as the latest hook report and publish it as a synthetic
+    /// `runtime/hooks/report` notification (`params = {correlationId, issues, autoApprovals}`),
+    /// clean or not, so subscribers see one consolidated report per hooked run.
+    /// Reaches the event sink, live and lossless subscribers in seq order with server events.
+    /// Allocation: one JSON payload. Complexity: O(i + a).
+    pub(crate) fn publish_hook_report(&self, correlation_id: &str, report: HookReport) {
+        dispatch::emit_envelope(&self.inner, |seq| Envelope {
+            seq,
+            ts_millis: now_millis(),
+            direction: Direction::Inbound,
+            kind: MsgKind::Notification,
+            rpc_id: None,
+            method: Some(Arc::from(methods::RUNTIME_HOOKS_REPORT)),
+            thread_id: None,
+            turn_id: None,
+            item_id: None,
+            json: Arc::new(serde_json::json!({
+                "method": methods::RUNTIME_HOOKS_REPORT,
+                "params": {
+                    "correlationId": correlation_id,
+                    "issues": report.issues,
+                    "autoApprovals": report.auto_approvals,
+                },
+            })),
+        });
         self.inner.hooks.set_latest_report(report);
     }
 
+    /// Record `PromptRunParams::metadata` for a started run turn as a synthetic
+    /// `runtime/run/metadata` notification (`params = {threadId, turnId, metadata}`).
+    /// Reaches the event sink and lossless subscribers too, in seq order with server
+    /// events, so a sink can persist it alongside the turn.
+    /// No-op for `Value::Null`. Allocation: one JSON payload.
+    pub(crate) fn publish_run_metadata(&self, thread_id: &str, turn_id: &str, metadata: &Value) {
+        if metadata.is_null() {
+            return;
+        }
+        dispatch::emit_envelope(&self.inner, |seq| Envelope {
+            seq,
+            ts_millis: now_millis(),
+            direction: Direction::Inbound,
+            kind: MsgKind::Notification,
+            rpc_id: None,
+            method: Some(Arc::from(methods::RUNTIME_RUN_METADATA)),
+            thread_id: Some(Arc::from(thread_id)),
+            turn_id: Some(Arc::from(turn_id)),
+            item_id: None,
+            json: Arc::new(serde_json::json!({
+                "method": methods::RUNTIME_RUN_METADATA,
+                "params": {
+                    "threadId": thread_id,
+                    "turnId": turn_id,
+                    "metadata": metadata,
+                },
+            })),
+        });
+    }
+
     /// Run pre-hooks. Returns `Err(BlockReason)` if any hook blocks.
     /// Allocation: O(n) decisions vec, n = hook count.
     pub(crate) async fn run_pre_hooks_with(
@@ -319,6 +709,20 @@ impl Runtime {
             .await;
     }
 
+    /// Replace the child process spec without dropping this handle.
+    /// Waits up to `default_prompt_timeout` for in-progress turns of the current generation
+    /// and in-flight `turn/start` calls to end (`Timeout` leaves the current child
+    /// untouched); until the swap finishes, new `turn/start` calls fail with
+    /// `RpcError::Draining` (`PromptRunError::Rpc(RpcError::Draining)` from prompt runs,
+    /// `WebError::Draining` from the web adapter). Then it stops the current child and starts `new_process`
+    /// as the next generation. Every `Runtime` clone talks to the new child afterwards;
+    /// later supervisor restarts use `new_process` too. When the new child fails to spawn
+    /// or initialize, the previous spec is restored and respawned, and the error is returned.
+    /// Side effects: child restart; pending RPCs on the old child fail with transport closed.
+    pub async fn reconfigure(&self, new_process: StdioProcessSpec) -> Result<(), RuntimeError> {
+        reconfigure_runtime(&self.inner, new_process).await
+    }
+
     pub async fn shutdown(&self) -> Result<(), RuntimeError> {
         shutdown_runtime(&self.inner).await
     }
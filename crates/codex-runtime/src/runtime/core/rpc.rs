@@ -1,16 +1,55 @@
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
+use std::sync::{RwLock, RwLockWriteGuard};
 use tokio::time::Duration;
 
 use crate::runtime::errors::{RpcError, RuntimeError};
 use crate::runtime::rpc_contract::{
-    validate_rpc_request, validate_rpc_response, RpcValidationMode,
+    validate_rpc_request, validate_rpc_response, RpcValidationMode, ValidationFailure,
+    ValidationObserver, ValidationSurface,
 };
 
-use super::rpc_io::{call_raw_inner, notify_raw_inner};
+use super::rpc_io::{call_raw_inner, cancel_pending_rpc, list_pending_rpcs, notify_raw_inner};
 use super::Runtime;
 
+/// One outbound JSON-RPC call still waiting for its response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingRpc {
+    /// JSON-RPC request id, as accepted by `Runtime::cancel_rpc`.
+    pub id: u64,
+    pub method: String,
+    /// Time since the request was queued.
+    pub age: Duration,
+}
+
 impl Runtime {
+    /// Install `observer` for contract mismatches on `call_validated*` / `notify_validated*`,
+    /// replacing any previous one. It fires whether or not the mode rejects the call:
+    /// with `RpcValidationMode::None`, payloads are still checked against the
+    /// known-method contract and reported with `enforced = false`.
+    /// Allocation: none. Complexity: O(1).
+    pub fn set_validation_observer(&self, observer: ValidationObserver) {
+        *write_observer(&self.inner.validation_observer) = Some(observer);
+    }
+
+    /// Remove the validation observer, if any.
+    pub fn clear_validation_observer(&self) {
+        *write_observer(&self.inner.validation_observer) = None;
+    }
+
+    /// List calls still awaiting a response, oldest id first.
+    /// Allocation: one Vec + method Strings. Complexity: O(p log p), p = pending count.
+    pub async fn pending_rpcs(&self) -> Vec<PendingRpc> {
+        list_pending_rpcs(&self.inner).await
+    }
+
+    /// Fail one pending call with `RpcError::Cancelled` without waiting for its timeout.
+    /// A response arriving later for `id` is ignored. Returns false when `id` is not pending.
+    /// Side effects: no message is sent to the server. Complexity: O(1).
+    pub async fn cancel_rpc(&self, id: u64) -> bool {
+        cancel_pending_rpc(&self.inner, id).await
+    }
+
     pub async fn call_raw(&self, method: &str, params: Value) -> Result<Value, RpcError> {
         self.call_raw_internal(method, params, true, self.inner.spec.rpc_response_timeout)
             .await
@@ -46,11 +85,11 @@ impl Runtime {
         mode: RpcValidationMode,
         timeout_duration: Duration,
     ) -> Result<Value, RpcError> {
-        validate_rpc_request(method, &params, mode)?;
+        self.validate_observed(ValidationSurface::Request, method, &params, mode)?;
         let result = self
             .call_raw_internal(method, params, true, timeout_duration)
             .await?;
-        validate_rpc_response(method, &result, mode)?;
+        self.validate_observed(ValidationSurface::Response, method, &result, mode)?;
         Ok(result)
     }
 
@@ -118,9 +157,10 @@ impl Runtime {
         params: Value,
         mode: RpcValidationMode,
     ) -> Result<(), RuntimeError> {
-        validate_rpc_request(method, &params, mode).map_err(|err| {
-            RuntimeError::InvalidConfig(format!("invalid json-rpc notify payload: {err}"))
-        })?;
+        self.validate_observed(ValidationSurface::Request, method, &params, mode)
+            .map_err(|err| {
+                RuntimeError::InvalidConfig(format!("invalid json-rpc notify payload: {err}"))
+            })?;
         self.notify_raw_internal(method, params, true).await
     }
 
@@ -156,6 +196,41 @@ impl Runtime {
             .await
     }
 
+    /// Validate under `mode`; when an observer is installed, also report mismatches
+    /// (checked under `KnownMethods` when `mode` is `None`) without changing the outcome.
+    fn validate_observed(
+        &self,
+        surface: ValidationSurface,
+        method: &str,
+        value: &Value,
+        mode: RpcValidationMode,
+    ) -> Result<(), RpcError> {
+        let validate = |mode| match surface {
+            ValidationSurface::Request => validate_rpc_request(method, value, mode),
+            ValidationSurface::Response => validate_rpc_response(method, value, mode),
+        };
+        let enforced = validate(mode);
+        let Some(observer) = read_observer(&self.inner.validation_observer) else {
+            return enforced;
+        };
+        let observed = match (&enforced, mode) {
+            (Ok(()), RpcValidationMode::None) => validate(RpcValidationMode::KnownMethods),
+            _ => enforced.clone(),
+        };
+        if let Err(error) = observed {
+            observer(
+                method,
+                &ValidationFailure {
+                    surface,
+                    mode,
+                    enforced: enforced.is_err(),
+                    error,
+                },
+            );
+        }
+        enforced
+    }
+
     async fn call_raw_internal(
         &self,
         method: &str,
@@ -185,3 +260,19 @@ impl Runtime {
         notify_raw_inner(&self.inner, method, params).await
     }
 }
+
+fn read_observer(slot: &RwLock<Option<ValidationObserver>>) -> Option<ValidationObserver> {
+    match slot.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+fn write_observer(
+    slot: &RwLock<Option<ValidationObserver>>,
+) -> RwLockWriteGuard<'_, Option<ValidationObserver>> {
+    match slot.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
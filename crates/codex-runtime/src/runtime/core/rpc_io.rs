@@ -4,13 +4,15 @@ use std::time::Duration;
 
 use serde_json::{json, Value};
 use tokio::sync::oneshot;
-use tokio::time::timeout;
+use tokio::time::{timeout, Instant};
 
 use crate::runtime::detached_task::{current_detached_task_plan, spawn_detached_task};
 use crate::runtime::errors::{RpcError, RuntimeError};
+use crate::runtime::rpc_contract::methods;
 
 use super::io_policy::{build_rpc_request, project_pending_rpc_outcome, PendingRpcOutcome};
-use super::{state_projection::state_clear_pending_server_requests, RuntimeInner};
+use super::rpc::PendingRpc;
+use super::{state_projection::state_clear_pending_server_requests, PendingRpcEntry, RuntimeInner};
 
 pub(super) async fn call_raw_inner(
     inner: &Arc<RuntimeInner>,
@@ -26,7 +28,22 @@ pub(super) async fn call_raw_inner(
 
     let rpc_id = inner.counters.next_rpc_id.fetch_add(1, Ordering::Relaxed);
     let (pending_tx, pending_rx) = oneshot::channel();
-    inner.io.pending.lock().await.insert(rpc_id, pending_tx);
+    {
+        // Checked under the pending lock so a reconfigure drain either sees this
+        // turn/start in flight or this call sees the drain.
+        let mut pending = inner.io.pending.lock().await;
+        if method == methods::TURN_START && inner.counters.draining.load(Ordering::Acquire) {
+            return Err(RpcError::Draining);
+        }
+        pending.insert(
+            rpc_id,
+            PendingRpcEntry {
+                method: method.to_owned(),
+                started_at: Instant::now(),
+                tx: pending_tx,
+            },
+        );
+    }
     inner.metrics.inc_pending_rpc();
     let mut pending_guard = PendingRpcGuard::new(inner, rpc_id);
 
@@ -71,8 +88,8 @@ pub(super) async fn notify_raw_inner(
 
 pub(super) async fn resolve_transport_closed_pending(inner: &Arc<RuntimeInner>) {
     let mut pending = inner.io.pending.lock().await;
-    for (_, tx) in pending.drain() {
-        let _ = tx.send(Err(RpcError::TransportClosed));
+    for (_, entry) in pending.drain() {
+        let _ = entry.tx.send(Err(RpcError::TransportClosed));
     }
     drop(pending);
     inner.metrics.set_pending_rpc_count(0);
@@ -82,6 +99,37 @@ pub(super) async fn resolve_transport_closed_pending(inner: &Arc<RuntimeInner>)
     state_clear_pending_server_requests(inner);
 }
 
+/// Snapshot of pending calls, ordered by rpc id.
+/// Allocation: one Vec + method Strings. Complexity: O(p log p), p = pending count.
+pub(super) async fn list_pending_rpcs(inner: &Arc<RuntimeInner>) -> Vec<PendingRpc> {
+    let now = Instant::now();
+    let mut pending: Vec<PendingRpc> = inner
+        .io
+        .pending
+        .lock()
+        .await
+        .iter()
+        .map(|(id, entry)| PendingRpc {
+            id: *id,
+            method: entry.method.clone(),
+            age: now.saturating_duration_since(entry.started_at),
+        })
+        .collect();
+    pending.sort_by_key(|rpc| rpc.id);
+    pending
+}
+
+/// Resolve one pending call with `RpcError::Cancelled`. Returns false when `rpc_id`
+/// is not pending (already answered, timed out, or never issued).
+pub(super) async fn cancel_pending_rpc(inner: &Arc<RuntimeInner>, rpc_id: u64) -> bool {
+    let Some(entry) = inner.io.pending.lock().await.remove(&rpc_id) else {
+        return false;
+    };
+    inner.metrics.dec_pending_rpc();
+    let _ = entry.tx.send(Err(RpcError::Cancelled));
+    true
+}
+
 async fn clear_pending_rpc(inner: &Arc<RuntimeInner>, rpc_id: u64) {
     if inner.io.pending.lock().await.remove(&rpc_id).is_some() {
         inner.metrics.dec_pending_rpc();
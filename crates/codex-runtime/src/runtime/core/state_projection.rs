@@ -3,7 +3,8 @@ use std::sync::Arc;
 use crate::runtime::approvals::PendingServerRequest;
 use crate::runtime::events::Envelope;
 use crate::runtime::state::ConnectionState;
-use crate::runtime::state::{reduce_in_place_with_limits, RuntimeState};
+use crate::runtime::state::{reduce_in_place_with_limits, set_thread_metadata, RuntimeState};
+use serde_json::Value;
 
 use super::RuntimeInner;
 
@@ -28,7 +29,12 @@ fn with_state_write<T>(inner: &Arc<RuntimeInner>, f: impl FnOnce(&mut RuntimeSta
     }
 }
 
+/// Dead is terminal on every path (shutdown, supervisor give-up, failed rollback),
+/// so reaching it also cancels the hook shutdown token.
 pub(super) fn state_set_connection(inner: &Arc<RuntimeInner>, connection: ConnectionState) {
+    if connection == ConnectionState::Dead {
+        inner.hooks.signal_shutdown();
+    }
     with_state_write(inner, |state| {
         state.connection = connection;
     });
@@ -40,6 +46,16 @@ pub(super) fn state_apply_envelope(inner: &Arc<RuntimeInner>, envelope: &Envelop
     });
 }
 
+pub(super) fn state_set_thread_metadata(
+    inner: &Arc<RuntimeInner>,
+    thread_id: &str,
+    metadata: Value,
+) {
+    with_state_write(inner, |state| {
+        set_thread_metadata(state, thread_id, metadata);
+    });
+}
+
 pub(super) fn state_insert_pending_server_request(
     inner: &Arc<RuntimeInner>,
     rpc_id: &str,
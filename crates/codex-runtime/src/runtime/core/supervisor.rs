@@ -5,7 +5,8 @@ use tokio::time::{sleep, Duration};
 
 use crate::runtime::state::ConnectionState;
 
-use super::lifecycle::{detach_generation, spawn_connection_generation};
+use super::lifecycle::{attach_connection_generation, detach_generation, mark_generation_running};
+use super::rpc_io::call_raw_inner;
 use super::state_projection::state_set_connection;
 use super::{now_millis, RestartPolicy, RuntimeInner};
 
@@ -76,9 +77,13 @@ pub(super) async fn supervisor_loop(inner: Arc<RuntimeInner>) {
             break;
         }
 
+        let _generation_guard = inner.tasks.generation_lock.lock().await;
         if inner.counters.shutting_down.load(Ordering::Acquire) {
             break;
         }
+        if is_stale_transport_close(&inner) {
+            continue;
+        }
 
         let close_kind = classify_transport_close(&inner).await;
         inner.counters.initialized.store(false, Ordering::Release);
@@ -108,28 +113,32 @@ pub(super) async fn supervisor_loop(inner: Arc<RuntimeInner>) {
                     restart_attempts = 0;
                 }
 
-                if restart_attempts >= max_restarts {
-                    state_set_connection(&inner, ConnectionState::Dead);
-                    break;
-                }
-
-                state_set_connection(&inner, ConnectionState::Restarting { generation });
-                let delay =
-                    compute_restart_delay(restart_attempts, base_backoff_ms, max_backoff_ms);
-                restart_attempts = restart_attempts.saturating_add(1);
-                tokio::select! {
-                    _ = sleep(delay) => {}
-                    _ = inner.io.shutdown_signal.notified() => break,
-                }
-
-                if inner.counters.shutting_down.load(Ordering::Acquire) {
-                    break;
+                let mut recovered = false;
+                while restart_attempts < max_restarts {
+                    state_set_connection(&inner, ConnectionState::Restarting { generation });
+                    let delay =
+                        compute_restart_delay(restart_attempts, base_backoff_ms, max_backoff_ms);
+                    restart_attempts = restart_attempts.saturating_add(1);
+                    tokio::select! {
+                        _ = sleep(delay) => {}
+                        _ = inner.io.shutdown_signal.notified() => return,
+                    }
+
+                    if inner.counters.shutting_down.load(Ordering::Acquire) {
+                        return;
+                    }
+
+                    match restart_generation(&inner, generation.saturating_add(1)).await {
+                        RestartOutcome::Recovered => {
+                            recovered = true;
+                            break;
+                        }
+                        RestartOutcome::HealthcheckFailed => continue,
+                        RestartOutcome::SpawnFailed => break,
+                    }
                 }
 
-                if spawn_connection_generation(&inner, generation.saturating_add(1))
-                    .await
-                    .is_err()
-                {
+                if !recovered {
                     state_set_connection(&inner, ConnectionState::Dead);
                     break;
                 }
@@ -139,6 +148,63 @@ pub(super) async fn supervisor_loop(inner: Arc<RuntimeInner>) {
     }
 }
 
+/// True when the close signal came from a dispatcher that `reconfigure` already replaced.
+/// Allocation: none. Complexity: O(1).
+fn is_stale_transport_close(inner: &Arc<RuntimeInner>) -> bool {
+    inner
+        .counters
+        .closed_dispatcher_epoch
+        .load(Ordering::Acquire)
+        != inner.counters.dispatcher_epoch.load(Ordering::Acquire)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RestartOutcome {
+    Recovered,
+    HealthcheckFailed,
+    SpawnFailed,
+}
+
+/// Respawn one generation and gate `Running` on the optional post-restart health check.
+/// A failed health check tears the generation down again so the caller can retry.
+async fn restart_generation(inner: &Arc<RuntimeInner>, generation: u64) -> RestartOutcome {
+    if attach_connection_generation(inner).await.is_err() {
+        return RestartOutcome::SpawnFailed;
+    }
+
+    if let Some((method, params)) = &inner.spec.supervisor_cfg.post_restart_healthcheck {
+        if let Err(err) = call_raw_inner(
+            inner,
+            method,
+            params.clone(),
+            inner.spec.rpc_response_timeout,
+        )
+        .await
+        {
+            tracing::warn!(generation, method = %method, error = %err, "post-restart health check failed");
+            if detach_generation(inner).await.is_err() {
+                return RestartOutcome::SpawnFailed;
+            }
+            consume_transport_close_signal(inner).await;
+            return RestartOutcome::HealthcheckFailed;
+        }
+    }
+
+    mark_generation_running(inner, generation);
+    RestartOutcome::Recovered
+}
+
+/// Drop the close permit left by a generation the supervisor tore down itself,
+/// so the next wait does not mistake it for a crash of the recovered child.
+/// Allocation: none. Complexity: O(1).
+async fn consume_transport_close_signal(inner: &Arc<RuntimeInner>) {
+    tokio::select! {
+        biased;
+        _ = inner.io.transport_closed_signal.notified() => {}
+        _ = std::future::ready(()) => {}
+    }
+}
+
 async fn classify_transport_close(inner: &Arc<RuntimeInner>) -> TransportCloseKind {
     let mut transport_guard = inner.tasks.transport.lock().await;
     let Some(transport) = transport_guard.as_mut() else {
@@ -6,16 +6,21 @@ use serde::Serialize;
 use serde_json::json;
 use tokio::time::{sleep, timeout};
 
+use super::dispatch::validate_approval_edit;
 use super::*;
 use crate::plugin::{HookAction, HookContext, HookIssue, PreHook};
+use crate::runtime::approvals::ApprovalResult;
+use crate::runtime::client::{CompatibilityGuard, SemVerTriplet};
 use crate::runtime::errors::SinkError;
-use crate::runtime::events::MsgKind;
+use crate::runtime::events::{Direction, MsgKind};
 use crate::runtime::hooks::RuntimeHookConfig;
 use crate::runtime::sink::EventSink;
+use crate::runtime::state::ConnectionPhase;
 
 fn python_mock_process() -> StdioProcessSpec {
     let script = r#"
 import json
+import os
 import sys
 
 for line in sys.stdin:
@@ -34,7 +39,7 @@ for line in sys.stdin:
         continue
 
     if method is None and ("result" in msg or "error" in msg):
-        if rpc_id in (777, 778, 779, 780, 781, 782, "req_str_1"):
+        if rpc_id in (777, 778, 779, 780, 781, 782, "req_str_1", 18446744073709551614, -7):
             sys.stdout.write(json.dumps({
                 "method": "approval/ack",
                 "params": {
@@ -48,6 +53,8 @@ for line in sys.stdin:
 
     if method == "initialize":
         out = {"id": rpc_id, "result": {"ready": True}}
+        if os.environ.get("MOCK_APPROVAL_EDITS") == "1":
+            out["result"]["capabilities"] = {"approvalEditedParams": True}
         sys.stdout.write(json.dumps(out) + "\n")
         sys.stdout.flush()
         continue
@@ -124,6 +131,15 @@ for line in sys.stdin:
         }) + "\n")
         sys.stdout.flush()
 
+    if method == "probe_extreme_ids":
+        for extreme_id in (18446744073709551614, -7):
+            sys.stdout.write(json.dumps({
+                "id": extreme_id,
+                "method": "item/fileChange/requestApproval",
+                "params": {"threadId":"thr_1", "turnId":"turn_1", "itemId":"item_1"}
+            }) + "\n")
+        sys.stdout.flush()
+
     if method == "probe_state":
         sys.stdout.write(json.dumps({
             "method": "thread/started",
@@ -190,6 +206,14 @@ for line in sys.stdin:
     if rpc_id is None:
         continue
 
+    if method == "health/fail":
+        sys.stdout.write(json.dumps({
+            "id": rpc_id,
+            "error": {"code": -32000, "message": "degraded"}
+        }) + "\n")
+        sys.stdout.flush()
+        continue
+
     sys.stdout.write(json.dumps({
         "id": rpc_id,
         "result": {"echoMethod": method, "params": msg.get("params")}
@@ -200,6 +224,49 @@ for line in sys.stdin:
     crate::test_fixtures::python_inline_process(script)
 }
 
+/// Echo server that stamps every result with `tag`; `crash_now` exits non-zero.
+fn python_tagged_echo_process(tag: &str) -> StdioProcessSpec {
+    let script = r#"
+import json
+import sys
+
+TAG = sys.argv[1]
+
+for line in sys.stdin:
+    line = line.strip()
+    if not line:
+        continue
+    try:
+        msg = json.loads(line)
+    except Exception:
+        continue
+
+    method = msg.get("method")
+    rpc_id = msg.get("id")
+
+    if method == "crash_now":
+        sys.exit(42)
+
+    if method in ("start_turn", "start_turn_then_crash", "complete_turn"):
+        event = "turn/completed" if method == "complete_turn" else "turn/started"
+        sys.stdout.write(json.dumps({"method": event, "params": {"threadId": "thr_tag", "turnId": "turn_tag"}}) + "\n")
+        sys.stdout.flush()
+        if method == "start_turn_then_crash":
+            sys.exit(42)
+
+    if rpc_id is None:
+        continue
+
+    result = {"ready": True} if method == "initialize" else {"tag": TAG, "echoMethod": method}
+    sys.stdout.write(json.dumps({"id": rpc_id, "result": result}) + "\n")
+    sys.stdout.flush()
+"#;
+
+    let mut spec = crate::test_fixtures::python_inline_process(script);
+    spec.args.push(tag.to_owned());
+    spec
+}
+
 fn python_exit_on_initialized_process() -> StdioProcessSpec {
     let script = r#"
 import json
@@ -324,6 +391,67 @@ for line in sys.stdin:
     spec
 }
 
+fn python_exit_before_initialize_once(marker_path: &str) -> StdioProcessSpec {
+    let script = r#"
+import json
+import os
+import sys
+
+marker = os.environ["SPAWN_MARKER"]
+if not os.path.exists(marker):
+    with open(marker, "w", encoding="utf-8") as f:
+        f.write("seen")
+    sys.exit(3)
+
+for line in sys.stdin:
+    line = line.strip()
+    if not line:
+        continue
+    try:
+        msg = json.loads(line)
+    except Exception:
+        continue
+
+    if msg.get("method") == "initialize" and msg.get("id") is not None:
+        sys.stdout.write(json.dumps({"id": msg["id"], "result": {"ready": True}}) + "\n")
+        sys.stdout.flush()
+"#;
+
+    let mut spec = crate::test_fixtures::python_inline_process(script);
+    spec.env
+        .insert("SPAWN_MARKER".to_owned(), marker_path.to_owned());
+    spec
+}
+
+fn python_initialize_user_agent_process(user_agent: &str) -> StdioProcessSpec {
+    let script = r#"
+import json
+import os
+import sys
+
+for line in sys.stdin:
+    line = line.strip()
+    if not line:
+        continue
+    try:
+        msg = json.loads(line)
+    except Exception:
+        continue
+
+    if msg.get("method") == "initialize" and msg.get("id") is not None:
+        sys.stdout.write(json.dumps({
+            "id": msg["id"],
+            "result": {"ready": True, "userAgent": os.environ["INIT_USER_AGENT"]}
+        }) + "\n")
+        sys.stdout.flush()
+"#;
+
+    let mut spec = crate::test_fixtures::python_inline_process(script);
+    spec.env
+        .insert("INIT_USER_AGENT".to_owned(), user_agent.to_owned());
+    spec
+}
+
 fn unique_temp_marker_path(prefix: &str) -> String {
     let nanos = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -364,6 +492,37 @@ for line in sys.stdin:
     crate::test_fixtures::python_inline_process(script)
 }
 
+fn python_initialize_misbehaving_process(mode: &str) -> StdioProcessSpec {
+    let script = r#"
+import json
+import os
+import sys
+
+mode = os.environ.get("INIT_MODE", "silent")
+
+for line in sys.stdin:
+    line = line.strip()
+    if not line:
+        continue
+    try:
+        msg = json.loads(line)
+    except Exception:
+        continue
+
+    if msg.get("method") == "initialize" and msg.get("id") is not None:
+        if mode == "oversized":
+            sys.stdout.write(json.dumps({
+                "id": msg["id"],
+                "result": {"ready": True, "padding": "x" * 100000}
+            }) + "\n")
+            sys.stdout.flush()
+        continue
+"#;
+    let mut spec = crate::test_fixtures::python_inline_process(script);
+    spec.env.insert("INIT_MODE".to_owned(), mode.to_owned());
+    spec
+}
+
 #[derive(Debug)]
 struct FailAfterSink {
     fail_after: usize,
@@ -405,6 +564,29 @@ impl EventSink for FailAfterSink {
     }
 }
 
+#[derive(Debug, Default)]
+struct SeqRecordingSink {
+    seqs: std::sync::Mutex<Vec<u64>>,
+}
+
+impl SeqRecordingSink {
+    fn seqs(&self) -> Vec<u64> {
+        self.seqs.lock().expect("seq lock").clone()
+    }
+}
+
+impl EventSink for SeqRecordingSink {
+    fn on_envelope<'a>(
+        &'a self,
+        envelope: &'a Envelope,
+    ) -> crate::runtime::sink::EventSinkFuture<'a> {
+        Box::pin(async move {
+            self.seqs.lock().expect("seq lock").push(envelope.seq);
+            Ok(())
+        })
+    }
+}
+
 async fn spawn_mock_runtime() -> Runtime {
     let cfg = RuntimeConfig::new(python_mock_process());
     Runtime::spawn_local(cfg).await.expect("runtime spawn")
@@ -458,14 +640,26 @@ async fn spawn_runtime_with_supervisor_config(
     process: StdioProcessSpec,
     restart: RestartPolicy,
     restart_budget_reset_ms: u64,
+) -> Runtime {
+    spawn_runtime_with_supervisor_cfg(
+        process,
+        SupervisorConfig {
+            restart,
+            shutdown_flush_timeout_ms: 200,
+            shutdown_terminate_grace_ms: 200,
+            restart_budget_reset_ms,
+            post_restart_healthcheck: None,
+        },
+    )
+    .await
+}
+
+async fn spawn_runtime_with_supervisor_cfg(
+    process: StdioProcessSpec,
+    supervisor: SupervisorConfig,
 ) -> Runtime {
     let mut cfg = RuntimeConfig::new(process);
-    cfg.supervisor = SupervisorConfig {
-        restart,
-        shutdown_flush_timeout_ms: 200,
-        shutdown_terminate_grace_ms: 200,
-        restart_budget_reset_ms,
-    };
+    cfg.supervisor = supervisor;
     Runtime::spawn_local(cfg).await.expect("runtime spawn")
 }
 
@@ -527,6 +721,66 @@ mod core_lifecycle {
         runtime.shutdown().await.expect("shutdown");
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn lifecycle_state_tracks_running_and_shutdown() {
+        let runtime = spawn_mock_runtime().await;
+        assert_eq!(runtime.lifecycle_state(), RuntimeLifecycle::Running);
+
+        runtime.shutdown().await.expect("shutdown");
+        assert_eq!(runtime.lifecycle_state(), RuntimeLifecycle::Dead);
+        assert_eq!(runtime.state_snapshot().connection, ConnectionState::Dead);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn health_reports_serving_until_shutdown() {
+        let runtime = spawn_mock_runtime().await;
+        let health = runtime.health();
+        assert_eq!(health.connection, ConnectionPhase::Running);
+        assert!(health.generation.is_some());
+        assert!(health.is_serving());
+
+        runtime.shutdown().await.expect("shutdown");
+        let health = runtime.health();
+        assert_eq!(health.connection, ConnectionPhase::Dead);
+        assert_eq!(health.generation, None);
+        assert!(health.dead);
+        assert!(!health.is_serving());
+    }
+
+    #[test]
+    fn health_status_serializes_to_flat_schema() {
+        let health = HealthStatus {
+            connection: ConnectionPhase::Running,
+            generation: Some(2),
+            dead: false,
+            draining: true,
+            shutting_down: false,
+            uptime_millis: 1500,
+            pending_rpc_count: 1,
+            pending_server_request_count: 0,
+            turns_active: 3,
+            turns_queued: 4,
+        };
+        assert!(!health.is_serving());
+        assert_eq!(
+            serde_json::to_value(health).expect("serialize"),
+            json!({
+                "connection": "running",
+                "generation": 2,
+                "dead": false,
+                "draining": true,
+                "shuttingDown": false,
+                "uptimeMillis": 1500,
+                "pendingRpcCount": 1,
+                "pendingServerRequestCount": 0,
+                "turnsActive": 3,
+                "turnsQueued": 4
+            })
+        );
+        let restarting = ConnectionState::Restarting { generation: 5 }.phase();
+        assert_eq!(restarting, (ConnectionPhase::Restarting, Some(5)));
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn spawn_fails_fast_on_initialize_error_without_hanging() {
         let cfg = RuntimeConfig::new(python_initialize_error_process());
@@ -546,6 +800,151 @@ mod core_lifecycle {
         }
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn server_version_parses_initialize_user_agent() {
+        let cfg = RuntimeConfig::new(python_initialize_user_agent_process(
+            "Codex Desktop/0.90.1 (Linux; x86_64)",
+        ));
+        let runtime = Runtime::spawn_local(cfg)
+            .await
+            .expect("spawn without guard");
+
+        assert_eq!(runtime.server_version(), Some(SemVerTriplet::new(0, 90, 1)));
+        runtime.shutdown().await.expect("shutdown");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn spawn_fails_at_handshake_when_server_version_is_below_guard() {
+        let cfg = RuntimeConfig::new(python_initialize_user_agent_process("Codex Desktop/0.90.1"))
+            .with_compatibility_guard(CompatibilityGuard {
+                require_initialize_user_agent: true,
+                min_codex_version: Some(SemVerTriplet::new(0, 104, 0)),
+            })
+            .with_spawn_retries(3, Duration::from_secs(10));
+        let result = timeout(Duration::from_secs(3), Runtime::spawn_local(cfg))
+            .await
+            .expect("incompatible version must not be retried");
+
+        match result {
+            Err(RuntimeError::Incompatible(message)) => {
+                assert!(
+                    message.contains("detected=0.90.1 required>=0.104.0"),
+                    "{message}"
+                );
+            }
+            Err(other) => panic!("unexpected error: {other:?}"),
+            Ok(_) => panic!("spawn_local must reject an incompatible server"),
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn spawn_accepts_server_version_within_guard() {
+        let cfg = RuntimeConfig::new(python_initialize_user_agent_process(
+            "Codex Desktop/0.104.2",
+        ))
+        .with_compatibility_guard(CompatibilityGuard::default());
+        let runtime = Runtime::spawn_local(cfg).await.expect("compatible spawn");
+
+        assert_eq!(
+            runtime.server_version(),
+            Some(SemVerTriplet::new(0, 104, 2))
+        );
+        runtime.shutdown().await.expect("shutdown");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn spawn_retries_transient_initialize_failure() {
+        let marker = unique_temp_marker_path("spawn_retry");
+        let cfg = RuntimeConfig::new(python_exit_before_initialize_once(&marker))
+            .with_spawn_retries(2, Duration::from_millis(10));
+        let runtime = timeout(Duration::from_secs(5), Runtime::spawn_local(cfg))
+            .await
+            .expect("spawn_local must not hang")
+            .expect("second attempt succeeds");
+        let _ = std::fs::remove_file(&marker);
+
+        assert!(runtime.is_initialized());
+        assert_eq!(
+            runtime.state_snapshot().connection,
+            ConnectionState::Running { generation: 0 }
+        );
+        runtime.shutdown().await.expect("shutdown");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn spawn_without_retries_fails_on_transient_initialize_failure() {
+        let marker = unique_temp_marker_path("spawn_no_retry");
+        let cfg = RuntimeConfig::new(python_exit_before_initialize_once(&marker));
+        let result = timeout(Duration::from_secs(5), Runtime::spawn_local(cfg))
+            .await
+            .expect("spawn_local must not hang");
+        let _ = std::fs::remove_file(&marker);
+
+        match result {
+            Err(RuntimeError::Internal(message)) => {
+                assert!(message.contains("initialize"), "{message}");
+            }
+            Err(other) => panic!("unexpected error: {other:?}"),
+            Ok(_) => panic!("spawn_local must fail without retries"),
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn spawn_does_not_retry_initialize_error_response() {
+        let cfg = RuntimeConfig::new(python_initialize_error_process())
+            .with_spawn_retries(3, Duration::from_secs(10));
+        let result = timeout(Duration::from_secs(3), Runtime::spawn_local(cfg))
+            .await
+            .expect("initialize error must not be retried");
+
+        match result {
+            Err(RuntimeError::Internal(message)) => {
+                assert!(message.contains("initialize handshake failed"), "{message}");
+            }
+            Err(other) => panic!("unexpected error: {other:?}"),
+            Ok(_) => panic!("spawn_local must fail on initialize error"),
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn spawn_times_out_when_initialize_never_answers() {
+        let cfg = RuntimeConfig::new(python_initialize_misbehaving_process("silent"))
+            .with_initialize_timeout(Duration::from_millis(200));
+        let result = timeout(Duration::from_secs(3), Runtime::spawn_local(cfg))
+            .await
+            .expect("spawn_local must honor initialize_timeout");
+
+        match result {
+            Err(RuntimeError::Internal(message)) => {
+                assert!(
+                    message.contains("initialize handshake timed out after 200ms"),
+                    "{message}"
+                );
+            }
+            Err(other) => panic!("unexpected error: {other:?}"),
+            Ok(_) => panic!("spawn_local must fail when initialize stalls"),
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn spawn_rejects_oversized_initialize_result() {
+        let cfg = RuntimeConfig::new(python_initialize_misbehaving_process("oversized"));
+        match Runtime::spawn_local(cfg).await {
+            Err(RuntimeError::Internal(message)) => {
+                assert!(message.contains("initialize result too large"), "{message}");
+            }
+            Err(other) => panic!("unexpected error: {other:?}"),
+            Ok(_) => panic!("spawn_local must reject oversized initialize result"),
+        }
+
+        let mut cfg = RuntimeConfig::new(python_initialize_misbehaving_process("oversized"));
+        cfg.max_initialize_result_bytes = 0;
+        assert!(matches!(
+            Runtime::spawn_local(cfg).await,
+            Err(RuntimeError::InvalidConfig(_))
+        ));
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn spawn_rejects_zero_channel_capacities() {
         let mut cfg = RuntimeConfig::new(python_mock_process());
@@ -588,6 +987,13 @@ mod core_lifecycle {
             Err(err) => err,
         };
         assert!(matches!(err, RuntimeError::InvalidConfig(_)));
+
+        let cfg = RuntimeConfig::new(python_mock_process()).with_max_concurrent_turns(0);
+        let err = match Runtime::spawn_local(cfg).await {
+            Ok(_) => panic!("must reject zero max concurrent turns"),
+            Err(err) => err,
+        };
+        assert!(matches!(err, RuntimeError::InvalidConfig(_)));
     }
 
     #[tokio::test(flavor = "current_thread")]
@@ -606,6 +1012,89 @@ mod core_lifecycle {
         runtime.shutdown().await.expect("shutdown");
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn lossless_live_subscriber_sees_every_envelope_despite_small_capacity() {
+        let mut cfg = RuntimeConfig::new(python_mock_process());
+        cfg.live_channel_capacity = 1;
+        let runtime = Runtime::spawn_local(cfg).await.expect("runtime spawn");
+        let mut lossless_rx = runtime.subscribe_live_lossless();
+        let mut lossy_rx = runtime.subscribe_live();
+
+        let caller = runtime.clone();
+        let call = tokio::spawn(async move { caller.call_raw("probe_state", json!({})).await });
+
+        let mut methods = Vec::new();
+        loop {
+            let envelope = timeout(Duration::from_secs(2), lossless_rx.recv())
+                .await
+                .expect("lossless timeout")
+                .expect("lossless closed");
+            if envelope.kind == MsgKind::Response {
+                break;
+            }
+            methods.push(envelope.method.expect("notification method").to_string());
+        }
+        assert_eq!(
+            methods,
+            vec![
+                "thread/started",
+                "turn/started",
+                "item/started",
+                "item/agentMessage/delta",
+                "item/completed",
+                "turn/completed",
+            ]
+        );
+        call.await.expect("join").expect("probe_state");
+        assert!(matches!(
+            lossy_rx.try_recv(),
+            Err(broadcast::error::TryRecvError::Lagged(_))
+        ));
+
+        drop(lossless_rx);
+        runtime
+            .call_raw("echo/after_drop", json!({}))
+            .await
+            .expect("dispatch continues after lossless receiver drop");
+        runtime.shutdown().await.expect("shutdown");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn stalled_lossless_subscriber_does_not_block_rpc_dispatch() {
+        let mut cfg = RuntimeConfig::new(python_mock_process());
+        cfg.live_channel_capacity = 1;
+        let runtime = Runtime::spawn_local(cfg).await.expect("runtime spawn");
+        let mut stalled_rx = runtime.subscribe_live_lossless();
+
+        timeout(
+            Duration::from_secs(2),
+            runtime.call_raw("probe_state", json!({})),
+        )
+        .await
+        .expect("rpc must not wait on a stalled lossless consumer")
+        .expect("probe_state");
+        timeout(
+            Duration::from_secs(2),
+            runtime.call_raw("echo/while_stalled", json!({})),
+        )
+        .await
+        .expect("dispatch keeps running")
+        .expect("echo");
+
+        let mut methods = Vec::new();
+        while methods.len() < 8 {
+            let envelope = timeout(Duration::from_secs(2), stalled_rx.recv())
+                .await
+                .expect("spilled envelope")
+                .expect("lossless closed");
+            methods.push(envelope.method.map(|method| method.to_string()));
+        }
+        assert_eq!(methods[0].as_deref(), Some("thread/started"));
+        assert_eq!(methods[5].as_deref(), Some("turn/completed"));
+
+        runtime.shutdown().await.expect("shutdown");
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn supervisor_restarts_after_forced_exit() {
         let runtime = spawn_runtime_with_supervisor(
@@ -634,10 +1123,157 @@ mod core_lifecycle {
     }
 
     #[tokio::test(flavor = "current_thread")]
-    async fn supervisor_does_not_restart_after_clean_exit() {
-        let marker = unique_temp_marker_path("runtime_clean_exit_once");
-        let _ = std::fs::remove_file(&marker);
-
+    async fn reconfigure_swaps_child_for_every_clone_and_keeps_supervision() {
+        let runtime = spawn_runtime_with_supervisor(
+            python_tagged_echo_process("v1"),
+            RestartPolicy::OnCrash {
+                max_restarts: 3,
+                base_backoff_ms: 10,
+                max_backoff_ms: 40,
+            },
+        )
+        .await;
+        let held_clone = runtime.clone();
+        let before = held_clone
+            .call_raw("echo/before", json!({}))
+            .await
+            .expect("call before reconfigure");
+        assert_eq!(before["tag"], "v1");
+
+        runtime
+            .reconfigure(python_tagged_echo_process("v2"))
+            .await
+            .expect("reconfigure");
+
+        let after = held_clone
+            .call_raw("echo/after", json!({}))
+            .await
+            .expect("call after reconfigure");
+        assert_eq!(after["tag"], "v2");
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(
+            runtime.state_snapshot().connection,
+            ConnectionState::Running { generation: 1 },
+            "supervisor must not treat the swap as a crash"
+        );
+
+        let crash = runtime.call_raw("crash_now", json!({})).await;
+        assert!(matches!(crash, Err(RpcError::TransportClosed)));
+        let recovered = wait_for_recovery(&held_clone).await;
+        assert_eq!(recovered["tag"], "v2");
+        assert_eq!(
+            runtime.state_snapshot().connection,
+            ConnectionState::Running { generation: 2 }
+        );
+
+        runtime.shutdown().await.expect("shutdown");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn reconfigure_rejects_turn_start_while_draining() {
+        let runtime =
+            spawn_runtime_with_supervisor(python_tagged_echo_process("v1"), RestartPolicy::Never)
+                .await;
+        runtime
+            .call_raw("start_turn", json!({}))
+            .await
+            .expect("start turn");
+
+        let reconfiguring = runtime.clone();
+        let reconfigure = tokio::spawn(async move {
+            reconfiguring
+                .reconfigure(python_tagged_echo_process("v2"))
+                .await
+        });
+        timeout(Duration::from_secs(2), async {
+            while runtime.lifecycle_state() != RuntimeLifecycle::Draining {
+                sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("runtime enters draining");
+
+        let rejected = runtime.call_raw("turn/start", json!({})).await;
+        assert!(matches!(rejected, Err(RpcError::Draining)), "{rejected:?}");
+        assert!(
+            !reconfigure.is_finished(),
+            "in-progress turn must hold the drain"
+        );
+
+        runtime
+            .call_raw("complete_turn", json!({}))
+            .await
+            .expect("complete turn");
+        timeout(Duration::from_secs(2), reconfigure)
+            .await
+            .expect("reconfigure finishes after the turn ends")
+            .expect("join")
+            .expect("reconfigure");
+        let after = runtime
+            .call_raw("turn/start", json!({}))
+            .await
+            .expect("turn/start accepted after reconfigure");
+        assert_eq!(after["tag"], "v2");
+
+        runtime.shutdown().await.expect("shutdown");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn reconfigure_ignores_in_progress_turns_of_dead_generations() {
+        let runtime = spawn_runtime_with_supervisor(
+            python_tagged_echo_process("v1"),
+            RestartPolicy::OnCrash {
+                max_restarts: 3,
+                base_backoff_ms: 10,
+                max_backoff_ms: 40,
+            },
+        )
+        .await;
+        let crash = runtime.call_raw("start_turn_then_crash", json!({})).await;
+        assert!(matches!(crash, Err(RpcError::TransportClosed)));
+        wait_for_recovery(&runtime).await;
+        assert_eq!(
+            runtime.state_snapshot().threads["thr_tag"].in_progress_turn_ids(),
+            vec!["turn_tag"]
+        );
+
+        timeout(
+            Duration::from_secs(2),
+            runtime.reconfigure(python_tagged_echo_process("v2")),
+        )
+        .await
+        .expect("stale turn must not hold the drain")
+        .expect("reconfigure");
+
+        runtime.shutdown().await.expect("shutdown");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn reconfigure_restores_previous_process_when_new_one_fails() {
+        let runtime =
+            spawn_runtime_with_supervisor(python_tagged_echo_process("v1"), RestartPolicy::Never)
+                .await;
+
+        let err = runtime
+            .reconfigure(StdioProcessSpec::new("/nonexistent/codex-app-server"))
+            .await
+            .expect_err("missing binary must fail reconfigure");
+        assert!(!matches!(err, RuntimeError::Timeout));
+
+        let value = runtime
+            .call_raw("echo/rolled-back", json!({}))
+            .await
+            .expect("previous process respawned");
+        assert_eq!(value["tag"], "v1");
+
+        runtime.shutdown().await.expect("shutdown");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn supervisor_does_not_restart_after_clean_exit() {
+        let marker = unique_temp_marker_path("runtime_clean_exit_once");
+        let _ = std::fs::remove_file(&marker);
+
         let runtime = spawn_runtime_with_supervisor(
             python_clean_exit_once_then_stay(&marker),
             RestartPolicy::OnCrash {
@@ -767,6 +1403,78 @@ mod core_lifecycle {
         runtime.shutdown().await.expect("shutdown");
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn supervisor_marks_running_only_after_post_restart_healthcheck_passes() {
+        let runtime = spawn_runtime_with_supervisor_cfg(
+            python_restartable_process(),
+            SupervisorConfig {
+                restart: RestartPolicy::OnCrash {
+                    max_restarts: 2,
+                    base_backoff_ms: 10,
+                    max_backoff_ms: 40,
+                },
+                shutdown_flush_timeout_ms: 200,
+                shutdown_terminate_grace_ms: 200,
+                ..SupervisorConfig::default()
+            }
+            .with_post_restart_healthcheck("echo/health", json!({"probe": true})),
+        )
+        .await;
+
+        let crash = runtime.call_raw("crash_now", json!({})).await;
+        assert!(matches!(crash, Err(RpcError::TransportClosed)));
+
+        let recovered = wait_for_recovery(&runtime).await;
+        assert_eq!(recovered["echoMethod"], "echo/recovered");
+        match &runtime.state_snapshot().connection {
+            ConnectionState::Running { generation } => assert_eq!(*generation, 1),
+            other => panic!("unexpected connection state after recovery: {other:?}"),
+        }
+
+        runtime.shutdown().await.expect("shutdown");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn supervisor_counts_failed_post_restart_healthcheck_as_failed_restart() {
+        let runtime = spawn_runtime_with_supervisor_cfg(
+            python_restartable_process(),
+            SupervisorConfig {
+                restart: RestartPolicy::OnCrash {
+                    max_restarts: 2,
+                    base_backoff_ms: 10,
+                    max_backoff_ms: 20,
+                },
+                shutdown_flush_timeout_ms: 200,
+                shutdown_terminate_grace_ms: 200,
+                ..SupervisorConfig::default()
+            }
+            .with_post_restart_healthcheck("health/fail", json!({})),
+        )
+        .await;
+
+        let crash = runtime.call_raw("crash_now", json!({})).await;
+        assert!(matches!(crash, Err(RpcError::TransportClosed)));
+
+        timeout(Duration::from_secs(3), async {
+            loop {
+                if runtime.state_snapshot().connection == ConnectionState::Dead {
+                    break;
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("dead transition timeout");
+
+        let err = runtime
+            .call_raw("echo/dead", json!({}))
+            .await
+            .expect_err("unhealthy restart must not be declared recovered");
+        assert!(matches!(err, RpcError::InvalidRequest(_)));
+
+        runtime.shutdown().await.expect("shutdown");
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn pending_calls_resolve_transport_closed_on_child_exit() {
         let runtime =
@@ -799,6 +1507,42 @@ mod core_lifecycle {
         runtime.shutdown().await.expect("shutdown");
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn supervisor_give_up_cancels_hook_shutdown_token() {
+        let runtime =
+            spawn_runtime_with_supervisor(python_hold_and_crash_process(), RestartPolicy::Never)
+                .await;
+        let token = runtime.hook_shutdown_token();
+        assert!(!token.is_cancelled());
+
+        let _ = runtime.notify_raw("crash_now", json!({})).await;
+        timeout(Duration::from_secs(3), async {
+            loop {
+                if runtime.state_snapshot().connection == ConnectionState::Dead {
+                    break;
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("dead transition timeout");
+
+        assert!(token.is_cancelled());
+        runtime.shutdown().await.expect("shutdown");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn dropping_last_runtime_handle_cancels_hook_shutdown_token() {
+        let runtime = spawn_mock_runtime().await;
+        let token = runtime.hook_shutdown_token();
+        let clone = runtime.clone();
+
+        drop(runtime);
+        assert!(!token.is_cancelled(), "a live clone keeps the token armed");
+        drop(clone);
+        assert!(token.is_cancelled());
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn call_raw_returns_timeout_when_response_missing() {
         let runtime =
@@ -851,6 +1595,43 @@ mod core_lifecycle {
         runtime.shutdown().await.expect("shutdown");
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn cancel_rpc_resolves_listed_pending_call() {
+        let runtime =
+            spawn_runtime_with_supervisor(python_hold_and_crash_process(), RestartPolicy::Never)
+                .await;
+
+        let runtime_call = runtime.clone();
+        let handle =
+            tokio::spawn(async move { runtime_call.call_raw("hold", json!({"n":7})).await });
+
+        let pending = timeout(Duration::from_secs(2), async {
+            loop {
+                let pending = runtime.pending_rpcs().await;
+                if !pending.is_empty() {
+                    break pending;
+                }
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("hold call must become pending");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].method, "hold");
+
+        assert!(runtime.cancel_rpc(pending[0].id).await);
+        assert!(!runtime.cancel_rpc(pending[0].id).await);
+        let err = handle
+            .await
+            .expect("join hold call")
+            .expect_err("cancelled call must fail");
+        assert_eq!(err, RpcError::Cancelled);
+        assert!(runtime.pending_rpcs().await.is_empty());
+        assert_eq!(runtime.metrics_snapshot().pending_rpc_count, 0);
+
+        runtime.shutdown().await.expect("shutdown");
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn call_validated_rejects_invalid_known_method_params() {
         let runtime = spawn_mock_runtime().await;
@@ -890,6 +1671,72 @@ mod core_lifecycle {
         runtime.shutdown().await.expect("shutdown");
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn validation_observer_sees_enforced_and_permissive_mismatches() {
+        use crate::runtime::rpc_contract::{RpcValidationMode, ValidationSurface};
+
+        let runtime = spawn_mock_runtime().await;
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        runtime.set_validation_observer(Arc::new(move |method, failure| {
+            sink.lock().expect("observer lock").push((
+                method.to_owned(),
+                failure.surface,
+                failure.enforced,
+            ));
+        }));
+
+        runtime
+            .call_validated_with_mode(
+                "turn/interrupt",
+                json!({"threadId":"thr_only"}),
+                RpcValidationMode::None,
+            )
+            .await
+            .expect("permissive mode still sends the call");
+        runtime
+            .call_validated("turn/interrupt", json!({"threadId":"thr_only"}))
+            .await
+            .expect_err("known-method mode rejects");
+        runtime
+            .call_validated("thread/start", json!({}))
+            .await
+            .expect_err("response shape rejected");
+        runtime
+            .call_validated("echo/custom", json!({"k":"v"}))
+            .await
+            .expect("unknown methods are not reported");
+
+        runtime.clear_validation_observer();
+        runtime
+            .call_validated_with_mode(
+                "turn/interrupt",
+                json!({"threadId":"thr_only"}),
+                RpcValidationMode::None,
+            )
+            .await
+            .expect("cleared observer is not called");
+
+        assert_eq!(
+            *seen.lock().expect("observer lock"),
+            vec![
+                (
+                    "turn/interrupt".to_owned(),
+                    ValidationSurface::Request,
+                    false
+                ),
+                (
+                    "turn/interrupt".to_owned(),
+                    ValidationSurface::Request,
+                    true
+                ),
+                ("thread/start".to_owned(), ValidationSurface::Response, true),
+            ]
+        );
+
+        runtime.shutdown().await.expect("shutdown");
+    }
+
     #[derive(Debug, Serialize)]
     struct TurnInterruptNotifyMissingTurnId {
         #[serde(rename = "threadId")]
@@ -952,6 +1799,8 @@ mod server_requests {
                 default_timeout_ms: 30_000,
                 on_timeout: TimeoutAction::Decline,
                 auto_decline_unknown: true,
+                decline_payloads: Default::default(),
+                unknown_handler: None,
             })
             .await;
             let mut live_rx = runtime.subscribe_live();
@@ -998,6 +1847,8 @@ mod server_requests {
                 default_timeout_ms: 30_000,
                 on_timeout: TimeoutAction::Decline,
                 auto_decline_unknown: true,
+                decline_payloads: Default::default(),
+                unknown_handler: None,
             };
             cfg.server_request_channel_capacity = 1;
             let runtime = Runtime::spawn_local(cfg).await.expect("runtime spawn");
@@ -1085,6 +1936,131 @@ mod server_requests {
             runtime.shutdown().await.expect("shutdown");
         }
 
+        #[tokio::test(flavor = "current_thread")]
+        async fn approval_with_edit_fails_closed_without_server_capability() {
+            let runtime = spawn_mock_runtime().await;
+            let mut server_request_rx = runtime
+                .take_server_request_rx()
+                .await
+                .expect("take server request rx");
+            assert!(!runtime.server_supports_approval_edits());
+
+            runtime.call_raw("probe", json!({})).await.expect("probe");
+            let req = timeout(Duration::from_secs(2), server_request_rx.recv())
+                .await
+                .expect("server request timeout")
+                .expect("server request closed");
+
+            let edited = json!({"threadId":"thr_1","itemId":"item_1","path":"notes.md"});
+            let err = runtime
+                .respond_approval_with_edit(&req.approval_id, edited)
+                .await
+                .expect_err("edit without capability must fail");
+            assert!(matches!(err, RuntimeError::Unsupported(_)), "{err}");
+            assert_eq!(runtime.metrics_snapshot().approvals_approved, 0);
+
+            runtime
+                .respond_approval_ok(&req.approval_id, json!({"decision":"decline"}))
+                .await
+                .expect("request stays pending after unsupported edit");
+
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn approval_with_edit_validates_then_sends_edited_params() {
+            let mut process = python_mock_process();
+            process
+                .env
+                .insert("MOCK_APPROVAL_EDITS".to_owned(), "1".to_owned());
+            let runtime = Runtime::spawn_local(RuntimeConfig::new(process))
+                .await
+                .expect("runtime spawn");
+            assert!(runtime.server_supports_approval_edits());
+            let mut live_rx = runtime.subscribe_live();
+            let mut server_request_rx = runtime
+                .take_server_request_rx()
+                .await
+                .expect("take server request rx");
+
+            runtime.call_raw("probe", json!({})).await.expect("probe");
+            let req = timeout(Duration::from_secs(2), server_request_rx.recv())
+                .await
+                .expect("server request timeout")
+                .expect("server request closed");
+
+            let err = runtime
+                .respond_approval_with_edit(&req.approval_id, json!({"threadId":"thr_other"}))
+                .await
+                .expect_err("identity change must fail");
+            assert!(err.to_string().contains("threadId cannot change"), "{err}");
+
+            let edited = json!({"threadId":"thr_1","itemId":"item_1","path":"notes.md"});
+            runtime
+                .respond_approval_with_edit(&req.approval_id, edited.clone())
+                .await
+                .expect("respond with edit");
+
+            let mut ack = None;
+            for _ in 0..8 {
+                let envelope = timeout(Duration::from_secs(2), live_rx.recv())
+                    .await
+                    .expect("live timeout")
+                    .expect("live closed");
+                if envelope.method.as_deref() == Some("approval/ack")
+                    && envelope.json["params"]["approvalRpcId"] == 777
+                {
+                    ack = Some(envelope.json["params"]["result"].clone());
+                    break;
+                }
+            }
+            assert_eq!(
+                ack,
+                Some(json!({"decision":"accept","editedParams":edited}))
+            );
+            assert_eq!(runtime.metrics_snapshot().approvals_approved, 1);
+
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[test]
+        fn approval_edit_checks_method_and_command_shape() {
+            let command = methods::ITEM_COMMAND_EXECUTION_REQUEST_APPROVAL;
+            let original = json!({"threadId":"thr_1","command":"rm -rf build"});
+            assert!(validate_approval_edit(
+                command,
+                Some(&original),
+                &json!({"command":"rm -rf build/tmp"})
+            )
+            .is_ok());
+            assert!(validate_approval_edit(
+                command,
+                None,
+                &json!({"command":["cargo","test"],"cwd":"/work"})
+            )
+            .is_ok());
+            for bad in [
+                json!({"command":""}),
+                json!({"command":[]}),
+                json!({"command":["ls", 1]}),
+                json!({"command":"ls","cwd":7}),
+                json!({"cwd":"/work"}),
+                json!({}),
+                json!("ls"),
+            ] {
+                assert!(
+                    validate_approval_edit(command, Some(&original), &bad).is_err(),
+                    "{bad}"
+                );
+            }
+            assert!(validate_approval_edit(
+                methods::ITEM_TOOL_REQUEST_USER_INPUT,
+                None,
+                &json!({"answers":{}})
+            )
+            .is_err());
+        }
+
         #[tokio::test(flavor = "current_thread")]
         async fn tool_request_user_input_roundtrip() {
             let runtime = spawn_mock_runtime().await;
@@ -1137,6 +2113,12 @@ mod server_requests {
                 }
             }
             assert!(saw_ack);
+            let metrics = runtime.metrics_snapshot();
+            assert_eq!(
+                metrics.approvals_requested, 0,
+                "user input requests are not approvals"
+            );
+            assert_eq!(metrics.approvals_approved + metrics.approvals_declined, 0);
 
             runtime.shutdown().await.expect("shutdown");
         }
@@ -1322,91 +2304,312 @@ mod server_requests {
             assert!(!req.approval_id.is_empty());
             assert_eq!(req.params["itemId"], "item_1");
 
-            let mut saw_notification = false;
-            let mut saw_unknown = false;
-            let mut saw_response = false;
+            let mut saw_notification = false;
+            let mut saw_unknown = false;
+            let mut saw_response = false;
+            for _ in 0..8 {
+                let envelope = timeout(Duration::from_secs(2), live_rx.recv())
+                    .await
+                    .expect("live timeout")
+                    .expect("live closed");
+                if envelope.kind == MsgKind::Notification
+                    && envelope.method.as_deref() == Some("turn/started")
+                {
+                    saw_notification = true;
+                }
+                if envelope.kind == MsgKind::Unknown {
+                    saw_unknown = true;
+                }
+                if envelope.kind == MsgKind::Response {
+                    saw_response = true;
+                }
+                if saw_notification && saw_unknown && saw_response {
+                    break;
+                }
+            }
+
+            assert!(saw_notification);
+            assert!(saw_unknown);
+            assert!(saw_response);
+
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn raw_frame_observer_sees_malformed_inbound_and_outbound_frames() {
+            let frames = Arc::new(std::sync::Mutex::new(Vec::<(Direction, Vec<u8>)>::new()));
+            let observed = Arc::clone(&frames);
+            let cfg = RuntimeConfig::new(python_mock_process()).with_raw_frame_observer(Arc::new(
+                move |direction, frame| {
+                    observed
+                        .lock()
+                        .expect("frames lock")
+                        .push((direction, frame.to_vec()));
+                },
+            ));
+            let runtime = Runtime::spawn_local(cfg).await.expect("runtime spawn");
+            let _server_request_rx = runtime
+                .take_server_request_rx()
+                .await
+                .expect("take server request rx");
+
+            let value = runtime.call_raw("probe", json!({})).await.expect("probe");
+            assert_eq!(value["echoMethod"], "probe");
+
+            let deadline = Instant::now() + Duration::from_secs(2);
+            loop {
+                let saw_malformed =
+                    frames
+                        .lock()
+                        .expect("frames lock")
+                        .iter()
+                        .any(|(direction, frame)| {
+                            *direction == Direction::Inbound && frame.as_slice() == b"not-json\n"
+                        });
+                if saw_malformed {
+                    break;
+                }
+                assert!(Instant::now() < deadline, "malformed frame not observed");
+                sleep(Duration::from_millis(10)).await;
+            }
+
+            let frames = frames.lock().expect("frames lock").clone();
+            let probe_request = frames
+                .iter()
+                .find(|(direction, frame)| {
+                    *direction == Direction::Outbound
+                        && serde_json::from_slice::<serde_json::Value>(frame)
+                            .is_ok_and(|json| json["method"] == "probe")
+                })
+                .expect("outbound probe frame");
+            assert!(probe_request.1.ends_with(b"\n"));
+
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn take_server_request_rx_is_single_consumer() {
+            let runtime = spawn_mock_runtime().await;
+            let _first = runtime
+                .take_server_request_rx()
+                .await
+                .expect("first take server request rx");
+
+            let err = runtime
+                .take_server_request_rx()
+                .await
+                .expect_err("second take server request rx must fail");
+            assert_eq!(err, RuntimeError::ServerRequestReceiverTaken);
+
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn approval_response_roundtrip_ok() {
+            let runtime = spawn_mock_runtime().await;
+            let mut live_rx = runtime.subscribe_live();
+            let mut server_request_rx = runtime
+                .take_server_request_rx()
+                .await
+                .expect("take server request rx");
+
+            runtime.call_raw("probe", json!({})).await.expect("probe");
+            let req = timeout(Duration::from_secs(2), server_request_rx.recv())
+                .await
+                .expect("server request timeout")
+                .expect("server request closed");
+
+            runtime
+                .respond_approval_ok(&req.approval_id, json!({"decision":"accept"}))
+                .await
+                .expect("respond approval");
+
+            let mut saw_ack = false;
+            for _ in 0..8 {
+                let envelope = timeout(Duration::from_secs(2), live_rx.recv())
+                    .await
+                    .expect("live timeout")
+                    .expect("live closed");
+                if envelope.kind == MsgKind::Notification
+                    && envelope.method.as_deref() == Some("approval/ack")
+                    && envelope.json["params"]["approvalRpcId"] == 777
+                {
+                    assert_eq!(envelope.json["params"]["result"]["decision"], "accept");
+                    saw_ack = true;
+                    break;
+                }
+            }
+
+            assert!(saw_ack);
+            let metrics = runtime.metrics_snapshot();
+            assert_eq!(metrics.approvals_requested, 1);
+            assert_eq!(metrics.approvals_approved, 1);
+            assert_eq!(metrics.approvals_declined, 0);
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn auto_approve_turn_accepts_approvals_until_taken() {
+            let runtime = spawn_mock_runtime().await;
+            let mut live_rx = runtime.subscribe_live();
+            let mut server_request_rx = runtime
+                .take_server_request_rx()
+                .await
+                .expect("take server request rx");
+
+            runtime.register_turn_auto_approve("thr_1", "turn_1").await;
+            runtime.call_raw("probe", json!({})).await.expect("probe");
+
+            let mut saw_ack = false;
             for _ in 0..8 {
                 let envelope = timeout(Duration::from_secs(2), live_rx.recv())
                     .await
                     .expect("live timeout")
                     .expect("live closed");
                 if envelope.kind == MsgKind::Notification
-                    && envelope.method.as_deref() == Some("turn/started")
+                    && envelope.method.as_deref() == Some("approval/ack")
+                    && envelope.json["params"]["approvalRpcId"] == 777
                 {
-                    saw_notification = true;
-                }
-                if envelope.kind == MsgKind::Unknown {
-                    saw_unknown = true;
-                }
-                if envelope.kind == MsgKind::Response {
-                    saw_response = true;
-                }
-                if saw_notification && saw_unknown && saw_response {
+                    assert_eq!(envelope.json["params"]["result"]["decision"], "accept");
+                    saw_ack = true;
                     break;
                 }
             }
+            assert!(saw_ack);
+            assert!(server_request_rx.try_recv().is_err());
+            let metrics = runtime.metrics_snapshot();
+            assert_eq!(metrics.approvals_auto_approved, 1);
+            assert_eq!(metrics.approvals_approved, 0);
 
-            assert!(saw_notification);
-            assert!(saw_unknown);
-            assert!(saw_response);
-
-            runtime.shutdown().await.expect("shutdown");
-        }
-
-        #[tokio::test(flavor = "current_thread")]
-        async fn take_server_request_rx_is_single_consumer() {
-            let runtime = spawn_mock_runtime().await;
-            let _first = runtime
-                .take_server_request_rx()
+            let auto_approvals = runtime.take_turn_auto_approvals("thr_1", "turn_1");
+            assert_eq!(
+                auto_approvals,
+                vec![AutoApproval {
+                    thread_id: "thr_1".to_owned(),
+                    turn_id: "turn_1".to_owned(),
+                    method: "item/fileChange/requestApproval".to_owned(),
+                }]
+            );
+            runtime
+                .call_raw("probe_timeout", json!({}))
                 .await
-                .expect("first take server request rx");
-
-            let err = runtime
-                .take_server_request_rx()
+                .expect("probe_timeout");
+            let req = timeout(Duration::from_secs(2), server_request_rx.recv())
                 .await
-                .expect_err("second take server request rx must fail");
-            assert_eq!(err, RuntimeError::ServerRequestReceiverTaken);
+                .expect("server request timeout")
+                .expect("server request closed");
+            assert_eq!(req.method, "item/fileChange/requestApproval");
+            assert_eq!(runtime.metrics_snapshot().approvals_auto_approved, 1);
 
             runtime.shutdown().await.expect("shutdown");
         }
 
         #[tokio::test(flavor = "current_thread")]
-        async fn approval_response_roundtrip_ok() {
+        async fn auto_approve_turn_does_not_cover_other_turns_of_the_thread() {
             let runtime = spawn_mock_runtime().await;
-            let mut live_rx = runtime.subscribe_live();
             let mut server_request_rx = runtime
                 .take_server_request_rx()
                 .await
                 .expect("take server request rx");
 
-            runtime.call_raw("probe", json!({})).await.expect("probe");
+            runtime
+                .register_turn_auto_approve("thr_1", "turn_other")
+                .await;
+            runtime
+                .call_raw("probe_timeout", json!({}))
+                .await
+                .expect("probe_timeout");
             let req = timeout(Duration::from_secs(2), server_request_rx.recv())
                 .await
                 .expect("server request timeout")
                 .expect("server request closed");
+            assert_eq!(req.params["turnId"], "turn_1");
+            assert_eq!(runtime.metrics_snapshot().approvals_auto_approved, 0);
+            assert!(runtime
+                .take_turn_auto_approvals("thr_1", "turn_other")
+                .is_empty());
+
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn sink_and_live_share_ingest_seq_order() {
+            let sink_impl = Arc::new(SeqRecordingSink::default());
+            let sink: Arc<dyn EventSink> = sink_impl.clone();
+            let runtime = spawn_mock_runtime_with_sink(sink, 64).await;
+            let mut live_rx = runtime.subscribe_live();
 
             runtime
-                .respond_approval_ok(&req.approval_id, json!({"decision":"accept"}))
+                .call_raw("probe_state", json!({}))
                 .await
-                .expect("respond approval");
+                .expect("probe_state");
 
-            let mut saw_ack = false;
-            for _ in 0..8 {
+            let mut live_seqs = Vec::new();
+            loop {
                 let envelope = timeout(Duration::from_secs(2), live_rx.recv())
                     .await
                     .expect("live timeout")
                     .expect("live closed");
-                if envelope.kind == MsgKind::Notification
-                    && envelope.method.as_deref() == Some("approval/ack")
-                    && envelope.json["params"]["approvalRpcId"] == 777
-                {
-                    assert_eq!(envelope.json["params"]["result"]["decision"], "accept");
-                    saw_ack = true;
+                live_seqs.push(envelope.seq);
+                if envelope.kind == MsgKind::Response {
                     break;
                 }
             }
+            let last_live_seq = *live_seqs.last().expect("live seqs");
 
-            assert!(saw_ack);
+            timeout(Duration::from_secs(2), async {
+                while !sink_impl.seqs().contains(&last_live_seq) {
+                    sleep(Duration::from_millis(5)).await;
+                }
+            })
+            .await
+            .expect("sink caught up");
+
+            let sink_seqs = sink_impl.seqs();
+            assert!(
+                sink_seqs.windows(2).all(|pair| pair[0] < pair[1]),
+                "sink must observe strictly increasing seq: {sink_seqs:?}"
+            );
+            assert!(live_seqs.windows(2).all(|pair| pair[0] < pair[1]));
+            let shared: Vec<u64> = sink_seqs
+                .iter()
+                .copied()
+                .filter(|seq| live_seqs.contains(seq))
+                .collect();
+            assert_eq!(shared, live_seqs, "sink and live must agree on seq order");
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn hook_correlation_ids_do_not_consume_ingest_seq() {
+            let sink_impl = Arc::new(SeqRecordingSink::default());
+            let sink: Arc<dyn EventSink> = sink_impl.clone();
+            let runtime = spawn_mock_runtime_with_sink(sink, 64).await;
+
+            runtime
+                .call_raw("probe_state", json!({}))
+                .await
+                .expect("first probe_state");
+            assert_eq!(runtime.next_hook_correlation_id(), "hk-1");
+            assert_eq!(runtime.next_hook_correlation_id(), "hk-2");
+            runtime
+                .call_raw("probe_state", json!({}))
+                .await
+                .expect("second probe_state");
+
+            let last_seq = runtime.inner.counters.next_seq.load(Ordering::Acquire);
+            timeout(Duration::from_secs(2), async {
+                while !sink_impl.seqs().contains(&last_seq) {
+                    sleep(Duration::from_millis(5)).await;
+                }
+            })
+            .await
+            .expect("sink caught up");
+            let sink_seqs = sink_impl.seqs();
+            assert!(
+                sink_seqs.windows(2).all(|pair| pair[1] == pair[0] + 1),
+                "sink seqs must stay contiguous: {sink_seqs:?}"
+            );
             runtime.shutdown().await.expect("shutdown");
         }
 
@@ -1504,6 +2707,26 @@ mod server_requests {
 
             runtime.shutdown().await.expect("shutdown");
         }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn metrics_snapshot_counts_outbound_writes() {
+            let runtime = spawn_mock_runtime().await;
+            let before = runtime.metrics_snapshot();
+            // The initialize request was answered, so it has been written.
+            assert!(before.messages_written >= 1);
+
+            runtime
+                .call_raw("echo/write", json!({}))
+                .await
+                .expect("call");
+
+            let after = runtime.metrics_snapshot();
+            assert!(after.messages_written > before.messages_written);
+            assert!(after.bytes_written > before.bytes_written);
+            assert_eq!(after.pending_write_bytes, 0);
+
+            runtime.shutdown().await.expect("shutdown");
+        }
     }
 
     mod timeouts {
@@ -1515,6 +2738,8 @@ mod server_requests {
                 default_timeout_ms: 50,
                 on_timeout: TimeoutAction::Decline,
                 auto_decline_unknown: true,
+                decline_payloads: Default::default(),
+                unknown_handler: None,
             })
             .await;
             let mut live_rx = runtime.subscribe_live();
@@ -1551,6 +2776,10 @@ mod server_requests {
             }
 
             assert!(saw_ack);
+            let metrics = runtime.metrics_snapshot();
+            assert_eq!(metrics.approvals_requested, 1);
+            assert_eq!(metrics.approvals_timed_out, 1);
+            assert_eq!(metrics.approvals_approved + metrics.approvals_declined, 0);
             runtime.shutdown().await.expect("shutdown");
         }
 
@@ -1560,6 +2789,8 @@ mod server_requests {
                 default_timeout_ms: 50,
                 on_timeout: TimeoutAction::Decline,
                 auto_decline_unknown: true,
+                decline_payloads: Default::default(),
+                unknown_handler: None,
             })
             .await;
             let mut live_rx = runtime.subscribe_live();
@@ -1611,6 +2842,8 @@ mod server_requests {
                 default_timeout_ms: 50,
                 on_timeout: TimeoutAction::Decline,
                 auto_decline_unknown: true,
+                decline_payloads: Default::default(),
+                unknown_handler: None,
             })
             .await;
             let mut live_rx = runtime.subscribe_live();
@@ -1656,6 +2889,8 @@ mod server_requests {
                 default_timeout_ms: 50,
                 on_timeout: TimeoutAction::Decline,
                 auto_decline_unknown: true,
+                decline_payloads: Default::default(),
+                unknown_handler: None,
             })
             .await;
             let mut live_rx = runtime.subscribe_live();
@@ -1849,6 +3084,52 @@ mod server_requests {
             runtime.shutdown().await.expect("shutdown");
         }
 
+        #[tokio::test(flavor = "current_thread")]
+        async fn server_request_with_extreme_integer_ids_roundtrip() {
+            let runtime = spawn_mock_runtime().await;
+            let mut live_rx = runtime.subscribe_live();
+            let mut server_request_rx = runtime
+                .take_server_request_rx()
+                .await
+                .expect("take server request rx");
+
+            runtime
+                .call_raw("probe_extreme_ids", json!({}))
+                .await
+                .expect("probe_extreme_ids");
+            for _ in 0..2 {
+                let req = timeout(Duration::from_secs(2), server_request_rx.recv())
+                    .await
+                    .expect("server request timeout")
+                    .expect("server request closed");
+                runtime
+                    .respond_approval_ok(&req.approval_id, json!({"decision":"accept"}))
+                    .await
+                    .expect("respond approval");
+            }
+
+            let snapshot = runtime.state_snapshot();
+            assert!(snapshot.pending_server_requests.is_empty());
+
+            let mut acked = Vec::new();
+            while acked.len() < 2 {
+                let envelope = timeout(Duration::from_secs(2), live_rx.recv())
+                    .await
+                    .expect("live timeout")
+                    .expect("live closed");
+                if envelope.method.as_deref() == Some("approval/ack") {
+                    acked.push(envelope.json["params"]["approvalRpcId"].to_string());
+                }
+            }
+            acked.sort();
+            assert_eq!(
+                acked,
+                vec!["-7".to_owned(), "18446744073709551614".to_owned()]
+            );
+
+            runtime.shutdown().await.expect("shutdown");
+        }
+
         #[tokio::test(flavor = "current_thread")]
         async fn unknown_server_request_is_auto_declined() {
             let runtime = spawn_mock_runtime().await;
@@ -1883,8 +3164,178 @@ mod server_requests {
             }
 
             assert!(saw_ack);
+            let metrics = runtime.metrics_snapshot();
+            assert_eq!(
+                metrics.approvals_requested, 0,
+                "unknown methods are not approvals"
+            );
+            assert_eq!(metrics.approvals_auto_declined, 0);
+            assert_eq!(metrics.approvals_timed_out, 0);
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn auto_decline_uses_configured_decline_payload_for_method() {
+            let runtime = spawn_mock_runtime_with_server_cfg(
+                ServerRequestConfig::default().with_decline_payload(
+                    "item/unknown/requestApproval",
+                    json!({"outcome": "rejected", "reason": "policy"}),
+                ),
+            )
+            .await;
+            let mut live_rx = runtime.subscribe_live();
+
+            runtime
+                .call_raw("probe_unknown", json!({}))
+                .await
+                .expect("probe_unknown");
+
+            let mut saw_ack = false;
+            for _ in 0..8 {
+                let envelope = timeout(Duration::from_secs(2), live_rx.recv())
+                    .await
+                    .expect("live timeout")
+                    .expect("live closed");
+                if envelope.kind == MsgKind::Notification
+                    && envelope.method.as_deref() == Some("approval/ack")
+                    && envelope.json["params"]["approvalRpcId"] == 778
+                {
+                    assert_eq!(
+                        envelope.json["params"]["result"],
+                        json!({"outcome": "rejected", "reason": "policy"})
+                    );
+                    saw_ack = true;
+                    break;
+                }
+            }
+
+            assert!(saw_ack);
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn unknown_handler_answers_unknown_method_directly() {
+            let seen = Arc::new(AtomicUsize::new(0));
+            let seen_by_handler = Arc::clone(&seen);
+            let runtime = spawn_mock_runtime_with_server_cfg(
+                ServerRequestConfig::default().with_unknown_handler(Arc::new(move |request| {
+                    seen_by_handler.fetch_add(1, AtomicOrdering::SeqCst);
+                    assert_eq!(request.method, "item/unknown/requestApproval");
+                    assert_eq!(request.params["itemId"], "item_1");
+                    Some(crate::runtime::approvals::ApprovalDecision::Result(
+                        json!({"decision": "accept"}),
+                    ))
+                })),
+            )
+            .await;
+            let mut live_rx = runtime.subscribe_live();
+
+            runtime
+                .call_raw("probe_unknown", json!({}))
+                .await
+                .expect("probe_unknown");
+
+            let ack = wait_for_approval_ack(&mut live_rx, 778).await;
+            assert_eq!(ack["result"], json!({"decision": "accept"}));
+            assert_eq!(seen.load(AtomicOrdering::SeqCst), 1);
+            let metrics = runtime.metrics_snapshot();
+            assert_eq!(
+                metrics.approvals_approved, 0,
+                "unknown methods are not approvals"
+            );
+            assert_eq!(metrics.approvals_auto_declined, 0);
             runtime.shutdown().await.expect("shutdown");
         }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn unknown_handler_none_falls_back_to_auto_decline() {
+            let runtime = spawn_mock_runtime_with_server_cfg(
+                ServerRequestConfig::default().with_unknown_handler(Arc::new(|_| None)),
+            )
+            .await;
+            let mut live_rx = runtime.subscribe_live();
+
+            runtime
+                .call_raw("probe_unknown", json!({}))
+                .await
+                .expect("probe_unknown");
+
+            let ack = wait_for_approval_ack(&mut live_rx, 778).await;
+            assert_eq!(ack["result"]["decision"], "decline");
+            assert_eq!(runtime.metrics_snapshot().approvals_auto_declined, 0);
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        #[tokio::test(flavor = "current_thread")]
+        async fn typed_approval_results_pass_payload_validation() {
+            let runtime = spawn_mock_runtime().await;
+            let mut live_rx = runtime.subscribe_live();
+            let mut server_request_rx = runtime
+                .take_server_request_rx()
+                .await
+                .expect("take server request rx");
+
+            runtime
+                .call_raw("probe_user_input", json!({}))
+                .await
+                .expect("probe_user_input");
+            let req = timeout(Duration::from_secs(2), server_request_rx.recv())
+                .await
+                .expect("server request timeout")
+                .expect("server request closed");
+            runtime
+                .respond_approval_ok(
+                    &req.approval_id,
+                    ApprovalResult::user_input([("q1".to_owned(), vec!["alice".to_owned()])]),
+                )
+                .await
+                .expect("typed user input result");
+            let ack = wait_for_approval_ack(&mut live_rx, 780).await;
+            assert_eq!(ack["result"]["answers"]["q1"]["answers"][0], "alice");
+
+            runtime
+                .call_raw("probe_dynamic_tool_call", json!({}))
+                .await
+                .expect("probe_dynamic_tool_call");
+            let req = timeout(Duration::from_secs(2), server_request_rx.recv())
+                .await
+                .expect("server request timeout")
+                .expect("server request closed");
+            runtime
+                .respond_approval_ok(
+                    &req.approval_id,
+                    ApprovalResult::tool_call(
+                        true,
+                        vec![json!({"type":"inputText","text":"done"})],
+                    ),
+                )
+                .await
+                .expect("typed tool call result");
+            let ack = wait_for_approval_ack(&mut live_rx, 781).await;
+            assert_eq!(ack["result"]["success"], true);
+            assert_eq!(ack["result"]["contentItems"][0]["text"], "done");
+
+            runtime.shutdown().await.expect("shutdown");
+        }
+
+        async fn wait_for_approval_ack(
+            live_rx: &mut tokio::sync::broadcast::Receiver<Envelope>,
+            rpc_id: u64,
+        ) -> serde_json::Value {
+            for _ in 0..8 {
+                let envelope = timeout(Duration::from_secs(2), live_rx.recv())
+                    .await
+                    .expect("live timeout")
+                    .expect("live closed");
+                if envelope.kind == MsgKind::Notification
+                    && envelope.method.as_deref() == Some("approval/ack")
+                    && envelope.json["params"]["approvalRpcId"] == rpc_id
+                {
+                    return envelope.json["params"].clone();
+                }
+            }
+            panic!("no approval/ack for rpc id {rpc_id}");
+        }
     }
 }
 
@@ -1926,6 +3377,8 @@ mod state_and_snapshot {
             default_timeout_ms: 2_000,
             on_timeout: TimeoutAction::Decline,
             auto_decline_unknown: true,
+            decline_payloads: Default::default(),
+            unknown_handler: None,
         })
         .await;
         let mut server_request_rx = runtime
@@ -27,6 +27,14 @@ pub enum RuntimeError {
     Timeout,
     #[error("server request receiver already taken")]
     ServerRequestReceiverTaken,
+    #[error("blocking runtime API called from within an async context")]
+    BlockingInAsyncContext,
+    /// The connected server did not declare support for the requested feature.
+    #[error("unsupported by server: {0}")]
+    Unsupported(String),
+    /// The server failed `RuntimeConfig::compatibility_guard` at the initialize handshake.
+    #[error("incompatible server: {0}")]
+    Incompatible(String),
     #[error("internal error: {0}")]
     Internal(String),
 }
@@ -46,6 +54,15 @@ pub enum RpcError {
     ServerError(RpcErrorObject),
     #[error("transport is closed")]
     TransportClosed,
+    #[error("rpc call cancelled")]
+    Cancelled,
+    /// `turn/start` refused locally while `Runtime::reconfigure` drains the current child.
+    /// Temporary: retry once the reconfigure returns.
+    #[error("runtime is draining for reconfigure")]
+    Draining,
+    /// Client-side failure outside the RPC itself (e.g. a bulk helper's task panicked).
+    #[error("internal error: {0}")]
+    Internal(String),
 }
 
 #[derive(Clone, Debug, Error, Serialize, Deserialize, PartialEq, Eq)]
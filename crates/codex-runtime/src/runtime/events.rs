@@ -9,6 +9,8 @@ use crate::runtime::rpc_contract::methods;
 #[serde(untagged)]
 pub enum JsonRpcId {
     Number(u64),
+    /// Negative integer ids; non-negative integers always use `Number`.
+    Signed(i64),
     Text(String),
 }
 
@@ -31,6 +33,11 @@ pub enum MsgKind {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Envelope {
+    /// Ingest sequence number, assigned once per inbound message by the dispatcher and
+    /// once per runtime-synthesized envelope (`runtime/run/metadata`, `runtime/hooks/report`).
+    /// Strictly increasing for the runtime's lifetime (across restarts) and identical
+    /// on every channel (sink, live, lossless live), so it is the reconciliation key
+    /// between them. Gaps mean the channel dropped or lagged past envelopes.
     pub seq: u64,
     pub ts_millis: i64,
     pub direction: Direction,
@@ -74,6 +81,8 @@ pub struct TurnFailedNotification {
 pub struct TurnInterruptedNotification {
     pub thread_id: String,
     pub turn_id: String,
+    /// `params.reason`, when the server echoes the interrupt reason.
+    pub reason: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -183,7 +192,16 @@ pub fn extract_turn_interrupted(envelope: &Envelope) -> Option<TurnInterruptedNo
         return None;
     }
     let (thread_id, turn_id) = thread_turn_ids(envelope)?;
-    Some(TurnInterruptedNotification { thread_id, turn_id })
+    let reason = envelope
+        .json
+        .pointer("/params/reason")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned);
+    Some(TurnInterruptedNotification {
+        thread_id,
+        turn_id,
+        reason,
+    })
 }
 
 /// Parse one `turn/cancelled` notification into a typed payload.
@@ -402,13 +420,14 @@ mod tests {
             item_id: None,
             json: Arc::new(json!({
                 "method":"turn/interrupted",
-                "params":{"threadId":"thr_1","turnId":"turn_1"}
+                "params":{"threadId":"thr_1","turnId":"turn_1","reason":"timeout"}
             })),
         };
 
         let notification = extract_turn_interrupted(&envelope).expect("turn interrupted");
         assert_eq!(notification.thread_id, "thr_1");
         assert_eq!(notification.turn_id, "turn_1");
+        assert_eq!(notification.reason.as_deref(), Some("timeout"));
     }
 
     #[test]
@@ -1,26 +1,40 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 
 use crate::plugin::{
     BlockReason, HookAction, HookContext, HookIssue, HookPhase, HookReport, PostHook, PreHook,
+    ShutdownToken,
 };
 
 #[derive(Clone, Default)]
 pub struct RuntimeHookConfig {
     pub pre_hooks: Vec<Arc<dyn PreHook>>,
+    /// Phase subscriptions of `pre_hooks` by hook name; a hook without an entry runs in
+    /// every pre phase. Filled by `with_pre_hook_for_phases`.
+    pub pre_hook_phases: HashMap<&'static str, Vec<HookPhase>>,
     pub post_hooks: Vec<Arc<dyn PostHook>>,
     /// Hooks that fire specifically for PreToolUse phase via the internal approval loop.
     /// When non-empty, the runtime manages the approval channel internally and auto-escalates
     /// ApprovalPolicy from Never → Untrusted so codex sends approval requests.
     pub pre_tool_use_hooks: Vec<Arc<dyn PreHook>>,
+    /// When true, the thread `run_prompt` starts implicitly goes through the hooked
+    /// `thread_start` path, so PreSessionStart/PostSessionStart fire between PreRun and
+    /// PreTurn. Default false: implicit starts run only run/turn hooks.
+    pub session_hooks_on_implicit_start: bool,
 }
 
 impl std::fmt::Debug for RuntimeHookConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RuntimeHookConfig")
             .field("pre_hooks", &hook_names(&self.pre_hooks))
+            .field("pre_hook_phases", &self.pre_hook_phases)
             .field("post_hooks", &hook_names(&self.post_hooks))
             .field("pre_tool_use_hooks", &hook_names(&self.pre_tool_use_hooks))
+            .field(
+                "session_hooks_on_implicit_start",
+                &self.session_hooks_on_implicit_start,
+            )
             .finish()
     }
 }
@@ -28,8 +42,10 @@ impl std::fmt::Debug for RuntimeHookConfig {
 impl PartialEq for RuntimeHookConfig {
     fn eq(&self, other: &Self) -> bool {
         hook_names(&self.pre_hooks) == hook_names(&other.pre_hooks)
+            && self.pre_hook_phases == other.pre_hook_phases
             && hook_names(&self.post_hooks) == hook_names(&other.post_hooks)
             && hook_names(&self.pre_tool_use_hooks) == hook_names(&other.pre_tool_use_hooks)
+            && self.session_hooks_on_implicit_start == other.session_hooks_on_implicit_start
     }
 }
 
@@ -49,6 +65,19 @@ impl RuntimeHookConfig {
         self
     }
 
+    /// Register one pre hook that only runs in `phases`; other phases skip the call.
+    /// An empty slice registers a hook that never runs.
+    /// Allocation: amortized O(1) push + one Vec of `phases`. Complexity: O(p).
+    pub fn with_pre_hook_for_phases(
+        mut self,
+        hook: Arc<dyn PreHook>,
+        phases: &[HookPhase],
+    ) -> Self {
+        self.pre_hook_phases.insert(hook.name(), phases.to_vec());
+        self.pre_hooks.push(hook);
+        self
+    }
+
     /// Register one post hook.
     /// Allocation: amortized O(1) push. Complexity: O(1).
     pub fn with_post_hook(mut self, hook: Arc<dyn PostHook>) -> Self {
@@ -63,6 +92,13 @@ impl RuntimeHookConfig {
         self
     }
 
+    /// Fire session hooks for the thread `run_prompt` starts implicitly.
+    /// Allocation: none. Complexity: O(1).
+    pub fn with_session_hooks_on_implicit_start(mut self, enabled: bool) -> Self {
+        self.session_hooks_on_implicit_start = enabled;
+        self
+    }
+
     /// True when at least one tool-use hook is registered.
     /// Allocation: none. Complexity: O(1).
     pub fn has_pre_tool_use_hooks(&self) -> bool {
@@ -84,28 +120,46 @@ pub(crate) fn merge_hook_configs(
     defaults: &RuntimeHookConfig,
     overlay: &RuntimeHookConfig,
 ) -> RuntimeHookConfig {
+    let session_hooks_on_implicit_start =
+        defaults.session_hooks_on_implicit_start || overlay.session_hooks_on_implicit_start;
     if defaults.is_empty() {
-        return overlay.clone();
+        return overlay
+            .clone()
+            .with_session_hooks_on_implicit_start(session_hooks_on_implicit_start);
     }
     if overlay.is_empty() {
-        return defaults.clone();
-    }
+        return defaults
+            .clone()
+            .with_session_hooks_on_implicit_start(session_hooks_on_implicit_start);
+    }
+    let pre_hooks = merge_preferred_hooks(&overlay.pre_hooks, &defaults.pre_hooks);
+    let pre_hook_phases = merge_phase_filters(
+        &pre_hooks,
+        &overlay.pre_hooks,
+        &overlay.pre_hook_phases,
+        &defaults.pre_hook_phases,
+    );
     RuntimeHookConfig {
-        pre_hooks: merge_preferred_hooks(&overlay.pre_hooks, &defaults.pre_hooks),
+        pre_hooks,
+        pre_hook_phases,
         post_hooks: merge_preferred_hooks(&overlay.post_hooks, &defaults.post_hooks),
         pre_tool_use_hooks: merge_preferred_hooks(
             &overlay.pre_tool_use_hooks,
             &defaults.pre_tool_use_hooks,
         ),
+        session_hooks_on_implicit_start,
     }
 }
 
 pub(crate) struct HookKernel {
     pre_hooks: RwLock<Vec<Arc<dyn PreHook>>>,
+    pre_hook_phases: RwLock<HashMap<&'static str, Vec<HookPhase>>>,
     post_hooks: RwLock<Vec<Arc<dyn PostHook>>>,
     pre_tool_use_hooks: RwLock<Vec<Arc<dyn PreHook>>>,
     thread_scoped_pre_tool_use_hooks: RwLock<HashMap<String, Vec<Arc<dyn PreHook>>>>,
+    session_hooks_on_implicit_start: AtomicBool,
     latest_report: RwLock<HookReport>,
+    shutdown: ShutdownToken,
 }
 
 #[derive(Clone, Debug)]
@@ -118,19 +172,39 @@ impl HookKernel {
     pub(crate) fn new(config: RuntimeHookConfig) -> Self {
         Self {
             pre_hooks: RwLock::new(config.pre_hooks),
+            pre_hook_phases: RwLock::new(config.pre_hook_phases),
             post_hooks: RwLock::new(config.post_hooks),
             pre_tool_use_hooks: RwLock::new(config.pre_tool_use_hooks),
             thread_scoped_pre_tool_use_hooks: RwLock::new(HashMap::new()),
+            session_hooks_on_implicit_start: AtomicBool::new(
+                config.session_hooks_on_implicit_start,
+            ),
             latest_report: RwLock::new(HookReport::default()),
+            shutdown: ShutdownToken::new(),
         }
     }
 
+    /// Shared shutdown token stamped onto every hook context built by this kernel.
+    /// Allocation: none (Arc clone). Complexity: O(1).
+    pub(crate) fn shutdown_token(&self) -> ShutdownToken {
+        self.shutdown.clone()
+    }
+
+    /// Cancel the shared shutdown token so in-flight hooks can bail out.
+    pub(crate) fn signal_shutdown(&self) {
+        self.shutdown.cancel();
+    }
+
     pub(crate) fn is_enabled(&self) -> bool {
         rwlock_len(&self.pre_hooks) > 0
             || rwlock_len(&self.post_hooks) > 0
             || rwlock_len(&self.pre_tool_use_hooks) > 0
     }
 
+    pub(crate) fn session_hooks_on_implicit_start(&self) -> bool {
+        self.session_hooks_on_implicit_start.load(Ordering::Relaxed)
+    }
+
     /// True when at least one pre-tool-use hook is registered.
     /// Allocation: none (read lock only). Complexity: O(1).
     pub(crate) fn has_pre_tool_use_hooks(&self) -> bool {
@@ -177,9 +251,26 @@ impl HookKernel {
     /// Duplicate names are ignored to keep execution deterministic.
     /// Allocation: O(n) for name set snapshot. Complexity: O(n + m), n=existing, m=incoming.
     pub(crate) fn register(&self, config: RuntimeHookConfig) {
+        if config.session_hooks_on_implicit_start {
+            self.session_hooks_on_implicit_start
+                .store(true, Ordering::Relaxed);
+        }
         if config.is_empty() {
             return;
         }
+        {
+            // Filters follow the hook that wins deduplication: existing names keep theirs.
+            let existing = read_rwlock_vec(&self.pre_hooks);
+            let mut filters = match self.pre_hook_phases.write() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            for (name, phases) in config.pre_hook_phases {
+                if !existing.iter().any(|hook| hook.name() == name) {
+                    filters.insert(name, phases);
+                }
+            }
+        }
         register_dedup_hooks(&self.pre_hooks, config.pre_hooks);
         register_dedup_hooks(&self.post_hooks, config.post_hooks);
         register_dedup_hooks(&self.pre_tool_use_hooks, config.pre_tool_use_hooks);
@@ -202,6 +293,7 @@ impl HookKernel {
     /// Execute global pre hooks plus optional scoped hooks for one call.
     /// Scoped hooks are appended after globals and deduplicated by hook name.
     /// Returns `Err(BlockReason)` on the first hook that returns `HookAction::Block`.
+    /// A `HookAction::Respond` is kept as the last decision; later hooks do not run.
     /// Subsequent hooks are not executed. Allocation: O(n) decisions vec.
     pub(crate) async fn run_pre_with(
         &self,
@@ -209,14 +301,26 @@ impl HookKernel {
         report: &mut HookReport,
         scoped: Option<&RuntimeHookConfig>,
     ) -> Result<Vec<PreHookDecision>, BlockReason> {
-        let hooks = merge_owned_with_overlay(
-            read_rwlock_vec(&self.pre_hooks),
-            scoped.map(|cfg| cfg.pre_hooks.as_slice()),
+        let global = read_rwlock_vec(&self.pre_hooks);
+        let global_len = global.len();
+        let hooks = merge_owned_with_overlay(global, scoped.map(|cfg| cfg.pre_hooks.as_slice()));
+        let hooks = self.pre_hooks_for_phase(
+            hooks,
+            global_len,
+            scoped.map(|cfg| &cfg.pre_hook_phases),
+            ctx.phase,
         );
         let mut decisions = Vec::with_capacity(hooks.len());
         for hook in hooks {
             match hook.call(ctx).await {
                 Ok(HookAction::Block(reason)) => return Err(reason),
+                Ok(action @ HookAction::Respond { .. }) => {
+                    decisions.push(PreHookDecision {
+                        hook_name: hook.name().to_owned(),
+                        action,
+                    });
+                    break;
+                }
                 Ok(action) => decisions.push(PreHookDecision {
                     hook_name: hook.name().to_owned(),
                     action,
@@ -227,6 +331,40 @@ impl HookKernel {
         Ok(decisions)
     }
 
+    /// Drop hooks not subscribed to `phase`. The first `global_len` hooks are
+    /// kernel-registered and use the kernel filters; the rest come from `scoped`.
+    /// Allocation: none when no filter is set, else one Vec. Complexity: O(n).
+    fn pre_hooks_for_phase(
+        &self,
+        hooks: Vec<Arc<dyn PreHook>>,
+        global_len: usize,
+        scoped: Option<&HashMap<&'static str, Vec<HookPhase>>>,
+        phase: HookPhase,
+    ) -> Vec<Arc<dyn PreHook>> {
+        let global = match self.pre_hook_phases.read() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if global.is_empty() && scoped.is_none_or(HashMap::is_empty) {
+            return hooks;
+        }
+        hooks
+            .into_iter()
+            .enumerate()
+            .filter(|(index, hook)| {
+                let filters = if *index < global_len {
+                    Some(&*global)
+                } else {
+                    scoped
+                };
+                filters
+                    .and_then(|filters| filters.get(hook.name()))
+                    .is_none_or(|phases| phases.contains(&phase))
+            })
+            .map(|(_, hook)| hook)
+            .collect()
+    }
+
     /// Execute pre-tool-use hooks for one approval request.
     /// Returns `Err(BlockReason)` on the first hook that blocks (→ deny approval).
     /// Returns `Ok(())` when all hooks pass (→ approve).
@@ -350,6 +488,29 @@ where
     merged
 }
 
+/// Phase filters of a merged hook list: each name takes the filter of the config its
+/// hook came from (`preferred` wins on duplicate names, as in `merge_preferred_hooks`).
+fn merge_phase_filters(
+    merged: &[Arc<dyn PreHook>],
+    preferred_hooks: &[Arc<dyn PreHook>],
+    preferred: &HashMap<&'static str, Vec<HookPhase>>,
+    fallback: &HashMap<&'static str, Vec<HookPhase>>,
+) -> HashMap<&'static str, Vec<HookPhase>> {
+    let mut filters = HashMap::new();
+    for hook in merged {
+        let name = hook.name();
+        let source = if preferred_hooks.iter().any(|hook| hook.name() == name) {
+            preferred
+        } else {
+            fallback
+        };
+        if let Some(phases) = source.get(name) {
+            filters.insert(name, phases.clone());
+        }
+    }
+    filters
+}
+
 fn merge_owned_with_overlay<T>(mut base: Vec<Arc<T>>, overlay: Option<&[Arc<T>]>) -> Vec<Arc<T>>
 where
     T: ?Sized + HookName,
@@ -1,5 +1,42 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use serde_json::Value;
 
+/// Kind of id being minted, so one generator can keep per-kind prefixes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IdKind {
+    /// `HookContext::correlation_id` for runtime-issued hook contexts.
+    HookCorrelation,
+    /// Web adapter session id returned by `create_session`.
+    WebSession,
+}
+
+/// Injectable id source for runtime and web adapter ids.
+/// When none is configured the built-in behavior applies
+/// (`hk-<seq>` hook correlation ids, `sess_<uuid>` web session ids).
+pub trait IdGenerator: Send + Sync {
+    fn next_id(&self, kind: IdKind) -> String;
+}
+
+/// Deterministic generator for tests: `id-1`, `id-2`, ... shared across kinds.
+#[derive(Debug, Default)]
+pub struct SequentialIdGenerator {
+    issued: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn next_id(&self, _kind: IdKind) -> String {
+        let n = self.issued.fetch_add(1, Ordering::AcqRel) + 1;
+        format!("id-{n}")
+    }
+}
+
 /// Canonical non-empty ID parsing.
 /// Rejects empty values and strings with leading/trailing whitespace.
 pub(crate) fn parse_canonical_id(value: &Value) -> Option<&str> {
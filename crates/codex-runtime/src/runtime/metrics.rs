@@ -2,6 +2,8 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use serde::{Deserialize, Serialize};
 
+use crate::runtime::approvals::is_approval_method;
+
 const SINK_LATENCY_BUCKET_UPPER_US: [u64; 8] =
     [100, 250, 500, 1_000, 2_500, 5_000, 10_000, u64::MAX];
 const SINK_LATENCY_BUCKET_COUNT: usize = SINK_LATENCY_BUCKET_UPPER_US.len();
@@ -23,6 +25,25 @@ pub struct RuntimeMetricsSnapshot {
     pub sink_latency_avg_micros: f64,
     pub sink_latency_p95_micros: u64,
     pub sink_latency_max_micros: u64,
+    /// Command-execution and file-change approval requests; other server requests are
+    /// not counted here or in the outcome counters below.
+    pub approvals_requested: u64,
+    pub approvals_approved: u64,
+    pub approvals_declined: u64,
+    pub approvals_timed_out: u64,
+    pub approvals_auto_declined: u64,
+    /// Approval requests accepted for runs with `PromptRunParams::auto_approve_all`.
+    pub approvals_auto_approved: u64,
+    /// Bytes written to the child's stdin (newline-delimited JSON frames).
+    pub bytes_written: u64,
+    pub messages_written: u64,
+    /// Bytes of the frame currently blocked in a stdin write. Messages still
+    /// queued in the write channel are not serialized yet and are not counted.
+    pub pending_write_bytes: u64,
+    /// Prompt turns waiting for a `RuntimeConfig::max_concurrent_turns` slot.
+    pub turns_queued: u64,
+    /// Prompt turns between `turn/start` and their terminal result.
+    pub turns_active: u64,
 }
 
 /// Runtime counters used for snapshots and long-run regression checks.
@@ -41,6 +62,27 @@ pub(crate) struct RuntimeMetrics {
     sink_latency_total_micros: AtomicU64,
     sink_latency_max_micros: AtomicU64,
     sink_latency_buckets: [AtomicU64; SINK_LATENCY_BUCKET_COUNT],
+    approvals_requested: AtomicU64,
+    approvals_approved: AtomicU64,
+    approvals_declined: AtomicU64,
+    approvals_timed_out: AtomicU64,
+    approvals_auto_declined: AtomicU64,
+    approvals_auto_approved: AtomicU64,
+    bytes_written: AtomicU64,
+    messages_written: AtomicU64,
+    pending_write_bytes: AtomicU64,
+    turns_queued: AtomicU64,
+    turns_active: AtomicU64,
+}
+
+/// How one server request (approval) was resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ApprovalOutcome {
+    Approved,
+    Declined,
+    TimedOut,
+    AutoDeclined,
+    AutoApproved,
 }
 
 impl RuntimeMetrics {
@@ -61,6 +103,17 @@ impl RuntimeMetrics {
             sink_latency_total_micros: AtomicU64::new(0),
             sink_latency_max_micros: AtomicU64::new(0),
             sink_latency_buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            approvals_requested: AtomicU64::new(0),
+            approvals_approved: AtomicU64::new(0),
+            approvals_declined: AtomicU64::new(0),
+            approvals_timed_out: AtomicU64::new(0),
+            approvals_auto_declined: AtomicU64::new(0),
+            approvals_auto_approved: AtomicU64::new(0),
+            bytes_written: AtomicU64::new(0),
+            messages_written: AtomicU64::new(0),
+            pending_write_bytes: AtomicU64::new(0),
+            turns_queued: AtomicU64::new(0),
+            turns_active: AtomicU64::new(0),
         }
     }
 
@@ -140,6 +193,71 @@ impl RuntimeMetrics {
         self.broadcast_send_failed.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record one inbound server request when `method` is a command or file-change
+    /// approval; user input, tool calls and auth refreshes are not counted.
+    /// Allocation: none. Complexity: O(1).
+    pub(crate) fn record_approval_requested(&self, method: &str) {
+        if is_approval_method(method) {
+            self.approvals_requested.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record how one approval request was resolved; other methods are ignored.
+    /// Allocation: none. Complexity: O(1).
+    pub(crate) fn record_approval_outcome(&self, method: &str, outcome: ApprovalOutcome) {
+        if !is_approval_method(method) {
+            return;
+        }
+        let counter = match outcome {
+            ApprovalOutcome::Approved => &self.approvals_approved,
+            ApprovalOutcome::Declined => &self.approvals_declined,
+            ApprovalOutcome::TimedOut => &self.approvals_timed_out,
+            ApprovalOutcome::AutoDeclined => &self.approvals_auto_declined,
+            ApprovalOutcome::AutoApproved => &self.approvals_auto_approved,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Mark `bytes` as handed to the child's stdin but not yet accepted.
+    /// Allocation: none. Complexity: O(1).
+    pub(crate) fn record_write_started(&self, bytes: u64) {
+        self.pending_write_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Settle one stdin write started with `record_write_started`.
+    /// Allocation: none. Complexity: O(1).
+    pub(crate) fn record_write_finished(&self, bytes: u64, written: bool) {
+        saturating_sub(&self.pending_write_bytes, bytes);
+        if written {
+            self.bytes_written.fetch_add(bytes, Ordering::Relaxed);
+            self.messages_written.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record one prompt turn starting to wait for a concurrency slot.
+    /// Allocation: none. Complexity: O(1).
+    pub(crate) fn inc_turns_queued(&self) {
+        self.turns_queued.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record one prompt turn leaving the slot queue (admitted or cancelled).
+    /// Allocation: none. Complexity: O(1).
+    pub(crate) fn dec_turns_queued(&self) {
+        saturating_dec(&self.turns_queued);
+    }
+
+    /// Record one prompt turn holding a slot.
+    /// Allocation: none. Complexity: O(1).
+    pub(crate) fn inc_turns_active(&self) {
+        self.turns_active.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record one prompt turn releasing its slot.
+    /// Allocation: none. Complexity: O(1).
+    pub(crate) fn dec_turns_active(&self) {
+        saturating_dec(&self.turns_active);
+    }
+
     /// Record one sink write attempt with elapsed latency.
     /// Allocation: none. Complexity: O(1).
     pub(crate) fn record_sink_write(&self, latency_micros: u64, is_error: bool) {
@@ -195,6 +313,17 @@ impl RuntimeMetrics {
             sink_latency_avg_micros,
             sink_latency_p95_micros: self.sink_latency_p95_micros(),
             sink_latency_max_micros: self.sink_latency_max_micros.load(Ordering::Relaxed),
+            approvals_requested: self.approvals_requested.load(Ordering::Relaxed),
+            approvals_approved: self.approvals_approved.load(Ordering::Relaxed),
+            approvals_declined: self.approvals_declined.load(Ordering::Relaxed),
+            approvals_timed_out: self.approvals_timed_out.load(Ordering::Relaxed),
+            approvals_auto_declined: self.approvals_auto_declined.load(Ordering::Relaxed),
+            approvals_auto_approved: self.approvals_auto_approved.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            messages_written: self.messages_written.load(Ordering::Relaxed),
+            pending_write_bytes: self.pending_write_bytes.load(Ordering::Relaxed),
+            turns_queued: self.turns_queued.load(Ordering::Relaxed),
+            turns_active: self.turns_active.load(Ordering::Relaxed),
         }
     }
 
@@ -226,12 +355,17 @@ fn sink_latency_bucket_index(latency_micros: u64) -> usize {
 }
 
 fn saturating_dec(v: &AtomicU64) {
+    saturating_sub(v, 1);
+}
+
+fn saturating_sub(v: &AtomicU64, amount: u64) {
     let mut current = v.load(Ordering::Relaxed);
     loop {
         if current == 0 {
             return;
         }
-        match v.compare_exchange_weak(current, current - 1, Ordering::Relaxed, Ordering::Relaxed) {
+        let next_value = current.saturating_sub(amount);
+        match v.compare_exchange_weak(current, next_value, Ordering::Relaxed, Ordering::Relaxed) {
             Ok(_) => return,
             Err(next) => current = next,
         }
@@ -278,6 +412,21 @@ mod tests {
         assert_eq!(snapshot.pending_server_request_count, 0);
     }
 
+    #[test]
+    fn write_counters_track_pending_and_completed_frames() {
+        let metrics = RuntimeMetrics::new(0);
+        metrics.record_write_started(10);
+        metrics.record_write_started(4);
+        assert_eq!(metrics.snapshot(1_000).pending_write_bytes, 14);
+
+        metrics.record_write_finished(10, true);
+        metrics.record_write_finished(4, false);
+        let snapshot = metrics.snapshot(1_000);
+        assert_eq!(snapshot.pending_write_bytes, 0);
+        assert_eq!(snapshot.bytes_written, 10);
+        assert_eq!(snapshot.messages_written, 1);
+    }
+
     #[test]
     fn snapshot_tracks_detached_task_init_failures() {
         let metrics = RuntimeMetrics::new(0);
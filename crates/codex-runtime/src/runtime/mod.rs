@@ -8,6 +8,7 @@ pub mod events;
 pub mod hooks;
 pub(crate) mod id;
 pub mod metrics;
+pub mod prompt_cache;
 pub mod rpc;
 pub mod rpc_contract;
 pub(crate) mod runtime_validation;
@@ -16,36 +17,55 @@ pub mod sink;
 pub mod state;
 pub mod transport;
 pub(crate) mod turn_lifecycle;
+pub(crate) mod turn_limiter;
 pub mod turn_output;
 
 pub use api::{
-    ApprovalPolicy, ByteRange, CommandExecOutputDeltaNotification, CommandExecOutputStream,
-    CommandExecParams, CommandExecResizeParams, CommandExecResizeResponse, CommandExecResponse,
+    run_prompt_with_backend, ApprovalPolicy, AttachmentPlaceholderStrategy, ByteRange,
+    CommandExecOutputDeltaNotification, CommandExecOutputStream, CommandExecParams,
+    CommandExecResizeParams, CommandExecResizeResponse, CommandExecResponse,
     CommandExecTerminalSize, CommandExecTerminateParams, CommandExecTerminateResponse,
-    CommandExecWriteParams, CommandExecWriteResponse, ExternalNetworkAccess, InputItem,
-    Personality, PromptAttachment, PromptRunError, PromptRunParams, PromptRunResult,
+    CommandExecWriteParams, CommandExecWriteResponse, ExternalNetworkAccess, FullRunResult,
+    InputItem, ModelPricing, ModelStatus, Personality, PricingTable, PromptAttachment,
+    PromptBackend, PromptBackendFuture, PromptRunError, PromptRunParams, PromptRunResult,
     PromptRunStream, PromptRunStreamEvent, ReasoningEffort, SandboxPolicy, SandboxPreset,
-    ServiceTier, SkillDependencies, SkillErrorInfo, SkillInterface, SkillMetadata, SkillScope,
-    SkillToolDependency, SkillsListEntry, SkillsListExtraRootsForCwd, SkillsListParams,
-    SkillsListResponse, TextElement, ThreadAgentMessageItemView, ThreadCommandExecutionItemView,
-    ThreadHandle, ThreadItemPayloadView, ThreadItemType, ThreadItemView, ThreadListParams,
-    ThreadListResponse, ThreadListSortKey, ThreadLoadedListParams, ThreadLoadedListResponse,
-    ThreadReadParams, ThreadReadResponse, ThreadRollbackParams, ThreadRollbackResponse,
-    ThreadStartParams, ThreadTurnErrorView, ThreadTurnStatus, ThreadTurnView, ThreadView,
-    TurnHandle, TurnStartParams, DEFAULT_REASONING_EFFORT,
+    SchemaViolationObserver, ServiceTier, SkillDependencies, SkillErrorInfo, SkillInterface,
+    SkillMetadata, SkillScope, SkillToolDependency, SkillsListEntry, SkillsListExtraRootsForCwd,
+    SkillsListParams, SkillsListResponse, SteerOutcome, TextElement, ThreadAgentMessageItemView,
+    ThreadCommandExecutionItemView, ThreadExport, ThreadExportItem, ThreadExportTurn,
+    ThreadGitInfo, ThreadHandle, ThreadImageViewItemView, ThreadItemPayloadView, ThreadItemType,
+    ThreadItemView, ThreadListParams, ThreadListResponse, ThreadListSortKey,
+    ThreadLoadedListParams, ThreadLoadedListResponse, ThreadMcpToolCallItemView, ThreadPool,
+    ThreadReadParams, ThreadReadResponse, ThreadReviewModeItemView, ThreadRollbackParams,
+    ThreadRollbackResponse, ThreadStartParams, ThreadTurnErrorView, ThreadTurnStatus,
+    ThreadTurnView, ThreadView, ThreadWebSearchItemView, TurnGuard, TurnHandle, TurnStartParams,
+    TurnUsage, DEFAULT_REASONING_EFFORT,
+};
+pub use approvals::{
+    ApprovalDecision, ApprovalResult, ServerRequest, ServerRequestConfig, TimeoutAction,
+    UnknownServerRequestHandler,
 };
-pub use approvals::{ServerRequest, ServerRequestConfig, TimeoutAction};
 pub use client::{
     Client, ClientConfig, ClientError, CompatibilityGuard, RunProfile, SemVerTriplet, Session,
-    SessionConfig,
+    SessionConfig, SessionPrompt,
+};
+#[cfg(feature = "blocking")]
+pub use core::BlockingRuntime;
+pub use core::{
+    InitializeCapabilities, PendingRpc, RestartPolicy, ResumeIdChangeObserver, Runtime,
+    RuntimeConfig, SupervisorConfig,
 };
-pub use core::{InitializeCapabilities, RestartPolicy, Runtime, RuntimeConfig, SupervisorConfig};
 pub use errors::{RpcError, RpcErrorObject, RuntimeError, SinkError};
 pub use hooks::RuntimeHookConfig;
+pub use id::{IdGenerator, IdKind, SequentialIdGenerator};
 pub use metrics::RuntimeMetricsSnapshot;
-pub use rpc_contract::RpcValidationMode;
+pub use prompt_cache::{InMemoryPromptCache, PromptCache, PromptCacheKey};
+pub use rpc_contract::{
+    RpcValidationMode, ValidationFailure, ValidationObserver, ValidationSurface,
+};
 pub use shell_hook::ShellCommandHook;
-pub use transport::{StdioProcessSpec, StdioTransportConfig};
+pub use transport::{RawFrameObserver, StdioProcessSpec, StdioTransportConfig};
+pub use turn_output::{parse_thread_id, parse_turn_id};
 
 pub type ServerRequestRx = tokio::sync::mpsc::Receiver<ServerRequest>;
 
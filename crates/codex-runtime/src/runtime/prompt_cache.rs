@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::runtime::api::{
+    ApprovalPolicy, PromptRunParams, PromptRunResult, ReasoningEffort, SandboxPolicy, SandboxPreset,
+};
+
+/// Identity of one cacheable prompt run. Model, effort, the assistant item separator, and
+/// `fail_on_command_error` are part of the key so runs with different settings, output
+/// shape, or failure rules never share an entry.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PromptCacheKey {
+    pub cwd: String,
+    pub prompt: String,
+    pub model: Option<String>,
+    pub effort: Option<ReasoningEffort>,
+    pub assistant_item_separator: String,
+    pub fail_on_command_error: bool,
+}
+
+impl PromptCacheKey {
+    /// Key for `p` when the run is side-effect free and fully described by the key:
+    /// approval `Never`, sandbox `read-only`, no privileged escalation, and no
+    /// attachments, output schema, steer anchor, raw turn params, or reasoning budget.
+    /// `None` otherwise.
+    /// Allocation: key String clones when eligible. Complexity: O(n), n = input lengths.
+    pub fn for_params(p: &PromptRunParams) -> Option<Self> {
+        let read_only = p.approval_policy == ApprovalPolicy::Never
+            && p.sandbox_policy == SandboxPolicy::Preset(SandboxPreset::ReadOnly)
+            && !p.privileged_escalation_approved;
+        let fully_keyed = p.attachments.is_empty()
+            && p.output_schema.is_none()
+            && p.steer_from.is_none()
+            && p.extra_turn_params.is_empty()
+            && p.max_reasoning_tokens.is_none();
+        if !read_only || !fully_keyed {
+            return None;
+        }
+        Some(Self {
+            cwd: p.cwd.clone(),
+            prompt: p.prompt.clone(),
+            model: p.model.clone(),
+            effort: p.effort,
+            assistant_item_separator: p.assistant_item_separator.clone(),
+            fail_on_command_error: p.fail_on_command_error,
+        })
+    }
+}
+
+/// Result store consulted by `Runtime::run_prompt` for read-only runs
+/// (see `PromptCacheKey::for_params`). Lookup happens after PreRun hooks, on the patched
+/// params; a hit skips every RPC and the turn-level hooks, and PostRun still sees it.
+/// Results short-circuited by a hook are never stored.
+/// A hit is returned as stored: its `thread_id`/`turn_id` are those of the run that
+/// filled the entry, which may since be archived; scope shared caches accordingly.
+pub trait PromptCache: Send + Sync {
+    fn get(&self, key: &PromptCacheKey) -> Option<PromptRunResult>;
+    /// Called once per successful, cacheable run.
+    fn put(&self, key: PromptCacheKey, result: &PromptRunResult);
+}
+
+/// Unbounded in-process cache; entries live as long as the cache.
+#[derive(Debug, Default)]
+pub struct InMemoryPromptCache {
+    entries: Mutex<HashMap<PromptCacheKey, PromptRunResult>>,
+}
+
+impl InMemoryPromptCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.len())
+            .unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl PromptCache for InMemoryPromptCache {
+    fn get(&self, key: &PromptCacheKey) -> Option<PromptRunResult> {
+        self.entries.lock().ok()?.get(key).cloned()
+    }
+
+    fn put(&self, key: PromptCacheKey, result: &PromptRunResult) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(key, result.clone());
+        }
+    }
+}
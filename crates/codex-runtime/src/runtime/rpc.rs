@@ -133,9 +133,14 @@ fn parse_response_rpc_id_value(id_value: Option<&Value>) -> Option<u64> {
     }
 }
 
+/// Integer ids stay exact: serde_json keeps every integer in `i64::MIN..=u64::MAX` as an
+/// integer, never a float. Fractional or out-of-range numbers are not valid ids here.
 fn parse_jsonrpc_id_value(id_value: Option<&Value>) -> Option<JsonRpcId> {
     match id_value {
-        Some(Value::Number(number)) => number.as_u64().map(JsonRpcId::Number),
+        Some(Value::Number(number)) => number
+            .as_u64()
+            .map(JsonRpcId::Number)
+            .or_else(|| number.as_i64().map(JsonRpcId::Signed)),
         Some(Value::String(text)) => Some(JsonRpcId::Text(text.clone())),
         _ => None,
     }
@@ -247,6 +252,29 @@ mod tests {
         assert_eq!(ids.item_id.as_deref(), Some("item_ok"));
     }
 
+    #[test]
+    fn extract_message_metadata_keeps_extreme_integer_ids_exact() {
+        let near_max: Value = serde_json::from_str(
+            r#"{"id":18446744073709551614,"method":"item/fileChange/requestApproval","params":{}}"#,
+        )
+        .expect("parse near-max id");
+        let meta = extract_message_metadata(&near_max);
+        assert_eq!(meta.rpc_id, Some(JsonRpcId::Number(u64::MAX - 1)));
+        assert_eq!(
+            serde_json::to_string(&meta.rpc_id).expect("serialize id"),
+            "18446744073709551614"
+        );
+
+        let negative = json!({"id": -7, "method": "item/tool/call", "params": {}});
+        assert_eq!(
+            extract_message_metadata(&negative).rpc_id,
+            Some(JsonRpcId::Signed(-7))
+        );
+
+        let fractional = json!({"id": 1.5, "method": "item/tool/call", "params": {}});
+        assert_eq!(extract_message_metadata(&fractional).rpc_id, None);
+    }
+
     #[test]
     fn map_overloaded_error() {
         let v = json!({"code": -32001, "message": "ingress overload"});
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use serde_json::Value;
 
 use crate::runtime::api::summarize_sandbox_policy_wire_value;
@@ -10,6 +12,7 @@ pub mod methods {
     pub const THREAD_RESUME: &str = "thread/resume";
     pub const THREAD_FORK: &str = "thread/fork";
     pub const THREAD_ARCHIVE: &str = "thread/archive";
+    pub const THREAD_UNARCHIVE: &str = "thread/unarchive";
     pub const THREAD_READ: &str = "thread/read";
     pub const THREAD_LIST: &str = "thread/list";
     pub const THREAD_LOADED_LIST: &str = "thread/loaded/list";
@@ -22,6 +25,12 @@ pub mod methods {
     pub const TURN_START: &str = "turn/start";
     pub const TURN_INTERRUPT: &str = "turn/interrupt";
 
+    // Experimental thread metadata methods; outside `KNOWN`, so not contract-validated.
+    pub const THREAD_SET_METADATA: &str = "thread/setMetadata";
+    pub const THREAD_GET_METADATA: &str = "thread/getMetadata";
+    // Experimental model catalog; outside `KNOWN`, `check_model` falls back when absent.
+    pub const MODEL_LIST: &str = "model/list";
+
     // Server-request methods (runtime inbound requests requiring a client response)
     pub const ITEM_COMMAND_EXECUTION_REQUEST_APPROVAL: &str =
         "item/commandExecution/requestApproval";
@@ -44,14 +53,21 @@ pub mod methods {
     pub const ITEM_COMMAND_EXECUTION_OUTPUT_DELTA: &str = "item/commandExecution/outputDelta";
     pub const COMMAND_EXEC_OUTPUT_DELTA: &str = "command/exec/outputDelta";
     pub const ITEM_COMPLETED: &str = "item/completed";
+    pub const THREAD_TOKEN_USAGE_UPDATED: &str = "thread/tokenUsage/updated";
     pub const APPROVAL_ACK: &str = "approval/ack";
     pub const SKILLS_CHANGED: &str = "skills/changed";
 
-    pub const KNOWN: [&str; 15] = [
+    // Runtime-synthesized live events; the `runtime/` namespace is never used by the server.
+    pub const RUNTIME_HOOKS_REPORT: &str = "runtime/hooks/report";
+    pub const RUNTIME_RUN_METADATA: &str = "runtime/run/metadata";
+    pub const RUNTIME_LIVE_LAGGED: &str = "runtime/live/lagged";
+
+    pub const KNOWN: [&str; 16] = [
         THREAD_START,
         THREAD_RESUME,
         THREAD_FORK,
         THREAD_ARCHIVE,
+        THREAD_UNARCHIVE,
         THREAD_READ,
         THREAD_LIST,
         THREAD_LOADED_LIST,
@@ -76,6 +92,28 @@ pub enum RpcValidationMode {
     KnownMethods,
 }
 
+/// Payload side a contract check ran against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationSurface {
+    Request,
+    Response,
+}
+
+/// One contract mismatch reported to a `ValidationObserver`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationFailure {
+    pub surface: ValidationSurface,
+    /// Validation mode the caller requested.
+    pub mode: RpcValidationMode,
+    /// True when the call was rejected; false when the mismatch was only observed
+    /// (e.g. `RpcValidationMode::None` checked against the known-method contract).
+    pub enforced: bool,
+    pub error: RpcError,
+}
+
+/// Callback receiving `(method, failure)` for every contract mismatch on validated calls.
+pub type ValidationObserver = Arc<dyn Fn(&str, &ValidationFailure) + Send + Sync>;
+
 /// Request-shape rule for one RPC method contract descriptor.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RpcRequestContract {
@@ -114,7 +152,7 @@ const KEY_DATA: &str = "data";
 const KEY_PROCESS_ID: &str = "processId";
 const KEY_SIZE: &str = "size";
 
-const RPC_CONTRACT_DESCRIPTORS: [RpcContractDescriptor; 15] = [
+const RPC_CONTRACT_DESCRIPTORS: [RpcContractDescriptor; 16] = [
     RpcContractDescriptor {
         method: methods::THREAD_START,
         request: RpcRequestContract::ThreadStart,
@@ -135,6 +173,11 @@ const RPC_CONTRACT_DESCRIPTORS: [RpcContractDescriptor; 15] = [
         request: RpcRequestContract::ThreadId,
         response: RpcResponseContract::Object,
     },
+    RpcContractDescriptor {
+        method: methods::THREAD_UNARCHIVE,
+        request: RpcRequestContract::ThreadId,
+        response: RpcResponseContract::Object,
+    },
     RpcContractDescriptor {
         method: methods::THREAD_READ,
         request: RpcRequestContract::ThreadId,
@@ -898,6 +941,7 @@ mod tests {
                 methods::THREAD_RESUME,
                 methods::THREAD_FORK,
                 methods::THREAD_ARCHIVE,
+                methods::THREAD_UNARCHIVE,
                 methods::THREAD_READ,
                 methods::THREAD_LIST,
                 methods::THREAD_LOADED_LIST,
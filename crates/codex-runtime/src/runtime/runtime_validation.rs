@@ -31,6 +31,34 @@ pub(crate) fn validate_runtime_capacities(
     Ok(())
 }
 
+pub(crate) fn validate_max_concurrent_turns(
+    max_concurrent_turns: Option<usize>,
+) -> Result<(), RuntimeError> {
+    if max_concurrent_turns == Some(0) {
+        return Err(RuntimeError::InvalidConfig(
+            "max_concurrent_turns must be > 0 when set".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_initialize_limits(
+    initialize_timeout: std::time::Duration,
+    max_initialize_result_bytes: usize,
+) -> Result<(), RuntimeError> {
+    if initialize_timeout.is_zero() {
+        return Err(RuntimeError::InvalidConfig(
+            "initialize_timeout must be > 0".to_owned(),
+        ));
+    }
+    if max_initialize_result_bytes == 0 {
+        return Err(RuntimeError::InvalidConfig(
+            "max_initialize_result_bytes must be > 0".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) fn validate_state_projection_limits(
     limits: &StateProjectionLimits,
 ) -> Result<(), RuntimeError> {
@@ -10,6 +10,7 @@
 //! |------|--------|--------|
 //! | `0`  | `{}` or `{"action":"noop"}` | `HookAction::Noop` |
 //! | `0`  | `{"action":"mutate", ...}` | `HookAction::Mutate(patch)` |
+//! | `0`  | `{"action":"respond","text":"..."}` | `HookAction::Respond { text }` |
 //! | `2`  | `{"message":"..."}` or plain text | `HookAction::Block(reason)` |
 //! | any other | — | `Err(HookIssue { class: Execution })` |
 //!
@@ -123,9 +124,12 @@ async fn run_process(
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ShellPreOutput {
-    /// `"noop"` or `"mutate"`. Anything else is treated as `"noop"`.
+    /// `"noop"`, `"mutate"`, or `"respond"`. Anything else is treated as `"noop"`.
     #[serde(default)]
     action: String,
+    /// Answer text for `"respond"`; missing text responds with an empty string.
+    #[serde(default)]
+    text: String,
     prompt_override: Option<String>,
     model_override: Option<String>,
     #[serde(default)]
@@ -171,6 +175,8 @@ fn parse_pre_output(
             add_attachments: parsed.add_attachments,
             metadata_delta: parsed.metadata_delta,
         }))
+    } else if parsed.action.eq_ignore_ascii_case("respond") {
+        Ok(HookAction::Respond { text: parsed.text })
     } else {
         Ok(HookAction::Noop)
     }
@@ -379,6 +385,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn action_respond_carries_text() {
+        assert_eq!(
+            parse_pre_output("h", phase(), r#"{"action":"respond","text":"cached"}"#),
+            Ok(HookAction::Respond {
+                text: "cached".to_owned()
+            })
+        );
+    }
+
     #[test]
     fn unknown_action_is_noop() {
         assert_eq!(
@@ -441,8 +457,10 @@ mod tests {
             correlation_id: "hk-1".to_owned(),
             ts_ms: 0,
             metadata: json!({}),
+            assistant_text: None,
             tool_name: None,
             tool_input: None,
+            shutdown: Default::default(),
         }
     }
 
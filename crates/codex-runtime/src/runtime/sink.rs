@@ -17,10 +17,19 @@ const DEFAULT_EVENTS_PER_FLUSH: u64 = 64;
 
 /// Optional event persistence/export hook.
 /// Implementations should avoid panics and return `SinkError` on write failures.
+/// Ordering: envelopes arrive one at a time in ingest order (strictly increasing
+/// `Envelope::seq`). Delivery is asynchronous relative to live subscribers, so there is
+/// no cross-channel timing guarantee; reconcile sink and live views by `seq`.
 pub trait EventSink: Send + Sync + 'static {
     /// Consume one envelope.
     /// Side effects: sink-specific I/O. Complexity depends on implementation.
     fn on_envelope<'a>(&'a self, envelope: &'a Envelope) -> EventSinkFuture<'a>;
+
+    /// Push buffered writes to durable storage. Called once when the owner stops
+    /// feeding the sink (e.g. `WebAdapter::shutdown`). Default: nothing buffered.
+    fn flush(&self) -> EventSinkFuture<'_> {
+        Box::pin(async { Ok(()) })
+    }
 }
 
 #[derive(Debug)]
@@ -114,6 +123,21 @@ impl EventSink for JsonlFileSink {
             Ok(())
         })
     }
+
+    /// Flush writes still buffered by `EveryNEvents`.
+    /// Side effects: one file flush. Complexity: O(1).
+    fn flush(&self) -> EventSinkFuture<'_> {
+        Box::pin(async move {
+            let mut state = self.state.lock().await;
+            state
+                .file
+                .flush()
+                .await
+                .map_err(|err| SinkError::Io(err.to_string()))?;
+            state.pending_writes = 0;
+            Ok(())
+        })
+    }
 }
 
 fn should_flush(policy: JsonlFlushPolicy, pending_writes: u64) -> bool {
@@ -206,6 +230,10 @@ mod tests {
         sink.on_envelope(&envelope).await.expect("write #2");
         assert_eq!(sink.debug_pending_writes().await, 0);
 
+        sink.on_envelope(&envelope).await.expect("write #3");
+        sink.flush().await.expect("flush");
+        assert_eq!(sink.debug_pending_writes().await, 0);
+
         let _ = fs::remove_file(path);
     }
 
@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 
+use crate::runtime::api::ThreadTurnErrorView;
 use crate::runtime::approvals::PendingServerRequest;
 use crate::runtime::events::Envelope;
 use crate::runtime::rpc_contract::methods as events;
 
+/// Appended once to `ItemState::text_accum` when `max_text_bytes_per_item` cuts it short.
+pub const ITEM_TEXT_TRUNCATION_MARKER: &str = "…[truncated]";
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum ConnectionState {
@@ -18,20 +22,103 @@ pub enum ConnectionState {
     Dead,
 }
 
+/// Whether the runtime accepts new work, independent of the child's `ConnectionState`:
+/// the child may be `Running` while the runtime is draining for a reconfigure.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RuntimeLifecycle {
+    /// Accepting new sessions and turns (the child may be restarting).
+    Running,
+    /// `reconfigure` is waiting for in-progress turns to end.
+    Draining,
+    /// `shutdown` started; new work fails with transport closed.
+    ShuttingDown,
+    /// Shut down, or the supervisor gave up restarting the child.
+    Dead,
+}
+
+/// `ConnectionState` without its payload, for flat wire schemas such as `HealthStatus`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionPhase {
+    Starting,
+    Handshaking,
+    Running,
+    Restarting,
+    ShuttingDown,
+    Dead,
+}
+
+impl ConnectionState {
+    /// Tag and generation (`Running`/`Restarting` only) of this state.
+    /// Allocation: none. Complexity: O(1).
+    pub fn phase(&self) -> (ConnectionPhase, Option<u64>) {
+        match self {
+            Self::Starting => (ConnectionPhase::Starting, None),
+            Self::Handshaking => (ConnectionPhase::Handshaking, None),
+            Self::Running { generation } => (ConnectionPhase::Running, Some(*generation)),
+            Self::Restarting { generation } => (ConnectionPhase::Restarting, Some(*generation)),
+            Self::ShuttingDown => (ConnectionPhase::ShuttingDown, None),
+            Self::Dead => (ConnectionPhase::Dead, None),
+        }
+    }
+}
+
+/// Point-in-time runtime health from `Runtime::health`. The serialized shape is flat and
+/// stable (camelCase keys, string `connection` tag, `generation` always present, `null`
+/// outside `running`/`restarting`) so it can be returned from a health endpoint as is.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthStatus {
+    pub connection: ConnectionPhase,
+    pub generation: Option<u64>,
+    /// Shut down, or the supervisor gave up restarting the child.
+    pub dead: bool,
+    /// `reconfigure` is waiting for in-progress turns to end.
+    pub draining: bool,
+    /// `shutdown` started.
+    pub shutting_down: bool,
+    pub uptime_millis: u64,
+    pub pending_rpc_count: u64,
+    pub pending_server_request_count: u64,
+    pub turns_active: u64,
+    pub turns_queued: u64,
+}
+
+impl HealthStatus {
+    /// Ready for new work: child running, runtime neither dead, draining, nor shutting down.
+    /// Allocation: none. Complexity: O(1).
+    pub fn is_serving(&self) -> bool {
+        self.connection == ConnectionPhase::Running
+            && !self.dead
+            && !self.draining
+            && !self.shutting_down
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct RuntimeState {
     pub connection: ConnectionState,
     pub threads: HashMap<String, ThreadState>,
     pub pending_server_requests: HashMap<String, PendingServerRequest>,
+    /// Threads dropped so far by the `max_threads` limit. Non-zero means the projection
+    /// no longer holds every thread seen; use `thread_read` for full history.
+    #[serde(default)]
+    pub evicted_thread_count: u64,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct StateProjectionLimits {
+    /// Retained thread cap. Past it the least recently active threads (lowest
+    /// `ThreadState::last_seq`) are evicted and `RuntimeState::evicted_thread_count` grows.
     pub max_threads: usize,
     pub max_turns_per_thread: usize,
     pub max_items_per_turn: usize,
+    /// Cap on retained `ItemState::text_accum` bytes. Past it the text ends with
+    /// `ITEM_TEXT_TRUNCATION_MARKER` and `text_truncated` is set; live subscribers
+    /// and sinks still receive every delta.
     pub max_text_bytes_per_item: usize,
     pub max_stdout_bytes_per_item: usize,
     pub max_stderr_bytes_per_item: usize,
@@ -41,11 +128,17 @@ pub struct StateProjectionLimits {
 #[serde(rename_all = "camelCase")]
 pub struct ThreadState {
     pub id: String,
+    /// Most recently started turn that is still in progress. Threads may run several
+    /// turns concurrently; see `in_progress_turn_ids` for all of them.
     pub active_turn: Option<String>,
     pub turns: HashMap<String, TurnState>,
     pub last_diff: Option<String>,
     pub plan: Option<Value>,
     pub last_seq: u64,
+    /// Labels from `Runtime::thread_set_metadata` when the server lacks `thread/setMetadata`.
+    /// Process-local: lost on restart and subject to projection pruning.
+    #[serde(default)]
+    pub metadata: Option<Value>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -64,7 +157,10 @@ pub struct TurnState {
     pub id: String,
     pub status: TurnStatus,
     pub items: HashMap<String, ItemState>,
-    pub error: Option<Value>,
+    /// Error reported by `turn/failed` (`params.error`) or `turn/completed`
+    /// (`params.turn.error`); a bare string error becomes the `message`, and an error
+    /// without a string `message` keeps its fields in `extra` with its JSON as `message`.
+    pub error: Option<ThreadTurnErrorView>,
     pub last_seq: u64,
 }
 
@@ -84,12 +180,124 @@ pub struct ItemState {
     pub last_seq: u64,
 }
 
+/// Structured difference between two `RuntimeState` snapshots (`old.diff(&new)`).
+/// Entries are sorted by id so deltas are stable across runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDelta {
+    pub added_threads: Vec<String>,
+    pub removed_threads: Vec<String>,
+    pub turn_transitions: Vec<TurnTransition>,
+    pub new_items: Vec<NewItem>,
+}
+
+/// Turn whose status differs between snapshots; `from` is `None` for a newly seen turn.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TurnTransition {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub from: Option<TurnStatus>,
+    pub to: TurnStatus,
+}
+
+/// Item present in the newer snapshot but not in the older one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NewItem {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub item_id: String,
+}
+
+impl StateDelta {
+    pub fn is_empty(&self) -> bool {
+        self.added_threads.is_empty()
+            && self.removed_threads.is_empty()
+            && self.turn_transitions.is_empty()
+            && self.new_items.is_empty()
+    }
+}
+
+impl RuntimeState {
+    /// Describe what changed from `self` (older) to `other` (newer): added/removed threads,
+    /// turn status transitions, and new items. Pruned turns/items are not reported.
+    /// Read-only. Allocation: none when nothing changed; otherwise one String per reported id.
+    /// Complexity: O(t + i), t/i = turn/item count across both snapshots' threads.
+    pub fn diff(&self, other: &RuntimeState) -> StateDelta {
+        let mut delta = StateDelta::default();
+        for thread_id in self.threads.keys() {
+            if !other.threads.contains_key(thread_id) {
+                delta.removed_threads.push(thread_id.clone());
+            }
+        }
+        for (thread_id, thread) in &other.threads {
+            let old_thread = self.threads.get(thread_id);
+            if old_thread.is_none() {
+                delta.added_threads.push(thread_id.clone());
+            }
+            for (turn_id, turn) in &thread.turns {
+                let old_turn = old_thread.and_then(|old| old.turns.get(turn_id));
+                let from = old_turn.map(|old| &old.status);
+                if from != Some(&turn.status) {
+                    delta.turn_transitions.push(TurnTransition {
+                        thread_id: thread_id.clone(),
+                        turn_id: turn_id.clone(),
+                        from: from.cloned(),
+                        to: turn.status.clone(),
+                    });
+                }
+                for item_id in turn.items.keys() {
+                    if !old_turn.is_some_and(|old| old.items.contains_key(item_id)) {
+                        delta.new_items.push(NewItem {
+                            thread_id: thread_id.clone(),
+                            turn_id: turn_id.clone(),
+                            item_id: item_id.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        if !delta.is_empty() {
+            delta.added_threads.sort_unstable();
+            delta.removed_threads.sort_unstable();
+            delta.turn_transitions.sort_unstable_by(|a, b| {
+                (&a.thread_id, &a.turn_id).cmp(&(&b.thread_id, &b.turn_id))
+            });
+            delta.new_items.sort_unstable_by(|a, b| {
+                (&a.thread_id, &a.turn_id, &a.item_id).cmp(&(&b.thread_id, &b.turn_id, &b.item_id))
+            });
+        }
+        delta
+    }
+}
+
+impl RuntimeState {
+    /// False once any thread was evicted by `StateProjectionLimits::max_threads`.
+    /// Allocation: none. Complexity: O(1).
+    pub fn is_complete(&self) -> bool {
+        self.evicted_thread_count == 0
+    }
+}
+
+impl ThreadState {
+    /// Ids of every `InProgress` turn, most recently touched first.
+    /// Allocation: one Vec of borrowed ids. Complexity: O(t log t), t = turn count.
+    pub fn in_progress_turn_ids(&self) -> Vec<&str> {
+        let mut turns: Vec<&TurnState> = self
+            .turns
+            .values()
+            .filter(|turn| turn.status == TurnStatus::InProgress)
+            .collect();
+        turns.sort_unstable_by(|a, b| b.last_seq.cmp(&a.last_seq).then_with(|| a.id.cmp(&b.id)));
+        turns.into_iter().map(|turn| turn.id.as_str()).collect()
+    }
+}
+
 impl Default for RuntimeState {
     fn default() -> Self {
         Self {
             connection: ConnectionState::Starting,
             threads: HashMap::new(),
             pending_server_requests: HashMap::new(),
+            evicted_thread_count: 0,
         }
     }
 }
@@ -144,7 +352,7 @@ pub fn reduce_in_place_with_limits(
         events::THREAD_STARTED => handle_thread_started(state, envelope, seq),
         events::TURN_STARTED => handle_turn_started(state, envelope, seq),
         events::TURN_COMPLETED => {
-            handle_turn_terminal(state, envelope, seq, TurnStatus::Completed, false)
+            handle_turn_terminal(state, envelope, seq, TurnStatus::Completed, true)
         }
         events::TURN_FAILED => handle_turn_terminal(state, envelope, seq, TurnStatus::Failed, true),
         events::TURN_CANCELLED => {
@@ -196,6 +404,9 @@ fn handle_turn_started(state: &mut RuntimeState, envelope: &Envelope, seq: u64)
     turn.status = TurnStatus::InProgress;
 }
 
+/// Apply a terminal turn event. Terminal statuses are sticky: once a turn has ended,
+/// later terminal events for it (duplicates from a flaky server, replayed logs) leave
+/// status, error, and `last_seq` untouched.
 fn handle_turn_terminal(
     state: &mut RuntimeState,
     envelope: &Envelope,
@@ -206,22 +417,62 @@ fn handle_turn_terminal(
     let Some((thread_id, turn_id)) = thread_and_turn_ids(envelope) else {
         return;
     };
+    if turn_has_ended(state, thread_id, turn_id) {
+        return;
+    }
     let thread = thread_mut(state, thread_id, seq);
-    clear_active_turn_if_matching(thread, turn_id);
     let turn = turn_mut(thread, turn_id, seq);
     turn.status = status;
     if with_error {
-        turn.error = envelope
-            .json
-            .get("params")
-            .and_then(|p| p.get("error"))
-            .cloned();
+        turn.error = envelope.json.get("params").and_then(turn_error_view);
+    }
+    replace_active_turn_if_matching(thread, turn_id);
+}
+
+/// Typed `params.error` (or `params.turn.error`) of a terminal turn event.
+/// `None` only when absent or null. Shapes without a string `message` (e.g. `{"code":429}`)
+/// keep their fields in `extra` and use the compact JSON as `message`.
+fn turn_error_view(params: &Value) -> Option<ThreadTurnErrorView> {
+    let error = params
+        .get("error")
+        .or_else(|| params.get("turn").and_then(|turn| turn.get("error")))?;
+    let raw_view = |extra: Map<String, Value>| ThreadTurnErrorView {
+        message: error.to_string(),
+        additional_details: None,
+        codex_error_info: None,
+        extra,
+    };
+    match error {
+        Value::Null => None,
+        Value::String(message) => Some(ThreadTurnErrorView {
+            message: message.clone(),
+            additional_details: None,
+            codex_error_info: None,
+            extra: Map::new(),
+        }),
+        Value::Object(fields) => {
+            Some(serde_json::from_value(error.clone()).unwrap_or_else(|_| raw_view(fields.clone())))
+        }
+        _ => Some(raw_view(Map::new())),
     }
 }
 
-fn clear_active_turn_if_matching(thread: &mut ThreadState, turn_id: &str) {
+fn turn_has_ended(state: &RuntimeState, thread_id: &str, turn_id: &str) -> bool {
+    state
+        .threads
+        .get(thread_id)
+        .and_then(|thread| thread.turns.get(turn_id))
+        .is_some_and(|turn| turn.status != TurnStatus::InProgress)
+}
+
+/// When the active turn ends, hand `active_turn` to the most recently touched turn
+/// still in progress on the same thread (concurrent turns), or clear it.
+fn replace_active_turn_if_matching(thread: &mut ThreadState, turn_id: &str) {
     if thread.active_turn.as_deref() == Some(turn_id) {
-        thread.active_turn = None;
+        thread.active_turn = thread
+            .in_progress_turn_ids()
+            .first()
+            .map(|id| (*id).to_owned());
     }
 }
 
@@ -279,12 +530,16 @@ fn handle_item_agent_message_delta(
     let Some(item) = item_from_envelope(state, envelope, seq) else {
         return;
     };
+    let was_truncated = item.text_truncated;
     append_capped(
         &mut item.text_accum,
         delta,
         limits.max_text_bytes_per_item,
         &mut item.text_truncated,
     );
+    if item.text_truncated && !was_truncated {
+        item.text_accum.push_str(ITEM_TEXT_TRUNCATION_MARKER);
+    }
 }
 
 fn handle_item_command_output_delta(
@@ -367,11 +622,22 @@ fn thread_mut<'a>(state: &'a mut RuntimeState, thread_id: &str, seq: u64) -> &'a
             last_diff: None,
             plan: None,
             last_seq: seq,
+            metadata: None,
         });
     thread.last_seq = seq;
     thread
 }
 
+/// Store local thread metadata without touching `last_seq` (no envelope drove this change).
+/// Allocation: one ThreadState when the thread is not yet projected. Complexity: O(1).
+pub(crate) fn set_thread_metadata(state: &mut RuntimeState, thread_id: &str, metadata: Value) {
+    let seq = state
+        .threads
+        .get(thread_id)
+        .map_or(0, |thread| thread.last_seq);
+    thread_mut(state, thread_id, seq).metadata = Some(metadata);
+}
+
 fn turn_mut<'a>(thread: &'a mut ThreadState, turn_id: &str, seq: u64) -> &'a mut TurnState {
     thread.last_seq = seq;
     let turn = thread
@@ -451,6 +717,9 @@ fn prune_state(
         for (id, _) in by_age.into_iter().take(remove_count) {
             state.threads.remove(&id);
         }
+        state.evicted_thread_count = state
+            .evicted_thread_count
+            .saturating_add(remove_count as u64);
     }
 
     let Some(thread_id) = touched_thread_id else {
@@ -576,6 +845,128 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reduce_keeps_concurrent_turns_on_one_thread_apart() {
+        let events = [
+            ("turn/started", "turn_a", None, json!({})),
+            ("turn/started", "turn_b", None, json!({})),
+            (
+                "item/started",
+                "turn_a",
+                Some("item_a"),
+                json!({"itemType": "agentMessage"}),
+            ),
+            (
+                "item/started",
+                "turn_b",
+                Some("item_b"),
+                json!({"itemType": "agentMessage"}),
+            ),
+            (
+                "item/agentMessage/delta",
+                "turn_b",
+                Some("item_b"),
+                json!({"delta": "b"}),
+            ),
+            (
+                "item/agentMessage/delta",
+                "turn_a",
+                Some("item_a"),
+                json!({"delta": "a"}),
+            ),
+        ];
+        let mut state = RuntimeState::default();
+        for (seq, (method, turn, item, params)) in events.into_iter().enumerate() {
+            state = reduce(
+                state,
+                &envelope_with_seq(seq as u64 + 1, method, "thr", turn, item, params),
+            );
+        }
+        let thread = &state.threads["thr"];
+        assert_eq!(thread.active_turn.as_deref(), Some("turn_b"));
+        assert_eq!(thread.in_progress_turn_ids(), ["turn_a", "turn_b"]);
+        assert_eq!(thread.turns["turn_a"].items["item_a"].text_accum, "a");
+        assert_eq!(thread.turns["turn_b"].items["item_b"].text_accum, "b");
+        assert!(!thread.turns["turn_a"].items.contains_key("item_b"));
+
+        let state = reduce(
+            state,
+            &envelope_with_seq(7, "turn/completed", "thr", "turn_b", None, json!({})),
+        );
+        let thread = &state.threads["thr"];
+        assert_eq!(thread.active_turn.as_deref(), Some("turn_a"));
+        assert_eq!(thread.turns["turn_a"].status, TurnStatus::InProgress);
+
+        let state = reduce(
+            state,
+            &envelope_with_seq(8, "turn/completed", "thr", "turn_a", None, json!({})),
+        );
+        assert_eq!(state.threads["thr"].active_turn, None);
+        assert!(state.threads["thr"].in_progress_turn_ids().is_empty());
+    }
+
+    #[test]
+    fn diff_reports_thread_turn_and_item_changes() {
+        let before = reduce(
+            RuntimeState::default(),
+            &envelope("turn/started", "thr_a", "turn_1", None, json!({})),
+        );
+        assert!(before.diff(&before).is_empty());
+
+        let mut after = reduce(
+            before.clone(),
+            &envelope_with_seq(
+                2,
+                "item/started",
+                "thr_a",
+                "turn_1",
+                Some("item_1"),
+                json!({"item": {"type": "agentMessage"}}),
+            ),
+        );
+        after = reduce(
+            after,
+            &envelope_with_seq(3, "turn/completed", "thr_a", "turn_1", None, json!({})),
+        );
+        after = reduce(
+            after,
+            &envelope_with_seq(4, "turn/started", "thr_b", "turn_2", None, json!({})),
+        );
+
+        let delta = before.diff(&after);
+        assert_eq!(delta.added_threads, ["thr_b"]);
+        assert!(delta.removed_threads.is_empty());
+        assert_eq!(
+            delta.turn_transitions,
+            [
+                TurnTransition {
+                    thread_id: "thr_a".to_owned(),
+                    turn_id: "turn_1".to_owned(),
+                    from: Some(TurnStatus::InProgress),
+                    to: TurnStatus::Completed,
+                },
+                TurnTransition {
+                    thread_id: "thr_b".to_owned(),
+                    turn_id: "turn_2".to_owned(),
+                    from: None,
+                    to: TurnStatus::InProgress,
+                },
+            ]
+        );
+        assert_eq!(
+            delta.new_items,
+            [NewItem {
+                thread_id: "thr_a".to_owned(),
+                turn_id: "turn_1".to_owned(),
+                item_id: "item_1".to_owned(),
+            }]
+        );
+
+        let reverse = after.diff(&before);
+        assert_eq!(reverse.removed_threads, ["thr_b"]);
+        assert!(reverse.new_items.is_empty());
+    }
+
     #[test]
     fn reduce_turn_cancelled_marks_cancelled_and_clears_active_turn() {
         let state = RuntimeState::default();
@@ -690,9 +1081,24 @@ mod tests {
             ),
             &limits,
         );
+        reduce_in_place_with_limits(
+            &mut state,
+            &envelope_with_seq(
+                4,
+                "item/agentMessage/delta",
+                "thr",
+                "turn",
+                Some("item"),
+                json!({"delta":" world"}),
+            ),
+            &limits,
+        );
 
         let item = &state.threads["thr"].turns["turn"].items["item"];
-        assert_eq!(item.text_accum, "hell");
+        assert_eq!(
+            item.text_accum,
+            format!("hell{ITEM_TEXT_TRUNCATION_MARKER}")
+        );
         assert!(item.text_truncated);
         assert_eq!(item.stdout_accum, "abc");
         assert!(item.stdout_truncated);
@@ -700,6 +1106,126 @@ mod tests {
         assert!(item.stderr_truncated);
     }
 
+    #[test]
+    fn reduce_keeps_typed_turn_error_from_terminal_events() {
+        let mut state = RuntimeState::default();
+        for envelope in [
+            envelope_with_seq(1, "turn/started", "thr", "failed", None, json!({})),
+            envelope_with_seq(
+                2,
+                "turn/failed",
+                "thr",
+                "failed",
+                None,
+                json!({"error":{
+                    "message":"rate limited",
+                    "additionalDetails":"retry later",
+                    "codexErrorInfo":"usageLimitExceeded",
+                    "code":429
+                }}),
+            ),
+            envelope_with_seq(3, "turn/started", "thr", "completed", None, json!({})),
+            envelope_with_seq(
+                4,
+                "turn/completed",
+                "thr",
+                "completed",
+                None,
+                json!({"turn":{"id":"completed","status":"failed","error":"context overflow"}}),
+            ),
+        ] {
+            state = reduce(state, &envelope);
+        }
+
+        let failed = &state.threads["thr"].turns["failed"];
+        assert_eq!(failed.status, TurnStatus::Failed);
+        let error = failed.error.as_ref().expect("turn/failed error");
+        assert_eq!(error.message, "rate limited");
+        assert_eq!(error.additional_details.as_deref(), Some("retry later"));
+        assert_eq!(error.codex_error_info, Some(json!("usageLimitExceeded")));
+        assert_eq!(error.extra.get("code"), Some(&json!(429)));
+
+        let completed = &state.threads["thr"].turns["completed"];
+        assert_eq!(
+            completed.error.as_ref().map(|error| error.message.as_str()),
+            Some("context overflow")
+        );
+    }
+
+    #[test]
+    fn reduce_keeps_turn_errors_without_a_message() {
+        let mut state = RuntimeState::default();
+        for envelope in [
+            envelope_with_seq(1, "turn/started", "thr", "coded", None, json!({})),
+            envelope_with_seq(
+                2,
+                "turn/failed",
+                "thr",
+                "coded",
+                None,
+                json!({"error":{"code":429}}),
+            ),
+            envelope_with_seq(3, "turn/started", "thr", "numeric", None, json!({})),
+            envelope_with_seq(4, "turn/failed", "thr", "numeric", None, json!({"error":7})),
+        ] {
+            state = reduce(state, &envelope);
+        }
+
+        let coded = state.threads["thr"].turns["coded"]
+            .error
+            .as_ref()
+            .expect("error without message is kept");
+        assert_eq!(coded.message, r#"{"code":429}"#);
+        assert_eq!(coded.extra.get("code"), Some(&json!(429)));
+
+        let numeric = state.threads["thr"].turns["numeric"]
+            .error
+            .as_ref()
+            .expect("scalar error is kept");
+        assert_eq!(numeric.message, "7");
+    }
+
+    #[test]
+    fn reduce_ignores_terminal_events_for_ended_turns() {
+        let mut state = RuntimeState::default();
+        for envelope in [
+            envelope_with_seq(1, "turn/started", "thr", "turn", None, json!({})),
+            envelope_with_seq(
+                2,
+                "item/agentMessage/delta",
+                "thr",
+                "turn",
+                Some("item"),
+                json!({"delta":"done"}),
+            ),
+            envelope_with_seq(3, "turn/completed", "thr", "turn", None, json!({})),
+        ] {
+            state = reduce(state, &envelope);
+        }
+        let settled = state.clone();
+
+        for envelope in [
+            envelope_with_seq(4, "turn/completed", "thr", "turn", None, json!({})),
+            envelope_with_seq(
+                5,
+                "turn/failed",
+                "thr",
+                "turn",
+                None,
+                json!({"error":{"message":"late"}}),
+            ),
+            envelope_with_seq(6, "turn/cancelled", "thr", "turn", None, json!({})),
+        ] {
+            state = reduce(state, &envelope);
+        }
+
+        let turn = &state.threads["thr"].turns["turn"];
+        assert_eq!(turn.status, TurnStatus::Completed);
+        assert_eq!(turn.error, None);
+        assert_eq!(state, settled);
+        assert!(settled.diff(&state).is_empty());
+    }
+
     #[test]
     fn reduce_prunes_old_threads_turns_and_items() {
         let mut state = RuntimeState::default();
@@ -730,6 +1256,28 @@ mod tests {
         assert!(!state.threads.contains_key("thr_1"));
         assert!(state.threads.contains_key("thr_2"));
         assert!(state.threads.contains_key("thr_3"));
+        assert_eq!(state.evicted_thread_count, 1);
+        assert!(!state.is_complete());
+
+        // Activity on thr_2 makes thr_3 the least recently active thread.
+        reduce_in_place_with_limits(
+            &mut state,
+            &envelope_with_seq(4, "thread/started", "thr_2", "turn_a", None, json!({})),
+            &limits,
+        );
+        reduce_in_place_with_limits(
+            &mut state,
+            &envelope_with_seq(5, "thread/started", "thr_4", "turn_a", None, json!({})),
+            &limits,
+        );
+        assert!(state.threads.contains_key("thr_2"));
+        assert!(!state.threads.contains_key("thr_3"));
+        assert_eq!(state.evicted_thread_count, 2);
+        reduce_in_place_with_limits(
+            &mut state,
+            &envelope_with_seq(6, "thread/started", "thr_3", "turn_a", None, json!({})),
+            &limits,
+        );
 
         for seq in 10..=12 {
             let turn = format!("turn_{seq}");
@@ -15,10 +15,16 @@ use crate::runtime::core::io_policy::{
     normalize_text_tail, trim_ascii_line_endings, trim_tail_bytes, validate_positive_capacity,
 };
 use crate::runtime::errors::RuntimeError;
+use crate::runtime::events::Direction;
+use crate::runtime::metrics::RuntimeMetrics;
 
 const DEFAULT_MAX_INBOUND_FRAME_BYTES: usize = 1024 * 1024;
 const DEFAULT_STDERR_TAIL_MAX_BYTES: usize = 16 * 1024;
 
+/// Callback receiving every raw frame before parsing (inbound) or after serialization
+/// (outbound), newline included. Runs on the transport reader/writer tasks.
+pub type RawFrameObserver = Arc<dyn Fn(Direction, &[u8]) + Send + Sync>;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StdioProcessSpec {
     pub program: PathBuf,
@@ -112,6 +118,17 @@ impl StdioTransport {
     pub async fn spawn(
         spec: StdioProcessSpec,
         config: StdioTransportConfig,
+    ) -> Result<Self, RuntimeError> {
+        Self::spawn_with_metrics(spec, config, None, None).await
+    }
+
+    /// `spawn`, additionally reporting stdin write counters into `metrics` and every
+    /// raw frame into `raw_frame_observer`.
+    pub(crate) async fn spawn_with_metrics(
+        spec: StdioProcessSpec,
+        config: StdioTransportConfig,
+        metrics: Option<Arc<RuntimeMetrics>>,
+        raw_frame_observer: Option<RawFrameObserver>,
     ) -> Result<Self, RuntimeError> {
         validate_positive_capacity("read_channel_capacity", config.read_channel_capacity)?;
         validate_positive_capacity("write_channel_capacity", config.write_channel_capacity)?;
@@ -159,8 +176,9 @@ impl StdioTransport {
             read_tx,
             malformed_line_count_clone,
             config.max_inbound_frame_bytes,
+            raw_frame_observer.clone(),
         ));
-        let writer_task = tokio::spawn(writer_loop(write_rx, stdin));
+        let writer_task = tokio::spawn(writer_loop(write_rx, stdin, metrics, raw_frame_observer));
         let stderr_task = tokio::spawn(stderr_loop(
             stderr,
             stderr_diagnostics_clone,
@@ -421,12 +439,15 @@ async fn wait_child_exit_inner(
 }
 
 /// Reader loop: one line -> one JSON parse attempt.
+/// `raw_frame_observer` sees each line as read, including malformed and oversized
+/// (capped at `max_inbound_frame_bytes + 1`) lines.
 /// Allocation: one reusable byte buffer per task. Complexity: O(line_length) per line.
 async fn reader_loop(
     stdout: ChildStdout,
     inbound_tx: mpsc::Sender<Value>,
     malformed_line_count: Arc<AtomicU64>,
     max_inbound_frame_bytes: usize,
+    raw_frame_observer: Option<RawFrameObserver>,
 ) -> std::io::Result<()> {
     let mut reader = BufReader::new(stdout);
     let mut line = Vec::<u8>::with_capacity(4096);
@@ -440,6 +461,9 @@ async fn reader_loop(
         if read == 0 {
             break;
         }
+        if let Some(observer) = &raw_frame_observer {
+            observer(Direction::Inbound, &line);
+        }
 
         if line.len() > max_inbound_frame_bytes {
             malformed_line_count.fetch_add(1, Ordering::Relaxed);
@@ -508,10 +532,14 @@ async fn stderr_loop(
 }
 
 /// Writer loop: single serialization/write path into child stdin.
+/// Every frame is written and flushed before the next one is dequeued, so no
+/// outbound message sits in a Rust-side buffer once the loop moves on.
 /// Allocation: one reusable byte buffer per task. Complexity: O(frame_size) per message.
 async fn writer_loop(
     mut outbound_rx: mpsc::Receiver<Value>,
     mut stdin: ChildStdin,
+    metrics: Option<Arc<RuntimeMetrics>>,
+    raw_frame_observer: Option<RawFrameObserver>,
 ) -> std::io::Result<()> {
     let mut frame = Vec::<u8>::with_capacity(4096);
 
@@ -525,8 +553,19 @@ async fn writer_loop(
             )
         })?;
         frame.push(b'\n');
+        if let Some(observer) = &raw_frame_observer {
+            observer(Direction::Outbound, &frame);
+        }
 
-        if let Err(err) = stdin.write_all(&frame).await {
+        let frame_len = frame.len() as u64;
+        if let Some(metrics) = &metrics {
+            metrics.record_write_started(frame_len);
+        }
+        let written = write_frame(&mut stdin, &frame).await;
+        if let Some(metrics) = &metrics {
+            metrics.record_write_finished(frame_len, written.is_ok());
+        }
+        if let Err(err) = written {
             if err.kind() == std::io::ErrorKind::BrokenPipe {
                 return Ok(());
             }
@@ -544,6 +583,11 @@ async fn writer_loop(
     Ok(())
 }
 
+async fn write_frame(stdin: &mut ChildStdin, frame: &[u8]) -> std::io::Result<()> {
+    stdin.write_all(frame).await?;
+    stdin.flush().await
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
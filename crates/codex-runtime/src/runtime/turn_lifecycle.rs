@@ -10,8 +10,14 @@ use crate::runtime::turn_output::{TurnStreamCollector, TurnTerminalEvent};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum LaggedTurnTerminal {
-    Completed { assistant_text: Option<String> },
-    Failed { message: Option<String> },
+    Completed {
+        assistant_text: Option<String>,
+        /// First failed `commandExecution` item in the snapshot, as `(command, exit code)`.
+        failed_command: Option<(String, Option<i32>)>,
+    },
+    Failed {
+        message: Option<String>,
+    },
     Cancelled,
     Interrupted,
 }
@@ -94,14 +100,22 @@ where
     }
 }
 
+/// `turn/interrupt` reason sent when a run's deadline expires.
+pub(crate) const INTERRUPT_REASON_TIMEOUT: &str = "timeout";
+/// `turn/interrupt` reason sent when an unfinished stream or turn guard is dropped.
+pub(crate) const INTERRUPT_REASON_CANCELLED: &str = "cancelled";
+/// `turn/interrupt` reason sent when the runtime abandons a turn after a local error.
+pub(crate) const INTERRUPT_REASON_ERROR: &str = "error";
+
 pub(crate) async fn interrupt_turn_best_effort_with_timeout(
     runtime: &Runtime,
     thread_id: &str,
     turn_id: &str,
+    reason: &'static str,
     timeout_duration: Duration,
 ) {
     let _ = runtime
-        .turn_interrupt_with_timeout(thread_id, turn_id, timeout_duration)
+        .turn_interrupt_with_reason_and_timeout(thread_id, turn_id, Some(reason), timeout_duration)
         .await;
 }
 
@@ -109,11 +123,18 @@ pub(crate) fn interrupt_turn_best_effort_detached(
     runtime: Runtime,
     thread_id: String,
     turn_id: String,
+    reason: &'static str,
     timeout_duration: Duration,
 ) {
     tokio::spawn(async move {
-        interrupt_turn_best_effort_with_timeout(&runtime, &thread_id, &turn_id, timeout_duration)
-            .await;
+        interrupt_turn_best_effort_with_timeout(
+            &runtime,
+            &thread_id,
+            &turn_id,
+            reason,
+            timeout_duration,
+        )
+        .await;
     });
 }
 
@@ -203,6 +224,7 @@ mod tests {
             |_| async {
                 Ok(Some(LaggedTurnTerminal::Completed {
                     assistant_text: Some("lagged".to_owned()),
+                    failed_command: None,
                 }))
             },
         )
@@ -213,7 +235,8 @@ mod tests {
         assert!(matches!(
             lagged,
             Some(LaggedTurnTerminal::Completed {
-                assistant_text: Some(text)
+                assistant_text: Some(text),
+                ..
             }) if text == "lagged"
         ));
     }
@@ -0,0 +1,73 @@
+//! Bounded admission for prompt turns (`RuntimeConfig::max_concurrent_turns`).
+
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::runtime::metrics::RuntimeMetrics;
+
+/// Admission gate shared by every `Runtime` clone.
+/// `None` limit admits immediately and only keeps the `turns_active` count.
+pub(crate) struct TurnLimiter {
+    semaphore: Option<Arc<Semaphore>>,
+    metrics: Arc<RuntimeMetrics>,
+}
+
+/// Held for one turn from just before `turn/start` until its terminal result.
+/// Dropping it frees the slot for the longest-waiting queued turn.
+pub(crate) struct TurnSlot {
+    _permit: Option<OwnedSemaphorePermit>,
+    metrics: Arc<RuntimeMetrics>,
+}
+
+impl TurnLimiter {
+    pub(crate) fn new(max_concurrent_turns: Option<usize>, metrics: Arc<RuntimeMetrics>) -> Self {
+        Self {
+            semaphore: max_concurrent_turns.map(|limit| Arc::new(Semaphore::new(limit))),
+            metrics,
+        }
+    }
+
+    /// Wait for a free slot. Waiters are admitted in FIFO order (tokio semaphore fairness).
+    /// Cancel-safe: dropping the future leaves the queue without taking a slot.
+    /// Allocation: none. Complexity: O(1) plus queue wait.
+    pub(crate) async fn acquire(&self) -> TurnSlot {
+        let permit = match &self.semaphore {
+            Some(semaphore) => {
+                let _queued = QueuedTurn::enter(&self.metrics);
+                // The semaphore is never closed, so acquisition only fails on misuse.
+                Arc::clone(semaphore).acquire_owned().await.ok()
+            }
+            None => None,
+        };
+        self.metrics.inc_turns_active();
+        TurnSlot {
+            _permit: permit,
+            metrics: Arc::clone(&self.metrics),
+        }
+    }
+}
+
+impl Drop for TurnSlot {
+    fn drop(&mut self) {
+        self.metrics.dec_turns_active();
+    }
+}
+
+/// Counts one waiter in `turns_queued` for as long as it is alive.
+struct QueuedTurn<'a> {
+    metrics: &'a RuntimeMetrics,
+}
+
+impl<'a> QueuedTurn<'a> {
+    fn enter(metrics: &'a RuntimeMetrics) -> Self {
+        metrics.inc_turns_queued();
+        Self { metrics }
+    }
+}
+
+impl Drop for QueuedTurn<'_> {
+    fn drop(&mut self) {
+        self.metrics.dec_turns_queued();
+    }
+}
@@ -8,33 +8,59 @@ use crate::runtime::rpc_contract::methods as events;
 
 use std::sync::Arc;
 
+/// Separator inserted between distinct assistant message items by default.
+pub const DEFAULT_ASSISTANT_ITEM_SEPARATOR: &str = "\n";
+
 /// Incremental assistant text collector for one turn stream.
 /// Keeps explicit state to avoid duplicate text from both delta and completed payloads.
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Deltas of one item are joined as-is; distinct items are joined with `item_separator`.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AssistantTextCollector {
     assistant_item_ids: HashSet<Arc<str>>,
     assistant_items_with_delta: HashSet<Arc<str>>,
+    last_text_item: Option<Arc<str>>,
+    item_separator: String,
     text: String,
 }
 
+impl Default for AssistantTextCollector {
+    fn default() -> Self {
+        Self {
+            assistant_item_ids: HashSet::new(),
+            assistant_items_with_delta: HashSet::new(),
+            last_text_item: None,
+            item_separator: DEFAULT_ASSISTANT_ITEM_SEPARATOR.to_owned(),
+            text: String::new(),
+        }
+    }
+}
+
 impl AssistantTextCollector {
     /// Create empty collector.
-    /// Allocation: none. Complexity: O(1).
+    /// Allocation: one separator String. Complexity: O(1).
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Join distinct assistant items with `separator` instead of a newline.
+    /// Allocation: one String. Complexity: O(separator length).
+    pub fn with_item_separator(mut self, separator: impl Into<String>) -> Self {
+        self.item_separator = separator.into();
+        self
+    }
+
     /// Consume one envelope and update internal text state.
     /// Allocation: O(delta) for appended text and newly seen item ids.
     /// Complexity: O(1).
     pub fn push_envelope(&mut self, envelope: &Envelope) {
         track_assistant_item(&mut self.assistant_item_ids, envelope);
-        append_text_from_envelope(
-            &mut self.text,
-            &self.assistant_item_ids,
-            &mut self.assistant_items_with_delta,
-            envelope,
-        );
+        self.append_text_from_envelope(envelope);
+    }
+
+    /// Borrow the separator placed between distinct assistant items.
+    /// Allocation: none. Complexity: O(1).
+    pub fn item_separator(&self) -> &str {
+        &self.item_separator
     }
 
     /// Borrow collected raw text.
@@ -48,6 +74,73 @@ impl AssistantTextCollector {
     pub fn into_text(self) -> String {
         self.text
     }
+
+    fn append_text_from_envelope(&mut self, envelope: &Envelope) {
+        let params = envelope.json.get("params");
+        match envelope.method.as_deref() {
+            Some(events::ITEM_AGENT_MESSAGE_DELTA) => {
+                if let Some(delta) = params.and_then(|p| p.get("delta")).and_then(Value::as_str) {
+                    if let Some(item_id) = envelope.item_id.as_ref() {
+                        self.assistant_items_with_delta.insert(item_id.clone());
+                    }
+                    self.append_item_text(envelope.item_id.as_ref(), delta);
+                }
+            }
+            Some(events::ITEM_COMPLETED) => {
+                let is_assistant_item = envelope
+                    .item_id
+                    .as_ref()
+                    .map(|id| self.assistant_item_ids.contains(id))
+                    .unwrap_or(false)
+                    || params
+                        .and_then(|p| p.get("item"))
+                        .and_then(|v| v.get("type"))
+                        .and_then(Value::as_str)
+                        .map(|t| t == "agent_message" || t == "agentMessage")
+                        .unwrap_or(false);
+                if !is_assistant_item {
+                    return;
+                }
+                if envelope
+                    .item_id
+                    .as_ref()
+                    .map(|id| self.assistant_items_with_delta.contains(id))
+                    .unwrap_or(false)
+                {
+                    return;
+                }
+
+                if let Some(text) = params.and_then(extract_text_from_params) {
+                    if !text.is_empty() {
+                        // A completed payload is a whole item, even without an item id.
+                        if !self.text.is_empty() {
+                            self.text.push_str(&self.item_separator);
+                        }
+                        self.text.push_str(&text);
+                        self.last_text_item = envelope.item_id.clone();
+                    }
+                }
+            }
+            Some(events::TURN_COMPLETED) => {
+                if let Some(text) = params.and_then(extract_text_from_params) {
+                    merge_turn_completed_text(&mut self.text, &text, &self.item_separator);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Append streamed text, separating it from a different preceding item.
+    fn append_item_text(&mut self, item_id: Option<&Arc<str>>, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        if !self.text.is_empty() && self.last_text_item.as_ref() != item_id {
+            self.text.push_str(&self.item_separator);
+        }
+        self.text.push_str(text);
+        self.last_text_item = item_id.cloned();
+    }
 }
 
 /// Terminal state of one turn observed from live stream events.
@@ -80,6 +173,12 @@ impl TurnStreamCollector {
         }
     }
 
+    /// Join distinct assistant items with `separator` (see `AssistantTextCollector`).
+    pub fn with_item_separator(mut self, separator: impl Into<String>) -> Self {
+        self.assistant = self.assistant.with_item_separator(separator);
+        self
+    }
+
     /// Consume one envelope. Returns terminal event when this envelope closes the target turn.
     pub fn push_envelope(&mut self, envelope: &Envelope) -> Option<TurnTerminalEvent> {
         if envelope.thread_id.as_deref() != Some(self.thread_id.as_ref())
@@ -111,6 +210,11 @@ impl TurnStreamCollector {
         self.matching_turn_events
     }
 
+    /// Separator placed between distinct assistant items.
+    pub fn item_separator(&self) -> &str {
+        self.assistant.item_separator()
+    }
+
     /// Borrow current collected assistant text.
     pub fn assistant_text(&self) -> &str {
         self.assistant.text()
@@ -122,13 +226,15 @@ impl TurnStreamCollector {
     }
 }
 
-/// Parse thread id from common JSON-RPC result shapes.
+/// Parse thread id from common JSON-RPC result shapes: `{thread:{id}}`, then `{threadId}`.
+/// Empty or whitespace-padded ids are rejected. Use on raw `call_raw` results.
 /// Allocation: one String on match. Complexity: O(1).
 pub fn parse_thread_id(value: &Value) -> Option<String> {
     parse_result_thread_id(value).map(ToOwned::to_owned)
 }
 
-/// Parse turn id from common JSON-RPC result shapes.
+/// Parse turn id from common JSON-RPC result shapes: `{turn:{id}}`, then `{turnId}`.
+/// Empty or whitespace-padded ids are rejected. Use on raw `call_raw` results.
 /// Allocation: one String on match. Complexity: O(1).
 pub fn parse_turn_id(value: &Value) -> Option<String> {
     parse_result_turn_id(value).map(ToOwned::to_owned)
@@ -152,65 +258,7 @@ fn track_assistant_item(assistant_item_ids: &mut HashSet<Arc<str>>, envelope: &E
     }
 }
 
-fn append_text_from_envelope(
-    out: &mut String,
-    assistant_item_ids: &HashSet<Arc<str>>,
-    assistant_items_with_delta: &mut HashSet<Arc<str>>,
-    envelope: &Envelope,
-) {
-    let params = envelope.json.get("params");
-    match envelope.method.as_deref() {
-        Some(events::ITEM_AGENT_MESSAGE_DELTA) => {
-            if let Some(delta) = params.and_then(|p| p.get("delta")).and_then(Value::as_str) {
-                if let Some(item_id) = envelope.item_id.as_ref() {
-                    assistant_items_with_delta.insert(item_id.clone());
-                }
-                out.push_str(delta);
-            }
-        }
-        Some(events::ITEM_COMPLETED) => {
-            let is_assistant_item = envelope
-                .item_id
-                .as_ref()
-                .map(|id| assistant_item_ids.contains(id))
-                .unwrap_or(false)
-                || params
-                    .and_then(|p| p.get("item"))
-                    .and_then(|v| v.get("type"))
-                    .and_then(Value::as_str)
-                    .map(|t| t == "agent_message" || t == "agentMessage")
-                    .unwrap_or(false);
-            if !is_assistant_item {
-                return;
-            }
-            if envelope
-                .item_id
-                .as_ref()
-                .map(|id| assistant_items_with_delta.contains(id))
-                .unwrap_or(false)
-            {
-                return;
-            }
-
-            if let Some(text) = params.and_then(extract_text_from_params) {
-                if !text.is_empty() {
-                    if !out.is_empty() {
-                        out.push('\n');
-                    }
-                    out.push_str(&text);
-                }
-            }
-        }
-        Some(events::TURN_COMPLETED) => {
-            if let Some(text) = params.and_then(extract_text_from_params) {
-                merge_turn_completed_text(out, &text);
-            }
-        }
-        _ => {}
-    }
-}
-
-fn merge_turn_completed_text(out: &mut String, text: &str) {
+fn merge_turn_completed_text(out: &mut String, text: &str, separator: &str) {
     if text.is_empty() {
         return;
     }
@@ -231,7 +279,7 @@ fn merge_turn_completed_text(out: &mut String, text: &str) {
     if out.ends_with(text) {
         return;
     }
-    out.push('\n');
+    out.push_str(separator);
     out.push_str(text);
 }
 
@@ -289,6 +337,36 @@ mod tests {
         assert_eq!(collector.text(), "hello");
     }
 
+    fn collect_two_items(mut collector: AssistantTextCollector) -> String {
+        for (item_id, deltas) in [("it_a", ["hel", "lo"]), ("it_b", ["wor", "ld"])] {
+            collector.push_envelope(&envelope(
+                "item/started",
+                Some(item_id),
+                json!({"itemType":"agentMessage"}),
+            ));
+            for delta in deltas {
+                collector.push_envelope(&envelope(
+                    "item/agentMessage/delta",
+                    Some(item_id),
+                    json!({ "delta": delta }),
+                ));
+            }
+        }
+        collector.into_text()
+    }
+
+    #[test]
+    fn collector_separates_distinct_items_but_not_deltas_of_one_item() {
+        assert_eq!(
+            collect_two_items(AssistantTextCollector::new()),
+            "hello\nworld"
+        );
+        assert_eq!(
+            collect_two_items(AssistantTextCollector::new().with_item_separator(" | ")),
+            "hello | world"
+        );
+    }
+
     #[test]
     fn collector_reads_completed_text_without_delta() {
         let mut collector = AssistantTextCollector::new();